@@ -0,0 +1,156 @@
+//! # Pipeline Benchmarks - Capture-to-Persist Throughput and Latency
+//!
+//! Feeds synthetic input through `flowtrace_lib::bench::Pipeline`: the real
+//! `handle_event()`, `record_event()`, screenshot capture (frames from a mock
+//! backend), and `stop_recording()`, with no OS access. Sessions are written
+//! to a temporary directory, removed at the end of the run.
+//!
+//! ## Running
+//! ```text
+//! cargo bench --features bench
+//! cargo bench --features bench -- --save-baseline before   # ahead of a redesign
+//! cargo bench --features bench -- --baseline before        # compare afterwards
+//! ```
+//!
+//! ## Regression Threshold
+//! Before the criterion groups run, the p99 latency of a single input
+//! (click with capture, key press, wait) is measured over `LATENCY_SAMPLES`
+//! inputs and the run exits with an error if it exceeds the budget:
+//! `FLOWTRACE_BENCH_P99_MS` (default `DEFAULT_P99_BUDGET_MS`). Set the budget
+//! for your CI hardware; the default fits a recent laptop in release mode.
+
+use criterion::{criterion_group, BatchSize, Criterion, Throughput};
+use flowtrace_lib::bench::{configure_root, synthetic_stream, Pipeline};
+use std::time::{Duration, Instant};
+
+/// Inputs per stream in the throughput benchmarks.
+const STREAM_LEN: usize = 1_000;
+
+/// Inputs timed individually for the p99 check.
+const LATENCY_SAMPLES: usize = 1_200;
+
+/// p99 budget per input (a click with capture dominates the tail).
+const DEFAULT_P99_BUDGET_MS: u64 = 250;
+
+/// Events per second through the handler, classification, and filtering.
+fn bench_events(c: &mut Criterion) {
+    let stream = synthetic_stream(STREAM_LEN);
+    let mut group = c.benchmark_group("pipeline");
+    group.throughput(Throughput::Elements(STREAM_LEN as u64));
+
+    group.bench_function("events_without_capture", |b| {
+        b.iter_batched(
+            || Pipeline::new("bench-events", false).unwrap(),
+            |pipeline| {
+                for input in &stream {
+                    pipeline.process(input);
+                }
+                pipeline
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function("persist", |b| {
+        b.iter_batched(
+            || {
+                let pipeline = Pipeline::new("bench-persist", false).unwrap();
+                for input in &stream {
+                    pipeline.process(input);
+                }
+                pipeline
+            },
+            |pipeline| pipeline.persist().unwrap(),
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+/// Inputs per second including screenshot capture (PNG encoding dominates).
+fn bench_capture(c: &mut Criterion) {
+    // One click per 12 inputs: 10 clicks per stream
+    let stream = synthetic_stream(120);
+    let mut group = c.benchmark_group("pipeline_capture");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(stream.len() as u64));
+
+    group.bench_function("capture_to_persist", |b| {
+        b.iter_batched(
+            || Pipeline::new("bench-capture", true).unwrap(),
+            |pipeline| {
+                for input in &stream {
+                    pipeline.process(input);
+                }
+                pipeline.persist().unwrap()
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+/// Measures the p99 per-input latency against the budget.
+///
+/// # Returns
+/// * `Ok(())` - p99 within the budget
+/// * `Err(String)` - Error describing the regression (or a failed recording)
+fn check_p99_latency() -> Result<(), String> {
+    let budget = Duration::from_millis(
+        std::env::var("FLOWTRACE_BENCH_P99_MS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_P99_BUDGET_MS),
+    );
+
+    let pipeline = Pipeline::new("bench-latency", true)?;
+    let mut latencies: Vec<Duration> = synthetic_stream(LATENCY_SAMPLES)
+        .iter()
+        .map(|input| {
+            let started = Instant::now();
+            pipeline.process(input);
+            started.elapsed()
+        })
+        .collect();
+    latencies.sort();
+
+    let p50 = latencies[latencies.len() / 2];
+    let p99 = latencies[latencies.len() * 99 / 100];
+    println!(
+        "pipeline latency over {} events: p50 {:?}, p99 {:?} (budget {:?})",
+        pipeline.event_count(),
+        p50,
+        p99,
+        budget
+    );
+    pipeline.persist()?;
+
+    if p99 > budget {
+        return Err(format!(
+            "p99 latency {:?} exceeds the budget of {:?} (FLOWTRACE_BENCH_P99_MS)",
+            p99, budget
+        ));
+    }
+    Ok(())
+}
+
+criterion_group!(benches, bench_events, bench_capture);
+
+fn main() {
+    let dir = std::env::temp_dir().join(format!("flowtrace-bench-{}", std::process::id()));
+    configure_root(&dir);
+
+    let latency = check_p99_latency();
+    if latency.is_ok() {
+        benches();
+        Criterion::default().configure_from_args().final_summary();
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+
+    if let Err(e) = latency {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,369 @@
+//! # Frame Diff Module - Partial Full-Screen Captures
+//!
+//! Dialog-heavy workflows produce bursts of clicks on a screen that barely
+//! changes, yet every click encodes and writes a ~2.2 MB full-screen PNG.
+//! With `partial_capture` enabled, a capture that differs from the previous
+//! full frame only in a small region is saved as a *patch*: just the changed
+//! rectangle, plus a `FramePatch` pointing at the base frame.
+//!
+//! ## When a Capture Becomes a Patch
+//! - Same session and same focused window as the base frame
+//! - Within `max_interval_seconds` of the previous click
+//! - Changed area at most `max_dirty_ratio` of the frame
+//! - No `max_dimension` override for the display (patches are full resolution)
+//!
+//! Otherwise the full frame is saved and becomes the new base. Patches are
+//! always diffed against the base (not the previous patch), so any frame is
+//! reconstructed from exactly two files.
+//!
+//! ## Diffing
+//! Frames are compared in `TILE_SIZE` tiles; the patch is the bounding box
+//! of all changed tiles. An unchanged screen still gets a one-tile patch, so
+//! no two events share a screenshot file (redaction deletes per event).
+//!
+//! ## Reconstruction
+//! ```text
+//! base (event_[a]_full.png) + patch (event_[b]_patch.png at x, y) → event_[b]_full.png
+//! ```
+//! Exports call `materialize()`, which writes the reconstructed frames next
+//! to the patches (untagged PNGs) and points the events at them. They are
+//! unreferenced by `session.json`, so `gc_storage` may reclaim them; the next
+//! export writes them again. Only exporters that write files do this:
+//! reviewing (`load_session`, checklists, comparisons) hands out the patch
+//! and its base, and the viewer draws one over the other.
+//!
+//! OCR indexes the patch only: text that didn't change stays indexed under
+//! the base frame's event.
+//!
+//! ## Deleting a Base
+//! Redaction deletes a base frame's file, which would leave its patches
+//! nothing to be drawn onto. `detach()` first turns each of them into a
+//! standalone full frame, so the later events keep a complete screenshot.
+
+use crate::buffer_pool;
+use crate::config::PartialCaptureConfig;
+use crate::storage;
+use crate::types::{FramePatch, RecordingSession, Screenshots};
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Edge length of the tiles frames are compared in (physical pixels).
+pub const TILE_SIZE: u32 = 16;
+
+/// A rectangle in physical pixels: `(x, y, width, height)`.
+pub type Rect = (u32, u32, u32, u32);
+
+/// Last full frame saved for the active recording.
+struct BaseFrame {
+    session_id: String,
+    /// Focused window when the frame was captured
+    window: String,
+    /// Stored path of the saved full frame
+    path: String,
+    /// Scrubbed full-resolution capture
+    image: RgbaImage,
+    /// Time of the most recent capture (full or patch)
+    last_capture: Instant,
+}
+
+static BASE_FRAME: Lazy<Arc<Mutex<Option<BaseFrame>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Decides whether a capture can be saved as a patch of the base frame.
+///
+/// # Arguments
+/// * `session_id` - Session the capture belongs to
+/// * `window` - Identity of the focused window (`None` if unknown)
+/// * `frame` - Scrubbed full-resolution capture
+/// * `config` - Partial capture settings
+///
+/// # Returns
+/// * `Some((FramePatch, Rect))` - Save `frame` cropped to the rectangle as a patch
+/// * `None` - Save the full frame, then call `set_base()`
+pub fn plan_patch(
+    session_id: &str,
+    window: Option<&str>,
+    frame: &RgbaImage,
+    config: &PartialCaptureConfig,
+) -> Option<(FramePatch, Rect)> {
+    let mut base_lock = BASE_FRAME.lock().unwrap();
+    let base = base_lock.as_mut()?;
+    if !config.enabled || base.session_id != session_id || Some(base.window.as_str()) != window {
+        return None;
+    }
+
+    let since_previous = base.last_capture.elapsed();
+    base.last_capture = Instant::now();
+    // A hand-edited config.json isn't validated (see `PartialCaptureConfig::validate()`)
+    let max_interval =
+        Duration::try_from_secs_f64(config.max_interval_seconds.max(0.0)).unwrap_or(Duration::ZERO);
+    if since_previous > max_interval {
+        return None;
+    }
+    if base.image.dimensions() != frame.dimensions() {
+        return None;
+    }
+
+    let (width, height) = frame.dimensions();
+    let rect = dirty_region(&base.image, frame).unwrap_or((
+        0,
+        0,
+        TILE_SIZE.min(width),
+        TILE_SIZE.min(height),
+    ));
+    let dirty_ratio = (rect.2 as f64 * rect.3 as f64) / (width as f64 * height as f64);
+    if dirty_ratio > config.max_dirty_ratio {
+        return None;
+    }
+
+    Some((
+        FramePatch {
+            base: base.path.clone(),
+            x: rect.0,
+            y: rect.1,
+        },
+        rect,
+    ))
+}
+
+/// Makes a saved full frame the base for the following captures.
+pub fn set_base(session_id: &str, window: Option<&str>, path: &str, frame: &RgbaImage) {
+    let base = window.and_then(|window| {
+        // Copied into a pooled buffer: a new base is kept for almost every full capture
+        let mut pixels = buffer_pool::take(frame.as_raw().len());
+        pixels.extend_from_slice(frame.as_raw());
+        Some(BaseFrame {
+            session_id: session_id.to_string(),
+            window: window.to_string(),
+            path: path.to_string(),
+            image: RgbaImage::from_raw(frame.width(), frame.height(), pixels)?,
+            last_capture: Instant::now(),
+        })
+    });
+    replace_base(base);
+}
+
+/// Forgets the base frame, so the next capture is saved in full.
+pub fn reset() {
+    replace_base(None);
+}
+
+fn replace_base(base: Option<BaseFrame>) {
+    let previous = std::mem::replace(&mut *BASE_FRAME.lock().unwrap(), base);
+    if let Some(previous) = previous {
+        buffer_pool::give(previous.image.into_raw());
+    }
+}
+
+/// Returns the bounding box of all tiles that differ between two frames.
+///
+/// # Returns
+/// * `Some(Rect)` - Changed region (the whole frame if the sizes differ)
+/// * `None` - Frames are identical
+pub fn dirty_region(previous: &RgbaImage, current: &RgbaImage) -> Option<Rect> {
+    let (width, height) = current.dimensions();
+    if previous.dimensions() != (width, height) {
+        return Some((0, 0, width, height));
+    }
+
+    changed_tiles(previous, current)
+        .into_iter()
+        .map(|(x, y, width, height)| (x, y, x + width, y + height))
+        .reduce(
+            |(left, top, right, bottom), (x, y, tile_right, tile_bottom)| {
+                (
+                    left.min(x),
+                    top.min(y),
+                    right.max(tile_right),
+                    bottom.max(tile_bottom),
+                )
+            },
+        )
+        .map(|(left, top, right, bottom)| (left, top, right - left, bottom - top))
+}
+
+/// Returns the tiles that differ between two frames of the same size.
+pub fn changed_tiles(previous: &RgbaImage, current: &RgbaImage) -> Vec<Rect> {
+    let (width, height) = current.dimensions();
+    let row_bytes = width as usize * 4;
+    let (previous, current) = (previous.as_raw(), current.as_raw());
+    let mut tiles = Vec::new();
+
+    for tile_y in (0..height).step_by(TILE_SIZE as usize) {
+        let tile_bottom = (tile_y + TILE_SIZE).min(height);
+        for tile_x in (0..width).step_by(TILE_SIZE as usize) {
+            let tile_right = (tile_x + TILE_SIZE).min(width);
+            let changed = (tile_y..tile_bottom).any(|y| {
+                let start = y as usize * row_bytes + tile_x as usize * 4;
+                let end = y as usize * row_bytes + tile_right as usize * 4;
+                previous[start..end] != current[start..end]
+            });
+            if changed {
+                tiles.push((tile_x, tile_y, tile_right - tile_x, tile_bottom - tile_y));
+            }
+        }
+    }
+
+    tiles
+}
+
+/// Draws a patch onto its base frame.
+pub fn apply_patch(base: &mut DynamicImage, patch: &DynamicImage, x: u32, y: u32) {
+    image::imageops::replace(base, patch, x as i64, y as i64);
+}
+
+/// Reconstructs the full frame of a patched capture.
+///
+/// # Returns
+/// * `Ok(Some(DynamicImage))` - Base frame with the patch applied
+/// * `Ok(None)` - The full-screen capture isn't a patch
+/// * `Err(String)` - Error if the base or the patch couldn't be read
+pub fn reconstruct(screenshots: &Screenshots) -> Result<Option<DynamicImage>, String> {
+    let (Some(patch_path), Some(patch)) =
+        (&screenshots.full_screen, &screenshots.full_screen_patch)
+    else {
+        return Ok(None);
+    };
+
+    let mut frame = image::open(storage::resolve_asset_path(&patch.base))
+        .map_err(|e| format!("Failed to read base frame {}: {:?}", patch.base, e))?;
+    let patch_image = image::open(storage::resolve_asset_path(patch_path))
+        .map_err(|e| format!("Failed to read patch {}: {:?}", patch_path, e))?;
+    if patch.x + patch_image.width() > frame.width()
+        || patch.y + patch_image.height() > frame.height()
+    {
+        return Err(format!("Patch {} lies outside its base frame", patch_path));
+    }
+
+    apply_patch(&mut frame, &patch_image, patch.x, patch.y);
+    Ok(Some(frame))
+}
+
+/// Replaces every patch in a session with its reconstructed full frame.
+///
+/// Frames are written as `event_[id]_full.png` (reused if already present).
+/// Patches that can't be reconstructed (e.g., the base event was redacted)
+/// are left as they are, with a warning.
+///
+/// # Returns
+/// Number of events now pointing at a complete frame
+pub fn materialize(session: &mut RecordingSession) -> usize {
+    let mut materialized = 0;
+
+    for event in session.events.iter_mut() {
+        if event.screenshots.full_screen_patch.is_none() {
+            continue;
+        }
+        let stored = reconstruction_path(&session.session_id, &event.id.to_string());
+        let path = storage::resolve_asset_path(&stored);
+
+        let written = if path.exists() {
+            Ok(())
+        } else {
+            reconstruct(&event.screenshots).and_then(|frame| match frame {
+                Some(frame) => frame
+                    .save_with_format(&path, ImageFormat::Png)
+                    .map_err(|e| format!("Failed to write reconstructed frame: {:?}", e)),
+                None => Ok(()),
+            })
+        };
+        match written {
+            Ok(()) => {
+                event.screenshots.full_screen = Some(stored);
+                event.screenshots.full_screen_patch = None;
+                materialized += 1;
+            }
+            Err(e) => eprintln!("⚠️  Keeping partial capture of event {}: {}", event.id, e),
+        }
+    }
+
+    materialized
+}
+
+/// Stored path of the reconstructed full frame of a partial capture.
+pub fn reconstruction_path(session_id: &str, event_id: &str) -> String {
+    storage::session_asset_path(session_id, &format!("event_{}_full.png", event_id))
+}
+
+/// Turns the patches drawn onto a base frame into standalone full frames,
+/// so the base can be deleted (see "Deleting a Base").
+///
+/// Each dependent is reconstructed into `event_[id]_full.png` (replacing a
+/// cached reconstruction), and its patch file is deleted.
+///
+/// # Arguments
+/// * `session` - Session to edit (the caller saves it)
+/// * `base` - Stored path of the base frame
+/// * `skip` - Events left as they are (e.g., deleted along with the base)
+///
+/// # Returns
+/// * `Ok(usize)` - Number of events that no longer depend on the base
+/// * `Err(String)` - Error if a dependent couldn't be reconstructed or
+///   written; the base must then be kept
+pub fn detach(
+    session: &mut RecordingSession,
+    base: &str,
+    skip: &HashSet<String>,
+) -> Result<usize, String> {
+    let mut detached = 0;
+
+    for event in session.events.iter_mut() {
+        let depends = event
+            .screenshots
+            .full_screen_patch
+            .as_ref()
+            .is_some_and(|patch| patch.base == base);
+        if !depends || skip.contains(&event.id.to_string()) {
+            continue;
+        }
+        let frame = reconstruct(&event.screenshots)?
+            .ok_or_else(|| format!("Event {} has no partial capture", event.id))?;
+        let stored = reconstruction_path(&session.session_id, &event.id.to_string());
+        frame
+            .save_with_format(storage::resolve_asset_path(&stored), ImageFormat::Png)
+            .map_err(|e| format!("Failed to write reconstructed frame: {:?}", e))?;
+
+        let patch_path = event.screenshots.full_screen.replace(stored.clone());
+        event.screenshots.full_screen_patch = None;
+        if let Some(patch_path) = patch_path.filter(|path| *path != stored) {
+            match fs::remove_file(storage::resolve_asset_path(&patch_path)) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to delete patch {}: {:?}", patch_path, e)),
+            }
+        }
+        detached += 1;
+    }
+
+    Ok(detached)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_dirty_region_and_patch_roundtrip() {
+        let previous = RgbaImage::from_pixel(100, 60, Rgba([255, 255, 255, 255]));
+        let mut current = previous.clone();
+        for x in 20..40 {
+            current.put_pixel(x, 35, Rgba([200, 0, 0, 255]));
+        }
+
+        assert_eq!(dirty_region(&previous, &previous), None);
+        // Tile-aligned: columns 16-47, rows 32-47
+        let rect = dirty_region(&previous, &current).unwrap();
+        assert_eq!(rect, (16, 32, 32, 16));
+
+        let current = DynamicImage::ImageRgba8(current);
+        let patch = current.crop_imm(rect.0, rect.1, rect.2, rect.3);
+        let mut rebuilt = DynamicImage::ImageRgba8(previous);
+        apply_patch(&mut rebuilt, &patch, rect.0, rect.1);
+        assert_eq!(rebuilt.to_rgba8(), current.to_rgba8());
+    }
+}
@@ -0,0 +1,299 @@
+//! # Integrity Module - Session Consistency Checks
+//!
+//! Checks that a session is internally consistent before it leaves or
+//! enters the recordings directory. Sessions are hand-edited, repaired by
+//! `recover_session`, and copied between machines, so `session.json` can
+//! parse fine and still point at files that are gone.
+//!
+//! ## Checks
+//! | Check | Severity | Flags |
+//! |-------|----------|-------|
+//! | `schema_version` | error | Sessions written by a newer FlowTrace (`schema_version` above `types::SCHEMA_VERSION`) |
+//! | `missing_screenshot` | error | Screenshot paths (and partial capture bases) without a file |
+//! | `negative_duration` | error | Negative, non-finite, or implausibly long (`MAX_DURATION_SECONDS`) `duration_seconds` / `gap_seconds`, `stopped_at` before `started_at` |
+//! | `timestamp_order` | warning | Events earlier than the event before them (in playback order, see `RecordingSession::timeline()`) |
+//!
+//! Drafts aren't checked for timestamp order: reordering steps is what they
+//! are for (see `draft` module).
+//!
+//! ## Where It Runs
+//! - `validate_session(session_id)` - On demand, for the frontend
+//! - Before exports (`ensure_exportable()`): errors abort the export
+//! - Before imports (`importer` module): errors reject the session
+//!
+//! Warnings never block anything; they are for the user to look at.
+
+use crate::storage;
+use crate::types::{EventType, RecordingSession, SCHEMA_VERSION};
+use serde::Serialize;
+
+/// Longest accepted `duration_seconds` / `gap_seconds` (30 days).
+///
+/// Longer ones are corrupt or hostile: replays would wait that long, and
+/// timestamps derived from them overflow.
+const MAX_DURATION_SECONDS: f64 = 30.0 * 24.0 * 3600.0;
+
+/// How serious a finding is.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The session can't be exported or imported
+    Error,
+    /// Suspicious, but usable
+    Warning,
+}
+
+/// Which check produced a finding (see module docs).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Check {
+    SchemaVersion,
+    MissingScreenshot,
+    NegativeDuration,
+    TimestampOrder,
+}
+
+/// One problem found in a session.
+#[derive(Serialize, Debug, Clone)]
+pub struct IntegrityFinding {
+    pub severity: Severity,
+    pub check: Check,
+    /// Event the finding is about (`None` for session-level findings)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    /// What is wrong (e.g., `"Missing full-screen screenshot: event_..._full.png"`)
+    pub message: String,
+}
+
+/// Result of validating a session.
+#[derive(Serialize, Debug, Clone)]
+pub struct IntegrityReport {
+    pub session_id: String,
+    /// No error findings (warnings allowed)
+    pub valid: bool,
+    /// Findings in event order, session-level findings first
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    /// Error message listing every error finding, or `Ok` if there are none.
+    pub fn into_result(self) -> Result<Self, String> {
+        if self.valid {
+            return Ok(self);
+        }
+        let errors: Vec<&str> = self
+            .findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .map(|finding| finding.message.as_str())
+            .collect();
+        Err(format!(
+            "Session {} failed validation: {}",
+            self.session_id,
+            errors.join("; ")
+        ))
+    }
+}
+
+/// Validates a saved session (screenshots are looked up in the recordings directory).
+///
+/// # Returns
+/// * `Ok(IntegrityReport)` - Findings, possibly none
+/// * `Err(String)` - Error if the session couldn't be loaded
+pub fn validate_saved(session_id: &str) -> Result<IntegrityReport, String> {
+    let session = storage::load_session(session_id)?;
+    Ok(validate(&session, |path| {
+        storage::resolve_asset_path(path).is_file()
+    }))
+}
+
+/// Fails if a saved session has errors, so exports don't link missing files.
+pub fn ensure_exportable(session_id: &str) -> Result<(), String> {
+    validate_saved(session_id)?.into_result().map(|_| ())
+}
+
+/// Runs all checks over a session.
+///
+/// # Arguments
+/// * `session` - Session to check
+/// * `asset_exists` - Whether a stored screenshot path has a file (saved
+///   sessions and import sources keep their files in different places)
+pub fn validate(
+    session: &RecordingSession,
+    asset_exists: impl Fn(&str) -> bool,
+) -> IntegrityReport {
+    let mut findings = Vec::new();
+    let mut flag = |severity, check, event_id: Option<&str>, message: String| {
+        findings.push(IntegrityFinding {
+            severity,
+            check,
+            event_id: event_id.map(str::to_string),
+            message,
+        })
+    };
+
+    if session.schema_version > SCHEMA_VERSION {
+        flag(
+            Severity::Error,
+            Check::SchemaVersion,
+            None,
+            format!(
+                "Schema version {} is newer than supported ({}); update FlowTrace",
+                session.schema_version, SCHEMA_VERSION
+            ),
+        );
+    }
+    if session
+        .stopped_at
+        .is_some_and(|stopped_at| stopped_at < session.started_at)
+    {
+        flag(
+            Severity::Error,
+            Check::NegativeDuration,
+            None,
+            "Session stopped before it started".to_string(),
+        );
+    }
+
+    let timeline = session.timeline();
+    for (index, event) in session.events.iter().enumerate() {
+        let event_id = event.id.to_string();
+        let id = Some(event_id.as_str());
+        let screenshots = &event.screenshots;
+        for (label, path) in [
+            ("full-screen screenshot", screenshots.full_screen.as_deref()),
+            ("window screenshot", screenshots.window_crop.as_deref()),
+            ("click screenshot", screenshots.click_crop.as_deref()),
+            (
+                "partial capture base",
+                screenshots
+                    .full_screen_patch
+                    .as_ref()
+                    .map(|patch| patch.base.as_str()),
+            ),
+        ] {
+            if let Some(path) = path.filter(|path| !asset_exists(path)) {
+                flag(
+                    Severity::Error,
+                    Check::MissingScreenshot,
+                    id,
+                    format!("Missing {}: {}", label, path),
+                );
+            }
+        }
+
+        if let Some(duration) = duration_seconds(&event.event_type) {
+            if !(0.0..=MAX_DURATION_SECONDS).contains(&duration) {
+                flag(
+                    Severity::Error,
+                    Check::NegativeDuration,
+                    id,
+                    format!(
+                        "{} event has an invalid duration: {}s",
+                        event.event_type.name(),
+                        duration
+                    ),
+                );
+            }
+        }
+
+        if session.draft_of.is_none() && index > 0 && timeline[index] < timeline[index - 1] {
+            flag(
+                Severity::Warning,
+                Check::TimestampOrder,
+                id,
+                format!(
+                    "Event #{} is {}ms earlier than the event before it",
+                    index + 1,
+                    (timeline[index - 1] - timeline[index]).num_milliseconds()
+                ),
+            );
+        }
+    }
+
+    IntegrityReport {
+        session_id: session.session_id.clone(),
+        valid: !findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error),
+        findings,
+    }
+}
+
+/// Duration field of an event type, in seconds.
+fn duration_seconds(event_type: &EventType) -> Option<f64> {
+    match event_type {
+        EventType::Wait {
+            duration_seconds, ..
+        }
+        | EventType::Idle { duration_seconds }
+        | EventType::Hover { duration_seconds }
+        | EventType::Paused { duration_seconds }
+        | EventType::CredentialsEntered { duration_seconds }
+        | EventType::Lifecycle {
+            duration_seconds, ..
+        } => Some(*duration_seconds),
+        EventType::RecorderRestart { gap_seconds } => Some(*gap_seconds),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, MouseButton, Position};
+    use chrono::Duration;
+
+    #[test]
+    fn test_findings_cover_each_check() {
+        let mut session = RecordingSession::new("test-integrity".to_string());
+        let click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(10.0, 20.0)),
+        )
+        .with_screenshots(
+            Some("recordings/test-integrity/present.png".to_string()),
+            None,
+            Some("recordings/test-integrity/gone.png".to_string()),
+        );
+        let mut wait = Event::new(
+            EventType::Wait {
+                duration_seconds: -1.5,
+                cause: None,
+            },
+            None,
+        );
+        wait.timestamp = click.timestamp - Duration::seconds(3);
+        session.add_event(click);
+        session.add_event(wait);
+
+        let exists = |path: &str| path.ends_with("present.png");
+        let report = validate(&session, exists);
+        let checks: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.check, finding.severity))
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                (Check::MissingScreenshot, Severity::Error),
+                (Check::NegativeDuration, Severity::Error),
+                (Check::TimestampOrder, Severity::Warning),
+            ]
+        );
+        assert!(!report.valid);
+        assert!(report.into_result().unwrap_err().contains("gone.png"));
+
+        // Drafts may be reordered; newer files are refused
+        session.events.remove(0);
+        session.draft_of = Some("original".to_string());
+        session.schema_version = SCHEMA_VERSION + 1;
+        let report = validate(&session, exists);
+        assert_eq!(report.findings.len(), 2);
+        assert_eq!(report.findings[0].check, Check::SchemaVersion);
+    }
+}
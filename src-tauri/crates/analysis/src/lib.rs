@@ -0,0 +1,20 @@
+//! # FlowTrace Analysis - Making Sense of a Recording
+//!
+//! Everything computed from a recorded session rather than captured:
+//!
+//! - **`postprocess`**: Cleaning up a finished recording
+//! - **`steps`**: Grouping events into procedure steps
+//! - **`parts`**: Sessions spanning several sittings
+//! - **`integrity`**: Session consistency checks
+//! - **`frame_diff`**: Partial full-screen captures
+//! - **`ocr`**: Screen text indexing (the external `tesseract` binary)
+
+pub mod frame_diff;
+pub mod integrity;
+pub mod ocr;
+pub mod parts;
+pub mod postprocess;
+pub mod steps;
+
+use flowtrace_capture::error_dialog;
+use flowtrace_core::{buffer_pool, config, storage, types};
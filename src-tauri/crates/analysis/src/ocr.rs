@@ -0,0 +1,413 @@
+//! # OCR Module - Screen Text Indexing
+//!
+//! Optionally extracts the text visible in full-screen captures so sessions
+//! can be searched by what was on screen ("when did 'quota exceeded' appear?").
+//!
+//! ## Pipeline
+//! ```text
+//! Click screenshot → enqueue() → [OCR worker thread] → tesseract → ocr.json
+//!                                                     search_screen_text() ←
+//! ```
+//!
+//! ## Throttling
+//! OCR takes 0.5-2s per 4K capture, so it never runs on the event thread.
+//! A single background worker processes captures in order and skips any
+//! capture taken less than `min_interval_seconds` after the last one it
+//! indexed for the same session (rapid clicks rarely change the screen text).
+//!
+//! ## Storage
+//! ```text
+//! recordings/[session-id]/ocr.json   (event id → recognized text)
+//! ```
+//! Kept separate from `session.json` so the worker never races the session
+//! save at `stop_recording()`.
+//!
+//! ## Word Boxes
+//! `recognize_words()` returns each word with its bounding box, used by the
+//! `scrub` module to blur configured keywords before a capture is written.
+//!
+//! ## Requirements
+//! Uses the `tesseract` command-line tool (`brew install tesseract`), found on
+//! `PATH` or configured via `ocr.tesseract_path`.
+
+use crate::config;
+use crate::error_dialog;
+use crate::storage;
+use chrono::{DateTime, Utc};
+use image::{DynamicImage, ImageOutputFormat};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+
+/// Characters of context kept on each side of a match in search results.
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// Recognized text of the captures in one session.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct OcrIndex {
+    pub entries: Vec<OcrEntry>,
+}
+
+/// Text recognized in the full-screen capture of one event.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OcrEntry {
+    pub event_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub text: String,
+}
+
+/// A search hit: where and when the query text was on screen.
+#[derive(Serialize, Debug, Clone)]
+pub struct ScreenTextMatch {
+    pub session_id: String,
+    pub event_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// Text surrounding the match
+    pub snippet: String,
+}
+
+/// One word recognized with its bounding box (image pixels).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrWord {
+    pub text: String,
+    /// Block, paragraph, and line number; words with the same key share a line
+    pub line: (u32, u32, u32),
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A capture waiting to be recognized.
+struct OcrJob {
+    session_id: String,
+    event_id: String,
+    timestamp: DateTime<Utc>,
+    image_path: PathBuf,
+}
+
+/// Sender into the OCR worker; the worker thread is spawned on first use.
+static OCR_QUEUE: Lazy<Mutex<Sender<OcrJob>>> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::channel::<OcrJob>();
+
+    std::thread::spawn(move || {
+        let mut last_indexed: HashMap<String, DateTime<Utc>> = HashMap::new();
+
+        for job in receiver {
+            let min_interval = config::current().ocr.min_interval_seconds;
+            if let Some(last) = last_indexed.get(&job.session_id) {
+                let elapsed = (job.timestamp - *last).num_milliseconds() as f64 / 1000.0;
+                if elapsed < min_interval {
+                    continue; // Throttled
+                }
+            }
+
+            match index_capture(&job) {
+                Ok(()) => {
+                    last_indexed.insert(job.session_id.clone(), job.timestamp);
+                }
+                Err(e) => eprintln!("⚠️  OCR failed for event {}: {}", job.event_id, e),
+            }
+        }
+    });
+
+    Mutex::new(sender)
+});
+
+/// Queues a full-screen capture for background OCR if indexing is enabled.
+///
+/// Returns immediately; recognition happens on the OCR worker thread.
+///
+/// # Arguments
+/// * `session_id` - Session the capture belongs to
+/// * `event_id` - Event the capture belongs to
+/// * `timestamp` - Event timestamp (used for throttling and search results)
+/// * `image_path` - Stored screenshot path (`recordings/...`)
+pub fn enqueue(session_id: &str, event_id: &str, timestamp: DateTime<Utc>, image_path: &str) {
+    if !config::current().ocr.enabled {
+        return;
+    }
+
+    let job = OcrJob {
+        session_id: session_id.to_string(),
+        event_id: event_id.to_string(),
+        timestamp,
+        image_path: storage::resolve_asset_path(image_path),
+    };
+
+    if let Err(e) = OCR_QUEUE.lock().unwrap().send(job) {
+        eprintln!("⚠️  OCR worker unavailable: {:?}", e);
+    }
+}
+
+/// Searches the screen text of saved sessions (case-insensitive).
+///
+/// # Arguments
+/// * `query` - Text to look for
+/// * `session_ids` - Sessions to search
+///
+/// # Returns
+/// Matches in session order, then event order
+pub fn search_screen_text(query: &str, session_ids: &[String]) -> Vec<ScreenTextMatch> {
+    session_ids
+        .iter()
+        .flat_map(|session_id| find_matches(session_id, &load_index(session_id), query))
+        .collect()
+}
+
+/// Returns the ids of events whose screen text contains an error keyword.
+pub fn error_keyword_events(session_id: &str) -> HashSet<String> {
+    load_index(session_id)
+        .entries
+        .into_iter()
+        .filter(|entry| error_dialog::find_error_keyword(&entry.text).is_some())
+        .map(|entry| entry.event_id)
+        .collect()
+}
+
+/// Returns the stored OCR index of a session (empty if none was created).
+pub fn load_index(session_id: &str) -> OcrIndex {
+    fs::read_to_string(index_path(session_id))
+        .ok()
+        .and_then(|json_data| serde_json::from_str(&json_data).ok())
+        .unwrap_or_default()
+}
+
+/// Location of a session's OCR index.
+fn index_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("ocr.json")
+}
+
+/// Runs OCR on one capture and appends the result to the session's index.
+fn index_capture(job: &OcrJob) -> Result<(), String> {
+    let text = recognize_text(&job.image_path)?;
+
+    let mut index = load_index(&job.session_id);
+    index.entries.push(OcrEntry {
+        event_id: job.event_id.clone(),
+        timestamp: job.timestamp,
+        text,
+    });
+
+    save_index(&job.session_id, &index)?;
+
+    #[cfg(debug_assertions)]
+    println!("🔤 OCR indexed event {}", job.event_id);
+
+    Ok(())
+}
+
+/// Drops the recognized text of the given events (privacy redaction).
+pub fn remove_entries(session_id: &str, event_ids: &HashSet<String>) -> Result<(), String> {
+    let mut index = load_index(session_id);
+    let before = index.entries.len();
+    index
+        .entries
+        .retain(|entry| !event_ids.contains(&entry.event_id));
+
+    if index.entries.len() == before {
+        return Ok(());
+    }
+    save_index(session_id, &index)
+}
+
+/// Writes a session's OCR index.
+fn save_index(session_id: &str, index: &OcrIndex) -> Result<(), String> {
+    let json_data = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Failed to serialize OCR index: {:?}", e))?;
+    fs::write(index_path(session_id), json_data)
+        .map_err(|e| format!("Failed to write OCR index: {:?}", e))
+}
+
+/// Recognizes the text in an image with the `tesseract` CLI.
+///
+/// # Returns
+/// * `Ok(String)` - Recognized text (whitespace-normalized)
+/// * `Err(String)` - Error if tesseract is missing or failed
+pub fn recognize_text(image_path: &Path) -> Result<String, String> {
+    let tesseract = tesseract_binary();
+
+    let output = Command::new(&tesseract)
+        .arg(image_path)
+        .arg("stdout")
+        .output()
+        .map_err(|e| format!("Failed to run {} (is it installed?): {:?}", tesseract, e))?;
+
+    Ok(String::from_utf8_lossy(&check_output(output)?)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+/// Recognizes the words in an in-memory image, with their bounding boxes.
+///
+/// The image is piped to tesseract through stdin, so it never touches the
+/// disk (used to scrub captures *before* they are written).
+///
+/// # Returns
+/// * `Ok(Vec<OcrWord>)` - Recognized words in reading order
+/// * `Err(String)` - Error if tesseract is missing or failed
+pub fn recognize_words(image: &DynamicImage) -> Result<Vec<OcrWord>, String> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {:?}", e))?;
+
+    let tesseract = tesseract_binary();
+    let mut child = Command::new(&tesseract)
+        .args(["stdin", "stdout", "tsv"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {} (is it installed?): {:?}", tesseract, e))?;
+
+    // Write from a separate thread so a full stdout pipe can't deadlock us
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to open tesseract stdin".to_string())?;
+    let writer = std::thread::spawn(move || stdin.write_all(&png));
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to read tesseract output: {:?}", e))?;
+    writer
+        .join()
+        .map_err(|_| "tesseract input thread panicked".to_string())?
+        .map_err(|e| format!("Failed to send image to tesseract: {:?}", e))?;
+
+    Ok(parse_tsv(&String::from_utf8_lossy(&check_output(output)?)))
+}
+
+/// Tesseract binary from the config, else `tesseract` on `PATH`.
+fn tesseract_binary() -> String {
+    config::current()
+        .ocr
+        .tesseract_path
+        .unwrap_or_else(|| "tesseract".to_string())
+}
+
+/// Returns stdout of a finished tesseract run, or its stderr as the error.
+fn check_output(output: Output) -> Result<Vec<u8>, String> {
+    if !output.status.success() {
+        return Err(format!(
+            "tesseract exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// Parses tesseract's TSV output into words (level 5 rows with text).
+///
+/// Columns: `level page_num block_num par_num line_num word_num left top
+/// width height conf text`
+fn parse_tsv(tsv: &str) -> Vec<OcrWord> {
+    tsv.lines()
+        .skip(1) // Header
+        .filter_map(|row| {
+            let columns: Vec<&str> = row.split('\t').collect();
+            if columns.len() < 12 || columns[0] != "5" {
+                return None;
+            }
+            let number = |index: usize| columns[index].parse::<u32>().ok();
+            let text = columns[11].trim();
+            if text.is_empty() {
+                return None;
+            }
+            Some(OcrWord {
+                text: text.to_string(),
+                line: (number(2)?, number(3)?, number(4)?),
+                left: number(6)?,
+                top: number(7)?,
+                width: number(8)?,
+                height: number(9)?,
+            })
+        })
+        .collect()
+}
+
+/// Finds case-insensitive occurrences of `query` in one session's index.
+fn find_matches(session_id: &str, index: &OcrIndex, query: &str) -> Vec<ScreenTextMatch> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    index
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let lowered = entry.text.to_lowercase();
+            let start = lowered.find(&query)?;
+            Some(ScreenTextMatch {
+                session_id: session_id.to_string(),
+                event_id: entry.event_id.clone(),
+                timestamp: entry.timestamp,
+                snippet: snippet(&lowered, &entry.text, start, query.len()),
+            })
+        })
+        .collect()
+}
+
+/// Cuts the original text around a match found in its lowercased copy.
+///
+/// Falls back to the lowercased text if lowercasing changed byte offsets
+/// (rare non-ASCII case mappings).
+fn snippet(lowered: &str, original: &str, start: usize, len: usize) -> String {
+    let text = if lowered.len() == original.len() {
+        original
+    } else {
+        lowered
+    };
+
+    let mut from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    while !text.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (start + len + SNIPPET_CONTEXT_CHARS).min(text.len());
+    while !text.is_char_boundary(to) {
+        to += 1;
+    }
+
+    text[from..to].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matches_is_case_insensitive() {
+        let index = OcrIndex {
+            entries: vec![
+                OcrEntry {
+                    event_id: "e1".to_string(),
+                    timestamp: Utc::now(),
+                    text: "Dashboard Settings Billing".to_string(),
+                },
+                OcrEntry {
+                    event_id: "e2".to_string(),
+                    timestamp: Utc::now(),
+                    text: "Error: Quota exceeded for project".to_string(),
+                },
+            ],
+        };
+
+        let matches = find_matches("s1", &index, "quota EXCEEDED");
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].event_id, "e2");
+        assert!(matches[0].snippet.contains("Quota exceeded"));
+        assert!(find_matches("s1", &index, "  ").is_empty());
+    }
+}
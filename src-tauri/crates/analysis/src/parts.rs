@@ -0,0 +1,186 @@
+//! # Parts Module - Sessions Spanning Several Sittings
+//!
+//! Some procedures genuinely take more than one sitting (waiting for an
+//! approval, a month-end close). `continue_session(session_id)` records the
+//! next sitting of a stopped session as a new session, with its own
+//! `session.json` and screenshots, linked to the first part by `part`:
+//!
+//! ```text
+//! f2e904d2-... (part 1, no link) ← 7c1d... {"number": 2} ← 9a40... {"number": 3}
+//! ```
+//!
+//! Each part is a complete session on its own (replay, redline, Parquet),
+//! while the checklist export loads the whole procedure and renders a section
+//! per day: `"Day 1"`, `"Day 2"`, ... Parts started on the same calendar day
+//! share a section; days without a part are skipped, not counted.
+//!
+//! ## Calendar Days
+//! A part's day is the local date when it started, using its recorded
+//! `utc_offset` if any, else the current local offset.
+
+use crate::steps::{self, Step};
+use crate::storage;
+use crate::types::{RecordingSession, SessionPart};
+use chrono::{FixedOffset, Local, NaiveDate};
+
+/// Loads every part of the procedure `session_id` belongs to.
+///
+/// # Arguments
+/// * `session_id` - Any part of the procedure
+///
+/// # Returns
+/// * `Ok(Vec<RecordingSession>)` - Parts ordered by number (just the session
+///   itself if it was never continued); a deleted first part is left out
+/// * `Err(String)` - Error if `session_id` couldn't be loaded
+pub fn load_parts(session_id: &str) -> Result<Vec<RecordingSession>, String> {
+    let session = storage::load_session(session_id)?;
+    let first_id = first_session_id(&session).to_string();
+
+    let mut parts: Vec<RecordingSession> = storage::list_session_ids()
+        .iter()
+        .filter(|id| **id != first_id)
+        .filter_map(|id| storage::load_session(id).ok())
+        .filter(|part| {
+            part.part
+                .as_ref()
+                .is_some_and(|link| link.first_session_id == first_id)
+        })
+        .collect();
+    if first_id == session_id {
+        parts.push(session);
+    } else if let Ok(first) = storage::load_session(&first_id) {
+        parts.push(first);
+    }
+
+    parts.sort_by_key(part_number);
+    Ok(parts)
+}
+
+/// Returns the link for a new part continuing `session_id`'s procedure.
+///
+/// Continuing any part appends after the last one.
+///
+/// # Returns
+/// * `Ok(SessionPart)` - Link for the next part
+/// * `Err(String)` - Error if the session couldn't be loaded or a part of
+///   the procedure hasn't been stopped (still recording or interrupted)
+pub fn next_part(session_id: &str) -> Result<SessionPart, String> {
+    let parts = load_parts(session_id)?;
+    if let Some(open) = parts.iter().find(|part| part.stopped_at.is_none()) {
+        return Err(format!(
+            "Session {} hasn't been stopped yet",
+            open.session_id
+        ));
+    }
+
+    let last = parts.last().map(part_number).unwrap_or(1);
+    let first = parts
+        .first()
+        .map(|part| first_session_id(part).to_string())
+        .unwrap_or_else(|| session_id.to_string());
+    Ok(SessionPart {
+        first_session_id: first,
+        number: last + 1,
+    })
+}
+
+/// Groups the steps of all parts, labelled with their day (see module docs).
+///
+/// Step numbers continue across parts; marker sections stay per part.
+pub fn group_steps(parts: &[RecordingSession]) -> Vec<Step> {
+    let mut steps = Vec::new();
+    for (part, day) in parts.iter().zip(day_labels(parts)) {
+        for mut step in steps::group_steps(part) {
+            step.number = steps.len() + 1;
+            step.part = Some(day.clone());
+            steps.push(step);
+        }
+    }
+    steps
+}
+
+/// `"Day N"` label of each part, counting the calendar days with a part.
+fn day_labels(parts: &[RecordingSession]) -> Vec<String> {
+    let mut labels = Vec::new();
+    let mut previous: Option<NaiveDate> = None;
+    let mut day = 0;
+
+    for part in parts {
+        let date = local_date(part);
+        if previous != Some(date) {
+            day += 1;
+            previous = Some(date);
+        }
+        labels.push(format!("Day {}", day));
+    }
+    labels
+}
+
+/// Local date when a part started.
+fn local_date(part: &RecordingSession) -> NaiveDate {
+    match part
+        .utc_offset
+        .as_deref()
+        .and_then(|offset| offset.parse::<FixedOffset>().ok())
+    {
+        Some(offset) => part.started_at.with_timezone(&offset).date_naive(),
+        None => part.started_at.with_timezone(&Local).date_naive(),
+    }
+}
+
+fn first_session_id(session: &RecordingSession) -> &str {
+    session
+        .part
+        .as_ref()
+        .map(|link| link.first_session_id.as_str())
+        .unwrap_or(&session.session_id)
+}
+
+fn part_number(session: &RecordingSession) -> u32 {
+    session.part.as_ref().map(|link| link.number).unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position};
+
+    fn part(number: u32, started_at: &str) -> RecordingSession {
+        let mut session = RecordingSession::new(format!("part-{}", number));
+        session.started_at = started_at.parse().unwrap();
+        session.utc_offset = Some("+01:00".to_string());
+        session.part = (number > 1).then(|| SessionPart {
+            first_session_id: "part-1".to_string(),
+            number,
+        });
+        session.add_event(Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(5.0, 5.0)),
+        ));
+        session
+    }
+
+    #[test]
+    fn test_parts_are_grouped_by_local_day() {
+        let parts = vec![
+            part(1, "2026-02-01T09:00:00Z"),
+            // 23:30 UTC is already Feb 2 at +01:00
+            part(2, "2026-02-01T23:30:00Z"),
+            part(3, "2026-02-02T15:00:00Z"),
+            // A week later is still only the third day with a part
+            part(4, "2026-02-09T08:00:00Z"),
+        ];
+
+        let steps = group_steps(&parts);
+        let days: Vec<_> = steps.iter().map(|step| step.part.as_deref()).collect();
+        assert_eq!(
+            days,
+            [Some("Day 1"), Some("Day 2"), Some("Day 2"), Some("Day 3")]
+        );
+        let numbers: Vec<_> = steps.iter().map(|step| step.number).collect();
+        assert_eq!(numbers, [1, 2, 3, 4]);
+    }
+}
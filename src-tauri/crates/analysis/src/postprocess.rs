@@ -0,0 +1,332 @@
+//! # Postprocess Module - Cleaning Up a Finished Recording
+//!
+//! Runs when a recording is stopped, before the session is saved.
+//!
+//! ## Typed Text Aggregation
+//! Typing produces one `KeyPress` per keystroke ("Typed: H", "Typed: e", ...).
+//! Consecutive typing keys (`text_input` and `correction` categories) are
+//! merged into a single `TextInput` event with the reconstructed text:
+//!
+//! ```text
+//! KeyH(shift) KeyI Space KeyX Backspace KeyT KeyH KeyE KeyR KeyE
+//!   → TextInput { text: "Hi there", keystrokes: 10 }
+//! ```
+//!
+//! - **Characters**: the text the OS reported for each key (any layout, dead
+//!   keys, non-ASCII); sessions recorded without it fall back to the key name
+//!   with Shift for capitals and shifted symbols (US layout)
+//! - **Backspace**: removes the last reconstructed character
+//! - **Delete**: forward delete, doesn't change the reconstructed text
+//!
+//! Mouse trails and hovers recorded while typing are moved after the merged text.
+//! The `TextInput` event keeps the id and timestamp of its first keystroke,
+//! so edited timelines and step references stay valid. Runs that produce no
+//! text (e.g., only Backspaces deleting existing content) are kept as is.
+//!
+//! ## Wait Normalization
+//! `normalize_waits()` applies the `waits` config after text aggregation:
+//!
+//! ```text
+//! Wait(3.0) Wait(2.5) Click Wait(900.0) Click Wait(2.2) Click
+//!   → coalesce, max 60, min 2.5 → Wait(5.5) Click Wait(60.0) Click Click
+//! ```
+//!
+//! - **Coalesce**: Consecutive Waits (e.g., around a filtered-out event)
+//!   merge into the first, durations summed; the longest one's `cause` is kept
+//! - **Cap**: Durations above `max_seconds` are reported as `max_seconds`
+//!   (an idle hour isn't a meaningful step)
+//! - **Floor**: Waits below `min_seconds` are dropped
+//!
+//! ## Limitations
+//! - Without recorded characters, Caps Lock isn't tracked and non-US layouts
+//!   may map shifted digits and symbols differently
+
+use crate::config::WaitConfig;
+use crate::types::{Event, EventType, RecordingSession};
+
+/// Merges consecutive typing keystrokes into `TextInput` events (see module docs).
+pub fn aggregate_text_input(session: &mut RecordingSession) {
+    #[cfg(debug_assertions)]
+    let before = session.events.len();
+    let mut events = Vec::with_capacity(session.events.len());
+    let mut run: Vec<Event> = Vec::new();
+    // Mouse events recorded mid-typing shouldn't split the text
+    let mut deferred: Vec<Event> = Vec::new();
+
+    for event in session.events.drain(..) {
+        if is_typing(&event) {
+            run.push(event);
+            continue;
+        }
+        if !run.is_empty()
+            && matches!(
+                event.event_type,
+                EventType::MouseTrail { .. } | EventType::Hover { .. }
+            )
+        {
+            deferred.push(event);
+            continue;
+        }
+        flush_run(&mut run, &mut events);
+        events.append(&mut deferred);
+        events.push(event);
+    }
+    flush_run(&mut run, &mut events);
+    events.append(&mut deferred);
+
+    #[cfg(debug_assertions)]
+    println!("🔤 Text aggregation: {} events → {}", before, events.len());
+
+    session.events = events;
+    retain_timeline_entries(session);
+}
+
+/// Coalesces, caps, and drops Wait events per the `waits` config (see module docs).
+pub fn normalize_waits(session: &mut RecordingSession, config: &WaitConfig) {
+    let mut events: Vec<Event> = Vec::with_capacity(session.events.len());
+
+    for event in session.events.drain(..) {
+        let previous = events.last_mut().filter(|_| config.coalesce);
+        match (previous, wait_seconds(&event)) {
+            (Some(previous), Some(seconds)) if wait_seconds(previous).is_some() => {
+                let previous_seconds = wait_seconds(previous).unwrap_or_default();
+                if let EventType::Wait { cause, .. } = event.event_type {
+                    if seconds > previous_seconds {
+                        previous.set_wait_cause(cause);
+                    }
+                }
+                previous.set_wait_duration(previous_seconds + seconds);
+            }
+            _ => events.push(event),
+        }
+    }
+
+    events.retain_mut(|event| match wait_seconds(event) {
+        Some(seconds) if config.min_seconds.is_some_and(|min| seconds < min) => false,
+        Some(seconds) => {
+            if let Some(max) = config.max_seconds.filter(|max| seconds > *max) {
+                event.set_wait_duration(max);
+            }
+            true
+        }
+        None => true,
+    });
+
+    session.events = events;
+    retain_timeline_entries(session);
+}
+
+/// Duration of a `Wait` event (`None` for other events).
+fn wait_seconds(event: &Event) -> Option<f64> {
+    match event.event_type {
+        EventType::Wait {
+            duration_seconds, ..
+        } => Some(duration_seconds),
+        _ => None,
+    }
+}
+
+/// Drops `edited_timeline` entries of events that were merged away.
+fn retain_timeline_entries(session: &mut RecordingSession) {
+    if let Some(timeline) = session.edited_timeline.as_mut() {
+        let ids: std::collections::HashSet<uuid::Uuid> =
+            session.events.iter().map(|event| event.id).collect();
+        timeline.retain(|entry| ids.contains(&entry.event_id));
+    }
+}
+
+/// Applies one keystroke to reconstructed text.
+///
+/// # Arguments
+/// * `text` - Text typed so far
+/// * `key` - `rdev` key name (e.g., `"KeyA"`, `"Num1"`, `"Backspace"`)
+/// * `shift` - Whether Shift was held
+/// * `typed` - Characters the OS reported for the key, if recorded (preferred
+///   over the US layout mapping)
+pub fn apply_key(text: &mut String, key: &str, shift: bool, typed: Option<&str>) {
+    match key {
+        "Backspace" => {
+            text.pop();
+        }
+        "Delete" => {}
+        _ => {
+            if let Some(typed) = typed {
+                text.push_str(typed);
+            } else if let Some(character) = typed_char(key, shift) {
+                text.push(character);
+            }
+        }
+    }
+}
+
+/// Character typed by a key on a US layout, if it types one.
+pub fn typed_char(key: &str, shift: bool) -> Option<char> {
+    if let Some(letter) = key.strip_prefix("Key") {
+        let letter = letter.chars().next()?;
+        return Some(if shift {
+            letter.to_ascii_uppercase()
+        } else {
+            letter.to_ascii_lowercase()
+        });
+    }
+    if let Some(digit) = key.strip_prefix("Num") {
+        let digit = digit.parse::<usize>().ok().filter(|digit| *digit < 10)?;
+        return Some(if shift {
+            ")!@#$%^&*(".as_bytes()[digit] as char
+        } else {
+            (b'0' + digit as u8) as char
+        });
+    }
+
+    let (plain, shifted) = match key {
+        "Space" => (' ', ' '),
+        "BackQuote" => ('`', '~'),
+        "Minus" => ('-', '_'),
+        "Equal" => ('=', '+'),
+        "LeftBracket" => ('[', '{'),
+        "RightBracket" => (']', '}'),
+        "SemiColon" => (';', ':'),
+        "Quote" => ('\'', '"'),
+        "BackSlash" | "IntlBackslash" => ('\\', '|'),
+        "Comma" => (',', '<'),
+        "Dot" => ('.', '>'),
+        "Slash" => ('/', '?'),
+        _ => return None,
+    };
+    Some(if shift { shifted } else { plain })
+}
+
+/// Keys that are part of typing text.
+fn is_typing(event: &Event) -> bool {
+    matches!(event.event_type, EventType::KeyPress { .. })
+        && (event.action_category == "text_input" || event.action_category == "correction")
+}
+
+/// Moves a finished run of keystrokes into `events`, merged if it typed text.
+fn flush_run(run: &mut Vec<Event>, events: &mut Vec<Event>) {
+    if run.is_empty() {
+        return;
+    }
+
+    let mut text = String::new();
+    let mut keystrokes = 0;
+    for event in run.iter() {
+        if let EventType::KeyPress {
+            key,
+            shift,
+            text: typed,
+            repeat_count,
+            ..
+        } = &event.event_type
+        {
+            // Auto-repeats of a held key type (or delete) again
+            for _ in 0..=*repeat_count {
+                apply_key(&mut text, key, *shift, typed.as_deref());
+            }
+            keystrokes += 1 + *repeat_count as usize;
+        }
+    }
+
+    if text.trim().is_empty() {
+        events.append(run);
+        return;
+    }
+
+    let first = &run[0];
+    let mut merged = Event::new(EventType::TextInput { text, keystrokes }, None);
+    merged.id = first.id;
+    merged.timestamp = first.timestamp;
+    events.push(merged);
+    run.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, shift: bool) -> Event {
+        Event::new(
+            EventType::KeyPress {
+                key: name.to_string(),
+                shift,
+                text: None,
+                duration_ms: None,
+                repeat_count: 0,
+            },
+            None,
+        )
+    }
+
+    #[test]
+    fn test_typing_is_aggregated_with_shift_and_backspace() {
+        let mut session = RecordingSession::new("postprocess-test".to_string());
+        let keys = [
+            ("KeyH", true),
+            ("KeyI", false),
+            ("Num1", true),
+            ("KeyX", false),
+            ("Backspace", false),
+            ("Return", false),
+            ("Backspace", false),
+        ];
+        for (name, shift) in keys {
+            session.add_event(key(name, shift));
+        }
+        let first_id = session.events[0].id;
+
+        aggregate_text_input(&mut session);
+
+        assert_eq!(session.events.len(), 3);
+        assert_eq!(session.events[0].id, first_id);
+        assert_eq!(session.events[0].description(), "Typed \"Hi!\"");
+        match &session.events[0].event_type {
+            EventType::TextInput { text, keystrokes } => {
+                assert_eq!(text, "Hi!");
+                assert_eq!(*keystrokes, 5);
+            }
+            other => panic!("expected TextInput, got {:?}", other),
+        }
+        // A lone Backspace typed no text and stays a KeyPress
+        assert_eq!(session.events[2].action_category, "correction");
+    }
+
+    #[test]
+    fn test_waits_are_coalesced_capped_and_floored() {
+        let wait = |seconds: f64| {
+            Event::new(
+                EventType::Wait {
+                    duration_seconds: seconds,
+                    cause: None,
+                },
+                None,
+            )
+        };
+        let mut session = RecordingSession::new("waits-test".to_string());
+        for event in [
+            wait(3.0),
+            wait(2.5),
+            key("Return", false),
+            wait(900.0),
+            key("Return", false),
+            wait(2.2),
+            key("Return", false),
+        ] {
+            session.add_event(event);
+        }
+        let first_id = session.events[0].id;
+
+        normalize_waits(
+            &mut session,
+            &WaitConfig {
+                max_seconds: Some(60.0),
+                min_seconds: Some(2.5),
+                ..Default::default()
+            },
+        );
+
+        let durations: Vec<Option<f64>> = session.events.iter().map(wait_seconds).collect();
+        assert_eq!(durations, vec![Some(5.5), None, Some(60.0), None, None]);
+        assert_eq!(session.events[0].id, first_id);
+        assert_eq!(session.events[2].description(), "Paused for 60.0 seconds");
+    }
+}
@@ -0,0 +1,405 @@
+//! # Steps Module - Grouping Events into Procedure Steps
+//!
+//! Raw events are too fine-grained for documentation ("Typed: H", "Typed: e",
+//! ...). This module groups them into the steps a person would follow.
+//!
+//! ## Grouping Rules
+//! | Events | Step |
+//! |--------|------|
+//! | Click | One step per click, with its screenshots |
+//! | `TextInput` (aggregated typing) | One "Type ..." step |
+//! | Consecutive `text_input` / `correction` keys | One "Type ..." step with the reconstructed text (sessions saved before aggregation) |
+//! | Submit, navigation, cancel, shortcut, special keys, scroll | One step each |
+//! | `CredentialsEntered` | One "Enter credentials" step (nothing typed is shown) |
+//! | Wait, Idle, Paused, Lifecycle, RecorderRestart | Not a step (timing only) |
+//! | CaptureError | Not a step (diagnostics) |
+//! | Hover, MouseTrail | Not a step (research data) |
+//! | Marker | Not a step; starts a new section (see below) |
+//! | AppSwitch | Not a step (the click or shortcut that caused it is) |
+//! | Capture | One step with its screenshot |
+//! | ManualStep (drafts) | One step with its text and image |
+//!
+//! ## Manual Grouping
+//! Drafts can override the rules per event (`RecordingSession::step_grouping`):
+//! `split` starts a new step at the event (a typing run is cut in two), and
+//! `join` merges the step starting at the event into the one before (titles
+//! joined with `"; "`, the earlier step's screenshots kept).
+//!
+//! ## Sections
+//! Markers divide a recording into sections: every step after a marker gets
+//! its label as `section` (`"Section N"` for unlabelled markers), until the
+//! next marker. Steps before the first marker have no section.
+//!
+//! ## Screenshots
+//! Keyboard steps have no capture of their own, so they show the most recent
+//! click capture: the screen the operator should be looking at.
+//!
+//! A screenshot that is a partial capture (see `frame_diff` module) comes
+//! with its `screenshot_patch`: viewers draw it over the base frame.
+//! Exporters materialize the session first, so their steps never have one.
+
+use crate::postprocess;
+use crate::types::{
+    AnnotationColors, Event, EventType, FramePatch, GroupingRule, RecordingSession, ScrollPosition,
+    WaitCause,
+};
+use serde::Serialize;
+
+/// One step of a recorded procedure.
+#[derive(Serialize, Debug, Clone)]
+pub struct Step {
+    /// 1-based position in the procedure
+    pub number: usize,
+    /// Instruction text (e.g., "Clicked left button at position (709, 328)")
+    pub title: String,
+    /// Action category of the step's first event
+    pub category: String,
+    /// Events that make up the step, in order
+    pub event_ids: Vec<uuid::Uuid>,
+    /// Screen the operator should see (window crop, else full screen)
+    pub screenshot: Option<String>,
+    /// Where `screenshot` goes in its base frame, if it is a partial capture
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screenshot_patch: Option<FramePatch>,
+    /// Close-up of the clicked element (click steps only)
+    pub detail_screenshot: Option<String>,
+    /// Focused window title of the step's first event
+    pub window_title: Option<String>,
+    /// Colors for markers and the step badge on the screenshot (click steps only)
+    pub annotation_colors: Option<AnnotationColors>,
+    /// Time until the screen responded (click steps only, see `latency` module)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_latency_ms: Option<u64>,
+    /// How far the clicked area was scrolled (click steps only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scroll_position: Option<ScrollPosition>,
+    /// Text entered (typing steps only; the title quotes it too)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typed_text: Option<String>,
+    /// Label of the last `Marker` before the step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Day of a multi-part procedure (e.g., "Day 2"; see `parts` module)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<String>,
+}
+
+/// Groups the events of a session into steps (see module docs).
+pub fn group_steps(session: &RecordingSession) -> Vec<Step> {
+    let mut steps: Vec<Step> = Vec::new();
+    let mut last_screenshot: Option<String> = None;
+    let mut typing: Option<(Step, String)> = None;
+    // (index of the first step in the section, section name)
+    let mut sections: Vec<(usize, String)> = Vec::new();
+    let rule = |event_id: &uuid::Uuid| {
+        session
+            .step_grouping
+            .iter()
+            .find(|grouping| grouping.event_id == *event_id)
+            .map(|grouping| grouping.rule)
+    };
+    let push = |steps: &mut Vec<Step>, step: Step| {
+        let first_rule = step.event_ids.first().and_then(rule);
+        match steps.last_mut() {
+            Some(previous) if first_rule == Some(GroupingRule::Join) => join(previous, step),
+            _ => steps.push(step),
+        }
+    };
+
+    for event in &session.events {
+        if rule(&event.id) == Some(GroupingRule::Split) {
+            if let Some((step, text)) = typing.take() {
+                push(&mut steps, finish_typing(step, &text));
+            }
+        }
+
+        if is_typing(event) {
+            let (step, text) = typing.get_or_insert_with(|| {
+                (
+                    new_step(event, String::new(), last_screenshot.clone(), None),
+                    String::new(),
+                )
+            });
+            apply_key(text, event);
+            step.event_ids.push(event.id);
+            continue;
+        }
+
+        if let Some((step, text)) = typing.take() {
+            push(&mut steps, finish_typing(step, &text));
+        }
+
+        match &event.event_type {
+            EventType::Wait { .. }
+            | EventType::Idle { .. }
+            | EventType::Hover { .. }
+            | EventType::Paused { .. }
+            | EventType::Lifecycle { .. }
+            | EventType::RecorderRestart { .. }
+            | EventType::CaptureError { .. }
+            | EventType::MouseTrail { .. }
+            | EventType::AppSwitch { .. } => {}
+            EventType::Marker { label } => {
+                let name = label
+                    .clone()
+                    .unwrap_or_else(|| format!("Section {}", sections.len() + 1));
+                sections.push((steps.len(), name));
+            }
+            EventType::ManualStep { text } => {
+                let screenshot = event.screenshots.full_screen.clone();
+                if screenshot.is_some() {
+                    last_screenshot = screenshot.clone();
+                }
+                let mut step = new_step(event, text.clone(), screenshot, None);
+                step.event_ids.push(event.id);
+                push(&mut steps, step);
+            }
+            EventType::Capture { .. } => {
+                let screenshot = event
+                    .screenshots
+                    .window_crop
+                    .clone()
+                    .or_else(|| event.screenshots.full_screen.clone());
+                if screenshot.is_some() {
+                    last_screenshot = screenshot.clone();
+                }
+                let mut step = new_step(event, event.description().into_owned(), screenshot, None);
+                step.event_ids.push(event.id);
+                push(&mut steps, step);
+            }
+            EventType::Click { .. } => {
+                let screenshot = event
+                    .screenshots
+                    .window_crop
+                    .clone()
+                    .or_else(|| event.screenshots.full_screen.clone());
+                if screenshot.is_some() {
+                    last_screenshot = screenshot.clone();
+                }
+                let mut step = new_step(
+                    event,
+                    event.description().into_owned(),
+                    screenshot,
+                    event.screenshots.click_crop.clone(),
+                );
+                step.annotation_colors = event.annotation_colors.clone();
+                step.event_ids.push(event.id);
+                push(&mut steps, step);
+            }
+            EventType::TextInput { text, .. } => {
+                let mut step = new_step(
+                    event,
+                    format!("Type \"{}\"", text),
+                    last_screenshot.clone(),
+                    None,
+                );
+                step.typed_text = Some(text.clone());
+                step.event_ids.push(event.id);
+                push(&mut steps, step);
+            }
+            EventType::CredentialsEntered { .. } => {
+                let mut step = new_step(
+                    event,
+                    "Enter credentials".to_string(),
+                    last_screenshot.clone(),
+                    None,
+                );
+                step.event_ids.push(event.id);
+                push(&mut steps, step);
+            }
+            EventType::KeyPress { .. } | EventType::Shortcut { .. } | EventType::Scroll { .. } => {
+                let mut step = new_step(
+                    event,
+                    event.description().into_owned(),
+                    last_screenshot.clone(),
+                    None,
+                );
+                step.event_ids.push(event.id);
+                push(&mut steps, step);
+            }
+        }
+    }
+
+    if let Some((step, text)) = typing.take() {
+        push(&mut steps, finish_typing(step, &text));
+    }
+
+    for (index, step) in steps.iter_mut().enumerate() {
+        step.number = index + 1;
+        step.screenshot_patch = step.screenshot.as_deref().and_then(|screenshot| {
+            session
+                .events
+                .iter()
+                .map(|event| &event.screenshots)
+                .find(|screenshots| screenshots.full_screen.as_deref() == Some(screenshot))
+                .and_then(|screenshots| screenshots.full_screen_patch.clone())
+        });
+        step.section = sections
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= index)
+            .map(|(_, name)| name.clone());
+    }
+    steps
+}
+
+/// Pause recorded between a step and the next one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pause {
+    /// Total of the Wait and Idle events after the step
+    pub seconds: f64,
+    /// Cause of the longest of them (`Away` for Idle, see `wait_cause` module)
+    pub cause: Option<WaitCause>,
+}
+
+/// Returns the pause recorded right after a step, if any.
+///
+/// # Arguments
+/// * `events` - Events the steps were grouped from, in order (all parts of a
+///   multi-part procedure)
+/// * `step` - Step to look after
+pub fn pause_after(events: &[&Event], step: &Step) -> Option<Pause> {
+    let last = events
+        .iter()
+        .rposition(|event| step.event_ids.contains(&event.id))?;
+    let mut pause: Option<Pause> = None;
+    let mut longest = 0.0;
+    for event in &events[last + 1..] {
+        let (seconds, cause) = match &event.event_type {
+            EventType::Wait {
+                duration_seconds,
+                cause,
+            } => (*duration_seconds, *cause),
+            EventType::Idle { duration_seconds } => (*duration_seconds, Some(WaitCause::Away)),
+            _ => break,
+        };
+        let total = pause.get_or_insert(Pause {
+            seconds: 0.0,
+            cause: None,
+        });
+        total.seconds += seconds;
+        if seconds > longest {
+            longest = seconds;
+            total.cause = cause;
+        }
+    }
+    pause.filter(|pause| pause.seconds > 0.0)
+}
+
+/// Merges a step into the one before (see "Manual Grouping").
+fn join(previous: &mut Step, step: Step) {
+    previous.title = format!("{}; {}", previous.title, step.title);
+    previous.event_ids.extend(step.event_ids);
+    if previous.screenshot.is_none() {
+        previous.screenshot = step.screenshot;
+    }
+    if previous.detail_screenshot.is_none() {
+        previous.detail_screenshot = step.detail_screenshot;
+    }
+    if previous.typed_text.is_none() {
+        previous.typed_text = step.typed_text;
+    }
+}
+
+/// Keys that are part of typing text (merged into one step).
+fn is_typing(event: &Event) -> bool {
+    matches!(event.event_type, EventType::KeyPress { .. })
+        && (event.action_category == "text_input" || event.action_category == "correction")
+}
+
+fn new_step(
+    event: &Event,
+    title: String,
+    screenshot: Option<String>,
+    detail_screenshot: Option<String>,
+) -> Step {
+    Step {
+        number: 0,
+        title,
+        category: event.action_category.clone(),
+        event_ids: Vec::new(),
+        screenshot,
+        screenshot_patch: None,
+        detail_screenshot,
+        window_title: event.window_title.clone(),
+        annotation_colors: None,
+        response_latency_ms: event.response_latency_ms,
+        scroll_position: event.scroll_position.clone(),
+        typed_text: None,
+        section: None,
+        part: None,
+    }
+}
+
+/// Applies one typing key (and its auto-repeats) to the reconstructed text.
+fn apply_key(text: &mut String, event: &Event) {
+    if let EventType::KeyPress {
+        key,
+        shift,
+        text: typed,
+        repeat_count,
+        ..
+    } = &event.event_type
+    {
+        for _ in 0..=*repeat_count {
+            postprocess::apply_key(text, key, *shift, typed.as_deref());
+        }
+    }
+}
+
+fn finish_typing(mut step: Step, text: &str) -> Step {
+    step.category = "text_input".to_string();
+    if text.trim().is_empty() {
+        step.title = format!("Edit text ({} keys)", step.event_ids.len());
+    } else {
+        step.title = format!("Type \"{}\"", text);
+        step.typed_text = Some(text.to_string());
+    }
+    step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MouseButton, Position};
+
+    fn key(name: &str) -> Event {
+        Event::new(
+            EventType::KeyPress {
+                key: name.to_string(),
+                shift: false,
+                text: None,
+                duration_ms: None,
+                repeat_count: 0,
+            },
+            None,
+        )
+    }
+
+    #[test]
+    fn test_typing_is_merged_into_one_step() {
+        let mut session = RecordingSession::new("steps-test".to_string());
+        session.add_event(
+            Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(10.0, 10.0)),
+            )
+            .with_screenshots(Some("full.png".to_string()), None, None),
+        );
+        for name in ["KeyH", "KeyI", "KeyX", "Backspace", "Return"] {
+            session.add_event(key(name));
+        }
+
+        let steps = group_steps(&session);
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[1].title, "Type \"hi\"");
+        assert_eq!(steps[1].typed_text.as_deref(), Some("hi"));
+        assert_eq!(steps[1].event_ids.len(), 4);
+        assert_eq!(steps[1].screenshot.as_deref(), Some("full.png"));
+        assert_eq!(steps[2].number, 3);
+    }
+}
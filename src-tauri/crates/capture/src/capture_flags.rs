@@ -0,0 +1,74 @@
+//! # Capture Flags Module - Runtime Input Toggles
+//!
+//! Lets the user switch individual kinds of input off (and on again) during
+//! a live recording, e.g., disable keyboard capture while typing a password
+//! without pausing everything else.
+//!
+//! | Flag | Default | When off |
+//! |------|---------|----------|
+//! | `clicks` | on | Button presses aren't recorded |
+//! | `keys` | on | Key presses and shortcuts aren't recorded |
+//! | `scroll` | off | Wheel events aren't recorded (noisy, opt-in) |
+//! | `screenshots` | on | Clicks are recorded without screenshots |
+//!
+//! Flags are runtime state only: they take effect for the next event and are
+//! reset to the defaults when a recording starts, so a forgotten toggle
+//! never silently drops input from the next session.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Which kinds of input are currently recorded.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct CaptureFlags {
+    pub clicks: bool,
+    pub keys: bool,
+    pub scroll: bool,
+    pub screenshots: bool,
+}
+
+impl Default for CaptureFlags {
+    fn default() -> Self {
+        Self {
+            clicks: true,
+            keys: true,
+            scroll: false,
+            screenshots: true,
+        }
+    }
+}
+
+/// Flags of the active recording.
+static FLAGS: Lazy<Arc<Mutex<CaptureFlags>>> =
+    Lazy::new(|| Arc::new(Mutex::new(CaptureFlags::default())));
+
+/// Returns the current flags (cheap enough to call for every event).
+pub fn current() -> CaptureFlags {
+    *FLAGS.lock().unwrap()
+}
+
+/// Replaces the flags; takes effect for the next event.
+pub fn set(flags: CaptureFlags) {
+    *FLAGS.lock().unwrap() = flags;
+}
+
+/// Restores the defaults (at the start of every recording).
+pub fn reset() {
+    set(CaptureFlags::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_flags_keep_defaults() {
+        let flags: CaptureFlags = serde_json::from_str(r#"{"keys": false}"#).unwrap();
+
+        assert!(!flags.keys);
+        assert!(flags.clicks && flags.screenshots);
+        assert!(!flags.scroll);
+    }
+}
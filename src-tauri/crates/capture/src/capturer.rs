@@ -0,0 +1,220 @@
+//! # Capturer Module - Screen Capture Backends
+//!
+//! Everything that reads the screen (event captures, latency and wait-cause
+//! sampling, the spike screenshot) goes through a `ScreenCapturer` instead
+//! of calling the `screenshots` crate directly:
+//!
+//! | Backend | Frames |
+//! |---------|--------|
+//! | `ScreenshotsCapturer` | The `screenshots` crate (CoreGraphics, DXGI/GDI, X11/Wayland); used for recordings |
+//! | `MockCapturer` | Canned images per display, nothing read from the screen (unit tests, benchmarks; `mock` feature) |
+//!
+//! Other backends (ScreenCaptureKit, `xcap`) implement the trait the same
+//! way and replace `default_backend()`.
+//!
+//! ## Displays
+//! Displays are described by `display::DisplayGeometry` with the physical
+//! size unknown (`0`); the caller fills it in from the captured image, which
+//! is what coordinate translation derives the scale from (see `display`
+//! module).
+
+use crate::display::{self, DisplayGeometry};
+use image::RgbaImage;
+use screenshots::Screen;
+
+/// A source of display lists and full-display frames (see module docs).
+pub trait ScreenCapturer: Send + Sync {
+    /// Short name for logs (e.g., `"screenshots"`).
+    fn name(&self) -> &'static str;
+
+    /// All connected displays, primary first.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<DisplayGeometry>)` - Displays (physical size unknown)
+    /// * `Err(String)` - Error if the displays couldn't be listed
+    fn displays(&self) -> Result<Vec<DisplayGeometry>, String>;
+
+    /// Captures a whole display in physical pixels.
+    ///
+    /// # Returns
+    /// * `Ok(RgbaImage)` - 8-bit RGBA frame (see `display::rgba_from_capture()`)
+    /// * `Err(String)` - Error if the display is gone or capture isn't permitted
+    fn capture(&self, display: &DisplayGeometry) -> Result<RgbaImage, String>;
+
+    /// The display containing a global logical point, if any.
+    fn display_at(&self, x: i32, y: i32) -> Option<DisplayGeometry> {
+        self.displays()
+            .ok()?
+            .into_iter()
+            .find(|display| display.contains(x as f64, y as f64))
+    }
+}
+
+/// Backend used for recordings.
+pub fn default_backend() -> &'static dyn ScreenCapturer {
+    &ScreenshotsCapturer
+}
+
+/// The display containing a global logical point, or the primary display.
+///
+/// # Returns
+/// * `Ok(DisplayGeometry)` - Display to capture (physical size unknown)
+/// * `Err(String)` - Error if no display could be found
+pub fn display_or_primary(
+    capturer: &dyn ScreenCapturer,
+    x: i32,
+    y: i32,
+) -> Result<DisplayGeometry, String> {
+    if let Some(display) = capturer.display_at(x, y) {
+        return Ok(display);
+    }
+    capturer
+        .displays()?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No screens found".to_string())
+}
+
+/// The `screenshots` crate.
+pub struct ScreenshotsCapturer;
+
+impl ScreenshotsCapturer {
+    /// Finds the `screenshots` screen of a display.
+    fn screen(display: &DisplayGeometry) -> Result<Screen, String> {
+        // The display's origin lies on it, and looking up one point is cheap
+        if let Ok(screen) = Screen::from_point(display.origin_x, display.origin_y) {
+            if screen.display_info.id == display.id {
+                return Ok(screen);
+            }
+        }
+        Screen::all()
+            .map_err(|e| format!("Failed to get screens: {:?}", e))?
+            .into_iter()
+            .find(|screen| screen.display_info.id == display.id)
+            .ok_or_else(|| format!("Display {} not found", display.id))
+    }
+}
+
+impl ScreenCapturer for ScreenshotsCapturer {
+    fn name(&self) -> &'static str {
+        "screenshots"
+    }
+
+    fn displays(&self) -> Result<Vec<DisplayGeometry>, String> {
+        let screens = Screen::all().map_err(|e| format!("Failed to get screens: {:?}", e))?;
+        Ok(screens.iter().map(geometry).collect())
+    }
+
+    fn capture(&self, display: &DisplayGeometry) -> Result<RgbaImage, String> {
+        let raw = Self::screen(display)?
+            .capture()
+            .map_err(|e| format!("Failed to capture screen: {:?}", e))?;
+        // The `screenshots` crate always delivers 8-bit RGBA
+        display::rgba_from_capture(
+            raw.width(),
+            raw.height(),
+            display::PixelFormat::Rgba8,
+            raw.as_raw(),
+        )
+    }
+
+    fn display_at(&self, x: i32, y: i32) -> Option<DisplayGeometry> {
+        Screen::from_point(x, y)
+            .ok()
+            .map(|screen| geometry(&screen))
+    }
+}
+
+/// Geometry of a `screenshots` screen (physical size unknown).
+fn geometry(screen: &Screen) -> DisplayGeometry {
+    let info = &screen.display_info;
+    DisplayGeometry {
+        id: info.id,
+        origin_x: info.x,
+        origin_y: info.y,
+        logical_width: info.width,
+        logical_height: info.height,
+        physical_width: 0,
+        physical_height: 0,
+        reported_scale: info.scale_factor,
+    }
+}
+
+/// Canned frames instead of the screen.
+#[cfg(any(test, feature = "mock"))]
+pub struct MockCapturer {
+    /// Displays with the frame each one returns
+    pub screens: Vec<(DisplayGeometry, RgbaImage)>,
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl MockCapturer {
+    /// Displays filled with one color each, at their reported scale.
+    pub fn solid(displays: &[DisplayGeometry], color: image::Rgba<u8>) -> Self {
+        let screens = displays
+            .iter()
+            .map(|display| {
+                let scale = display.reported_scale as f64;
+                let frame = RgbaImage::from_pixel(
+                    (display.logical_width as f64 * scale) as u32,
+                    (display.logical_height as f64 * scale) as u32,
+                    color,
+                );
+                (*display, frame)
+            })
+            .collect();
+        MockCapturer { screens }
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl ScreenCapturer for MockCapturer {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn displays(&self) -> Result<Vec<DisplayGeometry>, String> {
+        Ok(self.screens.iter().map(|(display, _)| *display).collect())
+    }
+
+    fn capture(&self, display: &DisplayGeometry) -> Result<RgbaImage, String> {
+        self.screens
+            .iter()
+            .find(|(screen, _)| screen.id == display.id)
+            .map(|(_, frame)| frame.clone())
+            .ok_or_else(|| format!("Display {} not found", display.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_backend_finds_display_and_frame() {
+        let display = |id: u32, origin_x: i32, scale: f32| DisplayGeometry {
+            id,
+            origin_x,
+            origin_y: 0,
+            logical_width: 1440,
+            logical_height: 900,
+            physical_width: 0,
+            physical_height: 0,
+            reported_scale: scale,
+        };
+        let mock = MockCapturer::solid(
+            &[display(1, 0, 2.0), display(2, 1440, 1.0)],
+            image::Rgba([255, 255, 255, 255]),
+        );
+
+        // A click on the secondary display captures that display
+        let secondary = display_or_primary(&mock, 2000, 100).unwrap();
+        assert_eq!(secondary.id, 2);
+        assert_eq!(mock.capture(&secondary).unwrap().dimensions(), (1440, 900));
+
+        // Off every display: the primary
+        let primary = display_or_primary(&mock, -50, 100).unwrap();
+        assert_eq!(primary.id, 1);
+        assert_eq!(mock.capture(&primary).unwrap().dimensions(), (2880, 1800));
+    }
+}
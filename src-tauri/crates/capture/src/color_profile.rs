@@ -0,0 +1,161 @@
+//! # Color Profile Module - Display ICC Tagging
+//!
+//! Screen captures contain pixels in the *display's* color space. Saved as
+//! untagged PNGs, viewers assume sRGB, so captures from wide-gamut displays
+//! (Display P3 on modern Macs) look washed out.
+//!
+//! ## Approach
+//! 1. Read the display's ICC profile from the OS (`display_icc_profile()`)
+//! 2. Embed it as a PNG `iCCP` chunk (`embed_icc_profile()`)
+//!
+//! Color-managed viewers and browsers then render the screenshot exactly as
+//! the user saw it. Pixel data is left untouched (no lossy conversion).
+//!
+//! ## Platform Support
+//! - **macOS**: `CGDisplayCopyColorSpace` → `CGColorSpaceCopyICCData`
+//! - **Other platforms**: No profile (PNG saved untagged, as before)
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// PNG signature (8 bytes) + IHDR chunk (4 length + 4 type + 13 data + 4 CRC).
+const IHDR_END: usize = 8 + 4 + 4 + 13 + 4;
+
+/// Profile name written into the `iCCP` chunk (1-79 Latin-1 characters).
+const PROFILE_NAME: &[u8] = b"Display";
+
+/// Returns the ICC profile of a display, if the platform exposes one.
+///
+/// # Arguments
+/// * `display_id` - Display identifier from `screenshots::DisplayInfo::id`
+///   (a `CGDirectDisplayID` on macOS)
+#[cfg(target_os = "macos")]
+pub fn display_icc_profile(display_id: u32) -> Option<Vec<u8>> {
+    macos::display_icc_profile(display_id)
+}
+
+/// Returns the ICC profile of a display, if the platform exposes one.
+///
+/// Not implemented on this platform; screenshots are saved untagged.
+#[cfg(not(target_os = "macos"))]
+pub fn display_icc_profile(_display_id: u32) -> Option<Vec<u8>> {
+    None
+}
+
+/// Inserts an `iCCP` chunk carrying `icc_profile` into an encoded PNG.
+///
+/// The chunk is placed directly after `IHDR`, as required by the PNG spec
+/// (it must precede `PLTE` and `IDAT`).
+///
+/// # Arguments
+/// * `png` - Encoded PNG bytes (as produced by the `image` crate)
+/// * `icc_profile` - Raw ICC profile bytes
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - PNG bytes with the embedded profile
+/// * `Err(String)` - Error if `png` is not a PNG or compression fails
+pub fn embed_icc_profile(png: &[u8], icc_profile: &[u8]) -> Result<Vec<u8>, String> {
+    if png.len() < IHDR_END || &png[12..16] != b"IHDR" {
+        return Err("Not a PNG image (missing IHDR chunk)".to_string());
+    }
+
+    // Chunk data: name, null separator, compression method 0 (zlib), compressed profile
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(icc_profile)
+        .map_err(|e| format!("Failed to compress ICC profile: {:?}", e))?;
+    let compressed = encoder
+        .finish()
+        .map_err(|e| format!("Failed to compress ICC profile: {:?}", e))?;
+
+    let mut data = Vec::with_capacity(PROFILE_NAME.len() + 2 + compressed.len());
+    data.extend_from_slice(PROFILE_NAME);
+    data.push(0);
+    data.push(0);
+    data.extend_from_slice(&compressed);
+
+    // CRC covers chunk type + chunk data
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(b"iCCP");
+    hasher.update(&data);
+    let crc = hasher.finalize();
+
+    let mut output = Vec::with_capacity(png.len() + data.len() + 12);
+    output.extend_from_slice(&png[..IHDR_END]);
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    output.extend_from_slice(b"iCCP");
+    output.extend_from_slice(&data);
+    output.extend_from_slice(&crc.to_be_bytes());
+    output.extend_from_slice(&png[IHDR_END..]);
+
+    Ok(output)
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::ffi::c_void;
+
+    type CFTypeRef = *const c_void;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGDisplayCopyColorSpace(display: u32) -> CFTypeRef;
+        fn CGColorSpaceCopyICCData(space: CFTypeRef) -> CFTypeRef;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFDataGetLength(data: CFTypeRef) -> isize;
+        fn CFDataGetBytePtr(data: CFTypeRef) -> *const u8;
+        fn CFRelease(cf: CFTypeRef);
+    }
+
+    pub fn display_icc_profile(display_id: u32) -> Option<Vec<u8>> {
+        // SAFETY: Both Copy functions return owned (+1) references or null;
+        // each non-null reference is released exactly once below.
+        unsafe {
+            let space = CGDisplayCopyColorSpace(display_id);
+            if space.is_null() {
+                return None;
+            }
+
+            let data = CGColorSpaceCopyICCData(space);
+            CFRelease(space);
+            if data.is_null() {
+                return None;
+            }
+
+            let length = CFDataGetLength(data);
+            let bytes = CFDataGetBytePtr(data);
+            let profile = if length > 0 && !bytes.is_null() {
+                Some(std::slice::from_raw_parts(bytes, length as usize).to_vec())
+            } else {
+                None
+            };
+            CFRelease(data);
+
+            profile
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, ImageOutputFormat};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_embedded_profile_keeps_png_decodable() {
+        let mut png = Vec::new();
+        DynamicImage::new_rgba8(4, 4)
+            .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+            .unwrap();
+
+        let tagged = embed_icc_profile(&png, b"fake icc profile").unwrap();
+
+        assert_eq!(&tagged[IHDR_END + 4..IHDR_END + 8], b"iCCP");
+        assert!(image::load_from_memory(&tagged).is_ok());
+    }
+}
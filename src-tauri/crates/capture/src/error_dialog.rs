@@ -0,0 +1,142 @@
+//! # Error Dialog Module - Failure Moment Heuristics
+//!
+//! Flags events whose capture most likely shows an OS or application error
+//! dialog, so long recordings can be searched for failure moments
+//! (`error_dialog_detected` on each event).
+//!
+//! ## Signals
+//! | Signal | Source | Available |
+//! |--------|--------|-----------|
+//! | Error keyword in window title | `active-win-pos-rs` | At capture |
+//! | Alert-sized focused window | Window bounds vs. display | At capture |
+//! | Red iconography | Red pixel ratio of the window crop | At capture |
+//! | Error keyword in screen text | OCR index (`ocr` module) | After OCR |
+//!
+//! ## Decision
+//! - A title keyword or an OCR keyword alone is enough
+//! - Otherwise an alert-sized window must also contain red iconography;
+//!   either signal alone is too common (small utility windows, red logos)
+
+use image::DynamicImage;
+
+/// Lowercase phrases that indicate an error message.
+const ERROR_KEYWORDS: &[&str] = &[
+    "error",
+    "failed",
+    "failure",
+    "exception",
+    "unable to",
+    "cannot be",
+    "can't be",
+    "could not",
+    "not responding",
+    "quit unexpectedly",
+    "access denied",
+    "permission denied",
+    "something went wrong",
+];
+
+/// Focused windows smaller than this fraction of the display count as alert-sized.
+const ALERT_MAX_AREA_RATIO: f64 = 0.15;
+
+/// Red pixel fraction range typical of an error icon inside a dialog.
+/// Below: no icon; above: a red-themed window or photo rather than an icon.
+const RED_RATIO_RANGE: (f64, f64) = (0.002, 0.12);
+
+/// Capture-time observations about the focused window.
+#[derive(Debug, Clone, Default)]
+pub struct DialogSignals<'a> {
+    /// Title of the focused window
+    pub window_title: &'a str,
+    /// Focused window area divided by display area (logical)
+    pub window_area_ratio: f64,
+    /// Fraction of strongly red pixels in the window crop
+    pub red_ratio: f64,
+}
+
+/// Decides whether the capture-time signals indicate an error dialog.
+pub fn is_error_dialog(signals: &DialogSignals) -> bool {
+    if find_error_keyword(signals.window_title).is_some() {
+        return true;
+    }
+
+    let alert_sized =
+        signals.window_area_ratio > 0.0 && signals.window_area_ratio <= ALERT_MAX_AREA_RATIO;
+    let red_icon = signals.red_ratio >= RED_RATIO_RANGE.0 && signals.red_ratio <= RED_RATIO_RANGE.1;
+
+    alert_sized && red_icon
+}
+
+/// Returns the first error keyword contained in `text` (case-insensitive).
+pub fn find_error_keyword(text: &str) -> Option<&'static str> {
+    let text = text.to_lowercase();
+    ERROR_KEYWORDS
+        .iter()
+        .copied()
+        .find(|keyword| text.contains(keyword))
+}
+
+/// Fraction of pixels that are strongly red (error icons, red badges).
+///
+/// Sampled on a grid so multi-megapixel crops stay cheap.
+pub fn red_pixel_ratio(image: &DynamicImage) -> f64 {
+    /// Examine every Nth pixel in each direction
+    const SAMPLE_STEP: u32 = 4;
+
+    let rgba = image.to_rgba8();
+    let mut sampled = 0u64;
+    let mut red = 0u64;
+
+    for y in (0..rgba.height()).step_by(SAMPLE_STEP as usize) {
+        for x in (0..rgba.width()).step_by(SAMPLE_STEP as usize) {
+            let [r, g, b, _] = rgba.get_pixel(x, y).0;
+            sampled += 1;
+            if r >= 180 && (g as u16) < r as u16 * 6 / 10 && (b as u16) < r as u16 * 6 / 10 {
+                red += 1;
+            }
+        }
+    }
+
+    if sampled == 0 {
+        0.0
+    } else {
+        red as f64 / sampled as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_alert_needs_size_and_red_icon() {
+        // Gray 100x100 dialog with a 10x10 red icon (1% red)
+        let mut dialog = RgbaImage::from_pixel(100, 100, Rgba([230, 230, 230, 255]));
+        for y in 0..10 {
+            for x in 0..10 {
+                dialog.put_pixel(x, y, Rgba([220, 30, 30, 255]));
+            }
+        }
+        let red_ratio = red_pixel_ratio(&DynamicImage::ImageRgba8(dialog));
+
+        let alert = DialogSignals {
+            window_title: "Finder",
+            window_area_ratio: 0.05,
+            red_ratio,
+        };
+        assert!(is_error_dialog(&alert));
+
+        let maximized = DialogSignals {
+            window_area_ratio: 0.9,
+            ..alert.clone()
+        };
+        assert!(!is_error_dialog(&maximized));
+
+        let titled = DialogSignals {
+            window_title: "Sync Failed",
+            ..maximized
+        };
+        assert!(is_error_dialog(&titled));
+    }
+}
@@ -46,7 +46,8 @@ use rdev::{listen, Event};
 /// # Invocation
 /// Called by `start_event_listener()` Tauri command, which spawns this
 /// in a background thread:
-/// ```rust
+/// ```no_run
+/// # use flowtrace_capture::event_monitor;
 /// std::thread::spawn(|| {
 ///     event_monitor::test_listener();
 /// });
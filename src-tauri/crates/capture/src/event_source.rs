@@ -0,0 +1,172 @@
+//! # Event Source Module - Where Input Comes From
+//!
+//! The recorder doesn't call `rdev` directly: the listener thread in
+//! `lib.rs` runs an `EventSource` and hands every event it delivers to
+//! `handle_event()`. Anything that produces `rdev::Event`s can drive a
+//! recording:
+//!
+//! | Source | Events |
+//! |--------|--------|
+//! | `RdevSource` | Global OS input via `rdev::listen()` (used for recordings) |
+//! | `ScriptedSource` | A fixed list, delivered with the scripted delays (tests, replays) |
+//!
+//! Future backends (a browser extension, a remote agent) implement the
+//! trait the same way: block in `run()` and call back once per event.
+//!
+//! ## Event Timestamps
+//! `handle_event()` measures waits and hold durations with the time of
+//! delivery, so sources should deliver events when they happen rather than
+//! in bursts, and stamp `rdev::Event::time` accordingly (`synthetic_event()`
+//! does).
+
+use std::time::{Duration, SystemTime};
+
+/// Receives every delivered event, in order, on the source's thread.
+pub type EventCallback = Box<dyn FnMut(rdev::Event) + Send>;
+
+/// A producer of input events (see module docs).
+pub trait EventSource: Send {
+    /// Short name for logs (e.g., `"rdev"`).
+    fn name(&self) -> &'static str;
+
+    /// Delivers events to `callback` until the source is exhausted.
+    ///
+    /// Blocks the calling thread; may never return (live input).
+    ///
+    /// # Returns
+    /// * `Ok(())` - The source ended normally
+    /// * `Err(String)` - Error if the source couldn't start or failed
+    fn run(self: Box<Self>, callback: EventCallback) -> Result<(), String>;
+}
+
+/// Global OS input (`rdev::listen()`, needs the Accessibility permission on macOS).
+pub struct RdevSource;
+
+impl EventSource for RdevSource {
+    fn name(&self) -> &'static str {
+        "rdev"
+    }
+
+    fn run(self: Box<Self>, callback: EventCallback) -> Result<(), String> {
+        rdev::listen(callback).map_err(|e| format!("{:?}", e))
+    }
+}
+
+/// A fixed sequence of events, each delivered after its delay.
+///
+/// # Example
+/// ```rust
+/// # use flowtrace_capture::event_source::ScriptedSource;
+/// # use rdev::{Button, EventType};
+/// # use std::time::Duration;
+/// let source = ScriptedSource::default()
+///     .then(Duration::ZERO, EventType::MouseMove { x: 709.0, y: 328.0 })
+///     .then(Duration::from_millis(80), EventType::ButtonPress(Button::Left))
+///     .then(Duration::from_millis(96), EventType::ButtonRelease(Button::Left));
+/// ```
+#[derive(Default)]
+pub struct ScriptedSource {
+    script: Vec<(Duration, rdev::Event)>,
+}
+
+impl ScriptedSource {
+    /// Appends an event of the given type, delivered `delay` after the previous one.
+    pub fn then(self, delay: Duration, event_type: rdev::EventType) -> Self {
+        self.then_event(delay, synthetic_event(event_type, None))
+    }
+
+    /// Appends an event as given (e.g., with `unicode` text), re-stamped on delivery.
+    pub fn then_event(mut self, delay: Duration, event: rdev::Event) -> Self {
+        self.script.push((delay, event));
+        self
+    }
+}
+
+impl EventSource for ScriptedSource {
+    fn name(&self) -> &'static str {
+        "scripted"
+    }
+
+    fn run(self: Box<Self>, mut callback: EventCallback) -> Result<(), String> {
+        for (delay, mut event) in self.script {
+            if !delay.is_zero() {
+                std::thread::sleep(delay);
+            }
+            event.time = SystemTime::now();
+            callback(event);
+        }
+        Ok(())
+    }
+}
+
+/// Builds an `rdev` event as the OS listener would report it, timestamped now.
+///
+/// # Arguments
+/// * `event_type` - What happened
+/// * `text` - Characters a key press produced (`None` for other events)
+pub fn synthetic_event(event_type: rdev::EventType, text: Option<&str>) -> rdev::Event {
+    rdev::Event {
+        time: SystemTime::now(),
+        unicode: text.map(|text| rdev::UnicodeInfo {
+            name: Some(text.to_string()),
+            unicode: text.encode_utf16().collect(),
+            is_dead: false,
+        }),
+        event_type,
+        platform_code: 0,
+        position_code: 0,
+        usb_hid: 0,
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        extra_data: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_scripted_source_delivers_in_order() {
+        let source = ScriptedSource::default()
+            .then(
+                Duration::ZERO,
+                rdev::EventType::MouseMove { x: 5.0, y: 5.0 },
+            )
+            .then(
+                Duration::from_millis(20),
+                rdev::EventType::ButtonPress(rdev::Button::Left),
+            )
+            .then_event(
+                Duration::ZERO,
+                synthetic_event(rdev::EventType::KeyPress(rdev::Key::KeyA), Some("a")),
+            );
+
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let sink = delivered.clone();
+        Box::new(source)
+            .run(Box::new(move |event| sink.lock().unwrap().push(event)))
+            .unwrap();
+
+        let delivered = delivered.lock().unwrap();
+        let types: Vec<_> = delivered.iter().map(|event| event.event_type).collect();
+        assert_eq!(
+            types,
+            [
+                rdev::EventType::MouseMove { x: 5.0, y: 5.0 },
+                rdev::EventType::ButtonPress(rdev::Button::Left),
+                rdev::EventType::KeyPress(rdev::Key::KeyA),
+            ]
+        );
+        // Stamped on delivery, after the delay
+        let delay = delivered[1].time.duration_since(delivered[0].time).unwrap();
+        assert!(delay >= Duration::from_millis(20));
+        assert_eq!(
+            delivered[2]
+                .unicode
+                .as_ref()
+                .and_then(|info| info.name.as_deref()),
+            Some("a")
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! # FlowTrace Capture - Screen and Input Sources
+//!
+//! Everything that reads the screen or the user's input, behind traits the
+//! app and its tests can swap out:
+//!
+//! - **`capturer`**: Screen capture backends (`MockCapturer` with the `mock` feature)
+//! - **`event_source`**: Input sources (OS listener, scripted events)
+//! - **`window_capture`**: Snapshots of a window by title
+//! - **`color_profile`**: Display ICC tagging of saved captures
+//! - **`capture_flags`**: Runtime toggles for kinds of input
+//! - **`error_dialog`**: Failure moment heuristics
+//! - **`event_monitor`**: `rdev` listener spike test
+
+pub mod capture_flags;
+pub mod capturer;
+pub mod color_profile;
+pub mod error_dialog;
+pub mod event_monitor;
+pub mod event_source;
+pub mod window_capture;
+
+use flowtrace_core::display;
@@ -0,0 +1,373 @@
+//! # Window Capture Module - Snapshots of a Window by Title
+//!
+//! Finds an on-screen window whose title matches a pattern and captures just
+//! that window, whether or not it is focused. Used by the
+//! `capture_window_by_title` command, and meant as a building block for
+//! recordings scoped to one window.
+//!
+//! ## Platform Support
+//! - **macOS**: `CGWindowListCopyWindowInfo` enumerates windows front to
+//!   back; `CGWindowListCreateImage` captures one window's own pixels, so
+//!   windows partly covered by others are captured intact. Window titles
+//!   require the Screen Recording permission the recorder already needs.
+//! - **Other platforms**: No windows are listed (capture fails with an error)
+//!
+//! Minimized windows and windows on other Spaces/virtual desktops aren't on
+//! screen and can't be captured.
+//!
+//! ## Patterns
+//! Titles are matched case-insensitively as regular expressions, so plain
+//! text matches anywhere in the title (`"invoice"` matches `"Invoice #1234 —
+//! Google Chrome"`). If several windows match, the front-most one wins.
+
+use image::RgbaImage;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+/// An on-screen application window.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct WindowInfo {
+    /// Platform window id (`kCGWindowNumber` on macOS)
+    pub id: u32,
+    pub title: String,
+    pub app_name: String,
+    /// Process id of the owning application
+    pub process_id: u64,
+    /// Bounds in global logical coordinates
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Lists on-screen application windows, front-most first.
+#[cfg(target_os = "macos")]
+pub fn list_windows() -> Vec<WindowInfo> {
+    macos::list_windows()
+}
+
+/// Lists on-screen application windows, front-most first.
+///
+/// Not implemented on this platform.
+#[cfg(not(target_os = "macos"))]
+pub fn list_windows() -> Vec<WindowInfo> {
+    Vec::new()
+}
+
+/// Captures a window's content (without its shadow).
+///
+/// # Returns
+/// * `Ok(RgbaImage)` - Window pixels in physical resolution
+/// * `Err(String)` - Window is gone, or capture isn't supported here
+#[cfg(target_os = "macos")]
+pub fn capture(window: &WindowInfo) -> Result<RgbaImage, String> {
+    macos::capture(window.id)
+}
+
+/// Captures a window's content (without its shadow).
+///
+/// Not implemented on this platform.
+#[cfg(not(target_os = "macos"))]
+pub fn capture(_window: &WindowInfo) -> Result<RgbaImage, String> {
+    Err("Capturing windows by title isn't supported on this platform".to_string())
+}
+
+/// Finds the front-most on-screen window whose title matches `pattern`.
+///
+/// # Returns
+/// * `Ok(WindowInfo)` - Matching window
+/// * `Err(String)` - Invalid pattern, or no window matches
+pub fn find_window(pattern: &str) -> Result<WindowInfo, String> {
+    let pattern = compile_pattern(pattern)?;
+    pick_window(list_windows(), &pattern)
+        .ok_or_else(|| format!("No window title matches '{}'", pattern.as_str()))
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    if pattern.trim().is_empty() {
+        return Err("Window title pattern is empty".to_string());
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid window title pattern '{}': {}", pattern, e))
+}
+
+/// First window (in front-to-back order) whose title matches.
+fn pick_window(windows: Vec<WindowInfo>, pattern: &Regex) -> Option<WindowInfo> {
+    windows
+        .into_iter()
+        .find(|window| pattern.is_match(&window.title))
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::WindowInfo;
+    use image::RgbaImage;
+    use std::ffi::{c_char, c_void, CString};
+
+    type CFTypeRef = *const c_void;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    struct CGPoint {
+        x: f64,
+        y: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    struct CGSize {
+        width: f64,
+        height: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    struct CGRect {
+        origin: CGPoint,
+        size: CGSize,
+    }
+
+    const ON_SCREEN_ONLY: u32 = 1 << 0;
+    const INCLUDING_WINDOW: u32 = 1 << 3;
+    const EXCLUDE_DESKTOP_ELEMENTS: u32 = 1 << 4;
+    const IMAGE_BOUNDS_IGNORE_FRAMING: u32 = 1 << 0;
+    const CF_NUMBER_FLOAT64_TYPE: isize = 6;
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGWindowListCopyWindowInfo(option: u32, relative_to_window: u32) -> CFTypeRef;
+        fn CGWindowListCreateImage(
+            screen_bounds: CGRect,
+            option: u32,
+            window_id: u32,
+            image_option: u32,
+        ) -> CFTypeRef;
+        fn CGRectMakeWithDictionaryRepresentation(dict: CFTypeRef, rect: *mut CGRect) -> bool;
+        fn CGImageGetWidth(image: CFTypeRef) -> usize;
+        fn CGImageGetHeight(image: CFTypeRef) -> usize;
+        fn CGImageGetBitsPerPixel(image: CFTypeRef) -> usize;
+        fn CGImageGetBytesPerRow(image: CFTypeRef) -> usize;
+        fn CGImageGetDataProvider(image: CFTypeRef) -> CFTypeRef;
+        fn CGDataProviderCopyData(provider: CFTypeRef) -> CFTypeRef;
+        fn CGImageRelease(image: CFTypeRef);
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFArrayGetCount(array: CFTypeRef) -> isize;
+        fn CFArrayGetValueAtIndex(array: CFTypeRef, index: isize) -> CFTypeRef;
+        fn CFDictionaryGetValue(dict: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+        fn CFNumberGetValue(number: CFTypeRef, number_type: isize, value: *mut c_void) -> bool;
+        fn CFStringCreateWithCString(
+            allocator: CFTypeRef,
+            string: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFStringGetLength(string: CFTypeRef) -> isize;
+        fn CFStringGetMaximumSizeForEncoding(length: isize, encoding: u32) -> isize;
+        fn CFStringGetCString(
+            string: CFTypeRef,
+            buffer: *mut c_char,
+            buffer_size: isize,
+            encoding: u32,
+        ) -> u8;
+        fn CFDataGetLength(data: CFTypeRef) -> isize;
+        fn CFDataGetBytePtr(data: CFTypeRef) -> *const u8;
+        fn CFRelease(cf: CFTypeRef);
+    }
+
+    pub fn list_windows() -> Vec<WindowInfo> {
+        // SAFETY: The window list is an owned (+1) array released below;
+        // dictionaries and values read from it are borrowed (get rule).
+        unsafe {
+            let list = CGWindowListCopyWindowInfo(ON_SCREEN_ONLY | EXCLUDE_DESKTOP_ELEMENTS, 0);
+            if list.is_null() {
+                return Vec::new();
+            }
+
+            let mut windows = Vec::new();
+            for index in 0..CFArrayGetCount(list) {
+                let info = CFArrayGetValueAtIndex(list, index);
+                // Layer 0 holds normal application windows (not menus or the Dock)
+                if number_value(info, "kCGWindowLayer").unwrap_or(-1.0) != 0.0 {
+                    continue;
+                }
+                let Some(id) = number_value(info, "kCGWindowNumber") else {
+                    continue;
+                };
+                let mut bounds = CGRect::default();
+                let Some(bounds_dict) = value(info, "kCGWindowBounds") else {
+                    continue;
+                };
+                if !CGRectMakeWithDictionaryRepresentation(bounds_dict, &mut bounds) {
+                    continue;
+                }
+
+                windows.push(WindowInfo {
+                    id: id as u32,
+                    title: string_value(info, "kCGWindowName").unwrap_or_default(),
+                    app_name: string_value(info, "kCGWindowOwnerName").unwrap_or_default(),
+                    process_id: number_value(info, "kCGWindowOwnerPID").unwrap_or(0.0) as u64,
+                    x: bounds.origin.x,
+                    y: bounds.origin.y,
+                    width: bounds.size.width,
+                    height: bounds.size.height,
+                });
+            }
+            CFRelease(list);
+            windows
+        }
+    }
+
+    pub fn capture(window_id: u32) -> Result<RgbaImage, String> {
+        // CGRectNull: capture exactly the window's bounds
+        let null_rect = CGRect {
+            origin: CGPoint {
+                x: f64::INFINITY,
+                y: f64::INFINITY,
+            },
+            size: CGSize::default(),
+        };
+
+        // SAFETY: The image and the copied pixel data are owned (+1) and
+        // released exactly once; the data provider is borrowed from the image.
+        unsafe {
+            let image = CGWindowListCreateImage(
+                null_rect,
+                INCLUDING_WINDOW,
+                window_id,
+                IMAGE_BOUNDS_IGNORE_FRAMING,
+            );
+            if image.is_null() {
+                return Err("Failed to capture window (closed or not on screen)".to_string());
+            }
+
+            let width = CGImageGetWidth(image);
+            let height = CGImageGetHeight(image);
+            let bits_per_pixel = CGImageGetBitsPerPixel(image);
+            let stride = CGImageGetBytesPerRow(image);
+            let data = CGDataProviderCopyData(CGImageGetDataProvider(image));
+            CGImageRelease(image);
+            if data.is_null() {
+                return Err("Failed to read window capture".to_string());
+            }
+
+            let length = CFDataGetLength(data).max(0) as usize;
+            let bytes = CFDataGetBytePtr(data);
+            let result = if bits_per_pixel != 32 || bytes.is_null() || length < stride * height {
+                Err(format!(
+                    "Unsupported window capture format ({} bits per pixel)",
+                    bits_per_pixel
+                ))
+            } else {
+                let raw = std::slice::from_raw_parts(bytes, length);
+                Ok(rgba_from_bgra(raw, width, height, stride))
+            };
+            CFRelease(data);
+            result
+        }
+    }
+
+    /// Window captures are premultiplied BGRA (32-bit little-endian, alpha first).
+    fn rgba_from_bgra(raw: &[u8], width: usize, height: usize, stride: usize) -> RgbaImage {
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for row in raw.chunks(stride).take(height) {
+            for bgra in row[..width * 4].chunks_exact(4) {
+                pixels.extend_from_slice(&[bgra[2], bgra[1], bgra[0], bgra[3]]);
+            }
+        }
+        RgbaImage::from_raw(width as u32, height as u32, pixels).unwrap_or_default()
+    }
+
+    /// Looks up a dictionary value by key name (borrowed, may be null).
+    unsafe fn value(dict: CFTypeRef, key: &str) -> Option<CFTypeRef> {
+        let key = CString::new(key).ok()?;
+        let key =
+            CFStringCreateWithCString(std::ptr::null(), key.as_ptr(), CF_STRING_ENCODING_UTF8);
+        if key.is_null() {
+            return None;
+        }
+        let value = CFDictionaryGetValue(dict, key);
+        CFRelease(key);
+        (!value.is_null()).then_some(value)
+    }
+
+    /// Reads a number (window ids, layers, and bounds are all exact as `f64`).
+    unsafe fn number_value(dict: CFTypeRef, key: &str) -> Option<f64> {
+        let number = value(dict, key)?;
+        let mut result = 0f64;
+        CFNumberGetValue(
+            number,
+            CF_NUMBER_FLOAT64_TYPE,
+            &mut result as *mut f64 as *mut c_void,
+        )
+        .then_some(result)
+    }
+
+    unsafe fn string_value(dict: CFTypeRef, key: &str) -> Option<String> {
+        let string = value(dict, key)?;
+        let length = CFStringGetLength(string);
+        let capacity = CFStringGetMaximumSizeForEncoding(length, CF_STRING_ENCODING_UTF8) + 1;
+        let mut buffer = vec![0 as c_char; capacity.max(1) as usize];
+        if CFStringGetCString(
+            string,
+            buffer.as_mut_ptr(),
+            capacity,
+            CF_STRING_ENCODING_UTF8,
+        ) == 0
+        {
+            return None;
+        }
+        let bytes: Vec<u8> = buffer
+            .into_iter()
+            .take_while(|&byte| byte != 0)
+            .map(|byte| byte as u8)
+            .collect();
+        String::from_utf8(bytes).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: u32, title: &str) -> WindowInfo {
+        WindowInfo {
+            id,
+            title: title.to_string(),
+            app_name: "Google Chrome".to_string(),
+            process_id: 42,
+            x: 0.0,
+            y: 0.0,
+            width: 800.0,
+            height: 600.0,
+        }
+    }
+
+    #[test]
+    fn test_front_most_matching_window_wins() {
+        let windows = vec![
+            window(1, "Inbox — Mail"),
+            window(2, "Invoice #1234 — Google Chrome"),
+            window(3, "Invoice #1235 — Google Chrome"),
+        ];
+
+        let pattern = compile_pattern("invoice").unwrap();
+        assert_eq!(
+            pick_window(windows.clone(), &pattern).map(|w| w.id),
+            Some(2)
+        );
+
+        let pattern = compile_pattern(r"#1235\b").unwrap();
+        assert_eq!(
+            pick_window(windows.clone(), &pattern).map(|w| w.id),
+            Some(3)
+        );
+
+        assert!(compile_pattern("(unclosed").is_err());
+        assert!(compile_pattern("  ").is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! # Buffer Pool Module - Reusable Screenshot Buffers
+//!
+//! A full-screen capture is 8-33 MB of RGBA (1080p to 5K). Converting it
+//! allocated a fresh buffer for every click, and so did the copy kept as the
+//! partial-capture base frame (see `frame_diff` module). On low-end machines
+//! those allocations, and the page faults of touching new memory, made
+//! clicks noticeably slower to capture.
+//!
+//! ## Usage
+//! ```text
+//! take(len) → fill → RgbaImage::from_raw() → ... → give(image.into_raw())
+//! ```
+//! `take()` hands out the smallest pooled buffer that fits (else a new
+//! one). At most `MAX_POOLED` buffers are kept, so idle memory stays
+//! bounded at a couple of frames. Both go through one shared `BufferPool`.
+
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+/// Buffers kept for reuse: the converted capture and the base frame.
+const MAX_POOLED: usize = 2;
+
+static POOL: Lazy<Mutex<BufferPool>> = Lazy::new(|| Mutex::new(BufferPool::new(MAX_POOLED)));
+
+/// Reusable buffers, the smallest dropped first when full.
+pub struct BufferPool {
+    buffers: Vec<Vec<u8>>,
+    max_pooled: usize,
+}
+
+impl BufferPool {
+    /// An empty pool keeping at most `max_pooled` buffers.
+    pub fn new(max_pooled: usize) -> Self {
+        Self {
+            buffers: Vec::new(),
+            max_pooled,
+        }
+    }
+
+    /// Takes an empty buffer with room for at least `capacity` bytes.
+    pub fn take(&mut self, capacity: usize) -> Vec<u8> {
+        let fitting = self
+            .buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| buffer.capacity() >= capacity)
+            .min_by_key(|(_, buffer)| buffer.capacity())
+            .map(|(index, _)| index);
+
+        match fitting {
+            Some(index) => {
+                let mut buffer = self.buffers.swap_remove(index);
+                buffer.clear();
+                buffer
+            }
+            None => Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns a buffer for later use (the smallest is dropped when full).
+    pub fn give(&mut self, buffer: Vec<u8>) {
+        if buffer.capacity() == 0 {
+            return;
+        }
+
+        if self.buffers.len() < self.max_pooled {
+            self.buffers.push(buffer);
+        } else if let Some(smallest) = self
+            .buffers
+            .iter_mut()
+            .min_by_key(|pooled| pooled.capacity())
+        {
+            if smallest.capacity() < buffer.capacity() {
+                *smallest = buffer;
+            }
+        }
+    }
+}
+
+/// Takes an empty buffer from the screenshot pool (see `BufferPool::take()`).
+pub fn take(capacity: usize) -> Vec<u8> {
+    POOL.lock().unwrap().take(capacity)
+}
+
+/// Returns a buffer to the screenshot pool for later captures.
+pub fn give(buffer: Vec<u8>) {
+    POOL.lock().unwrap().give(buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffers_are_reused() {
+        let mut pool = BufferPool::new(2);
+        let mut buffer = pool.take(4096);
+        buffer.extend_from_slice(&[7; 4096]);
+        let address = buffer.as_ptr();
+        pool.give(buffer);
+
+        let reused = pool.take(1024);
+        assert_eq!(reused.as_ptr(), address);
+        assert!(reused.is_empty());
+        assert!(reused.capacity() >= 4096);
+    }
+
+    #[test]
+    fn test_smallest_buffer_is_dropped_when_full() {
+        let mut pool = BufferPool::new(2);
+        pool.give(Vec::with_capacity(1024));
+        pool.give(Vec::with_capacity(2048));
+        pool.give(Vec::with_capacity(4096));
+
+        assert!(pool.take(4096).capacity() >= 4096);
+        assert!(pool.take(2048).capacity() >= 2048);
+        // The 1 KB buffer made room for the 4 KB one
+        assert_eq!(pool.buffers.len(), 0);
+    }
+}
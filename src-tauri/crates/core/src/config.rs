@@ -0,0 +1,1087 @@
+//! # Config Module - Recorder Configuration
+//!
+//! User-adjustable recorder settings, persisted as JSON and shared between
+//! the Tauri commands (read/write) and the background event handler (read).
+//!
+//! ## Storage
+//! ```text
+//! [recordings directory]/config.json     (see `storage` module)
+//! ```
+//!
+//! Choosing another recordings directory carries the settings in use over
+//! (`carry_over()`), so deny lists and scrub keywords stay in effect.
+//!
+//! ## Compatibility
+//! Every struct uses `#[serde(default)]`, so config files written by older
+//! versions (missing fields) and partial updates from the frontend load
+//! without errors; missing values fall back to `Default`.
+
+use crate::storage;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Highest accepted `mouse_trail.sample_hz` (the OS reports moves at about display rate).
+pub const MAX_TRAIL_SAMPLE_HZ: f64 = 240.0;
+
+/// Highest accepted `partial_capture.max_interval_seconds`.
+pub const MAX_PATCH_INTERVAL_SECONDS: f64 = 3600.0;
+
+/// Highest accepted `latency.timeout_ms`.
+pub const MAX_LATENCY_TIMEOUT_MS: u64 = 60_000;
+
+/// All recorder settings.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RecorderConfig {
+    /// Per-monitor capture overrides, matched by display id
+    pub displays: Vec<DisplayOverride>,
+    /// Opt-in usage statistics aggregation (see `stats` module)
+    pub telemetry: TelemetryConfig,
+    /// Screen text indexing of full-screen captures (see `ocr` module)
+    pub ocr: OcrConfig,
+    /// Sampled cursor trajectories (see `mouse_trail` module)
+    pub mouse_trail: MouseTrailConfig,
+    /// Keyword redaction of screenshot text (see `scrub` module)
+    pub scrub: ScrubConfig,
+    /// Rewrites of recorded window titles and app names (see `anonymize` module)
+    pub anonymize: AnonymizeConfig,
+    /// VM and remote desktop clients (see `remote_desktop` module)
+    pub remote_desktop: RemoteDesktopConfig,
+    /// Text or logo stamped on exported screenshots (see `export::watermark`)
+    pub watermark: WatermarkConfig,
+    /// Default event filter for recordings (see `filter` module)
+    pub filter: FilterConfig,
+    /// Hotkey that inserts `Marker` events (see `marker` module)
+    pub marker: MarkerConfig,
+    /// How timestamps are written to `session.json`
+    pub timestamps: TimestampConfig,
+    /// Automatic stop for forgotten recordings (see `limits` module)
+    pub limits: SessionLimits,
+    /// How often the active recording is saved (see `resume` module)
+    pub checkpoints: CheckpointConfig,
+    /// Saving only the changed region of rapid captures (see `frame_diff` module)
+    pub partial_capture: PartialCaptureConfig,
+    /// Click response time measurement (see `latency` module)
+    pub latency: LatencyConfig,
+    /// Automatic Wait event detection
+    pub waits: WaitConfig,
+    /// Away-from-keyboard detection and auto-pause (see `idle` module)
+    pub idle: IdleConfig,
+    /// Loading / reading / away attribution of Waits (see `wait_cause` module)
+    pub wait_causes: WaitCauseConfig,
+    /// Timeline preview track written after recording (see `preview` module)
+    pub preview: PreviewConfig,
+    /// How stored event categories and descriptions are treated on load
+    pub classification: ClassificationConfig,
+}
+
+/// Compatibility switch for derived event fields.
+///
+/// Categories and descriptions are recomputed whenever a session is loaded,
+/// so old recordings pick up classification improvements. Enable
+/// `keep_stored` to use the values saved in `session.json` instead.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ClassificationConfig {
+    /// Use stored `action_category`/`description` as-is (e.g., hand-edited files)
+    pub keep_stored: bool,
+}
+
+/// Settings for the local usage statistics job.
+///
+/// Nothing is ever sent anywhere; "telemetry" here is a local stats file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// Aggregate anonymized counts into `recordings/stats.json` after each recording
+    pub enabled: bool,
+}
+
+/// Capture settings for one monitor.
+///
+/// # Example JSON
+/// ```json
+/// {"display_id": 2, "skip_capture": true}
+/// {"display_id": 3, "max_dimension": 1920}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DisplayOverride {
+    /// Display identifier (`screenshots::DisplayInfo::id`)
+    pub display_id: u32,
+    /// Never capture this display (e.g., a confidential second monitor)
+    pub skip_capture: bool,
+    /// Downscale saved screenshots so their longest side is at most this many
+    /// pixels (e.g., 1920 on a 4K display to cut file size ~4x)
+    pub max_dimension: Option<u32>,
+}
+
+/// Settings for background screen text indexing.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct OcrConfig {
+    /// Run OCR over full-screen captures (requires `tesseract`)
+    pub enabled: bool,
+    /// Skip captures taken sooner than this after the last indexed one
+    pub min_interval_seconds: f64,
+    /// Path to the tesseract binary (defaults to `tesseract` on `PATH`)
+    pub tesseract_path: Option<String>,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_interval_seconds: 5.0,
+            tesseract_path: None,
+        }
+    }
+}
+
+impl OcrConfig {
+    /// Rejects a negative or non-finite `min_interval_seconds`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.min_interval_seconds.is_finite() && self.min_interval_seconds >= 0.0) {
+            return Err(format!(
+                "Invalid ocr min_interval_seconds {}: must be zero or a positive number of seconds",
+                self.min_interval_seconds
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for mouse trail sampling.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MouseTrailConfig {
+    /// Record `MouseTrail` events (off by default: research use only)
+    pub enabled: bool,
+    /// Samples per second while the mouse moves
+    pub sample_hz: f64,
+    /// Points per `MouseTrail` event before a new one is started
+    pub max_points: usize,
+}
+
+impl Default for MouseTrailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_hz: 5.0,
+            max_points: 300,
+        }
+    }
+}
+
+impl MouseTrailConfig {
+    /// Rejects rates outside 0-`MAX_TRAIL_SAMPLE_HZ` and trails of fewer than two points.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.sample_hz.is_finite()
+            && self.sample_hz > 0.0
+            && self.sample_hz <= MAX_TRAIL_SAMPLE_HZ)
+        {
+            return Err(format!(
+                "Invalid mouse_trail sample_hz {}: must be above 0 and at most {}",
+                self.sample_hz, MAX_TRAIL_SAMPLE_HZ
+            ));
+        }
+        if self.max_points < 2 {
+            return Err(format!(
+                "Invalid mouse_trail max_points {}: must be at least 2",
+                self.max_points
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for automatic `Wait` events.
+///
+/// `coalesce`, `max_seconds`, and `min_seconds` are applied when the
+/// recording stops (see `postprocess` module).
+///
+/// # Example JSON
+/// ```json
+/// {"threshold_seconds": 4.0, "max_seconds": 60.0, "min_seconds": 5.0}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WaitConfig {
+    /// Shortest pause between two events recorded as a Wait
+    pub threshold_seconds: f64,
+    /// Merge consecutive Waits into one
+    pub coalesce: bool,
+    /// Longest reported wait; longer idle periods are capped (`None` = no cap)
+    pub max_seconds: Option<f64>,
+    /// Drop Waits shorter than this after merging (`None` = keep all)
+    pub min_seconds: Option<f64>,
+    /// Threshold learned from the user's pace instead of `threshold_seconds`
+    /// (see `adaptive_wait` module)
+    pub adaptive: AdaptiveWaitConfig,
+}
+
+impl Default for WaitConfig {
+    fn default() -> Self {
+        Self {
+            threshold_seconds: 2.0,
+            coalesce: true,
+            max_seconds: None,
+            min_seconds: None,
+            adaptive: AdaptiveWaitConfig::default(),
+        }
+    }
+}
+
+/// Settings for the learned Wait threshold.
+///
+/// # Example JSON
+/// ```json
+/// {"enabled": true, "factor": 3.0, "learned": {"median_gap_seconds": 0.9, "samples": 200}}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AdaptiveWaitConfig {
+    pub enabled: bool,
+    /// Threshold as a multiple of the median gap between actions
+    pub factor: f64,
+    /// Bounds of the learned threshold
+    pub min_threshold_seconds: f64,
+    pub max_threshold_seconds: f64,
+    /// Gaps a recording needs before its own cadence replaces `learned`
+    pub min_samples: usize,
+    /// Cadence saved after the last recording (written by FlowTrace)
+    pub learned: Option<LearnedCadence>,
+}
+
+impl Default for AdaptiveWaitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            factor: 3.0,
+            min_threshold_seconds: 1.0,
+            max_threshold_seconds: 8.0,
+            min_samples: 30,
+            learned: None,
+        }
+    }
+}
+
+/// Typical gap between a user's actions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LearnedCadence {
+    pub median_gap_seconds: f64,
+    /// Gaps the median was taken over
+    pub samples: usize,
+}
+
+impl WaitConfig {
+    /// Rejects durations and factors that aren't positive (a zero threshold
+    /// would turn every event gap into a Wait) and inverted adaptive bounds.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Thresholds are positive numbers of seconds
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        let adaptive = &self.adaptive;
+        let seconds = [
+            ("threshold_seconds", Some(self.threshold_seconds)),
+            ("max_seconds", self.max_seconds),
+            ("min_seconds", self.min_seconds),
+            ("adaptive.factor", Some(adaptive.factor)),
+            (
+                "adaptive.min_threshold_seconds",
+                Some(adaptive.min_threshold_seconds),
+            ),
+            (
+                "adaptive.max_threshold_seconds",
+                Some(adaptive.max_threshold_seconds),
+            ),
+        ];
+        for (name, value) in seconds {
+            if let Some(value) = value.filter(|value| !(value.is_finite() && *value > 0.0)) {
+                return Err(format!(
+                    "Invalid wait {} {}: must be a positive number of seconds",
+                    name, value
+                ));
+            }
+        }
+        if adaptive.min_threshold_seconds > adaptive.max_threshold_seconds {
+            return Err(format!(
+                "Invalid adaptive wait bounds: min_threshold_seconds {} is above max_threshold_seconds {}",
+                adaptive.min_threshold_seconds, adaptive.max_threshold_seconds
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for `Idle` events (no input for minutes, unlike a 2-second Wait).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct IdleConfig {
+    /// Shortest gap without input recorded as Idle instead of Wait (`None` = off)
+    pub threshold_seconds: Option<f64>,
+    /// Pause the recording once the user has been idle for `threshold_seconds`
+    pub auto_pause: bool,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            threshold_seconds: Some(300.0),
+            auto_pause: false,
+        }
+    }
+}
+
+impl IdleConfig {
+    /// Rejects a threshold that isn't a positive number of seconds.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Threshold is off or positive
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        match self.threshold_seconds {
+            Some(value) if !(value.is_finite() && value > 0.0) => Err(format!(
+                "Invalid idle threshold_seconds {}: must be a positive number of seconds",
+                value
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Settings for attributing a cause to each `Wait` event.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WaitCauseConfig {
+    /// Sample the screen between inputs (off by default: repeated captures cost CPU)
+    pub enabled: bool,
+    /// Time between screen samples
+    pub sample_interval_ms: u64,
+    /// Smallest changed share of the screen (0-1) that counts as a change
+    pub min_changed_ratio: f64,
+    /// Shortest static stretch that makes a Wait `away` instead of `reading`
+    pub away_seconds: f64,
+}
+
+impl Default for WaitCauseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_interval_ms: 500,
+            min_changed_ratio: 0.002,
+            away_seconds: 60.0,
+        }
+    }
+}
+
+impl WaitCauseConfig {
+    /// Rejects an `away_seconds` that isn't a positive number of seconds.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Threshold is positive
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.away_seconds.is_finite() && self.away_seconds > 0.0) {
+            return Err(format!(
+                "Invalid wait_causes away_seconds {}: must be a positive number of seconds",
+                self.away_seconds
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for the timeline preview track.
+///
+/// # Example JSON
+/// ```json
+/// {"frame_interval_seconds": 10.0, "frame_width": 480}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PreviewConfig {
+    /// Generate the track when a recording stops
+    pub enabled: bool,
+    /// At most one frame per this many seconds (`None` = one per captured step)
+    pub frame_interval_seconds: Option<f64>,
+    /// Width of preview frames in pixels (height keeps the aspect ratio)
+    pub frame_width: u32,
+    /// Length of one event density bucket
+    pub bucket_seconds: f64,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            frame_interval_seconds: None,
+            frame_width: 320,
+            bucket_seconds: 5.0,
+        }
+    }
+}
+
+impl PreviewConfig {
+    /// Rejects intervals that aren't positive and a zero frame width.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        let seconds = [
+            ("frame_interval_seconds", self.frame_interval_seconds),
+            ("bucket_seconds", Some(self.bucket_seconds)),
+        ];
+        for (name, value) in seconds {
+            if let Some(value) = value.filter(|value| !(value.is_finite() && *value > 0.0)) {
+                return Err(format!(
+                    "Invalid preview {} {}: must be a positive number of seconds",
+                    name, value
+                ));
+            }
+        }
+        if self.frame_width == 0 {
+            return Err("Invalid preview frame_width 0: must be at least 1 pixel".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Settings for partial full-screen captures.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PartialCaptureConfig {
+    /// Save changed regions as patches (off by default: exports must reconstruct them)
+    pub enabled: bool,
+    /// Longest gap to the previous click for a capture to become a patch
+    pub max_interval_seconds: f64,
+    /// Largest changed share of the frame (0-1) still saved as a patch
+    pub max_dirty_ratio: f64,
+}
+
+impl Default for PartialCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_interval_seconds: 5.0,
+            max_dirty_ratio: 0.5,
+        }
+    }
+}
+
+impl PartialCaptureConfig {
+    /// Rejects intervals outside 0-`MAX_PATCH_INTERVAL_SECONDS` and ratios outside 0-1.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.max_interval_seconds.is_finite()
+            && self.max_interval_seconds > 0.0
+            && self.max_interval_seconds <= MAX_PATCH_INTERVAL_SECONDS)
+        {
+            return Err(format!(
+                "Invalid partial_capture max_interval_seconds {}: must be above 0 and at most {} seconds",
+                self.max_interval_seconds, MAX_PATCH_INTERVAL_SECONDS
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.max_dirty_ratio) {
+            return Err(format!(
+                "Invalid partial_capture max_dirty_ratio {}: must be between 0 and 1",
+                self.max_dirty_ratio
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for click response time measurement.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LatencyConfig {
+    /// Sample the screen after clicks (off by default: repeated captures cost CPU)
+    pub enabled: bool,
+    /// Time between screen samples
+    pub sample_interval_ms: u64,
+    /// Give up if the screen hasn't changed this long after the click
+    pub timeout_ms: u64,
+    /// Smallest changed share of the screen (0-1) that counts as a response
+    pub min_changed_ratio: f64,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_interval_ms: 50,
+            timeout_ms: 5000,
+            min_changed_ratio: 0.002,
+        }
+    }
+}
+
+impl LatencyConfig {
+    /// Rejects a zero sample interval, timeouts outside the interval to
+    /// `MAX_LATENCY_TIMEOUT_MS`, and ratios outside 0-1.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sample_interval_ms == 0 {
+            return Err("Invalid latency sample_interval_ms 0: must be at least 1".to_string());
+        }
+        if !(self.sample_interval_ms..=MAX_LATENCY_TIMEOUT_MS).contains(&self.timeout_ms) {
+            return Err(format!(
+                "Invalid latency timeout_ms {}: must be between sample_interval_ms ({}) and {}",
+                self.timeout_ms, self.sample_interval_ms, MAX_LATENCY_TIMEOUT_MS
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.min_changed_ratio) {
+            return Err(format!(
+                "Invalid latency min_changed_ratio {}: must be between 0 and 1",
+                self.min_changed_ratio
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for manual marker events.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MarkerConfig {
+    /// Hotkey such as `"Control+Alt+KeyM"` (empty = no hotkey)
+    pub hotkey: String,
+}
+
+impl Default for MarkerConfig {
+    fn default() -> Self {
+        Self {
+            hotkey: "Control+Alt+KeyM".to_string(),
+        }
+    }
+}
+
+impl MarkerConfig {
+    /// Rejects a hotkey that doesn't parse (see `marker::Hotkey::parse()`).
+    ///
+    /// # Returns
+    /// * `Ok(())` - Hotkey is empty or valid
+    /// * `Err(String)` - Error describing the invalid hotkey
+    pub fn validate(&self) -> Result<(), String> {
+        if self.hotkey.trim().is_empty() {
+            return Ok(());
+        }
+        crate::marker::Hotkey::parse(&self.hotkey).map(|_| ())
+    }
+}
+
+/// Limits after which a recording stops itself.
+///
+/// # Example JSON
+/// ```json
+/// {"max_duration_seconds": 7200, "max_events": 5000}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SessionLimits {
+    /// Stop after this many seconds (`None` = no limit)
+    pub max_duration_seconds: Option<f64>,
+    /// Stop once the session holds this many events (`None` = no limit)
+    pub max_events: Option<usize>,
+}
+
+impl SessionLimits {
+    /// Rejects a duration that isn't positive and `max_events: 0`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Limits are off or usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(value) = self
+            .max_duration_seconds
+            .filter(|value| !(value.is_finite() && *value > 0.0))
+        {
+            return Err(format!(
+                "Invalid limits max_duration_seconds {}: must be a positive number of seconds",
+                value
+            ));
+        }
+        if self.max_events == Some(0) {
+            return Err("Invalid limits max_events 0: must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// When the active recording is checkpointed to `session.json`.
+///
+/// A checkpoint is written when there are new events and either
+/// `interval_seconds` have passed or `every_events` were recorded since the
+/// last one, whichever comes first.
+///
+/// # Example JSON
+/// ```json
+/// {"interval_seconds": 10.0, "every_events": 50}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CheckpointConfig {
+    /// Longest time between checkpoints while events come in
+    pub interval_seconds: f64,
+    /// Checkpoint after this many new events (`None` = by time only)
+    pub every_events: Option<usize>,
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 5.0,
+            every_events: Some(25),
+        }
+    }
+}
+
+impl CheckpointConfig {
+    /// Rejects an interval that isn't positive and `every_events: 0`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.interval_seconds.is_finite() && self.interval_seconds > 0.0) {
+            return Err(format!(
+                "Invalid checkpoints interval_seconds {}: must be a positive number of seconds",
+                self.interval_seconds
+            ));
+        }
+        if self.every_events == Some(0) {
+            return Err("Invalid checkpoints every_events 0: must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Settings for recording inside VM and remote desktop windows.
+///
+/// # Example JSON
+/// ```json
+/// {"extra_apps": ["Citrix Viewer"], "click_crop_size": 600}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RemoteDesktopConfig {
+    /// App names treated as remote clients besides the built-in ones
+    pub extra_apps: Vec<String>,
+    /// Click crop size in logical px inside remote windows (`None` = the usual 300)
+    pub click_crop_size: Option<u32>,
+}
+
+impl RemoteDesktopConfig {
+    /// Rejects a click crop size of 0.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if self.click_crop_size == Some(0) {
+            return Err("Invalid remote_desktop click_crop_size 0: must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Settings for watermarking exported screenshots.
+///
+/// # Example JSON
+/// ```json
+/// {"enabled": true, "text": "Confidential - Example Corp", "position": "bottom_right", "opacity": 0.6}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WatermarkConfig {
+    /// Stamp screenshots when they are exported (recordings stay unchanged)
+    pub enabled: bool,
+    /// Text to stamp (letters, digits, and common punctuation)
+    pub text: Option<String>,
+    /// Logo image to stamp left of the text (any format `image` reads)
+    pub logo_path: Option<String>,
+    pub position: WatermarkPosition,
+    /// 0.0 (invisible) to 1.0 (opaque)
+    pub opacity: f32,
+    /// Height of the watermark relative to the image height
+    pub size: f32,
+}
+
+impl Default for WatermarkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            text: None,
+            logo_path: None,
+            position: WatermarkPosition::default(),
+            opacity: 0.5,
+            size: 0.04,
+        }
+    }
+}
+
+impl WatermarkConfig {
+    /// Rejects out-of-range opacity and size, and an enabled watermark
+    /// without text or logo.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Settings are usable
+    /// * `Err(String)` - Error describing the invalid value
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.opacity > 0.0 && self.opacity <= 1.0) {
+            return Err(format!(
+                "Invalid watermark opacity {}: must be above 0 and at most 1",
+                self.opacity
+            ));
+        }
+        if !(self.size > 0.0 && self.size <= 0.5) {
+            return Err(format!(
+                "Invalid watermark size {}: must be above 0 and at most 0.5",
+                self.size
+            ));
+        }
+        let given = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+        if self.enabled && !given(&self.text) && !given(&self.logo_path) {
+            return Err("Watermark needs a text or a logo_path".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Corner of the image a watermark is stamped in.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WatermarkPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Timestamp format of saved sessions.
+///
+/// # Example JSON
+/// ```json
+/// {"precision": "millis", "record_utc_offset": true}
+/// ```
+/// gives `"2026-02-01T15:43:08.646Z"` timestamps and `"utc_offset": "+01:00"`
+/// on the session.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TimestampConfig {
+    /// Fractional second digits written to `session.json`
+    pub precision: TimestampPrecision,
+    /// Store the local UTC offset at recording start as `utc_offset`
+    pub record_utc_offset: bool,
+}
+
+/// Sub-second precision of saved timestamps.
+///
+/// Timestamps are truncated, never rounded, so event order is preserved.
+/// Zero fractions are omitted (`"15:43:08Z"`), as RFC 3339 allows.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampPrecision {
+    /// 3 digits (`.646Z`)
+    Millis,
+    /// 6 digits (`.646618Z`)
+    #[default]
+    Micros,
+}
+
+impl TimestampPrecision {
+    /// Number of fractional second digits kept.
+    pub fn digits(self) -> u16 {
+        match self {
+            TimestampPrecision::Millis => 3,
+            TimestampPrecision::Micros => 6,
+        }
+    }
+}
+
+/// Keywords and patterns blurred out of every screenshot.
+///
+/// # Example JSON
+/// ```json
+/// {"keywords": ["Acme Corp", "Project Falcon"], "patterns": ["PRJ-\\d{4}"]}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ScrubConfig {
+    /// Words or phrases to blur, case-insensitive (e.g., customer names)
+    pub keywords: Vec<String>,
+    /// Regular expressions to blur, matched against each line of screen text
+    pub patterns: Vec<String>,
+}
+
+/// Rewrites applied to window titles and app names before they are recorded.
+///
+/// # Example JSON
+/// ```json
+/// {
+///   "app_aliases": {"AcmeDesk": "Ticket tool"},
+///   "rules": [
+///     {"pattern": "^.* - (Microsoft Word)$", "replacement": "Document - $1"},
+///     {"pattern": "(?i)[a-z0-9-]+\\.corp\\.example\\.com", "replacement": "internal-host"},
+///     {"pattern": "Falcon", "replacement": "Project", "target": "both"}
+///   ]
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AnonymizeConfig {
+    /// Exact application names to replace, case-insensitive
+    pub app_aliases: BTreeMap<String, String>,
+    /// Regex replacements, applied in order (after the aliases for app names)
+    pub rules: Vec<RewriteRule>,
+}
+
+/// One regex replacement of `AnonymizeConfig`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct RewriteRule {
+    /// Regular expression (case-sensitive unless it starts with `(?i)`)
+    pub pattern: String,
+    /// Replacement; `$1`, `${name}` refer to capture groups
+    pub replacement: String,
+    pub target: RewriteTarget,
+}
+
+/// Fields a `RewriteRule` applies to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RewriteTarget {
+    #[default]
+    WindowTitle,
+    AppName,
+    Both,
+}
+
+/// Which events a recording keeps.
+///
+/// `include`/`exclude` entries match an event type (`"Click"`, `"KeyPress"`,
+/// `"Wait"`, ...) or an action category (`"interaction"`, `"text_input"`,
+/// `"wait"`, ...). `allow_apps`/`deny_apps` entries match the focused
+/// application's name or process name (case-insensitive).
+///
+/// # Example JSON
+/// ```json
+/// {"include": ["Click"]}
+/// {"exclude": ["KeyPress", "Shortcut", "TextInput"]}
+/// {"exclude": ["wait", "hover"]}
+/// {"allow_apps": ["Google Chrome", "Figma"]}
+/// {"deny_apps": ["1Password", "KeePassXC"]}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct FilterConfig {
+    /// Keep only matching events (empty = keep everything)
+    pub include: Vec<String>,
+    /// Drop matching events (applied after `include`)
+    pub exclude: Vec<String>,
+    /// Record only while one of these applications is focused (empty = any)
+    pub allow_apps: Vec<String>,
+    /// Never record while one of these applications is focused (applied after `allow_apps`)
+    pub deny_apps: Vec<String>,
+}
+
+/// Active configuration, loaded from disk on first access.
+static CONFIG: Lazy<Arc<Mutex<RecorderConfig>>> =
+    Lazy::new(|| Arc::new(Mutex::new(load_or_default())));
+
+/// Returns a snapshot of the active configuration.
+pub fn current() -> RecorderConfig {
+    CONFIG.lock().unwrap().clone()
+}
+
+/// Replaces the active configuration and persists it.
+///
+/// # Returns
+/// * `Ok(())` - Saved and applied (takes effect for the next captured event)
+/// * `Err(String)` - Error if the file couldn't be written (config unchanged)
+pub fn update(config: RecorderConfig) -> Result<(), String> {
+    save(&config)?;
+    *CONFIG.lock().unwrap() = config;
+    Ok(())
+}
+
+/// Writes the settings in use to the recordings directory chosen since
+/// (see `storage::set_location()`), so moving the directory doesn't reset
+/// them. A config file already there is kept as `config.json.bak`.
+///
+/// # Arguments
+/// * `config` - Settings read before the directory changed
+///
+/// # Returns
+/// * `Ok(())` - Saved to the new directory and applied
+/// * `Err(String)` - Error if the file couldn't be backed up or written
+pub fn carry_over(config: RecorderConfig) -> Result<(), String> {
+    let path = config_path();
+    if path.exists() {
+        fs::rename(&path, path.with_extension("json.bak"))
+            .map_err(|e| format!("Failed to back up config file: {:?}", e))?;
+    }
+    update(config)
+}
+
+/// Re-reads the configuration file once the recordings directory is known
+/// at startup (see `storage::configure_root()`).
+pub fn reload() {
+    *CONFIG.lock().unwrap() = load_or_default();
+}
+
+/// Returns the override for a display, if one is configured.
+pub fn display_override(display_id: u32) -> Option<DisplayOverride> {
+    CONFIG
+        .lock()
+        .unwrap()
+        .displays
+        .iter()
+        .find(|display| display.display_id == display_id)
+        .cloned()
+}
+
+/// Returns the mouse trail settings (cheap enough to call for every mouse move).
+pub fn mouse_trail() -> MouseTrailConfig {
+    CONFIG.lock().unwrap().mouse_trail.clone()
+}
+
+/// Returns the Wait settings (read for every input event, so no full clone).
+pub fn waits() -> WaitConfig {
+    CONFIG.lock().unwrap().waits.clone()
+}
+
+/// Returns the idle detection settings.
+pub fn idle() -> IdleConfig {
+    CONFIG.lock().unwrap().idle.clone()
+}
+
+/// Returns the checkpoint settings (read by the checkpoint watcher several times a second).
+pub fn checkpoints() -> CheckpointConfig {
+    CONFIG.lock().unwrap().checkpoints.clone()
+}
+
+/// Returns the remote desktop settings (read for every event).
+pub fn remote_desktop() -> RemoteDesktopConfig {
+    CONFIG.lock().unwrap().remote_desktop.clone()
+}
+
+/// Returns the watermark settings for exports.
+pub fn watermark() -> WatermarkConfig {
+    CONFIG.lock().unwrap().watermark.clone()
+}
+
+/// Returns the anonymization rules (see `anonymize` module).
+pub fn anonymize() -> AnonymizeConfig {
+    CONFIG.lock().unwrap().anonymize.clone()
+}
+
+/// Returns the Wait cause attribution settings.
+pub fn wait_causes() -> WaitCauseConfig {
+    CONFIG.lock().unwrap().wait_causes.clone()
+}
+
+/// Location of the persisted configuration file.
+pub fn config_path() -> PathBuf {
+    storage::recordings_root().join("config.json")
+}
+
+/// Loads the configuration file, falling back to defaults if missing or invalid.
+fn load_or_default() -> RecorderConfig {
+    match fs::read_to_string(config_path()) {
+        Ok(json_data) => serde_json::from_str(&json_data).unwrap_or_else(|e| {
+            eprintln!("⚠️  Invalid config file, using defaults: {:?}", e);
+            RecorderConfig::default()
+        }),
+        Err(_) => RecorderConfig::default(),
+    }
+}
+
+/// Writes the configuration as pretty-printed JSON (atomically).
+fn save(config: &RecorderConfig) -> Result<(), String> {
+    fs::create_dir_all(storage::recordings_root())
+        .map_err(|e| format!("Failed to create recordings directory: {:?}", e))?;
+
+    let json_data = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {:?}", e))?;
+
+    // Written aside and renamed: a crash mid-write must not lose every setting
+    let path = config_path();
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json_data)
+        .map_err(|e| format!("Failed to write config file: {:?}", e))?;
+    fs::rename(&temp_path, &path).map_err(|e| format!("Failed to replace config file: {:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: RecorderConfig =
+            serde_json::from_str(r#"{"displays": [{"display_id": 2, "skip_capture": true}]}"#)
+                .unwrap();
+
+        assert_eq!(config.displays.len(), 1);
+        assert!(config.displays[0].skip_capture);
+        assert_eq!(config.displays[0].max_dimension, None);
+    }
+
+    #[test]
+    fn test_out_of_range_sections_are_rejected() {
+        let huge = PartialCaptureConfig {
+            max_interval_seconds: 1e300,
+            ..Default::default()
+        };
+        assert!(huge.validate().is_err());
+        assert!(PartialCaptureConfig::default().validate().is_ok());
+
+        let trail = MouseTrailConfig {
+            sample_hz: f64::NAN,
+            ..Default::default()
+        };
+        assert!(trail.validate().is_err());
+        let latency = LatencyConfig {
+            timeout_ms: u64::MAX,
+            ..Default::default()
+        };
+        assert!(latency.validate().is_err());
+        let limits = SessionLimits {
+            max_events: Some(0),
+            ..Default::default()
+        };
+        assert!(limits.validate().is_err());
+        let marker = MarkerConfig {
+            hotkey: "Control+".to_string(),
+        };
+        assert!(marker.validate().is_err());
+        let ocr = OcrConfig {
+            min_interval_seconds: -1.0,
+            ..Default::default()
+        };
+        assert!(ocr.validate().is_err());
+    }
+}
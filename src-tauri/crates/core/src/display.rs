@@ -0,0 +1,352 @@
+//! # Display Module - Coordinate Translation and Capture Conversion
+//!
+//! Maps event coordinates onto captured screenshot pixels for any display
+//! configuration, and normalizes raw capture buffers into RGBA8 images.
+//!
+//! ## Coordinate Spaces
+//! - **Logical**: Coordinates reported by `rdev` and `active-win-pos-rs`
+//!   (points on macOS), global across all displays
+//! - **Physical**: Pixels of the captured image for one display
+//!
+//! ## Scale Factor
+//! The scale is derived from the *actual* captured image size divided by the
+//! display's logical size, per axis, rather than assuming an integer 2x:
+//! - 2560x1440 logical captured at 5120x2880 → 2.0 (Retina)
+//! - 2048x1152 logical captured at 2560x1440 → 1.25 (125% scaling)
+//! - 2560x1440 logical captured at 3840x2160 → 1.5 (4K at 150%)
+//!
+//! The OS-reported `scale_factor` is only used when the sizes are unknown,
+//! because it is frequently rounded (e.g., reported as 2 for 175%).
+//!
+//! ## HDR Captures
+//! HDR/EDR displays may deliver 16 bits per channel, and some backends pad
+//! rows for alignment. `rgba_from_capture()` takes the buffer's `PixelFormat`
+//! from the backend (padding can't tell the two apart) and produces the 8-bit
+//! RGBA image the rest of the pipeline expects.
+
+use crate::buffer_pool;
+use image::RgbaImage;
+
+/// Geometry of one display as needed for coordinate translation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayGeometry {
+    /// Display identifier from `screenshots::DisplayInfo::id`
+    pub id: u32,
+    /// Top-left corner of the display in global logical coordinates
+    pub origin_x: i32,
+    pub origin_y: i32,
+    /// Display size in logical coordinates
+    pub logical_width: u32,
+    pub logical_height: u32,
+    /// Size of the captured image in physical pixels
+    pub physical_width: u32,
+    pub physical_height: u32,
+    /// Scale factor reported by the OS (fallback only)
+    pub reported_scale: f32,
+}
+
+impl DisplayGeometry {
+    /// Horizontal physical pixels per logical unit.
+    pub fn scale_x(&self) -> f64 {
+        Self::axis_scale(self.physical_width, self.logical_width, self.reported_scale)
+    }
+
+    /// Vertical physical pixels per logical unit.
+    pub fn scale_y(&self) -> f64 {
+        Self::axis_scale(
+            self.physical_height,
+            self.logical_height,
+            self.reported_scale,
+        )
+    }
+
+    fn axis_scale(physical: u32, logical: u32, reported: f32) -> f64 {
+        if physical > 0 && logical > 0 {
+            physical as f64 / logical as f64
+        } else if reported > 0.0 {
+            reported as f64
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns `true` if a global logical point lies on this display.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let left = self.origin_x as f64;
+        let top = self.origin_y as f64;
+        x >= left
+            && x < left + self.logical_width as f64
+            && y >= top
+            && y < top + self.logical_height as f64
+    }
+
+    /// Converts a global logical point to fractions of the display size.
+    ///
+    /// # Returns
+    /// `(x, y)` in `0.0..=1.0` (clamped), `(0.0, 0.0)` for a display without size
+    pub fn normalize(&self, x: f64, y: f64) -> (f64, f64) {
+        let fraction = |value: f64, origin: i32, size: u32| {
+            if size == 0 {
+                0.0
+            } else {
+                ((value - origin as f64) / size as f64).clamp(0.0, 1.0)
+            }
+        };
+        (
+            fraction(x, self.origin_x, self.logical_width),
+            fraction(y, self.origin_y, self.logical_height),
+        )
+    }
+
+    /// Converts a global logical point to (unclamped) physical image coordinates.
+    pub fn to_physical(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            (x - self.origin_x as f64) * self.scale_x(),
+            (y - self.origin_y as f64) * self.scale_y(),
+        )
+    }
+
+    /// Converts a global logical rectangle to a physical crop rectangle.
+    ///
+    /// The result is clipped to the captured image.
+    ///
+    /// # Returns
+    /// * `Some((x, y, width, height))` - Physical crop rectangle
+    /// * `None` - Rectangle doesn't overlap this display
+    pub fn rect_to_physical(
+        &self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Option<(u32, u32, u32, u32)> {
+        let (left, top) = self.to_physical(x, y);
+        let (right, bottom) = self.to_physical(x + width, y + height);
+
+        let max_x = self.physical_width as f64;
+        let max_y = self.physical_height as f64;
+        let left = left.round().clamp(0.0, max_x);
+        let top = top.round().clamp(0.0, max_y);
+        let right = right.round().clamp(0.0, max_x);
+        let bottom = bottom.round().clamp(0.0, max_y);
+
+        if right <= left || bottom <= top {
+            return None;
+        }
+
+        Some((
+            left as u32,
+            top as u32,
+            (right - left) as u32,
+            (bottom - top) as u32,
+        ))
+    }
+
+    /// Computes a square crop of `logical_size` centered on a global logical point.
+    ///
+    /// The crop keeps the same *logical* context on every display (so it is
+    /// 600 physical pixels wide on a 2x display for `logical_size = 300`) and
+    /// is shifted, not shrunk, to stay inside the image near screen edges.
+    ///
+    /// # Returns
+    /// `(x, y, width, height)` in physical pixels
+    pub fn centered_crop(&self, x: f64, y: f64, logical_size: u32) -> (u32, u32, u32, u32) {
+        let (center_x, center_y) = self.to_physical(x, y);
+        let width =
+            ((logical_size as f64 * self.scale_x()).round() as u32).min(self.physical_width);
+        let height =
+            ((logical_size as f64 * self.scale_y()).round() as u32).min(self.physical_height);
+
+        let left = (center_x.round() as i64 - width as i64 / 2)
+            .clamp(0, (self.physical_width - width) as i64) as u32;
+        let top = (center_y.round() as i64 - height as i64 / 2)
+            .clamp(0, (self.physical_height - height) as i64) as u32;
+
+        (left, top, width, height)
+    }
+}
+
+/// Pixel layout of a raw capture buffer, as reported by the capture backend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PixelFormat {
+    /// 8 bits per channel, RGBA order
+    Rgba8,
+    /// 16 bits per channel, RGBA order, little-endian (HDR/EDR captures)
+    Rgba16Le,
+}
+
+impl PixelFormat {
+    /// Bytes per pixel in a capture buffer.
+    fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgba8 => 4,
+            PixelFormat::Rgba16Le => 8,
+        }
+    }
+}
+
+/// Converts a raw capture buffer into an 8-bit RGBA image.
+///
+/// # Arguments
+/// * `width`, `height` - Image size in pixels
+/// * `format` - Pixel layout of `raw`
+/// * `raw` - `height` rows of `width` pixels, each row possibly padded at the
+///   end (the row stride is the buffer length divided by `height`)
+///
+/// 16-bit samples are reduced to their high byte, i.e. scaled linearly to
+/// 8 bits; no tone mapping is applied.
+///
+/// # Returns
+/// * `Ok(RgbaImage)` - Converted image
+/// * `Err(String)` - Buffer size doesn't fit `height` rows of `width` pixels
+pub fn rgba_from_capture(
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    raw: &[u8],
+) -> Result<RgbaImage, String> {
+    let packed_row = width as usize * 4;
+    let packed_len = packed_row * height as usize;
+
+    if format == PixelFormat::Rgba8 && raw.len() == packed_len {
+        let mut pixels = buffer_pool::take(packed_len);
+        pixels.extend_from_slice(raw);
+        return RgbaImage::from_raw(width, height, pixels)
+            .ok_or_else(|| "Failed to convert screenshot to image format".to_string());
+    }
+
+    if height == 0 || !raw.len().is_multiple_of(height as usize) {
+        return Err(format!(
+            "Unsupported capture buffer: {} bytes for {}x{}",
+            raw.len(),
+            width,
+            height
+        ));
+    }
+
+    let stride = raw.len() / height as usize;
+    let source_row = width as usize * format.bytes_per_pixel();
+    if stride == 0 || stride < source_row {
+        return Err(format!(
+            "Unsupported capture row stride: {} bytes for width {} ({:?})",
+            stride, width, format
+        ));
+    }
+
+    let mut pixels = buffer_pool::take(packed_len);
+    for row in raw.chunks_exact(stride) {
+        // Padded rows: drop the alignment bytes at the end of each row
+        let row = &row[..source_row];
+        match format {
+            PixelFormat::Rgba8 => pixels.extend_from_slice(row),
+            // Keep the high byte of each little-endian sample
+            PixelFormat::Rgba16Le => pixels.extend(row.chunks_exact(2).map(|sample| sample[1])),
+        }
+    }
+
+    RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| "Failed to convert screenshot to image format".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(logical: (u32, u32), physical: (u32, u32), origin: (i32, i32)) -> DisplayGeometry {
+        DisplayGeometry {
+            id: 1,
+            origin_x: origin.0,
+            origin_y: origin.1,
+            logical_width: logical.0,
+            logical_height: logical.1,
+            physical_width: physical.0,
+            physical_height: physical.1,
+            reported_scale: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_scale_matrix() {
+        // (logical size, physical size, expected scale)
+        let matrix = [
+            ((1920, 1080), (1920, 1080), 1.0),
+            ((2048, 1152), (2560, 1440), 1.25),
+            ((2560, 1440), (3840, 2160), 1.5),
+            ((2194, 1234), (3840, 2160), 1.75),
+            ((1440, 900), (2880, 1800), 2.0),
+            ((1280, 720), (3840, 2160), 3.0),
+        ];
+
+        for (logical, physical, expected) in matrix {
+            let geometry = display(logical, physical, (0, 0));
+            assert!(
+                (geometry.scale_x() - expected).abs() < 0.01,
+                "{:?} -> {:?}",
+                logical,
+                physical
+            );
+
+            // Center of the display maps to the center of the image
+            let (x, y) = geometry.to_physical(logical.0 as f64 / 2.0, logical.1 as f64 / 2.0);
+            assert!((x - physical.0 as f64 / 2.0).abs() < 2.0);
+            assert!((y - physical.1 as f64 / 2.0).abs() < 2.0);
+        }
+    }
+
+    #[test]
+    fn test_secondary_display_offset_and_clipping() {
+        // 4K monitor at 150% to the left of the primary display
+        let geometry = display((2560, 1440), (3840, 2160), (-2560, 0));
+
+        assert!(geometry.contains(-100.0, 100.0));
+        assert!(!geometry.contains(100.0, 100.0));
+        assert_eq!(geometry.to_physical(-2560.0, 0.0), (0.0, 0.0));
+
+        // Window hanging off the right edge is clipped to the image
+        let rect = geometry
+            .rect_to_physical(-200.0, 100.0, 800.0, 600.0)
+            .unwrap();
+        assert_eq!(rect, (3540, 150, 300, 900));
+
+        // Click in the corner keeps a full-size crop inside the image
+        let crop = geometry.centered_crop(-1.0, 1.0, 300);
+        assert_eq!(crop, (3390, 0, 450, 450));
+
+        // Normalized relative to this display, independent of its resolution
+        assert_eq!(geometry.normalize(-1280.0, 360.0), (0.5, 0.25));
+    }
+
+    #[test]
+    fn test_capture_conversion_layouts() {
+        let packed = vec![7u8; 2 * 2 * 4];
+        assert!(rgba_from_capture(2, 2, PixelFormat::Rgba8, &packed).is_ok());
+
+        // Each row padded from 8 to 12 bytes
+        let padded: Vec<u8> = (0..2)
+            .flat_map(|_| [vec![9u8; 8], vec![0u8; 4]].concat())
+            .collect();
+        let image = rgba_from_capture(2, 2, PixelFormat::Rgba8, &padded).unwrap();
+        assert!(image.as_raw().iter().all(|&byte| byte == 9));
+
+        // Padded to twice the row width: still 8-bit, not mistaken for 16-bit
+        let padded: Vec<u8> = (0..2)
+            .flat_map(|_| [vec![9u8; 8], vec![0u8; 8]].concat())
+            .collect();
+        let image = rgba_from_capture(2, 2, PixelFormat::Rgba8, &padded).unwrap();
+        assert!(image.as_raw().iter().all(|&byte| byte == 9));
+
+        // 16-bit HDR samples (little-endian 0xAB00 → 0xAB), packed and padded
+        let hdr: Vec<u8> = [0x00u8, 0xAB].repeat(2 * 2 * 4);
+        let image = rgba_from_capture(2, 2, PixelFormat::Rgba16Le, &hdr).unwrap();
+        assert!(image.as_raw().iter().all(|&byte| byte == 0xAB));
+        let padded_hdr: Vec<u8> = (0..2)
+            .flat_map(|_| [[0x00u8, 0xAB].repeat(2 * 4), vec![0u8; 16]].concat())
+            .collect();
+        let image = rgba_from_capture(2, 2, PixelFormat::Rgba16Le, &padded_hdr).unwrap();
+        assert!(image.as_raw().iter().all(|&byte| byte == 0xAB));
+
+        assert!(rgba_from_capture(2, 2, PixelFormat::Rgba8, &[0u8; 5]).is_err());
+        // Rows too short for 16-bit pixels
+        assert!(rgba_from_capture(2, 2, PixelFormat::Rgba16Le, &packed).is_err());
+    }
+}
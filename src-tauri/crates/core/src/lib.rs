@@ -0,0 +1,18 @@
+//! # FlowTrace Core - Session Data Model
+//!
+//! The types every other crate shares, and where sessions and settings
+//! live on disk, with no OS input or UI dependencies:
+//!
+//! - **`types`**: Events, sessions, and their JSON format
+//! - **`storage`**: Session persistence under the recordings directory
+//! - **`config`**: Recorder configuration (`config.json`)
+//! - **`marker`**: Hotkey-triggered step boundaries
+//! - **`display`**: Display geometry and coordinate translation
+//! - **`buffer_pool`**: Reusable screenshot buffers
+
+pub mod buffer_pool;
+pub mod config;
+pub mod display;
+pub mod marker;
+pub mod storage;
+pub mod types;
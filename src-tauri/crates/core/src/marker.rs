@@ -0,0 +1,141 @@
+//! # Marker Module - Hotkey-Triggered Step Boundaries
+//!
+//! Pressing the marker hotkey during a recording inserts a `Marker` event
+//! ("step boundary", "bug here"). The label is usually supplied afterwards
+//! via `label_marker`, so marking never interrupts the workflow.
+//!
+//! ## Hotkey Format
+//! Modifiers and a key joined by `+`, with the key named as `rdev` reports
+//! it (`KeyM`, `F8`, `Num1`, ...):
+//! ```text
+//! Control+Alt+KeyM   (default)
+//! Meta+Shift+F8      (Cmd+Shift+F8 on macOS)
+//! ```
+//! Modifier aliases: `Ctrl`, `Option`, `Cmd` / `Command` / `Super`.
+//!
+//! The hotkey itself is never recorded as a key press or shortcut, and it
+//! works while keyboard capture is switched off.
+//!
+//! ## Uses
+//! - Navigating long recordings (jump to markers)
+//! - Step segmentation: markers divide the steps into sections (see `steps` module)
+
+use crate::config::MarkerConfig;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// A parsed marker hotkey.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hotkey {
+    /// Required modifiers in `Control, Alt, Shift, Meta` order (as `held_modifiers()`)
+    modifiers: Vec<String>,
+    key: String,
+}
+
+impl Hotkey {
+    /// Parses a hotkey like `"Control+Alt+KeyM"`.
+    ///
+    /// # Returns
+    /// * `Ok(Hotkey)` - Parsed hotkey
+    /// * `Err(String)` - Error if a modifier is unknown or there isn't exactly one key
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut modifiers = Vec::new();
+        let mut key = None;
+
+        for part in text.split('+').map(str::trim) {
+            match modifier_name(part) {
+                Some(modifier) => modifiers.push(modifier),
+                None if part.is_empty() => {
+                    return Err(format!("Invalid hotkey '{}': empty key name", text))
+                }
+                None if key.is_some() => {
+                    return Err(format!("Invalid hotkey '{}': more than one key", text))
+                }
+                None => key = Some(part.to_string()),
+            }
+        }
+
+        let key = key.ok_or_else(|| format!("Invalid hotkey '{}': no key", text))?;
+        let modifiers = ["Control", "Alt", "Shift", "Meta"]
+            .into_iter()
+            .filter(|name| modifiers.contains(name))
+            .map(|name| name.to_string())
+            .collect();
+
+        Ok(Self { modifiers, key })
+    }
+
+    /// Whether a key press with these held modifiers is the hotkey.
+    pub fn matches(&self, modifiers: &[String], key: &str) -> bool {
+        self.key.eq_ignore_ascii_case(key) && self.modifiers == modifiers
+    }
+}
+
+/// Hotkey of the active recording (`None` if disabled or invalid).
+static HOTKEY: Lazy<Arc<Mutex<Option<Hotkey>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Sets the hotkey for the recording that is starting.
+///
+/// An invalid hotkey disables markers for the recording (with a warning)
+/// rather than preventing it from starting.
+pub fn activate(config: &MarkerConfig) {
+    let hotkey = if config.hotkey.trim().is_empty() {
+        None
+    } else {
+        Hotkey::parse(&config.hotkey)
+            .map_err(|e| eprintln!("⚠️  Marker hotkey disabled: {}", e))
+            .ok()
+    };
+    *HOTKEY.lock().unwrap() = hotkey;
+}
+
+/// Returns `true` if a key press is the active recording's marker hotkey.
+pub fn is_hotkey(modifiers: &[String], key: &str) -> bool {
+    HOTKEY
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|hotkey| hotkey.matches(modifiers, key))
+}
+
+/// Trims a user-supplied label; blank labels mean "no label".
+pub fn normalize_label(label: Option<String>) -> Option<String> {
+    label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
+}
+
+fn modifier_name(part: &str) -> Option<&'static str> {
+    match part.to_ascii_lowercase().as_str() {
+        "control" | "ctrl" => Some("Control"),
+        "alt" | "option" => Some("Alt"),
+        "shift" => Some("Shift"),
+        "meta" | "cmd" | "command" | "super" => Some("Meta"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hotkey_parse_and_match() {
+        let hotkey = Hotkey::parse("Alt + ctrl + KeyM").unwrap();
+        let held = vec!["Control".to_string(), "Alt".to_string()];
+
+        assert!(hotkey.matches(&held, "KeyM"));
+        assert!(!hotkey.matches(&held, "KeyN"));
+        // Extra modifiers make it a different combination
+        let with_shift = vec![
+            "Control".to_string(),
+            "Alt".to_string(),
+            "Shift".to_string(),
+        ];
+        assert!(!hotkey.matches(&with_shift, "KeyM"));
+
+        assert!(Hotkey::parse("Control+Alt").is_err());
+        assert!(Hotkey::parse("Control+KeyM+KeyN").is_err());
+        assert!(Hotkey::parse("Hyper+KeyM").is_err());
+    }
+}
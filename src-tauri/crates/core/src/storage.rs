@@ -0,0 +1,960 @@
+//! # Storage Module - Session Persistence
+//!
+//! Handles saving recording sessions to disk as JSON files with
+//! session-based directory organization.
+//!
+//! ## Location
+//! The recordings directory is `[app data]/recordings` (the platform
+//! app-data directory from Tauri's path resolver, e.g.
+//! `~/Library/Application Support/[bundle id]/recordings` on macOS), or the
+//! directory chosen with `set_storage_location`, remembered in
+//! `[app data]/storage.json`. `run()` sets it up with `configure_root()`
+//! before anything is read; until then (tests, benchmarks) it is the relative
+//! `recordings/` directory.
+//!
+//! Paths stored in sessions stay `recordings/[session-id]/[file]` wherever
+//! the directory is; `resolve_asset_path()` maps them onto the actual root,
+//! so sessions keep working when the directory is moved.
+//!
+//! ## Directory Structure
+//! ```text
+//! recordings/
+//! ├── bookmarks.json                (bookmarked events of all sessions)
+//! ├── adhoc-captures/               (spike screenshots as a session, see `adhoc` module)
+//! └── [session-id]/
+//!     ├── session.json              (event metadata)
+//!     ├── event_[id]_full.png       (full screen screenshots)
+//!     ├── event_[id]_window.png     (window crop screenshots)
+//!     ├── event_[id]_click.png      (click crop screenshots)
+//!     ├── ocr.json                  (screen text index, optional)
+//!     ├── comments.json             (review threads, optional)
+//!     └── privacy_report.json       (sensitive event review)
+//! ```
+//!
+//! ## Benefits of Session-Based Organization
+//! - Easy to zip/share individual sessions
+//! - No filename conflicts between sessions
+//! - Clean deletion of old recordings
+//! - Clear mapping between JSON and screenshots
+
+use crate::config;
+use crate::types::{Event, RecordingSession};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Largest `session.json` that will be parsed (typical files are 2-200 KB).
+///
+/// Guards against hostile or corrupted files, e.g., in imported recordings.
+pub const MAX_SESSION_FILE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Longest accepted session id (UUIDs are 36 characters).
+const MAX_SESSION_ID_LEN: usize = 128;
+
+/// Prefix of the asset paths stored in sessions (see module docs).
+const ASSET_PREFIX: &str = "recordings/";
+
+/// File in the app data directory that remembers a chosen recordings directory.
+const LOCATION_FILE: &str = "storage.json";
+
+/// Recordings directory set up by `configure_root()` (`None` = relative `recordings/`).
+static RECORDINGS_ROOT: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+/// Held for each load-change-save of a JSON index (see `update_json_index()`).
+///
+/// Without it two commands can both load the old file, and the second save
+/// drops the first one's change.
+static JSON_INDEX_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Persisted choice of recordings directory (`[app data]/storage.json`).
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct StorageLocation {
+    /// Directory chosen by the user (`None` = `[app data]/recordings`)
+    pub recordings_dir: Option<PathBuf>,
+}
+
+/// Recordings directory in use, as reported by `get_storage_location`.
+#[derive(Serialize, Debug, Clone)]
+pub struct StorageInfo {
+    /// Directory sessions are read from and written to
+    pub recordings_dir: String,
+    /// Directory used without a choice (`[app data]/recordings`)
+    pub default_dir: String,
+    /// Whether `recordings_dir` was chosen with `set_storage_location`
+    pub custom: bool,
+}
+
+/// Saves a recording session to a JSON file with pretty-printing.
+///
+/// Creates a session-specific directory and persists all event metadata
+/// as a human-readable JSON file. Screenshots are saved separately by
+/// the screenshot module to the same directory.
+///
+/// # Arguments
+/// * `session` - The recording session to save
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Absolute path to saved `session.json` file
+/// * `Err(String)` - Error message if directory creation or write fails
+///
+/// # File Location
+/// ```text
+/// recordings/[session-id]/session.json
+/// ```
+///
+/// # Directory Creation
+/// - Creates `recordings/` if it doesn't exist
+/// - Creates `recordings/[session-id]/` if it doesn't exist
+/// - Idempotent: Safe to call multiple times
+///
+/// # JSON Format
+/// Timestamps are truncated to `timestamps.precision` from the recorder
+/// config (microseconds by default); the in-memory session is unchanged.
+///
+/// Uses `serde_json::to_string_pretty()` for human-readable output:
+/// - 2-space indentation
+/// - Newlines between fields
+/// - Sorted keys (stable serialization)
+///
+/// # Example Output
+/// ```json
+/// {
+///   "session_id": "f2e904d2-286e-484c-83e8-5949bd8697f1",
+///   "started_at": "2026-02-01T15:43:08.646618Z",
+///   "stopped_at": "2026-02-01T15:43:18.855192Z",
+///   "events": [
+///     {
+///       "id": "cece1f95-8a90-4fa5-8fcc-2995113918ab",
+///       "event_type": {"type": "Click", "button": "Left"},
+///       ...
+///     }
+///   ]
+/// }
+/// ```
+///
+/// # Typical File Sizes
+/// - Minimal session (few events): ~2-5 KB
+/// - Moderate session (50 events): ~20-50 KB
+/// - Large session (200+ events): ~100-200 KB
+/// - Screenshots: ~2.2 MB each (stored separately)
+///
+/// # Error Handling
+/// Returns `Err(String)` if:
+/// - Unable to create directories (permission denied)
+/// - Unable to serialize session (should never happen)
+/// - Unable to write file (disk full, permission denied)
+pub fn save_session(session: &RecordingSession) -> Result<PathBuf, String> {
+    // Ensure base recordings directory exists
+    let recordings_dir = recordings_root();
+    fs::create_dir_all(&recordings_dir)
+        .map_err(|e| format!("Failed to create recordings directory: {:?}", e))?;
+
+    // Ensure session-specific subdirectory exists
+    let session_dir = recordings_dir.join(&session.session_id);
+    fs::create_dir_all(&session_dir)
+        .map_err(|e| format!("Failed to create session directory: {:?}", e))?;
+
+    // Serialize session to pretty-printed JSON at the configured precision
+    let json_path = session_dir.join("session.json");
+    let mut session = session.clone();
+    session.truncate_timestamps(config::current().timestamps.precision.digits());
+    let json_data = serde_json::to_string_pretty(&session)
+        .map_err(|e| format!("Failed to serialize session: {:?}", e))?;
+
+    // Write JSON to disk (overwrites if exists)
+    fs::write(&json_path, json_data)
+        .map_err(|e| format!("Failed to write session file: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!("💾 Session saved to: {:?}", json_path);
+
+    Ok(json_path)
+}
+
+/// Returns the directory path for a session's files (screenshots + JSON).
+///
+/// Used by screenshot module to determine where to save screenshot files.
+/// Does NOT create the directory - caller is responsible for creation.
+///
+/// # Arguments
+/// * `session_id` - UUID of the recording session
+///
+/// # Returns
+/// `PathBuf` to session directory: `recordings/[session-id]/`
+///
+/// # Example
+/// ```text
+/// let session_dir = get_session_dir("f2e904d2-286e-484c-83e8-5949bd8697f1");
+/// // Returns: PathBuf("recordings/f2e904d2-286e-484c-83e8-5949bd8697f1")
+/// ```
+pub fn get_session_dir(session_id: &str) -> PathBuf {
+    recordings_root().join(session_id)
+}
+
+/// Returns the root directory that holds all recording sessions.
+///
+/// Every module that touches the filesystem goes through this function
+/// instead of hard-coding `"recordings"`, so the storage location can be
+/// changed in one place (see module docs).
+pub fn recordings_root() -> PathBuf {
+    RECORDINGS_ROOT
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("recordings"))
+}
+
+/// Points `recordings_root()` at the chosen or default directory.
+///
+/// # Arguments
+/// * `app_data_dir` - Platform app-data directory (`app.path().app_data_dir()`)
+///
+/// # Returns
+/// The recordings directory now in use
+pub fn configure_root(app_data_dir: &Path) -> PathBuf {
+    let root = load_location(app_data_dir)
+        .recordings_dir
+        .unwrap_or_else(|| default_root(app_data_dir));
+    *RECORDINGS_ROOT.lock().unwrap() = Some(root.clone());
+
+    #[cfg(debug_assertions)]
+    println!("📁 Recordings directory: {:?}", root);
+
+    root
+}
+
+/// Remembers a recordings directory and switches to it.
+///
+/// Existing sessions are not moved; move the folder along to keep them.
+///
+/// # Arguments
+/// * `app_data_dir` - Platform app-data directory
+/// * `recordings_dir` - Absolute directory to use (`None` = back to the default)
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The recordings directory now in use
+/// * `Err(String)` - Error if the path is relative, or it or `storage.json`
+///   couldn't be written
+pub fn set_location(
+    app_data_dir: &Path,
+    recordings_dir: Option<PathBuf>,
+) -> Result<PathBuf, String> {
+    let location = StorageLocation { recordings_dir };
+    if let Some(dir) = &location.recordings_dir {
+        if !dir.is_absolute() {
+            return Err(format!(
+                "Recordings directory must be an absolute path: {:?}",
+                dir
+            ));
+        }
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create recordings directory: {:?}", e))?;
+    }
+
+    fs::create_dir_all(app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {:?}", e))?;
+    let json_data = serde_json::to_string_pretty(&location)
+        .map_err(|e| format!("Failed to serialize storage location: {:?}", e))?;
+    fs::write(app_data_dir.join(LOCATION_FILE), json_data)
+        .map_err(|e| format!("Failed to write storage location: {:?}", e))?;
+
+    Ok(configure_root(app_data_dir))
+}
+
+/// Describes the recordings directory in use.
+pub fn storage_info(app_data_dir: &Path) -> StorageInfo {
+    StorageInfo {
+        recordings_dir: recordings_root().display().to_string(),
+        default_dir: default_root(app_data_dir).display().to_string(),
+        custom: load_location(app_data_dir).recordings_dir.is_some(),
+    }
+}
+
+fn default_root(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("recordings")
+}
+
+/// Reads `storage.json`, falling back to the default directory if missing or invalid.
+fn load_location(app_data_dir: &Path) -> StorageLocation {
+    match fs::read_to_string(app_data_dir.join(LOCATION_FILE)) {
+        Ok(json_data) => serde_json::from_str(&json_data).unwrap_or_else(|e| {
+            eprintln!("⚠️  Invalid storage location, using the default: {:?}", e);
+            StorageLocation::default()
+        }),
+        Err(_) => StorageLocation::default(),
+    }
+}
+
+/// Builds the path string stored in `Screenshots` for a session asset.
+///
+/// The string doesn't depend on where the recordings directory is (see
+/// module docs).
+///
+/// # Example
+/// ```text
+/// let path = session_asset_path("f2e9...", "event_cece..._full.png");
+/// // Returns: "recordings/f2e9.../event_cece..._full.png"
+/// ```
+pub fn session_asset_path(session_id: &str, filename: &str) -> String {
+    format!("{}{}/{}", ASSET_PREFIX, session_id, filename)
+}
+
+/// Resolves a path string stored in `Screenshots` to a filesystem path.
+///
+/// Counterpart of `session_asset_path()`. Readers (import, export, GC)
+/// must use this rather than `PathBuf::from()` on the stored string.
+pub fn resolve_asset_path(relative: &str) -> PathBuf {
+    resolve_under(&recordings_root(), relative)
+}
+
+/// `recordings/...` paths relative to `root`; other paths as they are.
+fn resolve_under(root: &Path, stored: &str) -> PathBuf {
+    match stored.strip_prefix(ASSET_PREFIX) {
+        Some(inside) => root.join(inside),
+        None => PathBuf::from(stored),
+    }
+}
+
+/// Replaces the stored screenshot paths of a session by absolute filesystem
+/// paths, for a frontend that displays the files (e.g., via `convertFileSrc`).
+///
+/// Only for sessions handed out for viewing: saved sessions keep the
+/// `recordings/...` form (see module docs).
+pub fn resolve_screenshot_paths(session: &mut RecordingSession) {
+    let absolute = |stored: &mut String| {
+        let path = resolve_asset_path(stored);
+        let path = match std::env::current_dir() {
+            Ok(cwd) if path.is_relative() => cwd.join(path),
+            _ => path,
+        };
+        *stored = path.display().to_string();
+    };
+    for event in session.events.iter_mut() {
+        let screenshots = &mut event.screenshots;
+        for path in [
+            &mut screenshots.full_screen,
+            &mut screenshots.window_crop,
+            &mut screenshots.click_crop,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            absolute(path);
+        }
+        if let Some(patch) = screenshots.full_screen_patch.as_mut() {
+            absolute(&mut patch.base);
+        }
+    }
+}
+
+/// Lists the ids of all saved sessions (directories containing `session.json`).
+///
+/// # Returns
+/// Session ids sorted alphabetically; empty if the recordings directory doesn't exist
+pub fn list_session_ids() -> Vec<String> {
+    let mut ids: Vec<String> = fs::read_dir(recordings_root())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().join("session.json").is_file())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ids.sort();
+    ids
+}
+
+/// Overview of a saved session, as listed by `list_sessions()`.
+#[derive(Serialize, Debug, Clone)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// `None` if the recording never stopped (see `recovery` module)
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub event_count: usize,
+    /// Size of the session directory (JSON and screenshots) in bytes
+    pub total_bytes: u64,
+    /// Session a draft was duplicated from (see `draft` module)
+    pub draft_of: Option<String>,
+}
+
+/// Lists all saved sessions for the session browser.
+///
+/// # Returns
+/// Summaries, most recently started first. Sessions whose `session.json`
+/// can't be read are left out (`gc_storage` reports them).
+pub fn list_sessions() -> Vec<SessionSummary> {
+    let mut sessions: Vec<SessionSummary> = list_session_ids()
+        .into_iter()
+        .filter_map(|session_id| match load_session(&session_id) {
+            Ok(session) => Some(SessionSummary {
+                total_bytes: path_size(&get_session_dir(&session_id)),
+                session_id,
+                title: session.title,
+                started_at: session.started_at,
+                stopped_at: session.stopped_at,
+                event_count: session.events.len(),
+                draft_of: session.draft_of,
+            }),
+            Err(e) => {
+                eprintln!("⚠️  Session {} not listed: {}", session_id, e);
+                None
+            }
+        })
+        .collect();
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.started_at));
+    sessions
+}
+
+/// Loads a saved session from `recordings/[session-id]/session.json`.
+///
+/// # Returns
+/// * `Ok(RecordingSession)` - Deserialized session
+/// * `Err(String)` - Error if the id is invalid, or the file is missing or not a valid session
+pub fn load_session(session_id: &str) -> Result<RecordingSession, String> {
+    // Ids come from the frontend: never let one point outside the recordings root
+    if !is_valid_session_id(session_id) {
+        return Err(format!("Invalid session id: {:?}", session_id));
+    }
+    read_session_file(&get_session_dir(session_id).join("session.json"))
+}
+
+/// Whether a session id is safe to use as a directory name.
+///
+/// Accepts 1-128 ASCII letters, digits, `-` and `_` (UUIDs and test ids),
+/// which rules out path separators and `..`.
+pub fn is_valid_session_id(session_id: &str) -> bool {
+    (1..=MAX_SESSION_ID_LEN).contains(&session_id.len())
+        && session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Reads and deserializes a `session.json` file from an arbitrary location.
+///
+/// Used by `load_session()` and by the importer, which needs to inspect
+/// sessions before they are copied into the recordings directory.
+///
+/// # Errors
+/// Files larger than `MAX_SESSION_FILE_BYTES` are rejected before reading.
+pub fn read_session_file(path: &Path) -> Result<RecordingSession, String> {
+    let size = fs::metadata(path)
+        .map_err(|e| format!("Failed to read session file: {:?}", e))?
+        .len();
+    if size > MAX_SESSION_FILE_BYTES {
+        return Err(format!(
+            "Session file is too large ({} bytes, limit {})",
+            size, MAX_SESSION_FILE_BYTES
+        ));
+    }
+
+    let json_data =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {:?}", e))?;
+    parse_session(&json_data)
+}
+
+/// Deserializes the contents of a `session.json` file.
+///
+/// Event categories and descriptions are recomputed from the event data
+/// unless `classification.keep_stored` is set (see `types::Event`).
+/// Error messages include the line and column of the first problem.
+pub fn parse_session(json_data: &str) -> Result<RecordingSession, String> {
+    let mut session: RecordingSession = serde_json::from_str(json_data)
+        .map_err(|e| format!("Failed to parse session file: {}", e))?;
+    if !config::current().classification.keep_stored {
+        session.reclassify_events();
+    }
+    Ok(session)
+}
+
+/// Reads a JSON index file (bookmarks, presets, stats, comments, ...).
+///
+/// # Arguments
+/// * `path` - File to read
+/// * `what` - Name of the file's contents in error messages (e.g., `"bookmarks"`)
+///
+/// # Returns
+/// * `Ok(T)` - Parsed file, or `T::default()` if it doesn't exist yet
+/// * `Err(String)` - Error if the file can't be read or parsed. It is never
+///   replaced by an empty index, so the user's data survives until fixed
+pub fn read_json_index<T: DeserializeOwned + Default>(
+    path: &Path,
+    what: &str,
+) -> Result<T, String> {
+    let json_data = match fs::read_to_string(path) {
+        Ok(json_data) => json_data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(T::default()),
+        Err(e) => return Err(format!("Failed to read {}: {:?}", what, e)),
+    };
+    serde_json::from_str(&json_data).map_err(|e| format!("Failed to parse {}: {}", what, e))
+}
+
+/// Writes a JSON file via a temporary file so a crash never truncates it.
+///
+/// Creates the parent directory if needed.
+pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T, what: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory for {}: {:?}", what, e))?;
+    }
+
+    let json_data = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {:?}", what, e))?;
+
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json_data).map_err(|e| format!("Failed to write {}: {:?}", what, e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace {}: {:?}", what, e))
+}
+
+/// Loads a JSON index, lets `update` change it, and writes it back.
+///
+/// The whole cycle holds `JSON_INDEX_LOCK`, so concurrent updates never
+/// lose each other's changes. Nothing is written if the file can't be
+/// parsed (see `read_json_index()`) or `update` fails.
+///
+/// # Returns
+/// * `Ok(R)` - What `update` returned
+/// * `Err(String)` - Error from reading, `update`, or writing
+pub fn update_json_index<T, R>(
+    path: &Path,
+    what: &str,
+    update: impl FnOnce(&mut T) -> Result<R, String>,
+) -> Result<R, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let _guard = JSON_INDEX_LOCK.lock().unwrap();
+    let mut index = read_json_index(path, what)?;
+    let result = update(&mut index)?;
+    write_json_atomic(path, &index, what)?;
+    Ok(result)
+}
+
+/// Result of a storage garbage-collection pass.
+///
+/// All paths are relative to the recordings root.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct GcReport {
+    pub sessions_scanned: usize,
+    /// Screenshots not referenced by any event in their session
+    pub orphaned_screenshots: Vec<String>,
+    /// Leftover temporary files and directories (e.g., interrupted imports)
+    pub temporary_files: Vec<String>,
+    /// Session directories whose `session.json` couldn't be parsed (never touched)
+    pub skipped_sessions: Vec<String>,
+    /// Total size of orphaned + temporary files in bytes
+    pub reclaimable_bytes: u64,
+    /// Whether the listed files were actually deleted
+    pub deleted: bool,
+}
+
+/// Scans the recordings directory for orphaned screenshots and temporary files.
+///
+/// Screenshots are written to disk before their event is added to the session,
+/// so a crash or a failed save leaves PNGs that no `session.json` references.
+/// These silently accumulate at ~2.2MB each.
+///
+/// # Arguments
+/// * `delete` - If `false`, only report (dry run). If `true`, delete what was found.
+/// * `active_session_id` - Session currently being recorded; always skipped because
+///   its screenshots are not yet referenced by a saved `session.json`
+/// * `sessions_busy` - Whether a refresh or replay is running (see `refresh`
+///   module); no session is scanned then, because recaptured screenshots are
+///   only referenced once the refreshed session is saved. Temporary files are
+///   still reported.
+///
+/// # What Counts as Garbage
+/// - `*.png` in a session directory not referenced by any event in
+///   `session.json` or the journal (`events.jsonl`, see `journal` module)
+/// - `*.png` in a session directory without either (crashed recording)
+/// - `*.tmp` files and `.import-*` extraction directories
+///
+/// # Safety
+/// Directories with an unparseable `session.json` are reported in
+/// `skipped_sessions` and never modified.
+pub fn gc_storage(
+    delete: bool,
+    active_session_id: Option<&str>,
+    sessions_busy: bool,
+) -> Result<GcReport, String> {
+    gc_storage_at(&recordings_root(), delete, active_session_id, sessions_busy)
+}
+
+/// `gc_storage()` on the given recordings directory.
+fn gc_storage_at(
+    root: &Path,
+    delete: bool,
+    active_session_id: Option<&str>,
+    sessions_busy: bool,
+) -> Result<GcReport, String> {
+    let mut report = GcReport {
+        deleted: delete,
+        ..Default::default()
+    };
+
+    if !root.exists() {
+        return Ok(report);
+    }
+
+    let entries =
+        fs::read_dir(root).map_err(|e| format!("Failed to read recordings directory: {:?}", e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Temporary files/directories at the root level
+        if name.starts_with(".import-") || name.ends_with(".tmp") {
+            report.reclaimable_bytes += path_size(&path);
+            report.temporary_files.push(name);
+            if delete {
+                remove_path(&path)?;
+            }
+            continue;
+        }
+
+        if !path.is_dir() || sessions_busy || Some(name.as_str()) == active_session_id {
+            continue;
+        }
+
+        report.sessions_scanned += 1;
+
+        // Collect screenshot filenames referenced by the session
+        let json_path = path.join("session.json");
+        let mut referenced: HashSet<String> = if json_path.exists() {
+            match read_session_file(&json_path) {
+                Ok(session) => session
+                    .events
+                    .iter()
+                    .flat_map(|event| event.screenshots.paths())
+                    .filter_map(|p| Path::new(p).file_name())
+                    .map(|f| f.to_string_lossy().to_string())
+                    .collect(),
+                Err(_) => {
+                    report.skipped_sessions.push(name);
+                    continue;
+                }
+            }
+        } else {
+            HashSet::new()
+        };
+        // Events since the last checkpoint exist only in the journal
+        if let Ok(journal) = fs::read_to_string(path.join("events.jsonl")) {
+            referenced.extend(
+                journal
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<Event>(line).ok())
+                    .flat_map(|event| {
+                        event
+                            .screenshots
+                            .paths()
+                            .into_iter()
+                            .filter_map(|p| Path::new(p).file_name())
+                            .map(|f| f.to_string_lossy().to_string())
+                            .collect::<Vec<_>>()
+                    }),
+            );
+        }
+
+        let files = fs::read_dir(&path)
+            .map_err(|e| format!("Failed to read session directory: {:?}", e))?;
+        for file in files.flatten() {
+            let file_path = file.path();
+            let file_name = file.file_name().to_string_lossy().to_string();
+            let relative = format!("{}/{}", name, file_name);
+
+            let is_orphan = file_name.ends_with(".png") && !referenced.contains(&file_name);
+            let is_temp = file_name.ends_with(".tmp");
+            if !is_orphan && !is_temp {
+                continue;
+            }
+
+            report.reclaimable_bytes += path_size(&file_path);
+            if is_orphan {
+                report.orphaned_screenshots.push(relative);
+            } else {
+                report.temporary_files.push(relative);
+            }
+            if delete {
+                remove_path(&file_path)?;
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🧹 GC: {} orphaned screenshots, {} temporary files, {} bytes{}",
+        report.orphaned_screenshots.len(),
+        report.temporary_files.len(),
+        report.reclaimable_bytes,
+        if delete { " deleted" } else { " reclaimable" }
+    );
+
+    Ok(report)
+}
+
+/// Returns the size of a file, or the total size of a directory tree.
+fn path_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| path_size(&e.path())).sum())
+            .unwrap_or(0)
+    } else {
+        fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+    }
+}
+
+/// Deletes a file or directory tree.
+fn remove_path(path: &Path) -> Result<(), String> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    result.map_err(|e| format!("Failed to delete {:?}: {:?}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position};
+
+    #[test]
+    fn test_save_session() {
+        let mut session = RecordingSession::new("test-session".to_string());
+
+        let event = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(100.0, 200.0)),
+        );
+
+        session.add_event(event);
+        session.stop();
+
+        let result = save_session(&session);
+        assert!(result.is_ok());
+        println!("Test session saved to: {:?}", result.unwrap());
+    }
+
+    #[test]
+    fn test_list_sessions_summarizes_saved_sessions() {
+        let mut session = RecordingSession::new("test-list-sessions".to_string());
+        session.add_event(Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(100.0, 200.0)),
+        ));
+        session.stop();
+        save_session(&session).unwrap();
+
+        let sessions = list_sessions();
+        let summary = sessions
+            .iter()
+            .find(|summary| summary.session_id == "test-list-sessions")
+            .unwrap();
+        assert_eq!(summary.event_count, 1);
+        assert!(summary.stopped_at.is_some());
+        assert!(summary.total_bytes > 0);
+        assert!(sessions
+            .windows(2)
+            .all(|pair| pair[0].started_at >= pair[1].started_at));
+    }
+
+    #[test]
+    fn test_resolved_screenshot_paths_are_absolute() {
+        let mut session = RecordingSession::new("test-resolve".to_string());
+        session.add_event(
+            Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(100.0, 200.0)),
+            )
+            .with_screenshots(
+                Some(session_asset_path("test-resolve", "event_e1_full.png")),
+                None,
+                Some(session_asset_path("test-resolve", "event_e1_click.png")),
+            ),
+        );
+
+        resolve_screenshot_paths(&mut session);
+        let screenshots = &session.events[0].screenshots;
+        for path in screenshots.paths() {
+            assert!(Path::new(path).is_absolute());
+        }
+        assert!(Path::new(screenshots.full_screen.as_deref().unwrap())
+            .ends_with("test-resolve/event_e1_full.png"));
+        assert!(screenshots.window_crop.is_none());
+    }
+
+    #[test]
+    fn test_asset_paths_resolve_under_root() {
+        let root = Path::new("/data/FlowTrace/recordings");
+        let stored = session_asset_path("f2e9", "event_cece_full.png");
+        assert_eq!(stored, "recordings/f2e9/event_cece_full.png");
+        assert_eq!(
+            resolve_under(root, &stored),
+            root.join("f2e9").join("event_cece_full.png")
+        );
+        // Absolute paths (e.g. imported loose screenshots) stay as they are
+        assert_eq!(
+            resolve_under(root, "/tmp/shot.png"),
+            PathBuf::from("/tmp/shot.png")
+        );
+    }
+
+    #[test]
+    fn test_truncate_timestamps_to_millis() {
+        let mut session = RecordingSession::new("test-precision".to_string());
+        session.started_at = "2026-02-01T15:43:08.646618123Z".parse().unwrap();
+        session.truncate_timestamps(crate::config::TimestampPrecision::Millis.digits());
+
+        let json_data = serde_json::to_string(&session).unwrap();
+        assert!(json_data.contains(r#""started_at":"2026-02-01T15:43:08.646Z""#));
+    }
+
+    #[test]
+    fn test_descriptions_are_written_when_serialized() {
+        let mut session = RecordingSession::new("test-describe".to_string());
+        session.add_event(Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(100.0, 200.0)),
+        ));
+        // Not built while recording
+        assert!(session.events[0].description.is_empty());
+
+        let json = serde_json::to_value(&session).unwrap();
+        let event = &json["events"][0];
+        assert_eq!(
+            event["description"],
+            "Clicked left button at position (100, 200)"
+        );
+        assert_eq!(event["id"], session.events[0].id.to_string());
+        assert!(session.events[0].has_id(event["id"].as_str().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_session_recomputes_descriptions() {
+        let mut session = RecordingSession::new("test-reclassify".to_string());
+        session.add_event(Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(100.0, 200.0)),
+        ));
+        // Written by an older classifier (or missing entirely)
+        session.events[0].action_category = "unknown".to_string();
+        session.events[0].description = "Click".to_string();
+
+        let json_data = serde_json::to_string(&session).unwrap();
+        let loaded = parse_session(&json_data).unwrap();
+        assert_eq!(loaded.events[0].action_category, "interaction");
+        assert_eq!(
+            loaded.events[0].description(),
+            "Clicked left button at position (100, 200)"
+        );
+    }
+
+    #[test]
+    fn test_gc_storage_finds_orphans() {
+        let root = std::env::temp_dir().join(format!("flowtrace-gc-{}", uuid::Uuid::new_v4()));
+        let session_dir = root.join("test-gc-session");
+        fs::create_dir_all(&session_dir).unwrap();
+        let session = RecordingSession::new("test-gc-session".to_string());
+        fs::write(
+            session_dir.join("session.json"),
+            serde_json::to_string(&session).unwrap(),
+        )
+        .unwrap();
+        let orphan = session_dir.join("event_orphan_full.png");
+        fs::write(&orphan, b"not really a png").unwrap();
+        fs::write(root.join("bookmarks.json.tmp"), b"{}").unwrap();
+
+        let report = gc_storage_at(&root, false, None, false).unwrap();
+        assert_eq!(report.sessions_scanned, 1);
+        assert_eq!(
+            report.orphaned_screenshots,
+            vec!["test-gc-session/event_orphan_full.png".to_string()]
+        );
+        assert!(orphan.exists()); // Dry run must not delete
+
+        // A screenshot referenced only by the journal is not an orphan
+        let mut event = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(100.0, 200.0)),
+        );
+        let journaled = session_dir.join("event_journaled_full.png");
+        fs::write(&journaled, b"not really a png").unwrap();
+        event.screenshots.full_screen = Some(journaled.to_string_lossy().to_string());
+        fs::write(
+            session_dir.join("events.jsonl"),
+            format!("{}\n", serde_json::to_string(&event).unwrap()),
+        )
+        .unwrap();
+        let report = gc_storage_at(&root, false, None, false).unwrap();
+        assert_eq!(
+            report.orphaned_screenshots,
+            vec!["test-gc-session/event_orphan_full.png".to_string()]
+        );
+
+        // Neither the active session nor any session during a refresh is touched
+        for (active, busy) in [(Some("test-gc-session"), false), (None, true)] {
+            let report = gc_storage_at(&root, true, active, busy).unwrap();
+            assert_eq!(report.sessions_scanned, 0);
+            assert!(report.orphaned_screenshots.is_empty());
+            assert!(orphan.exists());
+        }
+        assert!(!root.join("bookmarks.json.tmp").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_index_is_never_replaced() {
+        let dir = std::env::temp_dir().join(format!("flowtrace-index-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("bookmarks.json");
+
+        // Missing: starts empty and is created on the first update
+        let added = update_json_index(&path, "bookmarks", |names: &mut Vec<String>| {
+            names.push("first".to_string());
+            Ok(names.len())
+        });
+        assert_eq!(added, Ok(1));
+
+        // Invalid: reported, and the next update leaves the file alone
+        fs::write(&path, "{ not json").unwrap();
+        assert!(read_json_index::<Vec<String>>(&path, "bookmarks").is_err());
+        let result = update_json_index(&path, "bookmarks", |names: &mut Vec<String>| {
+            names.clear();
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
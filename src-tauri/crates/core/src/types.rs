@@ -0,0 +1,1584 @@
+//! # Type Definitions for FlowTrace Recording System
+//!
+//! Core data structures representing:
+//! - **Recording sessions**: Container for captured events with timestamps
+//! - **Events**: Individual user actions (clicks, keypresses, waits)
+//! - **Event classification**: Automatic categorization and description generation
+//!
+//! ## Data Flow
+//! ```text
+//! User Action → rdev::Event → EventType → Event → RecordingSession → JSON
+//! ```
+//!
+//! ## Serialization
+//! All types derive `Serialize` + `Deserialize` for JSON persistence, except
+//! `Event`, which writes its description as it is serialized (see `Event`).
+
+use crate::display::DisplayGeometry;
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// A complete recording session containing all captured user interactions.
+///
+/// # Structure
+/// - **session_id**: UUIDv4 identifier (e.g., `f2e904d2-286e-484c-83e8-5949bd8697f1`)
+/// - **started_at**: UTC timestamp when recording began
+/// - **stopped_at**: UTC timestamp when recording ended (None if still recording)
+/// - **events**: Ordered list of all captured events
+/// - **edited_timeline**: Adjusted timestamps, only present after a step was
+///   re-recorded (see `splice` module)
+/// - **utc_offset**: Local UTC offset when recording began (e.g., `"+01:00"`),
+///   only present if `timestamps.record_utc_offset` is enabled
+/// - **title**: Given to `start_recording()`, else generated from the dominant
+///   app and first heading at stop (see `titling` module)
+/// - **capture_stats**: Capture degradations, only present if something was
+///   degraded (see `CaptureStats`)
+/// - **truncated**: Set if the event listener died mid-recording: nothing
+///   after `at` was recorded (see `watchdog` module)
+/// - **part**: Set on sessions recorded with `continue_session()`: the later
+///   sitting of a multi-day procedure (see `parts` module)
+/// - **draft_of**: Set on copies made with `duplicate_session()`, which can
+///   be reordered and extended (see `draft` module)
+/// - **step_grouping**: Manual step boundaries of a draft (see `StepGrouping`)
+/// - **schema_version**: Format of the file (`SCHEMA_VERSION` when written;
+///   1 for files from before it was recorded)
+///
+/// # Lifecycle
+/// 1. Created via `RecordingSession::new()` when user starts recording
+/// 2. Events added via `add_event()` as user interacts
+/// 3. Finalized via `stop()` when user stops recording
+/// 4. Serialized to JSON via `storage::save_session()`
+///
+/// # Example JSON Output
+/// ```json
+/// {
+///   "session_id": "f2e904d2-286e-484c-83e8-5949bd8697f1",
+///   "started_at": "2026-02-01T15:43:08.646618Z",
+///   "stopped_at": "2026-02-01T15:43:18.855192Z",
+///   "events": [...],
+///   "title": "Google Chrome — Create Purchase Order",
+///   "schema_version": 1
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordingSession {
+    pub session_id: String,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub events: Vec<Event>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_timeline: Option<Vec<TimelineEntry>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utc_offset: Option<String>,
+    /// Given at start or generated at stop (see `titling` module)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_stats: Option<CaptureStats>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<Truncation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part: Option<SessionPart>,
+    /// Session this draft was duplicated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_of: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub step_grouping: Vec<StepGrouping>,
+    /// Newer versions are refused by `integrity` checks
+    #[serde(default = "first_schema_version")]
+    pub schema_version: u32,
+}
+
+/// Version of the `session.json` format written by this build.
+///
+/// Bumped when a change can't be read by older builds (not for new
+/// optional fields, which they ignore).
+pub const SCHEMA_VERSION: u32 = 1;
+
+fn first_schema_version() -> u32 {
+    1
+}
+
+/// Overrides where a step starts (see `steps` module).
+///
+/// # Example JSON
+/// ```json
+/// {"event_id": "2b0c7c7e-...", "rule": "join"}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StepGrouping {
+    /// First event the rule applies to
+    pub event_id: uuid::Uuid,
+    pub rule: GroupingRule,
+}
+
+/// How an event's step is grouped.
+///
+/// - **Split** - The event starts a new step (e.g., in the middle of typing)
+/// - **Join** - The step starting with the event is merged into the one before
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupingRule {
+    Split,
+    Join,
+}
+
+/// Link from a continuation to the first part of its procedure.
+///
+/// # Example JSON
+/// ```json
+/// {"first_session_id": "f2e904d2-286e-484c-83e8-5949bd8697f1", "number": 2}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SessionPart {
+    /// Session that was continued first (part 1, which has no `part` itself)
+    pub first_session_id: String,
+    /// 1-based position in the procedure (2 for the first continuation)
+    pub number: u32,
+}
+
+/// Point after which a recording lost its events.
+///
+/// # Example JSON
+/// ```json
+/// {"at": "2026-02-01T15:45:00Z", "reason": "Event listener stopped: EventTapError"}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Truncation {
+    /// Last input recorded before the failure (or when it was noticed)
+    pub at: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// How capture was degraded during a recording.
+///
+/// # Example JSON
+/// ```json
+/// {"window_fallbacks": 14, "accessibility_denied": true}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CaptureStats {
+    /// Events whose window (crop, app name, title) came from the window list
+    /// instead of the accessibility-based lookup (see `focus` module)
+    pub window_fallbacks: u32,
+    /// The Accessibility permission was missing at some point
+    pub accessibility_denied: bool,
+}
+
+/// Adjusted timestamp of one event in an edited session.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimelineEntry {
+    pub event_id: uuid::Uuid,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl RecordingSession {
+    pub fn new(session_id: String) -> Self {
+        Self {
+            session_id,
+            started_at: Utc::now(),
+            stopped_at: None,
+            events: Vec::new(),
+            edited_timeline: None,
+            utc_offset: None,
+            title: None,
+            capture_stats: None,
+            truncated: None,
+            part: None,
+            draft_of: None,
+            step_grouping: Vec::new(),
+            schema_version: SCHEMA_VERSION,
+        }
+    }
+
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn stop(&mut self) {
+        self.stopped_at = Some(Utc::now());
+    }
+
+    /// Truncates every timestamp to `digits` fractional second digits.
+    pub fn truncate_timestamps(&mut self, digits: u16) {
+        self.started_at = self.started_at.trunc_subsecs(digits);
+        self.stopped_at = self.stopped_at.map(|at| at.trunc_subsecs(digits));
+        for event in self.events.iter_mut() {
+            event.timestamp = event.timestamp.trunc_subsecs(digits);
+        }
+        for entry in self.edited_timeline.iter_mut().flatten() {
+            entry.timestamp = entry.timestamp.trunc_subsecs(digits);
+        }
+    }
+
+    /// Recomputes every event's category and description (see `Event::reclassify()`).
+    pub fn reclassify_events(&mut self) {
+        for event in self.events.iter_mut() {
+            event.reclassify();
+        }
+    }
+
+    /// Timestamp of every event in playback order.
+    ///
+    /// Uses `edited_timeline` where it has an entry for the event, otherwise
+    /// the recorded timestamp.
+    pub fn timeline(&self) -> Vec<DateTime<Utc>> {
+        self.events
+            .iter()
+            .map(|event| {
+                self.edited_timeline
+                    .as_ref()
+                    .and_then(|timeline| timeline.iter().find(|entry| entry.event_id == event.id))
+                    .map(|entry| entry.timestamp)
+                    .unwrap_or(event.timestamp)
+            })
+            .collect()
+    }
+}
+
+/// Screenshot file paths for a single event.
+///
+/// Each event can have up to 3 associated screenshots.
+/// Paths are relative to project root: `recordings/[session-id]/[filename]`
+///
+/// # Fields
+/// - **full_screen**: Always `Some(String)` for click events, `None` for keyboard/wait
+/// - **window_crop**: `Some(String)` if window detection succeeded, `None` otherwise
+/// - **click_crop**: `Some(String)` if crop succeeded, `None` otherwise
+/// - **full_screen_patch**: Set if `full_screen` holds only the changed region
+///   of an earlier capture (see `frame_diff` module)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Screenshots {
+    pub full_screen: Option<String>,
+    pub window_crop: Option<String>,
+    pub click_crop: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_screen_patch: Option<FramePatch>,
+}
+
+/// Where a partial full-screen capture belongs in its base frame.
+///
+/// # Example JSON
+/// ```json
+/// {"base": "recordings/.../event_..._full.png", "x": 640, "y": 360}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FramePatch {
+    /// Stored path of the complete capture the patch is drawn onto
+    pub base: String,
+    /// Top-left corner of the patch in the base frame (physical pixels)
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Screenshots {
+    /// Returns the paths of all screenshots that were captured.
+    pub fn paths(&self) -> Vec<&str> {
+        [&self.full_screen, &self.window_crop, &self.click_crop]
+            .into_iter()
+            .flatten()
+            .map(|path| path.as_str())
+            .collect()
+    }
+}
+
+/// Stand-in for typed text removed by `Event::redact()`.
+pub const REDACTED_TEXT: &str = "[redacted]";
+
+/// A single captured user action with metadata, classification, and screenshots.
+///
+/// Events are the fundamental unit of recording. Each represents one user action:
+/// - Mouse click (with position and screenshots)
+/// - Keyboard press (with key name)
+/// - Wait/pause (with duration)
+///
+/// # Automatic Classification
+/// Events are automatically classified into 21 categories:
+/// - `interaction` - Click events
+/// - `text_input` - Letter, number, and punctuation keys; typed text
+/// - `submit` - Enter/Return key
+/// - `navigation` - Tab key
+/// - `correction` - Backspace/Delete keys
+/// - `cancel` - Escape key
+/// - `wait` - Automatic pause detection
+/// - `idle` - No input for minutes (see `idle` module)
+/// - `paused` - Recording paused by the user
+/// - `shortcut` - Key combinations with Ctrl/Alt/Cmd
+/// - `movement` - Sampled mouse trails (opt-in)
+/// - `hover` - Cursor resting in one place
+/// - `scroll` - Mouse wheel / trackpad scrolling (opt-in)
+/// - `credentials` - Password entry (keystrokes not recorded)
+/// - `marker` - Manual marker inserted with the hotkey
+/// - `app_switch` - Focus moved to another application
+/// - `capture` - Screenshot taken outside a recording (see `adhoc` module)
+/// - `lifecycle` - System asleep or screen locked (see `lifecycle` module)
+/// - `restart` - Recording resumed after FlowTrace restarted (see `resume` module)
+/// - `capture_error` - Event handling failed; events may be missing (see `watchdog` module)
+/// - `special_key` - Other special keys
+///
+/// # Fields
+/// - **id**: UUIDv4 unique identifier (written as a hyphenated string)
+/// - **event_type**: Discriminated union (Click | KeyPress | Wait)
+/// - **timestamp**: UTC timestamp when event occurred
+/// - **position**: Screen coordinates (Some for clicks, None for keyboard/wait)
+/// - **screenshots**: Paths to associated screenshot files
+/// - **action_category**: One of 21 classification categories
+/// - **description**: Human-readable description (e.g., "Clicked left button at (709, 328)")
+/// - **app_name**: Foreground application at capture time (omitted if unknown)
+/// - **window_title**: Focused window title at capture time (omitted if unknown)
+/// - **element**: Clicked UI element (role, label, value) where the platform exposes it
+/// - **annotation_colors**: Marker/badge colors that stand out on the click region
+/// - **response_latency_ms**: Time from the press until the screen changed
+///   (clicks only, opt-in; see `latency` module)
+/// - **scroll_position**: How far the scroll area under the click was scrolled
+///   (clicks only, where the platform exposes it)
+/// - **file_drag**: Source and target of a file dragged out of a file manager
+///   (clicks only, see `file_drag` module)
+/// - **redacted**: Text and screenshots were removed in the privacy review (omitted if not)
+///
+/// # Derived Fields
+/// `action_category` and `description` are derived from the other fields.
+/// They are still written to `session.json` for readers outside FlowTrace,
+/// but sessions are reclassified when loaded (`Event::reclassify()`), so
+/// classification improvements apply to old recordings as well. Set
+/// `classification.keep_stored` in the recorder config to use the stored
+/// values as-is (e.g., hand-edited descriptions).
+///
+/// The description isn't built while recording: `description()` derives it
+/// when read, and serialization writes what `description()` returns. The
+/// field only holds a stored description (loaded with `keep_stored`, or set
+/// explicitly), which takes precedence.
+///
+/// # Example JSON
+/// ```json
+/// {
+///   "id": "cece1f95-8a90-4fa5-8fcc-2995113918ab",
+///   "event_type": {"type": "Click", "button": "Left"},
+///   "timestamp": "2026-02-01T15:43:11.627959Z",
+///   "position": {"x": 709, "y": 328},
+///   "screenshots": {
+///     "full_screen": "recordings/.../event_..._full.png",
+///     "window_crop": "recordings/.../event_..._window.png",
+///     "click_crop": "recordings/.../event_..._click.png"
+///   },
+///   "action_category": "interaction",
+///   "description": "Clicked left button at position (709, 328)",
+///   "error_dialog_detected": false
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(remote = "Self")]
+pub struct Event {
+    pub id: uuid::Uuid,
+    pub event_type: EventType,
+    pub timestamp: DateTime<Utc>,
+    pub position: Option<Position>,
+    pub screenshots: Screenshots,
+    /// Derived (see "Derived Fields"); empty if missing from the file
+    #[serde(default)]
+    pub action_category: String,
+    /// Stored description, empty = derived (see "Derived Fields"); read it with `description()`
+    #[serde(default, skip_serializing)]
+    pub description: String,
+    /// Capture likely shows an OS/app error dialog (see `error_dialog` module)
+    #[serde(default)]
+    pub error_dialog_detected: bool,
+    /// Application in the foreground at capture time (e.g., `"Google Chrome"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_name: Option<String>,
+    /// Title of the focused window (e.g., `"Invoice #1234 — Google Chrome"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_title: Option<String>,
+    /// UI element under the click (clicks only, see `accessibility` module)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element: Option<UiElement>,
+    /// Annotation colors with enough contrast on the click region (clicks only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation_colors: Option<AnnotationColors>,
+    /// Time from the press until the screen meaningfully changed (clicks only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_latency_ms: Option<u64>,
+    /// Scroll position of the area under the click (clicks only, see `accessibility` module)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scroll_position: Option<ScrollPosition>,
+    /// File dragged from a file manager, if the click was one (see `file_drag` module)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_drag: Option<FileDrag>,
+    /// Text and screenshots were removed in the privacy review (see `privacy` module)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub redacted: bool,
+    /// Recorded in a VM or remote desktop window (see `remote_desktop` module)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub remote_desktop: bool,
+}
+
+impl Serialize for Event {
+    /// Serializes the event with the description `description()` returns.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Described<'a> {
+            #[serde(flatten, serialize_with = "Event::serialize")]
+            event: &'a Event,
+            description: Cow<'a, str>,
+        }
+
+        Described {
+            event: self,
+            description: self.description(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Event::deserialize(deserializer)
+    }
+}
+
+impl Event {
+    pub fn new(event_type: EventType, position: Option<Position>) -> Self {
+        let action_category = Self::classify(&event_type).to_string();
+
+        Self {
+            id: uuid::Uuid::new_v4(),
+            event_type,
+            timestamp: Utc::now(),
+            position,
+            screenshots: Screenshots {
+                full_screen: None,
+                window_crop: None,
+                click_crop: None,
+                full_screen_patch: None,
+            },
+            action_category,
+            description: String::new(),
+            error_dialog_detected: false,
+            app_name: None,
+            window_title: None,
+            element: None,
+            annotation_colors: None,
+            response_latency_ms: None,
+            scroll_position: None,
+            file_drag: None,
+            redacted: false,
+            remote_desktop: false,
+        }
+    }
+
+    pub fn with_screenshots(
+        mut self,
+        full: Option<String>,
+        window: Option<String>,
+        click: Option<String>,
+    ) -> Self {
+        self.screenshots = Screenshots {
+            full_screen: full,
+            window_crop: window,
+            click_crop: click,
+            full_screen_patch: None,
+        };
+        self
+    }
+
+    /// Attaches the clicked UI element, by which the click is described.
+    ///
+    /// `"Clicked left button at position (709, 328)"` becomes
+    /// `"Clicked button 'Submit'"` (`"Right-clicked ..."`, `"Clicked back button on ..."`
+    /// for other buttons).
+    pub fn with_element(mut self, element: UiElement) -> Self {
+        if matches!(self.event_type, EventType::Click { .. }) {
+            self.description.clear();
+        }
+        self.element = Some(element);
+        self
+    }
+
+    /// Records that a click was a file drag and describes it as one.
+    ///
+    /// # Returns
+    /// `false` (nothing changed) if the event isn't a click
+    pub fn set_file_drag(&mut self, drag: FileDrag) -> bool {
+        if !matches!(self.event_type, EventType::Click { .. }) {
+            return false;
+        }
+        self.description.clear();
+        self.file_drag = Some(drag);
+        true
+    }
+
+    /// Recomputes `action_category` and drops the stored description.
+    ///
+    /// Produces what `Event::new()` and the setters below would produce
+    /// today; `description()` then derives the description from the event
+    /// data. Redacted events keep their category: the redacted key no longer
+    /// says what it was.
+    pub fn reclassify(&mut self) {
+        self.description.clear();
+        if !self.redacted || self.action_category.is_empty() {
+            self.action_category = Self::classify(&self.event_type).to_string();
+        }
+    }
+
+    /// Whether `id` (e.g., sent by the frontend or read from an index) is this event's id.
+    pub fn has_id(&self, id: &str) -> bool {
+        uuid::Uuid::parse_str(id).is_ok_and(|id| id == self.id)
+    }
+
+    /// Human-readable description (e.g., `"Clicked button 'Submit'"`).
+    ///
+    /// The stored description if there is one, otherwise derived from the
+    /// event data, including element-based click descriptions and file drags.
+    pub fn description(&self) -> Cow<'_, str> {
+        if !self.description.is_empty() {
+            return Cow::Borrowed(&self.description);
+        }
+        Cow::Owned(match (&self.event_type, &self.element, &self.file_drag) {
+            (EventType::Click { .. }, _, Some(drag)) => drag.describe(),
+            (EventType::Click { button, .. }, Some(element), None) => {
+                Self::describe_element_click(button, element)
+            }
+            _ => Self::describe(&self.event_type, &self.position),
+        })
+    }
+
+    /// `"Clicked button 'Submit'"` style description of a click on an element.
+    fn describe_element_click(button: &MouseButton, element: &UiElement) -> String {
+        let verb = match button {
+            MouseButton::Left => "Clicked".to_string(),
+            MouseButton::Right => "Right-clicked".to_string(),
+            MouseButton::Middle => "Middle-clicked".to_string(),
+            other => format!("Clicked {} on", other.label()),
+        };
+        let target = match (element.role_name(), &element.label) {
+            (Some(role), Some(label)) => format!("{} '{}'", role, label),
+            (Some(role), None) => role,
+            (None, Some(label)) => format!("'{}'", label),
+            (None, None) => "element".to_string(),
+        };
+        format!("{} {}", verb, target)
+    }
+
+    /// Removes everything that could reveal what was typed or shown.
+    ///
+    /// Typed text, the window title, the clicked element, and the screenshot
+    /// paths are dropped and the description is derived from what's left,
+    /// so `"Typed \"jane@example.com\""` becomes `"Typed \"[redacted]\""`.
+    /// Deleting the screenshot files is up to the caller.
+    pub fn redact(&mut self) {
+        match &mut self.event_type {
+            EventType::TextInput { text, .. } => *text = REDACTED_TEXT.to_string(),
+            // Typing keys name the character itself; Enter, Tab, etc. are harmless
+            EventType::KeyPress { key, text, .. } if self.action_category == "text_input" => {
+                *key = REDACTED_TEXT.to_string();
+                *text = Some(REDACTED_TEXT.to_string());
+            }
+            _ => {}
+        }
+        self.screenshots = Screenshots {
+            full_screen: None,
+            window_crop: None,
+            click_crop: None,
+            full_screen_patch: None,
+        };
+        self.window_title = None;
+        self.element = None;
+        self.annotation_colors = None;
+        self.description.clear();
+        // The apps are kept: "Dragged file from Finder to Google Chrome"
+        if let Some(drag) = self.file_drag.as_mut() {
+            drag.file_name = None;
+            drag.target_window = None;
+            drag.target_path = None;
+        }
+        self.redacted = true;
+    }
+
+    /// Sets the label of a `Marker` event and updates its description.
+    ///
+    /// # Returns
+    /// `false` (nothing changed) if the event isn't a marker
+    pub fn set_marker_label(&mut self, new_label: Option<String>) -> bool {
+        let EventType::Marker { label } = &mut self.event_type else {
+            return false;
+        };
+        *label = new_label;
+        self.description.clear();
+        true
+    }
+
+    /// Sets how long the button of a `Click` event was held.
+    ///
+    /// # Returns
+    /// `false` (nothing changed) if the event isn't a click
+    pub fn set_click_duration(&mut self, milliseconds: u64) -> bool {
+        let EventType::Click { duration_ms, .. } = &mut self.event_type else {
+            return false;
+        };
+        *duration_ms = Some(milliseconds);
+        true
+    }
+
+    /// Sets how long the key of a `KeyPress` event was held.
+    ///
+    /// # Returns
+    /// `false` (nothing changed) if the event isn't a key press
+    pub fn set_key_duration(&mut self, milliseconds: u64) -> bool {
+        let EventType::KeyPress { duration_ms, .. } = &mut self.event_type else {
+            return false;
+        };
+        *duration_ms = Some(milliseconds);
+        true
+    }
+
+    /// Counts one auto-repeat of a held key and updates the description.
+    ///
+    /// # Returns
+    /// `false` (nothing changed) if the event isn't a key press
+    pub fn add_key_repeat(&mut self) -> bool {
+        let EventType::KeyPress { repeat_count, .. } = &mut self.event_type else {
+            return false;
+        };
+        *repeat_count += 1;
+        self.description.clear();
+        true
+    }
+
+    /// Sets the duration of a `Wait` event and updates its description.
+    ///
+    /// # Returns
+    /// `false` (nothing changed) if the event isn't a wait
+    pub fn set_wait_duration(&mut self, seconds: f64) -> bool {
+        let EventType::Wait {
+            duration_seconds, ..
+        } = &mut self.event_type
+        else {
+            return false;
+        };
+        *duration_seconds = seconds;
+        self.description.clear();
+        true
+    }
+
+    /// Sets the cause of a `Wait` event and updates its description.
+    ///
+    /// # Returns
+    /// `false` (nothing changed) if the event isn't a wait
+    pub fn set_wait_cause(&mut self, wait_cause: Option<WaitCause>) -> bool {
+        let EventType::Wait { cause, .. } = &mut self.event_type else {
+            return false;
+        };
+        *cause = wait_cause;
+        self.description.clear();
+        true
+    }
+
+    /// Automatically classifies an event.
+    ///
+    /// This is the core classification engine that analyzes event types and
+    /// assigns semantic meaning for workflow analysis. It runs for every
+    /// recorded event, so it returns a static name and allocates nothing;
+    /// descriptions are generated later, when read (see `describe()`).
+    ///
+    /// # Classification Categories
+    ///
+    /// | Category | Events | Purpose |
+    /// |----------|--------|---------|
+    /// | `interaction` | All click events | User interacting with UI elements |
+    /// | `text_input` | Letters (KeyA-KeyZ), Numbers (Num0-Num9), Space, punctuation, `TextInput` | User typing content |
+    /// | `submit` | Enter, Return keys | User submitting forms/commands |
+    /// | `navigation` | Tab key | User navigating between fields |
+    /// | `correction` | Backspace, Delete keys | User fixing mistakes |
+    /// | `cancel` | Escape key | User canceling operations |
+    /// | `wait` | Synthetic events (gap > `waits.threshold_seconds`, 2s by default) | User pausing/thinking |
+    /// | `idle` | Synthetic events (no input > `idle.threshold_seconds`, 5 min by default) | User away from the keyboard |
+    /// | `paused` | Recording paused by the user | Capture suspended (e.g., password entry) |
+    /// | `shortcut` | Key with Ctrl/Alt/Cmd held (Cmd+S, Ctrl+C) | User invoking a command |
+    /// | `movement` | Sampled mouse trails (opt-in) | Cursor trajectory between actions |
+    /// | `hover` | Synthetic events (cursor resting > 1.5s) | User reading a menu/tooltip |
+    /// | `scroll` | Wheel events (opt-in capture flag) | User scrolling content |
+    /// | `credentials` | Placeholder for password entry | Keystrokes withheld |
+    /// | `marker` | Marker hotkey / `add_marker` | User-labelled step boundary |
+    /// | `app_switch` | Foreground application changed (click, Cmd+Tab, ...) | User moving between apps |
+    /// | `capture` | Imported ad-hoc screenshots, window snapshots | Screen state without an action |
+    /// | `lifecycle` | System sleep, screen lock | Time to exclude from analysis |
+    /// | `restart` | Recording resumed after a crash or update | Events may be missing before it |
+    /// | `capture_error` | Panic while handling an input event | Events may be missing around it |
+    /// | `special_key` | Other keys (arrows, function keys, etc.) | Other keyboard actions |
+    ///
+    ///
+    /// # Key Classification Logic
+    /// - "Space" → `text_input`
+    /// - Recorded `text` (characters from the OS layout) → `text_input`
+    /// - Starts with "Key" → Letter key (KeyA, KeyB, ...) → `text_input`
+    /// - Starts with "Num" → Number key (Num1, Num2, ...) → `text_input`
+    /// - Punctuation (Comma, Dot, Minus, ...) → `text_input`
+    /// - "Return"/"Enter" → `submit`
+    /// - "Tab" → `navigation`
+    /// - "Backspace"/"Delete" → `correction`
+    /// - "Escape" → `cancel`
+    /// - Other → `special_key`
+    ///
+    ///
+    /// # Arguments
+    /// * `event_type` - The type of event to classify
+    ///
+    /// # Returns
+    /// The category name (e.g., `"interaction"`)
+    fn classify(event_type: &EventType) -> &'static str {
+        match event_type {
+            // CLICKS: Always classified as "interaction"
+            EventType::Click { .. } => "interaction",
+            // KEYBOARD: Classify based on key type and purpose
+            EventType::KeyPress { key, text, .. } => {
+                if key == "Space" || text.is_some() {
+                    // Space bar and printable characters as reported by the OS (any layout)
+                    "text_input"
+                } else if key.starts_with("Key") || key.starts_with("Num") {
+                    // Letter keys (KeyA ... KeyZ) and number keys (Num0 ... Num9)
+                    "text_input"
+                } else if PUNCTUATION_KEYS.contains(&key.as_str()) {
+                    // Punctuation keys: Comma, Dot, Minus, ...
+                    "text_input"
+                } else if key == "Return" || key == "Enter" {
+                    // Submit action: User confirming/submitting form or command
+                    "submit"
+                } else if key == "Tab" {
+                    // Navigation: User moving between fields or UI elements
+                    "navigation"
+                } else if key == "Backspace" || key == "Delete" {
+                    // Correction: User fixing typos or removing content
+                    "correction"
+                } else if key == "Escape" {
+                    // Cancel: User aborting operation or closing modal
+                    "cancel"
+                } else {
+                    // Other special keys: Arrows, Function keys, etc.
+                    "special_key"
+                }
+            }
+            EventType::TextInput { .. } => "text_input",
+            EventType::Wait { .. } => "wait",
+            EventType::Shortcut { .. } => "shortcut",
+            EventType::Hover { .. } => "hover",
+            EventType::MouseTrail { .. } => "movement",
+            EventType::Scroll { .. } => "scroll",
+            EventType::CredentialsEntered { .. } => "credentials",
+            EventType::Marker { .. } => "marker",
+            EventType::AppSwitch { .. } => "app_switch",
+            EventType::Capture { .. } => "capture",
+            EventType::ManualStep { .. } => "manual",
+            EventType::Lifecycle { .. } => "lifecycle",
+            EventType::RecorderRestart { .. } => "restart",
+            EventType::CaptureError { .. } => "capture_error",
+            EventType::Idle { .. } => "idle",
+            EventType::Paused { .. } => "paused",
+        }
+    }
+
+    /// Generates a human-readable description of an event.
+    ///
+    /// # Description Format
+    ///
+    /// **Clicks**: `"Clicked {button} button at position ({x}, {y})"`
+    /// - Example: `"Clicked left button at position (709, 328)"`,
+    ///   `"Clicked back button at position (709, 328)"`, `"Clicked button 12 at ..."`
+    ///
+    /// **Text Input**: `"Typed: {text}"` (recorded characters, else the key name)
+    /// - Example: `"Typed: A"`, `"Typed: é"`, `"Typed: 5"`, `"Typed: Space"`
+    /// - Aggregated: `"Typed \"{text}\""`, e.g., `"Typed \"Hello world\""`
+    ///
+    /// **Special Actions**: `"Pressed {key} ({category})"`
+    /// - Example: `"Pressed Enter (submit)"`, `"Pressed Tab (navigate)"`
+    ///
+    /// **Wait Events**: `"Paused for {duration} seconds"`, with the cause if known
+    /// - Example: `"Paused for 2.7 seconds"`, `"Paused for 8.1 seconds (loading)"`
+    ///
+    /// **Idle Events**: `"Idle for {duration} seconds"`
+    /// - Example: `"Idle for 612.3 seconds"`
+    ///
+    /// **Paused Events**: `"Recording paused for {duration} seconds"`
+    /// - Example: `"Recording paused for 42.0 seconds"`
+    ///
+    /// **Hover Events**: `"Hovered at position ({x}, {y}) for {duration} seconds"`
+    /// - Example: `"Hovered at position (640, 24) for 3.0 seconds"`
+    ///
+    /// **Mouse Trails**: `"Moved mouse through {n} points over {duration} seconds"`
+    /// - Example: `"Moved mouse through 12 points over 2.2 seconds"`
+    ///
+    /// **Scrolls**: `"Scrolled {direction} by {amount}"`
+    /// - Example: `"Scrolled down by 12"`, `"Scrolled right by 3"`
+    ///
+    /// **Credentials**: `"Entered credentials ({duration} seconds, not recorded)"`
+    /// - Example: `"Entered credentials (6.2 seconds, not recorded)"`
+    ///
+    /// **Markers**: `"Marker: {label}"`, or `"Marker"` until a label is supplied
+    /// - Example: `"Marker: bug here"`
+    ///
+    /// **App Switches**: `"Switched from {from} to {to}"`
+    /// - Example: `"Switched from Microsoft Excel to Google Chrome"`
+    ///
+    /// **Captures**: `"Captured the screen"`
+    ///
+    /// **Manual Steps**: The step's text
+    /// - Example: `"Sign the printed form"`
+    ///
+    /// **Lifecycle**: `"System asleep for {duration} seconds"`,
+    /// `"Screen locked for {duration} seconds"`
+    /// - Example: `"Screen locked for 312.4 seconds"`
+    ///
+    /// **Recorder Restarts**: `"Recorder restarted ({gap} seconds since the last checkpoint)"`
+    /// - Example: `"Recorder restarted (3.2 seconds since the last checkpoint)"`
+    ///
+    /// **Capture Errors**: `"Capture error: {message}"`
+    /// - Example: `"Capture error: PoisonError { .. }"`
+    ///
+    /// **Shortcuts**: `"{action} ({combo})"` for well-known shortcuts,
+    /// otherwise `"Pressed {combo}"`
+    /// - Example: `"Saved (Cmd+S)"`, `"Pressed Ctrl+Alt+K"`
+    ///
+    ///
+    /// # Arguments
+    /// * `event_type` - The type of event to describe
+    /// * `position` - Optional screen position (used for click descriptions)
+    ///
+    /// # Returns
+    /// The description, without element or file-drag details (see `description()`)
+    fn describe(event_type: &EventType, position: &Option<Position>) -> String {
+        match event_type {
+            EventType::Click { button, .. } => match position {
+                Some(pos) => format!(
+                    "Clicked {} at position ({}, {})",
+                    button.label(),
+                    pos.x,
+                    pos.y
+                ),
+                None => format!("Clicked {}", button.label()),
+            },
+            EventType::KeyPress {
+                key,
+                text,
+                repeat_count,
+                ..
+            } => {
+                let description = match Self::classify(event_type) {
+                    "text_input" => {
+                        let typed = match text {
+                            _ if key == "Space" => key.as_str(),
+                            Some(text) => text.as_str(),
+                            None => key
+                                .strip_prefix("Key")
+                                .or_else(|| key.strip_prefix("Num"))
+                                .unwrap_or(key),
+                        };
+                        format!("Typed: {}", typed)
+                    }
+                    "submit" => "Pressed Enter (submit)".to_string(),
+                    "navigation" => "Pressed Tab (navigate)".to_string(),
+                    "correction" => format!("Pressed {} (correction)", key),
+                    "cancel" => "Pressed Escape (cancel)".to_string(),
+                    _ => format!("Pressed: {}", key),
+                };
+                // Held keys: one event for the press and all its auto-repeats
+                match repeat_count {
+                    0 => description,
+                    repeats => format!("{} ×{}", description, repeats + 1),
+                }
+            }
+            EventType::TextInput { text, .. } => format!("Typed \"{}\"", text),
+            EventType::Wait {
+                duration_seconds,
+                cause,
+            } => {
+                let description = format!("Paused for {:.1} seconds", duration_seconds);
+                match cause {
+                    Some(cause) => format!("{} ({})", description, cause.label()),
+                    None => description,
+                }
+            }
+            EventType::Shortcut { modifiers, key } => {
+                let combo = shortcut_label(modifiers, key);
+                match shortcut_action(modifiers, key) {
+                    Some(action) => format!("{} ({})", action, combo),
+                    None => format!("Pressed {}", combo),
+                }
+            }
+            EventType::Hover { duration_seconds } => match position {
+                Some(pos) => format!(
+                    "Hovered at position ({}, {}) for {:.1} seconds",
+                    pos.x, pos.y, duration_seconds
+                ),
+                None => format!("Hovered for {:.1} seconds", duration_seconds),
+            },
+            EventType::MouseTrail { points } => {
+                let duration_ms = points.last().map(|point| point.0).unwrap_or(0);
+                format!(
+                    "Moved mouse through {} points over {:.1} seconds",
+                    points.len(),
+                    duration_ms as f64 / 1000.0
+                )
+            }
+            EventType::Scroll { delta_x, delta_y } => {
+                // Dominant axis; positive deltas scroll up / left (rdev convention)
+                let (direction, amount) = if delta_y.abs() >= delta_x.abs() {
+                    (if *delta_y > 0 { "up" } else { "down" }, delta_y.abs())
+                } else {
+                    (if *delta_x > 0 { "left" } else { "right" }, delta_x.abs())
+                };
+                format!("Scrolled {} by {}", direction, amount)
+            }
+            EventType::CredentialsEntered { duration_seconds } => format!(
+                "Entered credentials ({:.1} seconds, not recorded)",
+                duration_seconds
+            ),
+            EventType::Marker { label } => match label {
+                Some(label) => format!("Marker: {}", label),
+                None => "Marker".to_string(),
+            },
+            EventType::AppSwitch { from, to } => format!("Switched from {} to {}", from, to),
+            EventType::Capture { .. } => "Captured the screen".to_string(),
+            EventType::ManualStep { text } => text.clone(),
+            EventType::Lifecycle {
+                state,
+                duration_seconds,
+            } => match state {
+                LifecycleState::Sleep => {
+                    format!("System asleep for {:.1} seconds", duration_seconds)
+                }
+                LifecycleState::Locked => {
+                    format!("Screen locked for {:.1} seconds", duration_seconds)
+                }
+            },
+            EventType::RecorderRestart { gap_seconds } => format!(
+                "Recorder restarted ({:.1} seconds since the last checkpoint)",
+                gap_seconds
+            ),
+            EventType::CaptureError { message } => format!("Capture error: {}", message),
+            EventType::Idle { duration_seconds } => {
+                format!("Idle for {:.1} seconds", duration_seconds)
+            }
+            EventType::Paused { duration_seconds } => {
+                format!("Recording paused for {:.1} seconds", duration_seconds)
+            }
+        }
+    }
+}
+
+/// UI element resolved through the platform accessibility API.
+///
+/// # Example JSON
+/// ```json
+/// {"role": "AXButton", "label": "Submit"}
+/// {"role": "AXTextField", "label": "Invoice number", "value": "1234"}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct UiElement {
+    /// Platform role (e.g., `"AXButton"`, `"AXTextField"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Visible title or accessibility description (e.g., `"Submit"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Current value (text field contents, checkbox state); never for password fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl UiElement {
+    /// Human-readable role: `"AXPopUpButton"` → `"pop up button"`.
+    pub fn role_name(&self) -> Option<String> {
+        let role = self.role.as_deref()?;
+        let role = role.strip_prefix("AX").unwrap_or(role);
+        let mut name = String::new();
+        for character in role.chars() {
+            if character.is_uppercase() && !name.is_empty() {
+                name.push(' ');
+            }
+            name.extend(character.to_lowercase());
+        }
+        Some(name).filter(|name| !name.is_empty())
+    }
+}
+
+/// Scroll position of the area around a click (see `accessibility` module).
+///
+/// # Example JSON
+/// ```json
+/// {"vertical_percent": 60}
+/// {"vertical_percent": 100, "horizontal_percent": 25}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ScrollPosition {
+    /// 0 at the top, 100 at the bottom (omitted if the area doesn't scroll vertically)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_percent: Option<u8>,
+    /// 0 at the left edge, 100 at the right edge
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_percent: Option<u8>,
+}
+
+impl ScrollPosition {
+    /// `"scrolled ~60% down"` style note for documentation (rounded to 10%).
+    ///
+    /// `None` at the top-left, where there is nothing worth saying.
+    pub fn summary(&self) -> Option<String> {
+        let approximately = |percent: u8| (percent as f64 / 10.0).round() as u8 * 10;
+        match (
+            self.vertical_percent.map(approximately),
+            self.horizontal_percent.map(approximately),
+        ) {
+            (Some(100), _) => Some("scrolled to the bottom".to_string()),
+            (Some(vertical), _) if vertical > 0 => Some(format!("scrolled ~{}% down", vertical)),
+            (_, Some(horizontal)) if horizontal > 0 => {
+                Some(format!("scrolled ~{}% right", horizontal))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A file dragged out of a file manager and dropped elsewhere (see `file_drag` module).
+///
+/// # Example JSON
+/// ```json
+/// {"file_name": "report.pdf", "source_app": "Finder", "target_app": "Google Chrome",
+///  "target_window": "Upload files — Google Chrome", "drop_position": {"x": 1210, "y": 540}}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileDrag {
+    /// Dragged item (label of the element under the press), if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// File manager the drag started in (e.g., `"Finder"`)
+    pub source_app: String,
+    /// Application of the window under the release (`None` over the desktop)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_app: Option<String>,
+    /// Title of the window under the release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_window: Option<String>,
+    pub drop_position: Position,
+    /// Where the file appeared after a drop on a folder window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_path: Option<String>,
+}
+
+impl FileDrag {
+    /// `"Dragged file 'report.pdf' from Finder to Google Chrome"` style description.
+    pub fn describe(&self) -> String {
+        let file = match &self.file_name {
+            Some(name) => format!("file '{}'", name),
+            None => "file".to_string(),
+        };
+        match (&self.target_app, &self.target_window) {
+            (Some(app), Some(folder)) if *app == self.source_app => {
+                format!("Dragged {} to '{}' in {}", file, folder, app)
+            }
+            (Some(app), None) if *app == self.source_app => {
+                format!("Dragged {} within {}", file, app)
+            }
+            (Some(app), _) => format!("Dragged {} from {} to {}", file, self.source_app, app),
+            (None, _) => format!("Dragged {} out of {}", file, self.source_app),
+        }
+    }
+}
+
+/// Annotation colors chosen for the click region (see `annotation` module).
+///
+/// # Example JSON
+/// ```json
+/// {"tone": "dark", "marker": "#FFD600", "outline": "#000000", "badge_text": "#000000"}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnnotationColors {
+    /// Whether the click region is predominantly dark or light
+    pub tone: Tone,
+    /// Click markers, arrows, and step badge backgrounds (`"#RRGGBB"`)
+    pub marker: String,
+    /// Halo drawn around markers and arrows
+    pub outline: String,
+    /// Text drawn on step badges (on top of `marker`)
+    pub badge_text: String,
+}
+
+/// Overall brightness of a captured region.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Tone {
+    Dark,
+    Light,
+}
+
+/// Discriminated union representing the type of captured event.
+///
+/// Uses `#[serde(tag = "type")]` for tagged union serialization.
+/// Each variant carries its specific data.
+///
+/// # Variants
+///
+/// **Click** - Mouse button press
+/// - `button: MouseButton` - Which button was pressed
+/// - `duration_ms: Option<u64>` - How long the button was held (press to
+///   release); `None` if the release wasn't seen (e.g., recording stopped).
+///   The release is only processed after the click's screenshots, so holds
+///   shorter than the capture (100-500ms) read as the capture time
+/// - Has position (tracked from MouseMove events)
+/// - Triggers 3 screenshots
+///
+/// **KeyPress** - Keyboard key press
+/// - `key: String` - Key name (e.g., "KeyA", "Return", "Space")
+/// - No position (keyboard events aren't location-based)
+/// - No screenshots (design decision to reduce storage)
+/// - `shift: bool` - Shift was held (capitals / shifted symbols); omitted when `false`
+/// - `text: Option<String>` - Characters the key produced in the active keyboard
+///   layout (e.g., `"é"`, `"@"`); omitted for non-printing keys
+/// - `duration_ms: Option<u64>` - How long the key was held (press to release);
+///   `None` if the release wasn't seen
+/// - `repeat_count: u32` - Auto-repeats while the key was held (holding
+///   Backspace deletes `1 + repeat_count` characters); omitted when `0`.
+///   Platforms that report auto-repeat as separate release/press pairs
+///   still record one event per repeat
+///
+/// **TextInput** - Typed text (aggregated from consecutive typing KeyPresses)
+/// - `text: String` - Reconstructed text, with Shift and Backspace applied
+/// - `keystrokes: usize` - Number of keystrokes it replaces (auto-repeats included)
+/// - Created when a recording is stopped (see `postprocess` module)
+///
+/// **Shortcut** - Key pressed while Ctrl/Alt/Cmd were held
+/// - `modifiers: Vec<String>` - Held modifiers in `Control, Alt, Shift, Meta` order
+/// - `key: String` - Key name (e.g., "KeyS")
+/// - Shift alone doesn't make a shortcut (it's recorded as a KeyPress with `shift: true`)
+///
+/// **Wait** - Synthetic pause event (auto-generated)
+/// - `duration_seconds: f64` - Length of pause
+/// - `cause: Option<WaitCause>` - `loading`, `reading`, or `away` (see
+///   `wait_cause` module); omitted if unknown
+/// - No position
+/// - No screenshots
+///
+/// **Idle** - No input for longer than `idle.threshold_seconds` (see `idle` module)
+/// - `duration_seconds: f64` - Time without input (sleep and lock excluded)
+/// - Timestamp is when input stopped; recorded instead of a Wait
+///
+/// **Hover** - Synthetic dwell event (auto-generated)
+/// - `duration_seconds: f64` - How long the cursor rested
+/// - Has position (where it rested); timestamp is when the rest began
+/// - No screenshots
+///
+/// **Paused** - Interval where the user paused the recording
+/// - `duration_seconds: f64` - Time between pause and resume (or stop)
+/// - Timestamp is when the pause began; nothing is captured meanwhile
+///
+/// **MouseTrail** - Sampled cursor trajectory (opt-in, see `mouse_trail` module)
+/// - `points: Vec<TrailPoint>` - `[offset_ms, x, y]` since the event timestamp
+/// - No screenshots
+///
+/// **Scroll** - Wheel ticks merged while scrolling continues (opt-in capture flag)
+/// - `delta_x: i64`, `delta_y: i64` - Summed wheel deltas as reported by `rdev`
+///   (positive `delta_y` scrolls up)
+/// - Position is the cursor when scrolling began; no screenshots
+///
+/// **CredentialsEntered** - Placeholder for keys withheld during password entry
+/// - `duration_seconds: f64` - Time the password field / auth dialog had focus
+/// - Timestamp is when entry began; no keystrokes (or their count) are stored
+///
+/// **Marker** - Manual marker inserted with the hotkey (see `marker` module)
+/// - `label: Option<String>` - Free text such as "bug here", often supplied afterwards
+/// - No position; no screenshots
+///
+/// **AppSwitch** - Foreground application changed (see `app_switch` module)
+/// - `from: String`, `to: String` - Application names before and after
+/// - Recorded whatever caused it (click, Cmd+Tab, a dialog taking focus)
+/// - Timestamp is when the change was noticed (polled, up to 250ms late);
+///   no position, no screenshots
+///
+/// **Capture** - Full-screen screenshot not tied to an input (see `adhoc` module)
+/// - `original_file: Option<String>` - Loose file it was imported from
+/// - Timestamp is when the screenshot was taken; only `full_screen` (imported
+///   screenshots) or `window_crop` (window snapshots) is set, with the
+///   window's `app_name` and `window_title`
+///
+/// **ManualStep** - Step written into a draft session (see `draft` module)
+/// - `text: String` - Instruction text of the step
+/// - Timestamp is the one of the event before it; `full_screen` is the
+///   user-provided image, if any
+///
+/// **Lifecycle** - Interval where the machine slept or the screen was locked
+///   (see `lifecycle` module)
+/// - `state: LifecycleState` - `Sleep` or `Locked`
+/// - `duration_seconds: f64` - Length of the interval
+/// - Timestamp is when the interval began; Wait events don't count this time
+///
+/// **RecorderRestart** - Recording resumed after FlowTrace restarted (see `resume` module)
+/// - `gap_seconds: f64` - Time since the last checkpoint; events recorded
+///   in it before the restart are lost
+/// - Timestamp is when recording resumed; no position, no screenshots
+///
+/// **CaptureError** - Handling an input event panicked (see `watchdog` module)
+/// - `message: String` - Panic message; the event being handled is lost
+/// - Repeats of the same message within 10 seconds aren't recorded again
+///
+/// # JSON Serialization
+/// ```json
+/// {"type": "Click", "button": "Left", "duration_ms": 96}
+/// {"type": "KeyPress", "key": "KeyA"}
+/// {"type": "KeyPress", "key": "KeyA", "shift": true, "text": "A"}
+/// {"type": "KeyPress", "key": "Quote", "text": "é"}
+/// {"type": "KeyPress", "key": "Backspace", "duration_ms": 1450, "repeat_count": 23}
+/// {"type": "TextInput", "text": "Hello world", "keystrokes": 12}
+/// {"type": "Shortcut", "modifiers": ["Meta"], "key": "KeyS"}
+/// {"type": "Wait", "duration_seconds": 2.704}
+/// {"type": "Wait", "duration_seconds": 8.1, "cause": "loading"}
+/// {"type": "Idle", "duration_seconds": 612.3}
+/// {"type": "Hover", "duration_seconds": 3.0}
+/// {"type": "Paused", "duration_seconds": 42.0}
+/// {"type": "MouseTrail", "points": [[0, 100, 200], [200, 130, 210]]}
+/// {"type": "Scroll", "delta_x": 0, "delta_y": -12}
+/// {"type": "CredentialsEntered", "duration_seconds": 6.2}
+/// {"type": "Marker", "label": "bug here"}
+/// {"type": "AppSwitch", "from": "Microsoft Excel", "to": "Google Chrome"}
+/// {"type": "Capture", "original_file": "screenshot_20260201_154311.png"}
+/// {"type": "ManualStep", "text": "Sign the printed form"}
+/// {"type": "Lifecycle", "state": "Locked", "duration_seconds": 312.4}
+/// {"type": "RecorderRestart", "gap_seconds": 3.2}
+/// {"type": "CaptureError", "message": "index out of bounds: the len is 0 but the index is 0"}
+/// ```
+///
+/// # Descoped
+/// - `MouseMove` - Too noisy (100+ events/second), only used for position tracking
+///   (opt-in `MouseTrail` samples it instead)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum EventType {
+    Click {
+        button: MouseButton,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,
+    },
+    KeyPress {
+        key: String,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        shift: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "is_zero")]
+        repeat_count: u32,
+    },
+    TextInput {
+        text: String,
+        #[serde(default)]
+        keystrokes: usize,
+    },
+    Shortcut {
+        modifiers: Vec<String>,
+        key: String,
+    },
+    Wait {
+        duration_seconds: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cause: Option<WaitCause>,
+    },
+    Idle {
+        duration_seconds: f64,
+    },
+    Hover {
+        duration_seconds: f64,
+    },
+    Paused {
+        duration_seconds: f64,
+    },
+    MouseTrail {
+        points: Vec<TrailPoint>,
+    },
+    Scroll {
+        delta_x: i64,
+        delta_y: i64,
+    },
+    CredentialsEntered {
+        duration_seconds: f64,
+    },
+    Marker {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    },
+    AppSwitch {
+        from: String,
+        to: String,
+    },
+    Capture {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        original_file: Option<String>,
+    },
+    ManualStep {
+        text: String,
+    },
+    Lifecycle {
+        state: LifecycleState,
+        duration_seconds: f64,
+    },
+    RecorderRestart {
+        gap_seconds: f64,
+    },
+    CaptureError {
+        message: String,
+    },
+    // MouseMove, // Descoped for MVP (too noisy)
+}
+
+impl EventType {
+    /// Serde tag of the variant (`"Click"`, `"KeyPress"`, ...).
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventType::Click { .. } => "Click",
+            EventType::KeyPress { .. } => "KeyPress",
+            EventType::TextInput { .. } => "TextInput",
+            EventType::Shortcut { .. } => "Shortcut",
+            EventType::Wait { .. } => "Wait",
+            EventType::Idle { .. } => "Idle",
+            EventType::Hover { .. } => "Hover",
+            EventType::Paused { .. } => "Paused",
+            EventType::MouseTrail { .. } => "MouseTrail",
+            EventType::Scroll { .. } => "Scroll",
+            EventType::CredentialsEntered { .. } => "CredentialsEntered",
+            EventType::Marker { .. } => "Marker",
+            EventType::AppSwitch { .. } => "AppSwitch",
+            EventType::Capture { .. } => "Capture",
+            EventType::ManualStep { .. } => "ManualStep",
+            EventType::Lifecycle { .. } => "Lifecycle",
+            EventType::RecorderRestart { .. } => "RecorderRestart",
+            EventType::CaptureError { .. } => "CaptureError",
+        }
+    }
+}
+
+/// Why the machine was unavailable during a `Lifecycle` event.
+///
+/// - **Sleep** - System suspended (detected after wake)
+/// - **Locked** - Screen locked (macOS only)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LifecycleState {
+    Sleep,
+    Locked,
+}
+
+/// Likely reason nobody acted during a `Wait` (see `wait_cause` module).
+///
+/// - **Loading** - The screen kept changing (the system was busy)
+/// - **Reading** - The screen was static in the same window (user think-time)
+/// - **Away** - The screen was static for `wait_causes.away_seconds` or longer
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WaitCause {
+    Loading,
+    Reading,
+    Away,
+}
+
+impl WaitCause {
+    /// Cause name used in descriptions (e.g., `"loading"`).
+    pub fn label(&self) -> &'static str {
+        match self {
+            WaitCause::Loading => "loading",
+            WaitCause::Reading => "reading",
+            WaitCause::Away => "away",
+        }
+    }
+}
+
+/// One sample of a mouse trail: `(offset_ms, x, y)` in logical pixels.
+///
+/// Serialized as a `[offset_ms, x, y]` array to keep long trails compact.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TrailPoint(pub u32, pub i32, pub i32);
+
+/// Keys that type punctuation (`rdev` key names).
+pub const PUNCTUATION_KEYS: &[&str] = &[
+    "BackQuote",
+    "Minus",
+    "Equal",
+    "LeftBracket",
+    "RightBracket",
+    "SemiColon",
+    "Quote",
+    "BackSlash",
+    "IntlBackslash",
+    "Comma",
+    "Dot",
+    "Slash",
+];
+
+/// Serde helper: omit zero counts.
+fn is_zero(count: &u32) -> bool {
+    *count == 0
+}
+
+/// Human-readable key combination, e.g., `["Meta", "Shift"], "KeyZ"` → `"Cmd+Shift+Z"`.
+fn shortcut_label(modifiers: &[String], key: &str) -> String {
+    let key = key
+        .strip_prefix("Key")
+        .or_else(|| key.strip_prefix("Num"))
+        .unwrap_or(key);
+
+    modifiers
+        .iter()
+        .map(|modifier| match modifier.as_str() {
+            "Control" => "Ctrl",
+            "Meta" => "Cmd",
+            other => other,
+        })
+        .chain(std::iter::once(key))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Semantic action of well-known shortcuts (Cmd on macOS, Ctrl elsewhere).
+fn shortcut_action(modifiers: &[String], key: &str) -> Option<&'static str> {
+    let has = |name: &str| modifiers.iter().any(|modifier| modifier == name);
+    let command = has("Meta") || has("Control");
+    if !command || has("Alt") {
+        return None;
+    }
+
+    let action = match (key, has("Shift")) {
+        ("KeyS", false) => "Saved",
+        ("KeyS", true) => "Saved as",
+        ("KeyC", false) => "Copied",
+        ("KeyV", false) => "Pasted",
+        ("KeyX", false) => "Cut",
+        ("KeyZ", false) => "Undid",
+        ("KeyZ", true) | ("KeyY", false) => "Redid",
+        ("KeyA", false) => "Selected all",
+        ("KeyF", false) => "Opened find",
+        ("KeyN", false) => "Created new",
+        ("KeyO", false) => "Opened",
+        ("KeyP", false) => "Printed",
+        ("KeyT", false) => "Opened new tab",
+        ("KeyW", false) => "Closed window/tab",
+        ("KeyQ", false) if has("Meta") => "Quit application",
+        ("Tab", _) if has("Meta") => "Switched application",
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Mouse button types that can be captured.
+///
+/// # Supported Buttons
+/// - **Left** - Primary button (most common)
+/// - **Right** - Context menu button
+/// - **Middle** - Middle button / scroll wheel click
+/// - **Back** / **Forward** - Side buttons (browser navigation)
+/// - **Other(code)** - Any further button, by the platform code `rdev` reports
+///   (gaming mice)
+///
+/// # Example JSON
+/// ```json
+/// "Left"
+/// "Back"
+/// {"Other": 12}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+    Other(u8),
+}
+
+impl MouseButton {
+    /// Button name used in descriptions (e.g., `"left button"`, `"button 12"`).
+    pub fn label(&self) -> String {
+        match self {
+            MouseButton::Left => "left button".to_string(),
+            MouseButton::Right => "right button".to_string(),
+            MouseButton::Middle => "middle button".to_string(),
+            MouseButton::Back => "back button".to_string(),
+            MouseButton::Forward => "forward button".to_string(),
+            MouseButton::Other(code) => format!("button {}", code),
+        }
+    }
+}
+
+/// Screen coordinates for event position.
+///
+/// # Coordinate System
+/// - Origin: Top-left corner of primary display (0, 0)
+/// - X-axis: Increases right
+/// - Y-axis: Increases down
+/// - Units: **Logical pixels** (not physical pixels)
+///
+/// # HiDPI Displays
+/// On HiDPI displays (e.g., 2x Retina), these are logical coordinates:
+/// - Logical position: (713, 395)
+/// - Physical pixels: (1426, 790) on 2x display
+/// - The screenshot module converts via `display::DisplayGeometry` before cropping
+///
+/// # Normalized Coordinates
+/// Clicks also store the position as a fraction of their display's size
+/// (plus the display id), so replays and heatmaps work across resolutions
+/// without knowing the original screen size. Missing in older sessions and
+/// when the display couldn't be determined.
+///
+/// `x`/`y` are global: on a secondary display left of or above the primary
+/// they can be negative. `local_x`/`local_y` are the same point relative to
+/// the top-left corner of the display it is on (the display whose
+/// screenshots the click's captures come from).
+///
+/// # Example
+/// ```json
+/// {"x": 709, "y": 328}
+/// {"x": 709, "y": 328, "normalized": {"display_id": 1, "x": 0.4924, "y": 0.3644, "local_x": 709, "local_y": 328}}
+/// {"x": -731, "y": 328, "normalized": {"display_id": 2, "x": 0.4924, "y": 0.3644, "local_x": 709, "local_y": 328}}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized: Option<NormalizedPosition>,
+}
+
+/// Position relative to one display: `0.0` = left/top edge, `1.0` = right/bottom edge.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NormalizedPosition {
+    /// Display identifier (`screenshots::DisplayInfo::id`)
+    pub display_id: u32,
+    pub x: f64,
+    pub y: f64,
+    /// Logical offset from the display's top-left corner (`0` in older sessions)
+    #[serde(default)]
+    pub local_x: i32,
+    #[serde(default)]
+    pub local_y: i32,
+}
+
+impl Position {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self {
+            x: x as i32,
+            y: y as i32,
+            normalized: None,
+        }
+    }
+
+    /// Adds the position relative to `display` (see "Normalized Coordinates").
+    pub fn with_display(mut self, display: &DisplayGeometry) -> Self {
+        let (x, y) = display.normalize(self.x as f64, self.y as f64);
+        self.normalized = Some(NormalizedPosition {
+            display_id: display.id,
+            x,
+            y,
+            local_x: self.x - display.origin_x,
+            local_y: self.y - display.origin_y,
+        });
+        self
+    }
+}
@@ -0,0 +1,226 @@
+//! # Annotation Module - Contrast-Aware Marker Colors
+//!
+//! A fixed red click marker vanishes on dark UIs and red-themed pages. At
+//! capture time the click region is classified as predominantly dark or
+//! light, and annotation colors (click markers, arrows, step badges) are
+//! picked to stand out against it. They are stored on the event as
+//! `annotation_colors` so every renderer draws the same colors.
+//!
+//! ## Choosing Colors
+//! 1. Sample the region's relative luminance (WCAG definition) on a grid
+//! 2. **Tone**: `Dark` if most samples are darker than the black/white
+//!    crossover (`0.179`, where black and white text have equal contrast)
+//! 3. **Marker**: First candidate for the tone with a contrast ratio of at
+//!    least `MIN_CONTRAST` (WCAG 1.4.11 for graphical objects) against the
+//!    average of the majority samples, else the highest-contrast candidate
+//!    of either tone
+//! 4. **Outline / badge text**: Black or white, whichever contrasts more
+//!    with the marker
+//!
+//! | Tone | Preferred markers |
+//! |------|-------------------|
+//! | Light | Red, blue, magenta |
+//! | Dark | Yellow, cyan, orange |
+//!
+//! ## Drawing
+//! Most markers are drawn by the frontend; `draw_click_marker()` burns one
+//! into an image for exports that leave the app (see `export::snippet`).
+
+use crate::types::{AnnotationColors, Tone};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+/// Minimum contrast ratio for markers (WCAG non-text contrast).
+const MIN_CONTRAST: f64 = 3.0;
+
+/// Luminance at which black and white have the same contrast ratio.
+const TONE_THRESHOLD: f64 = 0.179;
+
+/// Marker candidates in order of preference for light regions.
+const LIGHT_MARKERS: &[[u8; 3]] = &[
+    [0xE5, 0x39, 0x35], // Red
+    [0x29, 0x62, 0xFF], // Blue
+    [0xC5, 0x11, 0x62], // Magenta
+];
+
+/// Marker candidates in order of preference for dark regions.
+const DARK_MARKERS: &[[u8; 3]] = &[
+    [0xFF, 0xD6, 0x00], // Yellow
+    [0x00, 0xE5, 0xFF], // Cyan
+    [0xFF, 0x91, 0x00], // Orange
+];
+
+const BLACK: [u8; 3] = [0x00, 0x00, 0x00];
+const WHITE: [u8; 3] = [0xFF, 0xFF, 0xFF];
+
+/// Chooses annotation colors that stand out on a captured region.
+pub fn colors_for(region: &DynamicImage) -> AnnotationColors {
+    let (tone, background) = region_tone(region);
+    let candidates = match tone {
+        Tone::Dark => DARK_MARKERS,
+        Tone::Light => LIGHT_MARKERS,
+    };
+
+    let marker = candidates
+        .iter()
+        .copied()
+        .find(|color| contrast_ratio(luminance(*color), background) >= MIN_CONTRAST)
+        .unwrap_or_else(|| {
+            // Nothing preferred is legible (e.g., a red-themed page): take the best
+            LIGHT_MARKERS
+                .iter()
+                .chain(DARK_MARKERS)
+                .copied()
+                .max_by(|a, b| {
+                    contrast_ratio(luminance(*a), background)
+                        .total_cmp(&contrast_ratio(luminance(*b), background))
+                })
+                .unwrap_or(WHITE)
+        });
+
+    let on_marker = if contrast_ratio(luminance(marker), luminance(BLACK))
+        >= contrast_ratio(luminance(marker), luminance(WHITE))
+    {
+        BLACK
+    } else {
+        WHITE
+    };
+
+    AnnotationColors {
+        tone,
+        marker: hex(marker),
+        outline: hex(on_marker),
+        badge_text: hex(on_marker),
+    }
+}
+
+/// Classifies a region and returns the average luminance of its majority tone.
+fn region_tone(region: &DynamicImage) -> (Tone, f64) {
+    /// Examine every Nth pixel in each direction
+    const SAMPLE_STEP: u32 = 4;
+
+    let rgba = region.to_rgba8();
+    let (mut dark_count, mut dark_sum) = (0u64, 0.0);
+    let (mut light_count, mut light_sum) = (0u64, 0.0);
+
+    for y in (0..rgba.height()).step_by(SAMPLE_STEP as usize) {
+        for x in (0..rgba.width()).step_by(SAMPLE_STEP as usize) {
+            let [r, g, b, _] = rgba.get_pixel(x, y).0;
+            let value = luminance([r, g, b]);
+            if value < TONE_THRESHOLD {
+                dark_count += 1;
+                dark_sum += value;
+            } else {
+                light_count += 1;
+                light_sum += value;
+            }
+        }
+    }
+
+    if dark_count > light_count {
+        (Tone::Dark, dark_sum / dark_count as f64)
+    } else if light_count > 0 {
+        (Tone::Light, light_sum / light_count as f64)
+    } else {
+        (Tone::Light, 1.0) // Empty region: assume a white background
+    }
+}
+
+/// Relative luminance of an sRGB color (0.0 = black, 1.0 = white).
+fn luminance([r, g, b]: [u8; 3]) -> f64 {
+    let linear = |channel: u8| {
+        let value = channel as f64 / 255.0;
+        if value <= 0.039_28 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// WCAG contrast ratio between two luminances (1.0 to 21.0).
+fn contrast_ratio(a: f64, b: f64) -> f64 {
+    let (lighter, darker) = if a >= b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+/// Parses a `"#RRGGBB"` color as written by `hex()`.
+fn parse_hex(color: &str) -> Option<[u8; 3]> {
+    let digits = color.strip_prefix('#').filter(|digits| digits.len() == 6)?;
+    let channel = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Draws a click marker (a ring in `marker` with an `outline` halo) centered on a pixel.
+///
+/// # Arguments
+/// * `image` - Frame to draw on
+/// * `x`, `y` - Click position in the frame's pixels
+/// * `radius` - Ring radius in pixels
+/// * `colors` - The event's annotation colors (invalid colors fall back to red on white)
+pub fn draw_click_marker(
+    image: &mut RgbaImage,
+    x: u32,
+    y: u32,
+    radius: u32,
+    colors: &AnnotationColors,
+) {
+    let marker = parse_hex(&colors.marker).unwrap_or(LIGHT_MARKERS[0]);
+    let outline = parse_hex(&colors.outline).unwrap_or(WHITE);
+    let ring = (radius / 6).max(2) as f64;
+    let halo = (ring / 2.0).max(1.0);
+    let radius = radius as f64;
+
+    let reach = (radius + ring / 2.0 + halo).ceil() as i64;
+    for dy in -reach..=reach {
+        for dx in -reach..=reach {
+            let (px, py) = (x as i64 + dx, y as i64 + dy);
+            if px < 0 || py < 0 || px >= image.width() as i64 || py >= image.height() as i64 {
+                continue;
+            }
+            let off_ring = ((dx as f64).hypot(dy as f64) - radius).abs();
+            let color = if off_ring <= ring / 2.0 {
+                marker
+            } else if off_ring <= ring / 2.0 + halo {
+                outline
+            } else {
+                continue;
+            };
+            image.put_pixel(
+                px as u32,
+                py as u32,
+                Rgba([color[0], color[1], color[2], 255]),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(color: [u8; 3]) -> DynamicImage {
+        let [r, g, b] = color;
+        DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 40, Rgba([r, g, b, 255])))
+    }
+
+    #[test]
+    fn test_markers_contrast_with_dark_and_light_regions() {
+        let light = colors_for(&region([0xF6, 0xF6, 0xF6]));
+        assert_eq!(light.tone, Tone::Light);
+        assert_eq!(light.marker, "#E53935");
+
+        let dark = colors_for(&region([0x1E, 0x1E, 0x1E]));
+        assert_eq!(dark.tone, Tone::Dark);
+        assert_eq!(dark.marker, "#FFD600");
+        assert_eq!(dark.outline, "#000000");
+
+        // A red-themed page doesn't get a red marker
+        let red = colors_for(&region([0xE0, 0x40, 0x40]));
+        assert_ne!(red.marker, "#E53935");
+    }
+}
@@ -0,0 +1,321 @@
+//! # Export Module - Session Export Formats
+//!
+//! Shared definitions for turning a recorded session into shareable output
+//! (documents, archives) plus size/duration estimation so users can decide
+//! on options before running a potentially large export.
+//!
+//! ## Size Model
+//! Screenshots dominate export size (~2.2MB each), so estimates are driven
+//! by the actual PNG sizes on disk of the images each exporter includes:
+//! - **Markdown** (`document`): The step screenshots, each file once
+//!   - **Linked images**: Copied next to the document as-is (1.0x)
+//!   - **Embedded images**: Base64 data URIs inside the document (~1.33x)
+//! - **ZIP** (`archive`): Every referenced screenshot; PNGs are already
+//!   compressed, so they store at ~1.0x
+//!
+//! HTML is only written per step (`snippet`) and PDF has no writer yet, so
+//! neither can be estimated for a session.
+//!
+//! ## Exporters
+//! - `archive` - The session and its screenshots as one `.flowtrace.zip`
+//! - `checklist` - Follow-along task list (JSON for the frontend, Markdown)
+//! - `clipboard` - A range of steps as Markdown or plain text on the system clipboard
+//! - `context_pack` - Steps as JSONL with element, screen, and typed text, for LLMs
+//! - `csv` - Flat event timeline for spreadsheets and pandas
+//! - `document` - Step-by-step Markdown document (SOP) with screenshots and wait notes
+//! - `parquet` - Columnar event data across sessions (`parquet` feature)
+//! - `presets` - Named format/filter/image/destination bundles for repeat publishing
+//! - `redline` - Steps added/removed/changed between two versions of a procedure
+//! - `script` - Playwright/Selenium test skeleton with locators from element info
+//! - `snippet` - One step's annotated screenshot and caption, to paste elsewhere
+//!
+//! Exported screenshots can carry a configured watermark (see `watermark`)
+//! and never carry metadata (see `clean`); `prepare_step_images()` applies
+//! both to the screenshots a document links.
+
+pub mod archive;
+pub mod checklist;
+pub mod clean;
+pub mod clipboard;
+pub mod context_pack;
+pub mod csv;
+pub mod document;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod presets;
+pub mod redline;
+pub mod script;
+pub mod snippet;
+pub mod watermark;
+
+use crate::parts;
+use crate::steps::Step;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Output format of an export.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Html,
+    Pdf,
+    Markdown,
+    Zip,
+}
+
+/// Options shared by all export formats.
+///
+/// Every field has a default so the frontend can send only what it changes.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Embed screenshots in the document (base64) instead of linking files next to it
+    pub embed_images: bool,
+}
+
+/// Projected output of an export, computed without writing anything.
+#[derive(Serialize, Debug, Clone)]
+pub struct ExportEstimate {
+    pub format: ExportFormat,
+    pub embed_images: bool,
+    pub event_count: usize,
+    pub image_count: usize,
+    /// Size of the screenshots on disk in bytes
+    pub image_bytes: u64,
+    /// Size of the main output file (document or archive) in bytes
+    pub document_bytes: u64,
+    /// Size of everything produced, including linked image files
+    pub total_bytes: u64,
+    pub estimated_duration_ms: u64,
+}
+
+/// Approximate text produced per event (step heading + description + markup).
+const TEXT_BYTES_PER_EVENT: u64 = 250;
+/// Base64 expands binary data by 4/3.
+const BASE64_RATIO: f64 = 4.0 / 3.0;
+/// Throughput for copying/base64-encoding image data (bytes per millisecond).
+const COPY_BYTES_PER_MS: f64 = 100_000.0;
+
+/// Points step screenshots at the copies a shared document may link.
+///
+/// With a watermark configured every screenshot is a stamped copy (see
+/// `watermark`), else only those carrying metadata are re-encoded (see
+/// `clean`). Copies are written to `image_dir`.
+///
+/// # Returns
+/// * `Ok(())` - Steps link their export copies
+/// * `Err(String)` - Error if the watermark or a copy couldn't be written
+pub fn prepare_step_images<'a>(
+    steps: impl IntoIterator<Item = &'a mut Step>,
+    image_dir: &Path,
+) -> Result<(), String> {
+    match watermark::current()? {
+        Some(mark) => watermark::stamp_steps(steps, image_dir, &mark),
+        None => clean::clean_steps(steps, image_dir).map(|_| ()),
+    }
+}
+
+/// Estimates output size and duration for exporting a saved session.
+///
+/// # Arguments
+/// * `session_id` - Session to estimate
+/// * `format` - Target export format
+/// * `options` - Export options (embedding, etc.)
+///
+/// # Returns
+/// * `Ok(ExportEstimate)` - Projected sizes and duration
+/// * `Err(String)` - Error if the session couldn't be loaded, or the format
+///   can't be exported for a whole session
+pub fn estimate_export(
+    session_id: &str,
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> Result<ExportEstimate, String> {
+    match format {
+        // The step-by-step document, over all parts of the procedure
+        ExportFormat::Markdown => {
+            let parts = parts::load_parts(session_id)?;
+            let event_count = parts.iter().map(|part| part.events.len()).sum();
+            let steps: Vec<Step> = checklist::build_checklist(&parts)
+                .items
+                .into_iter()
+                .map(|item| item.step)
+                .collect();
+            estimate(format, options, event_count, &step_image_sizes(&steps))
+        }
+        ExportFormat::Zip => {
+            let session = storage::load_session(session_id)?;
+            let (_, files) = archive::archive_entries(&session);
+            let image_sizes: Vec<u64> = files.values().filter_map(|path| file_size(path)).collect();
+            estimate(format, options, session.events.len(), &image_sizes)
+        }
+        ExportFormat::Html | ExportFormat::Pdf => estimate(format, options, 0, &[]),
+    }
+}
+
+/// Sizes of the screenshots a document links, each file once.
+///
+/// Partial captures count as the full frame the exporter writes (the size
+/// of their base). Screenshots that no longer exist are skipped.
+fn step_image_sizes(steps: &[Step]) -> Vec<u64> {
+    let mut linked = HashSet::new();
+    let mut sizes = Vec::new();
+    for step in steps {
+        for screenshot in [&step.screenshot, &step.detail_screenshot]
+            .into_iter()
+            .flatten()
+        {
+            if !linked.insert(screenshot.as_str()) {
+                continue;
+            }
+            let stored = match (&step.screenshot_patch, &step.screenshot) {
+                (Some(patch), Some(full)) if full == screenshot => &patch.base,
+                _ => screenshot,
+            };
+            sizes.extend(file_size(&storage::resolve_asset_path(stored)));
+        }
+    }
+    sizes
+}
+
+fn file_size(path: &Path) -> Option<u64> {
+    fs::metadata(path).ok().map(|metadata| metadata.len())
+}
+
+/// Pure size/duration model (see module docs).
+///
+/// # Returns
+/// * `Ok(ExportEstimate)` - Projected sizes and duration
+/// * `Err(String)` - The format has no session exporter (see "Size Model")
+fn estimate(
+    format: ExportFormat,
+    options: &ExportOptions,
+    event_count: usize,
+    image_sizes: &[u64],
+) -> Result<ExportEstimate, String> {
+    let image_count = image_sizes.len();
+    let image_bytes: u64 = image_sizes.iter().sum();
+    let text_bytes = event_count as u64 * TEXT_BYTES_PER_EVENT;
+    let embedded_bytes = (image_bytes as f64 * BASE64_RATIO) as u64;
+
+    // Archives store the screenshots as they are
+    let embed_images = options.embed_images && format == ExportFormat::Markdown;
+
+    let (document_bytes, total_bytes) = match format {
+        ExportFormat::Markdown if embed_images => {
+            let document = text_bytes + embedded_bytes;
+            (document, document)
+        }
+        ExportFormat::Markdown => (text_bytes, text_bytes + image_bytes),
+        ExportFormat::Zip => {
+            // session.json compresses well; PNGs don't compress further
+            let document = text_bytes / 4 + image_bytes;
+            (document, document)
+        }
+        ExportFormat::Html => {
+            return Err(
+                "HTML is only exported per step (see export_event), not for a whole session"
+                    .to_string(),
+            )
+        }
+        ExportFormat::Pdf => return Err("PDF export isn't available yet".to_string()),
+    };
+
+    Ok(ExportEstimate {
+        format,
+        embed_images,
+        event_count,
+        image_count,
+        image_bytes,
+        document_bytes,
+        total_bytes,
+        estimated_duration_ms: (total_bytes as f64 / COPY_BYTES_PER_MS).ceil() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position, RecordingSession};
+
+    #[test]
+    fn test_embedding_grows_document_but_not_total() {
+        let images = [2_200_000, 2_200_000];
+        let linked = estimate(
+            ExportFormat::Markdown,
+            &ExportOptions::default(),
+            2,
+            &images,
+        )
+        .unwrap();
+        let embedded = estimate(
+            ExportFormat::Markdown,
+            &ExportOptions { embed_images: true },
+            2,
+            &images,
+        )
+        .unwrap();
+
+        assert!(embedded.document_bytes > linked.document_bytes);
+        assert!(embedded.total_bytes > linked.total_bytes); // base64 overhead
+        assert_eq!(linked.total_bytes, linked.document_bytes + 4_400_000);
+    }
+
+    #[test]
+    fn test_formats_without_a_session_exporter_are_not_estimated() {
+        let options = ExportOptions::default();
+        assert!(estimate(ExportFormat::Pdf, &options, 2, &[2_200_000]).is_err());
+        assert!(estimate(ExportFormat::Html, &options, 2, &[2_200_000]).is_err());
+        // Archives never embed
+        let zip = estimate(
+            ExportFormat::Zip,
+            &ExportOptions { embed_images: true },
+            2,
+            &[],
+        )
+        .unwrap();
+        assert!(!zip.embed_images);
+    }
+
+    #[test]
+    fn test_linked_screenshots_count_once() {
+        let dir = std::env::temp_dir().join(format!("flowtrace-estimate-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let screenshot = dir.join("event_a_window.png");
+        fs::write(&screenshot, [0u8; 100]).unwrap();
+
+        let mut session = RecordingSession::new("estimate-test".to_string());
+        let mut click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(10.0, 10.0)),
+        );
+        click.screenshots.window_crop = Some(screenshot.display().to_string());
+        // Never linked by a step
+        click.screenshots.full_screen = Some(dir.join("missing.png").display().to_string());
+        session.add_event(click);
+        session.add_event(Event::new(
+            EventType::TextInput {
+                text: "INV-1234".to_string(),
+                keystrokes: 8,
+            },
+            None,
+        ));
+
+        // The typing step shows the click's screenshot again
+        let steps: Vec<Step> = checklist::build_checklist(&[session])
+            .items
+            .into_iter()
+            .map(|item| item.step)
+            .collect();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(step_image_sizes(&steps), vec![100]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,207 @@
+//! # Archive Export - Session as a Single ZIP File
+//!
+//! Packages a recording into one `.flowtrace.zip` that can be emailed or
+//! attached to a ticket, and imported again on another machine (see
+//! `importer` module).
+//!
+//! ## Layout
+//! ```text
+//! [session-id].flowtrace.zip
+//! └── [session-id]/
+//!     ├── session.json              (screenshot paths are file names)
+//!     └── event_[id]_*.png          (every referenced screenshot)
+//! ```
+//!
+//! Only screenshots referenced by the session are included (not OCR
+//! indexes, comments, or earlier exports). They are archived as recorded,
+//! without watermark or re-encoding, so an imported archive is the same
+//! recording; PNGs are stored uncompressed (they don't compress further),
+//! `session.json` is deflated.
+
+use crate::importer::ARCHIVE_EXTENSION;
+use crate::storage;
+use crate::types::RecordingSession;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Writes a saved session and its screenshots to a ZIP archive.
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `output_path` - Destination file (see `default_archive_path()`)
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written archive
+/// * `Err(String)` - Error if the session couldn't be loaded, a screenshot
+///   couldn't be read, or the archive written (no partial archive is left)
+pub fn export_session_zip(session_id: &str, output_path: &Path) -> Result<PathBuf, String> {
+    let session = storage::load_session(session_id)?;
+    let (archived, files) = archive_entries(&session);
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {:?}", e))?;
+    }
+    let file = fs::File::create(output_path)
+        .map_err(|e| format!("Failed to create archive {:?}: {:?}", output_path, e))?;
+    if let Err(e) = write_archive(&archived, &files, file) {
+        let _ = fs::remove_file(output_path);
+        return Err(e);
+    }
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🗜️  Session {} archived with {} screenshots to {:?}",
+        session_id,
+        files.len(),
+        output_path
+    );
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Default location: `recordings/[session-id]/[session-id].flowtrace.zip`.
+pub fn default_archive_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join(format!("{}{}", session_id, ARCHIVE_EXTENSION))
+}
+
+/// The session as archived (screenshot paths reduced to file names) and the
+/// files to include, by archived file name.
+pub fn archive_entries(
+    session: &RecordingSession,
+) -> (RecordingSession, BTreeMap<String, PathBuf>) {
+    let mut archived = session.clone();
+    let mut files = BTreeMap::new();
+    let mut relative = |stored: &mut String| {
+        let source = storage::resolve_asset_path(stored);
+        if let Some(name) = source.file_name() {
+            let name = name.to_string_lossy().to_string();
+            files.insert(name.clone(), source);
+            *stored = name;
+        }
+    };
+
+    for event in archived.events.iter_mut() {
+        let screenshots = &mut event.screenshots;
+        for path in [
+            &mut screenshots.full_screen,
+            &mut screenshots.window_crop,
+            &mut screenshots.click_crop,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            relative(path);
+        }
+        if let Some(patch) = screenshots.full_screen_patch.as_mut() {
+            relative(&mut patch.base);
+        }
+    }
+    (archived, files)
+}
+
+/// Writes `session.json` and the screenshots below a `[session-id]/` folder.
+fn write_archive<W: Write + Seek>(
+    session: &RecordingSession,
+    files: &BTreeMap<String, PathBuf>,
+    writer: W,
+) -> Result<(), String> {
+    let folder = &session.session_id;
+    let mut zip = ZipWriter::new(writer);
+
+    let json = serde_json::to_vec_pretty(session)
+        .map_err(|e| format!("Failed to serialize session: {:?}", e))?;
+    zip.start_file(
+        format!("{}/session.json", folder),
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+    )
+    .map_err(|e| format!("Failed to write archive entry: {:?}", e))?;
+    zip.write_all(&json)
+        .map_err(|e| format!("Failed to write archive entry: {:?}", e))?;
+
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    for (name, source) in files {
+        let bytes = fs::read(source)
+            .map_err(|e| format!("Failed to read screenshot {:?}: {:?}", source, e))?;
+        zip.start_file(format!("{}/{}", folder, name), stored)
+            .map_err(|e| format!("Failed to write archive entry: {:?}", e))?;
+        zip.write_all(&bytes)
+            .map_err(|e| format!("Failed to write archive entry: {:?}", e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish archive: {:?}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importer;
+    use crate::types::{Event, EventType, FramePatch, MouseButton, Position};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_archive_round_trips_through_import_extraction() {
+        let source_dir = std::env::temp_dir().join(format!("archive-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&source_dir).unwrap();
+        let full = source_dir.join("event_a_full.png");
+        let patch = source_dir.join("event_b_full.png");
+        fs::write(&full, b"full frame").unwrap();
+        fs::write(&patch, b"patch").unwrap();
+
+        let mut session = RecordingSession::new("test-archive".to_string());
+        let click = || {
+            Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(1.0, 2.0)),
+            )
+        };
+        session.add_event(click().with_screenshots(Some(full.display().to_string()), None, None));
+        let mut patched = click().with_screenshots(Some(patch.display().to_string()), None, None);
+        patched.screenshots.full_screen_patch = Some(FramePatch {
+            base: full.display().to_string(),
+            x: 0,
+            y: 0,
+        });
+        session.add_event(patched);
+
+        let (archived, files) = archive_entries(&session);
+        // The patch base is the first event's frame: archived once
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            archived.events[1]
+                .screenshots
+                .full_screen_patch
+                .as_ref()
+                .unwrap()
+                .base,
+            "event_a_full.png"
+        );
+
+        let mut zip = Cursor::new(Vec::new());
+        write_archive(&archived, &files, &mut zip).unwrap();
+        let extract_dir = source_dir.join("extracted");
+        let extracted =
+            importer::extract_archive_from(Cursor::new(zip.into_inner()), &extract_dir).unwrap();
+        assert!(extracted.ends_with("test-archive"));
+        assert_eq!(
+            fs::read(extracted.join("event_a_full.png")).unwrap(),
+            b"full frame"
+        );
+        let restored = storage::read_session_file(&extracted.join("session.json")).unwrap();
+        assert_eq!(
+            restored.events[0].screenshots.full_screen.as_deref(),
+            Some("event_a_full.png")
+        );
+
+        fs::remove_dir_all(&source_dir).unwrap();
+    }
+}
@@ -0,0 +1,224 @@
+//! # Checklist Export - Follow-Along Procedure
+//!
+//! Turns the grouped steps of a session (`steps` module) into a checklist
+//! an operator can tick off while repeating the recorded procedure.
+//!
+//! ## Formats
+//! - **JSON**: `Checklist` returned to the frontend, which renders it and
+//!   tracks the `done` flags
+//! - **Markdown**: GitHub-style task list (`- [ ] Step`) with the expected
+//!   screenshot under each item, and a `##` heading per marker section
+//!
+//! ## Multi-Part Procedures
+//! A session continued with `continue_session()` is exported with all its
+//! parts (see `parts` module): a `##` heading per day (`"Day 1"`, ...), with
+//! marker sections as `###` headings below it.
+//!
+//! ```markdown
+//! - [ ] **1.** Clicked left button at position (709, 328) — *Invoice #1234 — Google Chrome* (scrolled ~60% down)
+//!
+//!   ![Step 1](event_..._window.png)
+//! ```
+
+use crate::parts;
+use crate::steps::{self, Step};
+use crate::storage;
+use crate::types::{RecordingSession, ScrollPosition};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A recorded procedure as a checklist.
+#[derive(Serialize, Debug, Clone)]
+pub struct Checklist {
+    pub session_id: String,
+    pub title: String,
+    pub items: Vec<ChecklistItem>,
+}
+
+/// One item to tick off.
+#[derive(Serialize, Debug, Clone)]
+pub struct ChecklistItem {
+    #[serde(flatten)]
+    pub step: Step,
+    /// Always `false` on export; toggled by the frontend while following along
+    pub done: bool,
+}
+
+/// Builds the checklist for the parts of a procedure (see `parts::load_parts()`).
+///
+/// A single part is exported as before, without day headings.
+pub fn build_checklist(parts: &[RecordingSession]) -> Checklist {
+    let Some(session) = parts.first() else {
+        return Checklist {
+            session_id: String::new(),
+            title: String::new(),
+            items: Vec::new(),
+        };
+    };
+    let steps = match parts.len() {
+        1 => steps::group_steps(session),
+        _ => parts::group_steps(parts),
+    };
+
+    Checklist {
+        session_id: session.session_id.clone(),
+        title: session.title.clone().unwrap_or_else(|| {
+            format!(
+                "Workflow recorded {}",
+                session.started_at.format("%Y-%m-%d %H:%M UTC")
+            )
+        }),
+        items: steps
+            .into_iter()
+            .map(|step| ChecklistItem { step, done: false })
+            .collect(),
+    }
+}
+
+/// Writes a checklist as a Markdown task list.
+///
+/// # Arguments
+/// * `checklist` - Checklist to write
+/// * `output_path` - Destination `.md` file
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written file
+/// * `Err(String)` - Error if the file couldn't be written
+///
+/// # Image Links
+/// Screenshots inside the output directory are linked relatively (the default
+/// location is the session directory); others use absolute paths.
+pub fn write_markdown(checklist: &Checklist, output_path: &Path) -> Result<PathBuf, String> {
+    let output_dir = output_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create output directory: {:?}", e))?;
+
+    let markdown = render_markdown(checklist, |asset| image_link(asset, &output_dir));
+    fs::write(output_path, markdown).map_err(|e| format!("Failed to write checklist: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "☑️  Checklist with {} items written to {:?}",
+        checklist.items.len(),
+        output_path
+    );
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Default Markdown location: `recordings/[session-id]/checklist.md`.
+pub fn default_markdown_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("checklist.md")
+}
+
+/// Renders the Markdown document; `link` maps stored screenshot paths to link targets.
+fn render_markdown(checklist: &Checklist, link: impl Fn(&str) -> String) -> String {
+    let mut markdown = format!("# {}\n\n", checklist.title);
+    let mut part = None;
+    let mut section = None;
+    let section_heading = if checklist.items.iter().any(|item| item.step.part.is_some()) {
+        "###"
+    } else {
+        "##"
+    };
+
+    for item in &checklist.items {
+        let step = &item.step;
+        // Days of a multi-part procedure: one heading per day
+        if let Some(name) = &step.part {
+            if part != Some(name) {
+                part = Some(name);
+                section = None;
+                markdown.push_str(&format!("## {}\n\n", name));
+            }
+        }
+        // Markers start a new section: one heading per section
+        if let Some(name) = &step.section {
+            if section != Some(name) {
+                section = Some(name);
+                markdown.push_str(&format!("{} {}\n\n", section_heading, name));
+            }
+        }
+        markdown.push_str(&format!(
+            "- [{}] **{}.** {}",
+            if item.done { "x" } else { " " },
+            step.number,
+            step.title
+        ));
+        if let Some(window_title) = &step.window_title {
+            markdown.push_str(&format!(" — *{}*", window_title));
+        }
+        if let Some(scrolled) = step
+            .scroll_position
+            .as_ref()
+            .and_then(ScrollPosition::summary)
+        {
+            markdown.push_str(&format!(" ({})", scrolled));
+        }
+        markdown.push('\n');
+        if let Some(screenshot) = &step.screenshot {
+            markdown.push_str(&format!(
+                "\n  ![Step {}]({})\n",
+                step.number,
+                link(screenshot)
+            ));
+        }
+        if let Some(detail) = &step.detail_screenshot {
+            markdown.push_str(&format!(
+                "\n  ![Step {} detail]({})\n",
+                step.number,
+                link(detail)
+            ));
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Link target for a stored screenshot path, relative to `output_dir` when possible.
+pub fn image_link(asset: &str, output_dir: &Path) -> String {
+    let resolved = storage::resolve_asset_path(asset);
+    let absolute = fs::canonicalize(&resolved).unwrap_or(resolved);
+    let output_dir = fs::canonicalize(output_dir).unwrap_or_else(|_| output_dir.to_path_buf());
+
+    absolute
+        .strip_prefix(&output_dir)
+        .unwrap_or(&absolute)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position};
+
+    #[test]
+    fn test_markdown_is_task_list_with_images() {
+        let mut session = RecordingSession::new("checklist-test".to_string());
+        session.add_event(
+            Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(5.0, 5.0)),
+            )
+            .with_screenshots(
+                Some("full.png".to_string()),
+                Some("window.png".to_string()),
+                None,
+            ),
+        );
+
+        let markdown = render_markdown(&build_checklist(&[session]), |asset| asset.to_string());
+
+        assert!(markdown.contains("- [ ] **1.** Clicked left button"));
+        assert!(markdown.contains("![Step 1](window.png)"));
+    }
+}
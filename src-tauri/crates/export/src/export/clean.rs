@@ -0,0 +1,174 @@
+//! # Clean Images - Exported Screenshots Without Metadata
+//!
+//! Captures carry the display's ICC profile (see `color_profile` module),
+//! whose description names the monitor model, and imported screenshots may
+//! carry anything their tool wrote (`tEXt`, `eXIf`, `tIME` chunks). An
+//! exported image holds only its pixels:
+//!
+//! - Images written by exporters (snippets, downscaled or watermarked
+//!   copies) are encoded with `encode_png()`
+//! - Linked screenshots are checked with `has_metadata()`; those with any
+//!   ancillary chunk (or that aren't PNGs) are re-encoded into
+//!   `[document].images/`, clean ones are linked as they are
+//!
+//! ## Deterministic Encoding
+//! `encode_png()` converts to 8-bit RGBA and uses fixed compression and
+//! filter settings, so identical pixels produce identical bytes: exporting
+//! again doesn't change files that are committed to a docs repository.
+//!
+//! Dropping the ICC profile means wide-gamut captures are shown as sRGB.
+
+use crate::steps::Step;
+use crate::storage;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::{ColorType, DynamicImage, ImageEncoder};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// PNG file signature.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Chunks needed to decode a PNG; everything else is metadata.
+const CRITICAL_CHUNKS: &[&[u8]] = &[b"IHDR", b"PLTE", b"IDAT", b"IEND"];
+
+/// Encodes an image as a PNG with only its pixels (see module docs).
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - PNG bytes (`IHDR`, `IDAT`, `IEND` only)
+/// * `Err(String)` - Error if encoding fails
+pub fn encode_png(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let rgba = image.to_rgba8();
+    let mut png = Vec::new();
+    PngEncoder::new_with_quality(&mut png, CompressionType::Default, FilterType::Adaptive)
+        .write_image(rgba.as_raw(), rgba.width(), rgba.height(), ColorType::Rgba8)
+        .map_err(|e| format!("Failed to encode PNG: {:?}", e))?;
+    Ok(png)
+}
+
+/// Writes an image as a clean PNG (see `encode_png()`).
+pub fn save_png(image: &DynamicImage, path: &Path) -> Result<(), String> {
+    let png = encode_png(image)?;
+    fs::write(path, png).map_err(|e| format!("Failed to write {:?}: {:?}", path, e))
+}
+
+/// Whether image bytes carry anything besides pixels.
+///
+/// # Returns
+/// `true` for PNGs with ancillary chunks and for anything that isn't a
+/// well-formed PNG
+pub fn has_metadata(bytes: &[u8]) -> bool {
+    let Some(mut chunks) = bytes.strip_prefix(PNG_SIGNATURE) else {
+        return true;
+    };
+    while chunks.len() >= 12 {
+        let length = u32::from_be_bytes([chunks[0], chunks[1], chunks[2], chunks[3]]) as usize;
+        let chunk_type = &chunks[4..8];
+        if !CRITICAL_CHUNKS.contains(&chunk_type) {
+            return true;
+        }
+        if chunk_type == b"IEND" {
+            // Trailing bytes after IEND are hidden data too
+            return chunks.len() != 12 + length;
+        }
+        let Some(rest) = chunks.get(12 + length..) else {
+            return true;
+        };
+        chunks = rest;
+    }
+    true
+}
+
+/// File name of an exported copy: `[session-id]_[file]`.
+///
+/// Drafts keep the file names of their original (see `draft` module), so
+/// the session directory is part of the name.
+pub fn copy_name(source: &Path) -> Option<String> {
+    let filename = source.file_name()?.to_string_lossy();
+    let session_dir = source
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Some(format!("{}_{}", session_dir, filename))
+}
+
+/// Where the exported copy of `source` goes in `image_dir`.
+///
+/// A screenshot that already is a copy in `image_dir` (e.g., a downscaled
+/// one, see `presets` module) is replaced in place.
+pub fn copy_target(source: &Path, image_dir: &Path) -> Option<PathBuf> {
+    if source.parent() == Some(image_dir) {
+        return Some(source.to_path_buf());
+    }
+    copy_name(source).map(|name| image_dir.join(name))
+}
+
+/// Re-encodes step screenshots that carry metadata into `image_dir` and links those.
+///
+/// Screenshots that can't be read keep their link (there is nothing to
+/// export from them).
+///
+/// # Returns
+/// * `Ok(usize)` - Number of screenshots re-encoded
+/// * `Err(String)` - Error if a copy couldn't be written
+pub fn clean_steps<'a>(
+    steps: impl IntoIterator<Item = &'a mut Step>,
+    image_dir: &Path,
+) -> Result<usize, String> {
+    let mut cleaned = 0;
+    for step in steps {
+        for screenshot in [&mut step.screenshot, &mut step.detail_screenshot]
+            .into_iter()
+            .flatten()
+        {
+            let source = storage::resolve_asset_path(screenshot);
+            let Ok(bytes) = fs::read(&source) else {
+                continue;
+            };
+            if !has_metadata(&bytes) {
+                continue;
+            }
+            let (Ok(image), Some(target)) = (
+                image::load_from_memory(&bytes),
+                copy_target(&source, image_dir),
+            ) else {
+                eprintln!("⚠️  Can't re-encode screenshot {}", screenshot);
+                continue;
+            };
+
+            fs::create_dir_all(image_dir)
+                .map_err(|e| format!("Failed to create image directory: {:?}", e))?;
+            save_png(&image, &target)?;
+            *screenshot = target.display().to_string();
+            cleaned += 1;
+        }
+    }
+    Ok(cleaned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowtrace_capture::color_profile;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_encoding_is_clean_and_deterministic() {
+        let rgb = DynamicImage::ImageRgb8(RgbImage::from_fn(32, 16, |x, y| {
+            Rgb([(x * 8) as u8, (y * 16) as u8, 0x80])
+        }));
+        let rgba = DynamicImage::ImageRgba8(rgb.to_rgba8());
+
+        let png = encode_png(&rgb).unwrap();
+        assert!(!has_metadata(&png));
+        // Same pixels, other color type: same bytes
+        assert_eq!(png, encode_png(&rgba).unwrap());
+
+        let tagged = color_profile::embed_icc_profile(&png, b"Color LCD").unwrap();
+        assert!(has_metadata(&tagged));
+        let mut trailing = png.clone();
+        trailing.extend_from_slice(b"hidden");
+        assert!(has_metadata(&trailing));
+        assert!(has_metadata(b"\xff\xd8\xff\xe0 JPEG"));
+    }
+}
@@ -0,0 +1,153 @@
+//! # Clipboard Export - Steps as Text to Paste
+//!
+//! Puts a range of steps on the system clipboard, for the most common way
+//! a procedure is shared: "paste these five steps into an email".
+//!
+//! The clipboard is written from Rust (`arboard`), so it works the same on
+//! macOS, Windows, and Linux (X11/Wayland) without webview permissions.
+//!
+//! ## Formats
+//! ```text
+//! Markdown:  1. Clicked button 'Save' — *Invoice #1234 — Google Chrome*
+//! Text:      1. Clicked button 'Save' (Invoice #1234 — Google Chrome)
+//! ```
+//!
+//! Step numbers are the ones of the checklist (see `steps` module), so a
+//! range copied from the middle of a procedure keeps its numbering.
+
+use crate::export::checklist;
+use crate::frame_diff;
+use crate::parts;
+use crate::steps::Step;
+use serde::{Deserialize, Serialize};
+
+/// Text format of copied steps.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardFormat {
+    Markdown,
+    Text,
+}
+
+/// Steps to copy by number, inclusive (e.g., `{"first": 3, "last": 7}`).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct StepRange {
+    pub first: usize,
+    pub last: usize,
+}
+
+/// Result of a copy.
+#[derive(Serialize, Debug, Clone)]
+pub struct ClipboardCopy {
+    /// Text placed on the clipboard
+    pub text: String,
+    pub step_count: usize,
+}
+
+/// Renders steps of a saved session and places them on the clipboard.
+///
+/// # Arguments
+/// * `session_id` - Saved session (all parts of a multi-part procedure)
+/// * `range` - Steps to copy (`None` for all)
+/// * `format` - Markdown or plain text
+///
+/// # Returns
+/// * `Ok(ClipboardCopy)` - The copied text
+/// * `Err(String)` - Error if the session couldn't be loaded, the range
+///   selects no step, or the clipboard is unavailable
+pub fn copy_steps_to_clipboard(
+    session_id: &str,
+    range: Option<StepRange>,
+    format: ClipboardFormat,
+) -> Result<ClipboardCopy, String> {
+    let mut parts = parts::load_parts(session_id)?;
+    for part in parts.iter_mut() {
+        frame_diff::materialize(part);
+    }
+    let checklist = checklist::build_checklist(&parts);
+    let steps: Vec<Step> = checklist
+        .items
+        .into_iter()
+        .map(|item| item.step)
+        .filter(|step| range.is_none_or(|range| in_range(range, step.number)))
+        .collect();
+    if steps.is_empty() {
+        return Err(match range {
+            Some(range) => format!("No steps {}-{} in session", range.first, range.last),
+            None => "Session has no steps to copy".to_string(),
+        });
+    }
+
+    let text = render(&steps, format);
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.clone()))
+        .map_err(|e| format!("Failed to copy to clipboard: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!("📋 {} steps copied to the clipboard", steps.len());
+
+    Ok(ClipboardCopy {
+        text,
+        step_count: steps.len(),
+    })
+}
+
+fn in_range(range: StepRange, number: usize) -> bool {
+    range.first <= number && number <= range.last
+}
+
+/// Renders steps as a numbered list, one line per step.
+fn render(steps: &[Step], format: ClipboardFormat) -> String {
+    steps
+        .iter()
+        .map(|step| {
+            let window = step.window_title.as_deref().map(|title| match format {
+                ClipboardFormat::Markdown => format!(" — *{}*", title),
+                ClipboardFormat::Text => format!(" ({})", title),
+            });
+            format!(
+                "{}. {}{}",
+                step.number,
+                step.title,
+                window.unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::steps;
+    use crate::types::{Event, EventType, MouseButton, Position, RecordingSession};
+
+    #[test]
+    fn test_range_keeps_numbering_in_both_formats() {
+        let mut session = RecordingSession::new("clipboard-test".to_string());
+        for _ in 0..3 {
+            let mut click = Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(5.0, 5.0)),
+            );
+            click.window_title = Some("Invoices".to_string());
+            session.add_event(click);
+        }
+
+        let range = StepRange { first: 2, last: 3 };
+        let steps: Vec<Step> = steps::group_steps(&session)
+            .into_iter()
+            .filter(|step| in_range(range, step.number))
+            .collect();
+
+        let markdown = render(&steps, ClipboardFormat::Markdown);
+        assert!(markdown.starts_with("2. Clicked left button"));
+        assert!(markdown.contains(" — *Invoices*\n3. "));
+        let text = render(&steps, ClipboardFormat::Text);
+        assert!(text.ends_with(" (Invoices)"));
+        assert_eq!(text.lines().count(), 2);
+    }
+}
@@ -0,0 +1,386 @@
+//! # Context Pack Export - Recordings as LLM Input
+//!
+//! Writes a session in a layout that agents and RAG pipelines can read
+//! without knowing FlowTrace's event model: one compact JSON object per
+//! step, with everything a model needs to understand it in the line itself.
+//!
+//! ## Layout
+//! ```text
+//! [output directory]/                 (default: recordings/[session-id]/context-pack/)
+//! ├── manifest.json                   (format, session, counts)
+//! ├── steps.jsonl                     (one `PackStep` per line, in order)
+//! └── images/step_001.png             (optional, downscaled to `max_image_width`)
+//! ```
+//!
+//! ## Steps
+//! Steps are the ones of the checklist (see `steps` module), including all
+//! parts of a multi-day procedure. Each line carries:
+//! - Instruction, category, section/day, app and window
+//! - The clicked element (role, label, value) and scroll position
+//! - Reconstructed typed text (never for credential steps)
+//! - Screen text recognized by OCR in the step's captures (`ocr` module),
+//!   whitespace-collapsed and capped at `MAX_SCREEN_TEXT_CHARS`
+//! - Pause before the next step, if one was recorded
+//!
+//! ```json
+//! {"step": 2, "instruction": "Clicked button 'Save'", "app": "Google Chrome", "element": {"role": "AXButton", "label": "Save"}, "screen_text": "Invoice #1234 ...", "image": "images/step_002.png"}
+//! ```
+//!
+//! Images are optional (`include_images`) and written like other exported
+//! screenshots: watermarked if configured, without metadata (see `clean`).
+
+use crate::export::checklist;
+use crate::export::clean;
+use crate::export::watermark::{self, Watermark};
+use crate::frame_diff;
+use crate::ocr;
+use crate::parts;
+use crate::steps::{self, Step};
+use crate::storage;
+use crate::types::{Event, RecordingSession, ScrollPosition, UiElement};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Identifies the layout in `manifest.json`.
+const FORMAT: &str = "flowtrace-context-pack";
+
+/// Version of the layout; bumped when a field changes meaning.
+const FORMAT_VERSION: u32 = 1;
+
+/// Longest screen text kept per step (a full 4K screen can hold pages of it).
+const MAX_SCREEN_TEXT_CHARS: usize = 2_000;
+
+/// Options of a context pack export.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ContextPackOptions {
+    /// Write each step's screenshot to `images/`
+    pub include_images: bool,
+    /// Images wider than this are downscaled (vision models rarely need more)
+    pub max_image_width: u32,
+}
+
+impl Default for ContextPackOptions {
+    fn default() -> Self {
+        Self {
+            include_images: false,
+            max_image_width: 1024,
+        }
+    }
+}
+
+/// Contents of `manifest.json`.
+#[derive(Serialize, Debug, Clone)]
+pub struct PackManifest {
+    pub format: &'static str,
+    pub version: u32,
+    pub session_id: String,
+    pub title: String,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub step_count: usize,
+    pub image_count: usize,
+}
+
+/// One line of `steps.jsonl` (see module docs).
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PackStep {
+    pub step: usize,
+    pub instruction: String,
+    pub category: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element: Option<UiElement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scroll_position: Option<ScrollPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typed_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen_text: Option<String>,
+    /// Recorded pause (Wait/Idle) between this step and the next
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_after_seconds: Option<f64>,
+    /// Path relative to the pack directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// Result of `export_context_pack()`.
+#[derive(Serialize, Debug, Clone)]
+pub struct ContextPackExport {
+    /// Pack directory
+    pub path: String,
+    pub step_count: usize,
+    pub image_count: usize,
+}
+
+/// Writes the context pack of a saved session.
+///
+/// # Arguments
+/// * `session_id` - Saved session (its other parts are included)
+/// * `output_dir` - Pack directory (see `default_pack_dir()`); an `images/`
+///   directory from an earlier export is replaced
+/// * `options` - Whether to include images, and how large
+///
+/// # Returns
+/// * `Ok(ContextPackExport)` - Where the pack was written
+/// * `Err(String)` - Error if the session couldn't be loaded or a file written
+pub fn export_context_pack(
+    session_id: &str,
+    output_dir: &Path,
+    options: &ContextPackOptions,
+) -> Result<ContextPackExport, String> {
+    let mut parts = parts::load_parts(session_id)?;
+    for part in parts.iter_mut() {
+        frame_diff::materialize(part);
+    }
+    let checklist = checklist::build_checklist(&parts);
+    let steps: Vec<Step> = checklist.items.into_iter().map(|item| item.step).collect();
+    let screen_text: HashMap<String, String> = parts
+        .iter()
+        .flat_map(|part| ocr::load_index(&part.session_id).entries)
+        .map(|entry| (entry.event_id, entry.text))
+        .collect();
+    let events: Vec<&Event> = parts.iter().flat_map(|part| &part.events).collect();
+    let mut pack_steps = pack_steps(&steps, &events, &screen_text);
+
+    fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create context pack directory: {:?}", e))?;
+    let image_dir = output_dir.join("images");
+    if image_dir.exists() {
+        fs::remove_dir_all(&image_dir)
+            .map_err(|e| format!("Failed to replace context pack images: {:?}", e))?;
+    }
+    let image_count = if options.include_images {
+        write_images(&steps, &mut pack_steps, &image_dir, options)?
+    } else {
+        0
+    };
+
+    let mut jsonl = String::new();
+    for step in &pack_steps {
+        let line = serde_json::to_string(step)
+            .map_err(|e| format!("Failed to serialize step: {:?}", e))?;
+        jsonl.push_str(&line);
+        jsonl.push('\n');
+    }
+    fs::write(output_dir.join("steps.jsonl"), jsonl)
+        .map_err(|e| format!("Failed to write steps.jsonl: {:?}", e))?;
+
+    let session: &RecordingSession = &parts[0];
+    let manifest = PackManifest {
+        format: FORMAT,
+        version: FORMAT_VERSION,
+        session_id: session_id.to_string(),
+        title: checklist.title,
+        started_at: session.started_at,
+        stopped_at: parts.last().and_then(|part| part.stopped_at),
+        step_count: pack_steps.len(),
+        image_count,
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {:?}", e))?;
+    fs::write(output_dir.join("manifest.json"), manifest_json)
+        .map_err(|e| format!("Failed to write manifest.json: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🤖 Context pack with {} steps ({} images) written to {:?}",
+        pack_steps.len(),
+        image_count,
+        output_dir
+    );
+
+    Ok(ContextPackExport {
+        path: output_dir.display().to_string(),
+        step_count: pack_steps.len(),
+        image_count,
+    })
+}
+
+/// Default location: `recordings/[session-id]/context-pack/`.
+pub fn default_pack_dir(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("context-pack")
+}
+
+/// Builds the lines of `steps.jsonl` (without images).
+fn pack_steps(
+    steps: &[Step],
+    events: &[&Event],
+    screen_text: &HashMap<String, String>,
+) -> Vec<PackStep> {
+    let by_id: HashMap<uuid::Uuid, &Event> =
+        events.iter().map(|event| (event.id, *event)).collect();
+
+    steps
+        .iter()
+        .filter_map(|step| {
+            let step_events: Vec<&Event> = step
+                .event_ids
+                .iter()
+                .filter_map(|id| by_id.get(id).copied())
+                .collect();
+            let first = *step_events.first()?;
+
+            let text: Vec<&str> = step_events
+                .iter()
+                .filter_map(|event| screen_text.get(&event.id.to_string()))
+                .map(String::as_str)
+                .collect();
+
+            Some(PackStep {
+                step: step.number,
+                instruction: step.title.clone(),
+                category: step.category.clone(),
+                section: step.section.clone(),
+                part: step.part.clone(),
+                timestamp: first.timestamp,
+                app: first.app_name.clone(),
+                window_title: step.window_title.clone(),
+                element: step_events.iter().find_map(|event| event.element.clone()),
+                scroll_position: step.scroll_position.clone(),
+                typed_text: step.typed_text.clone(),
+                screen_text: compact_text(&text.join(" ")),
+                wait_after_seconds: steps::pause_after(events, step)
+                    .map(|pause| (pause.seconds * 10.0).round() / 10.0),
+                image: None,
+            })
+        })
+        .collect()
+}
+
+/// Collapses whitespace and caps the length of OCR text.
+fn compact_text(text: &str) -> Option<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let compact: String = words
+        .join(" ")
+        .chars()
+        .take(MAX_SCREEN_TEXT_CHARS)
+        .collect();
+    Some(compact).filter(|compact| !compact.is_empty())
+}
+
+/// Writes `images/step_NNN.png` for every step with a screenshot.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of images written
+/// * `Err(String)` - Error if the watermark or an image couldn't be written
+fn write_images(
+    steps: &[Step],
+    pack_steps: &mut [PackStep],
+    image_dir: &Path,
+    options: &ContextPackOptions,
+) -> Result<usize, String> {
+    let watermark: Option<Watermark> = watermark::current()?;
+    let screenshots: HashMap<usize, &str> = steps
+        .iter()
+        .filter_map(|step| Some((step.number, step.screenshot.as_deref()?)))
+        .collect();
+    fs::create_dir_all(image_dir)
+        .map_err(|e| format!("Failed to create image directory: {:?}", e))?;
+
+    let mut written = 0;
+    for pack_step in pack_steps.iter_mut() {
+        let Some(screenshot) = screenshots.get(&pack_step.step) else {
+            continue;
+        };
+        let image = match image::open(storage::resolve_asset_path(screenshot)) {
+            Ok(image) => image,
+            Err(e) => {
+                eprintln!("⚠️  Skipping screenshot {}: {:?}", screenshot, e);
+                continue;
+            }
+        };
+        let image = if image.width() > options.max_image_width {
+            image.thumbnail(options.max_image_width, u32::MAX)
+        } else {
+            image
+        };
+        let mut image = image.into_rgba8();
+        if let Some(watermark) = &watermark {
+            watermark.apply(&mut image);
+        }
+
+        let name = format!("step_{:03}.png", pack_step.step);
+        clean::save_png(
+            &image::DynamicImage::ImageRgba8(image),
+            &image_dir.join(&name),
+        )?;
+        pack_step.image = Some(format!("images/{}", name));
+        written += 1;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::steps;
+    use crate::types::{EventType, MouseButton, Position};
+
+    #[test]
+    fn test_steps_carry_element_text_and_pauses() {
+        let mut session = RecordingSession::new("test-pack".to_string());
+        let click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(10.0, 20.0)),
+        )
+        .with_element(UiElement {
+            role: Some("AXButton".to_string()),
+            label: Some("Save".to_string()),
+            value: None,
+        });
+        let click_id = click.id.to_string();
+        session.add_event(click);
+        for seconds in [2.5, 1.25] {
+            session.add_event(Event::new(
+                EventType::Wait {
+                    duration_seconds: seconds,
+                    cause: None,
+                },
+                None,
+            ));
+        }
+        session.add_event(Event::new(
+            EventType::TextInput {
+                text: "Invoice 1234".to_string(),
+                keystrokes: 12,
+            },
+            None,
+        ));
+
+        let steps = steps::group_steps(&session);
+        let events: Vec<&Event> = session.events.iter().collect();
+        let screen_text = HashMap::from([(click_id, "  Invoice\n  #1234   Save ".to_string())]);
+        let packed = pack_steps(&steps, &events, &screen_text);
+
+        assert_eq!(packed.len(), 2);
+        assert_eq!(packed[0].instruction, "Clicked button 'Save'");
+        assert_eq!(
+            packed[0].element.as_ref().unwrap().role.as_deref(),
+            Some("AXButton")
+        );
+        assert_eq!(packed[0].screen_text.as_deref(), Some("Invoice #1234 Save"));
+        assert_eq!(packed[0].wait_after_seconds, Some(3.8));
+        assert_eq!(packed[1].typed_text.as_deref(), Some("Invoice 1234"));
+        assert_eq!(packed[1].wait_after_seconds, None);
+
+        let line = serde_json::to_string(&packed[1]).unwrap();
+        assert!(!line.contains('\n'));
+        assert!(!line.contains("\"image\""));
+    }
+}
@@ -0,0 +1,190 @@
+//! # CSV Export - Flat Event Timeline
+//!
+//! Writes the events of one session as a CSV file that opens directly in
+//! Excel or `pandas.read_csv()`, for analysts who want to pivot the data
+//! without parsing nested JSON (for many sessions at once, see `parquet`).
+//!
+//! ## Columns (one row per event, in recording order)
+//!
+//! | Column | Notes |
+//! |--------|-------|
+//! | `timestamp` | RFC 3339, UTC, milliseconds |
+//! | `type` | `Click`, `KeyPress`, `Wait`, ... |
+//! | `category` | Classification category (`text_input`, `navigation`, ...) |
+//! | `key_or_button` | Key of KeyPress/Shortcut events, button of clicks |
+//! | `x`, `y` | Logical screen position (empty without one) |
+//! | `app` | Frontmost application |
+//! | `description` | Human-readable description |
+//!
+//! Fields are quoted per RFC 4180 when they contain commas, quotes, or line
+//! breaks. The file starts with a UTF-8 byte order mark so Excel doesn't
+//! misread window titles and app names with non-ASCII characters.
+//!
+//! ## Formula Injection
+//! Window titles, app names, and typed text come from whatever was on
+//! screen, so a cell could read `=HYPERLINK(...)` and run as a formula when
+//! the file is opened. Text starting with `=`, `+`, `-`, `@`, a tab, or a
+//! carriage return gets a leading `'` so spreadsheets show it as text.
+//! Numbers (e.g., negative coordinates) are written as they are.
+
+use crate::storage;
+use crate::types::{Event, EventType, RecordingSession};
+use chrono::SecondsFormat;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Header row (see module docs).
+const HEADER: [&str; 8] = [
+    "timestamp",
+    "type",
+    "category",
+    "key_or_button",
+    "x",
+    "y",
+    "app",
+    "description",
+];
+
+/// Tells Excel the file is UTF-8.
+const UTF8_BOM: &str = "\u{feff}";
+
+/// Writes the event timeline of a saved session as CSV.
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `output_path` - Destination `.csv` file (see `default_csv_path()`)
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written file
+/// * `Err(String)` - Error if the session couldn't be loaded or the file written
+pub fn export_csv(session_id: &str, output_path: &Path) -> Result<PathBuf, String> {
+    let session = storage::load_session(session_id)?;
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {:?}", e))?;
+    }
+    fs::write(output_path, render(&session))
+        .map_err(|e| format!("Failed to write CSV: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📊 CSV with {} events written to {:?}",
+        session.events.len(),
+        output_path
+    );
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Default location: `recordings/[session-id]/events.csv`.
+pub fn default_csv_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("events.csv")
+}
+
+fn render(session: &RecordingSession) -> String {
+    let mut csv = format!("{}{}\r\n", UTF8_BOM, HEADER.join(","));
+    for event in &session.events {
+        let position = event.position.as_ref();
+        let row = [
+            event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            event.event_type.name().to_string(),
+            event.action_category.clone(),
+            key_or_button(event).unwrap_or_default(),
+            position.map(|p| p.x.to_string()).unwrap_or_default(),
+            position.map(|p| p.y.to_string()).unwrap_or_default(),
+            event.app_name.clone().unwrap_or_default(),
+            event.description().into_owned(),
+        ];
+        let fields: Vec<String> = row.iter().map(|field| escape(&neutralize(field))).collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+fn key_or_button(event: &Event) -> Option<String> {
+    match &event.event_type {
+        EventType::Click { button, .. } => Some(format!("{:?}", button)),
+        EventType::KeyPress { key, .. } | EventType::Shortcut { key, .. } => Some(key.clone()),
+        _ => None,
+    }
+}
+
+/// Prefixes text a spreadsheet would run as a formula (see "Formula Injection").
+fn neutralize(field: &str) -> String {
+    let formula = field.starts_with(['=', '+', '-', '@', '\t', '\r']);
+    if formula && field.parse::<f64>().is_err() {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    }
+}
+
+/// Quotes a field if it contains a separator, quote, or line break.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MouseButton, Position};
+
+    #[test]
+    fn test_rows_are_flat_and_quoted() {
+        let mut session = RecordingSession::new("csv-test".to_string());
+        let mut click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(709.0, 328.0)),
+        );
+        click.app_name = Some("Google Chrome".to_string());
+        click.description = "Clicked button 'Save, then close'".to_string();
+        session.add_event(click);
+
+        let csv = render(&session);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "\u{feff}timestamp,type,category,key_or_button,x,y,app,description"
+        );
+        assert!(lines[1].contains(",Click,"));
+        assert!(
+            lines[1].ends_with(",Left,709,328,Google Chrome,\"Clicked button 'Save, then close'\"")
+        );
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_formulas_are_written_as_text() {
+        let mut session = RecordingSession::new("csv-formula-test".to_string());
+        let mut click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(-1280.0, 40.0)),
+        );
+        click.app_name = Some("=HYPERLINK(\"http://example.com\",\"Excel\")".to_string());
+        click.description = "@SUM(A1:A9)".to_string();
+        session.add_event(click);
+
+        let csv = render(&session);
+        let row = csv.split("\r\n").nth(1).unwrap();
+        assert!(row.contains(",-1280,40,"));
+        assert!(row.contains(",\"'=HYPERLINK(\"\"http://example.com\"\",\"\"Excel\"\")\","));
+        assert!(row.ends_with(",'@SUM(A1:A9)"));
+
+        for field in ["+cmd", "-2+3", "\tx", "\rx"] {
+            assert!(neutralize(field).starts_with('\''), "{:?}", field);
+        }
+        assert_eq!(neutralize("-12.5"), "-12.5");
+        assert_eq!(neutralize("Clicked"), "Clicked");
+    }
+}
@@ -0,0 +1,312 @@
+//! # Document Export - Step-by-Step Markdown (SOP)
+//!
+//! Turns a session into the document most recordings are made for: a
+//! standard operating procedure with numbered steps, the screenshot of each
+//! step, and a note wherever the recording shows the user had to wait.
+//!
+//! ## Layout
+//! ```markdown
+//! # Create an invoice
+//!
+//! *Recorded 2026-03-02 09:14 UTC · 12 steps*
+//!
+//! 1. Clicked button 'New invoice' — *Invoices — Google Chrome*
+//!
+//!    ![Step 1](document.images/event_..._window.png)
+//!
+//!    > ⏳ Wait for the screen to load (about 4 seconds).
+//! ```
+//!
+//! Steps are the ones of the checklist (see `steps` module), with the same
+//! `##` headings per marker section and per day of a multi-part procedure.
+//!
+//! ## Wait Notes
+//! Pauses recorded after a step (`Wait` and `Idle` events, see
+//! `steps::pause_after()`) become notes. Loading pauses tell the reader to
+//! wait for the screen; others only record how long the user paused and why.
+//!
+//! ## Images
+//! - **Linked** (default): Export copies in `[document].images/` next to the
+//!   document, linked relatively (see `export::prepare_step_images()`)
+//! - **Embedded** (`embed_images`): Base64 data URIs, so the `.md` file is
+//!   self-contained
+//!
+//! Either way screenshots are watermarked if configured and carry no metadata.
+
+use crate::export::{self, checklist, clean, watermark, ExportOptions};
+use crate::frame_diff;
+use crate::parts;
+use crate::steps::{self, Pause, Step};
+use crate::storage;
+use crate::types::{Event, ScrollPosition, WaitCause};
+use base64::Engine;
+use image::DynamicImage;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Shortest pause worth a note in the document.
+const MIN_NOTE_SECONDS: f64 = 1.0;
+
+/// Writes the step-by-step document of a saved session.
+///
+/// # Arguments
+/// * `session_id` - Saved session (all parts of a multi-part procedure are included)
+/// * `output_path` - Destination `.md` file (see `default_document_path()`)
+/// * `options` - Export options (`embed_images`)
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written document
+/// * `Err(String)` - Error if the session couldn't be loaded, a screenshot
+///   couldn't be read, or the document written
+pub fn export_document(
+    session_id: &str,
+    output_path: &Path,
+    options: &ExportOptions,
+) -> Result<PathBuf, String> {
+    let mut parts = parts::load_parts(session_id)?;
+    for part in parts.iter_mut() {
+        frame_diff::materialize(part);
+    }
+    let checklist = checklist::build_checklist(&parts);
+    let mut steps: Vec<Step> = checklist.items.into_iter().map(|item| item.step).collect();
+    let events: Vec<&Event> = parts.iter().flat_map(|part| &part.events).collect();
+    let pauses: Vec<Option<Pause>> = steps
+        .iter()
+        .map(|step| steps::pause_after(&events, step))
+        .collect();
+
+    let output_dir = output_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create output directory: {:?}", e))?;
+
+    let summary = format!(
+        "Recorded {} · {} steps",
+        parts[0].started_at.format("%Y-%m-%d %H:%M UTC"),
+        steps.len()
+    );
+    let markdown = if options.embed_images {
+        let data_uris = embed_images(&steps)?;
+        render(&checklist.title, &summary, &steps, &pauses, |asset| {
+            data_uris.get(asset).cloned().unwrap_or_default()
+        })
+    } else {
+        export::prepare_step_images(steps.iter_mut(), &output_path.with_extension("images"))?;
+        render(&checklist.title, &summary, &steps, &pauses, |asset| {
+            checklist::image_link(asset, &output_dir)
+        })
+    };
+    fs::write(output_path, markdown).map_err(|e| format!("Failed to write document: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📄 Document with {} steps written to {:?}",
+        steps.len(),
+        output_path
+    );
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Default location: `recordings/[session-id]/document.md`.
+pub fn default_document_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("document.md")
+}
+
+/// Data URIs of the step screenshots, by stored path.
+fn embed_images(steps: &[Step]) -> Result<HashMap<String, String>, String> {
+    let mark = watermark::current()?;
+    let mut data_uris = HashMap::new();
+    let assets = steps.iter().flat_map(|step| {
+        [step.screenshot.as_ref(), step.detail_screenshot.as_ref()]
+            .into_iter()
+            .flatten()
+    });
+    for asset in assets {
+        if data_uris.contains_key(asset) {
+            continue;
+        }
+        let image = image::open(storage::resolve_asset_path(asset))
+            .map_err(|e| format!("Failed to read {}: {:?}", asset, e))?;
+        let image = match &mark {
+            Some(mark) => {
+                let mut stamped = image.into_rgba8();
+                mark.apply(&mut stamped);
+                DynamicImage::ImageRgba8(stamped)
+            }
+            None => image,
+        };
+        // Re-encoding drops whatever metadata the capture carried
+        let png = clean::encode_png(&image)?;
+        data_uris.insert(
+            asset.clone(),
+            format!(
+                "data:image/png;base64,{}",
+                base64::engine::general_purpose::STANDARD.encode(png)
+            ),
+        );
+    }
+    Ok(data_uris)
+}
+
+/// Renders the document; `link` maps stored screenshot paths to image targets.
+fn render(
+    title: &str,
+    summary: &str,
+    steps: &[Step],
+    pauses: &[Option<Pause>],
+    link: impl Fn(&str) -> String,
+) -> String {
+    let mut markdown = format!("# {}\n\n*{}*\n\n", title, summary);
+    let mut part = None;
+    let mut section = None;
+    let section_heading = if steps.iter().any(|step| step.part.is_some()) {
+        "###"
+    } else {
+        "##"
+    };
+
+    for (step, pause) in steps.iter().zip(pauses) {
+        if let Some(name) = &step.part {
+            if part != Some(name) {
+                part = Some(name);
+                section = None;
+                markdown.push_str(&format!("## {}\n\n", name));
+            }
+        }
+        if let Some(name) = &step.section {
+            if section != Some(name) {
+                section = Some(name);
+                markdown.push_str(&format!("{} {}\n\n", section_heading, name));
+            }
+        }
+
+        markdown.push_str(&format!("{}. {}", step.number, step.title));
+        if let Some(window_title) = &step.window_title {
+            markdown.push_str(&format!(" — *{}*", window_title));
+        }
+        if let Some(scrolled) = step
+            .scroll_position
+            .as_ref()
+            .and_then(ScrollPosition::summary)
+        {
+            markdown.push_str(&format!(" ({})", scrolled));
+        }
+        markdown.push('\n');
+        // Indented to the list item's text so the images stay inside it
+        let indent = " ".repeat(step.number.to_string().len() + 2);
+        if let Some(screenshot) = &step.screenshot {
+            markdown.push_str(&format!(
+                "\n{}![Step {}]({})\n",
+                indent,
+                step.number,
+                link(screenshot)
+            ));
+        }
+        if let Some(detail) = &step.detail_screenshot {
+            markdown.push_str(&format!(
+                "\n{}![Step {} detail]({})\n",
+                indent,
+                step.number,
+                link(detail)
+            ));
+        }
+        if let Some(note) = pause.as_ref().and_then(pause_note) {
+            markdown.push_str(&format!("\n{}> ⏳ {}\n", indent, note));
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Note for a pause after a step (`None` for pauses too short to mention).
+fn pause_note(pause: &Pause) -> Option<String> {
+    if pause.seconds < MIN_NOTE_SECONDS {
+        return None;
+    }
+    let seconds = pause.seconds.round() as u64;
+    let duration = match seconds {
+        1 => "1 second".to_string(),
+        seconds => format!("{} seconds", seconds),
+    };
+    Some(match pause.cause {
+        Some(WaitCause::Loading) => format!("Wait for the screen to load (about {}).", duration),
+        Some(cause) => format!("Paused for {} ({}).", duration, cause.label()),
+        None => format!("Paused for {}.", duration),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EventType, MouseButton, Position, RecordingSession};
+
+    #[test]
+    fn test_document_numbers_steps_with_images_and_wait_notes() {
+        let mut session = RecordingSession::new("document-test".to_string());
+        let click = || {
+            Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(5.0, 5.0)),
+            )
+        };
+        session.add_event(click().with_screenshots(
+            Some("full.png".to_string()),
+            Some("window.png".to_string()),
+            None,
+        ));
+        session.add_event(Event::new(
+            EventType::Wait {
+                duration_seconds: 2.5,
+                cause: None,
+            },
+            None,
+        ));
+        session.add_event(Event::new(
+            EventType::Wait {
+                duration_seconds: 1.5,
+                cause: Some(WaitCause::Loading),
+            },
+            None,
+        ));
+        session.add_event(click());
+
+        let steps = steps::group_steps(&session);
+        let events: Vec<&Event> = session.events.iter().collect();
+        let pauses: Vec<Option<Pause>> = steps
+            .iter()
+            .map(|step| steps::pause_after(&events, step))
+            .collect();
+        assert_eq!(
+            pauses[0],
+            Some(Pause {
+                seconds: 4.0,
+                cause: None,
+            })
+        );
+
+        let markdown = render("Test", "Recorded", &steps, &pauses, |asset| {
+            asset.to_string()
+        });
+        assert!(markdown.starts_with("# Test\n\n*Recorded*\n\n1. Clicked left button"));
+        assert!(markdown.contains("   ![Step 1](window.png)"));
+        assert!(markdown.contains("   > ⏳ Paused for 4 seconds."));
+        assert!(markdown.contains("\n2. Clicked left button"));
+
+        let loading = Pause {
+            seconds: 4.0,
+            cause: Some(WaitCause::Loading),
+        };
+        assert_eq!(
+            pause_note(&loading).as_deref(),
+            Some("Wait for the screen to load (about 4 seconds).")
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! # Parquet Export - Columnar Event Data
+//!
+//! Writes events from many sessions into a single Parquet file so analysts
+//! can query months of recordings with DuckDB/Spark/pandas instead of
+//! parsing thousands of `session.json` files.
+//!
+//! Only compiled with the `parquet` Cargo feature (pulls in arrow + parquet).
+//!
+//! ## Schema (one row per event)
+//!
+//! | Column | Type | Notes |
+//! |--------|------|-------|
+//! | `session_id` | Utf8 | |
+//! | `session_started_at` | Timestamp(µs, UTC) | |
+//! | `event_index` | UInt32 | Position within the session |
+//! | `event_id` | Utf8 | |
+//! | `timestamp` | Timestamp(µs, UTC) | |
+//! | `event_type` | Utf8 | `Click`, `KeyPress`, `Wait`, ... |
+//! | `action_category` | Utf8 | Classification category |
+//! | `description` | Utf8 | Human-readable description |
+//! | `button` | Utf8 (nullable) | Click events |
+//! | `key` | Utf8 (nullable) | KeyPress and Shortcut events (modifiers in `event_data`) |
+//! | `duration_seconds` | Float64 (nullable) | Wait, Idle, Hover, Paused, CredentialsEntered, and Lifecycle events; gap of RecorderRestart events; hold time of clicks and key presses |
+//! | `response_latency_ms` | UInt64 (nullable) | Click response time (see `latency` module) |
+//! | `x`, `y` | Int32 (nullable) | Logical screen position (global across displays) |
+//! | `display_id` | UInt32 (nullable) | Display the position is on |
+//! | `full_screen_path` | Utf8 (nullable) | Stored screenshot path |
+//! | `event_data` | Utf8 | Full `event_type` as JSON (forward compatible) |
+//!
+//! Sessions are written one record batch at a time, so memory use is bounded
+//! by the largest session rather than the whole export.
+
+use crate::storage;
+use crate::types::{Event, EventType, RecordingSession};
+use arrow::array::{
+    ArrayRef, Float64Builder, Int32Builder, StringBuilder, TimestampMicrosecondBuilder,
+    UInt32Builder, UInt64Builder,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Summary returned after a Parquet export.
+#[derive(Serialize, Debug, Clone)]
+pub struct ParquetExportSummary {
+    pub output_path: String,
+    pub sessions_exported: usize,
+    /// Sessions that couldn't be loaded (skipped, not fatal)
+    pub sessions_skipped: Vec<String>,
+    pub rows_written: usize,
+}
+
+/// Exports the events of the given sessions into one Parquet file.
+///
+/// # Arguments
+/// * `output_path` - Destination `.parquet` file (overwritten if it exists)
+/// * `session_ids` - Sessions to include
+///
+/// # Returns
+/// * `Ok(ParquetExportSummary)` - Rows written and sessions skipped
+/// * `Err(String)` - Error if the file couldn't be created or written
+pub fn export_parquet(
+    output_path: &Path,
+    session_ids: &[String],
+) -> Result<ParquetExportSummary, String> {
+    let schema = event_schema();
+    let file =
+        File::create(output_path).map_err(|e| format!("Failed to create Parquet file: {:?}", e))?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))
+        .map_err(|e| format!("Failed to create Parquet writer: {:?}", e))?;
+
+    let mut summary = ParquetExportSummary {
+        output_path: output_path.display().to_string(),
+        sessions_exported: 0,
+        sessions_skipped: Vec::new(),
+        rows_written: 0,
+    };
+
+    for session_id in session_ids {
+        let session = match storage::load_session(session_id) {
+            Ok(session) => session,
+            Err(e) => {
+                eprintln!(
+                    "⚠️  Skipping session {} in Parquet export: {}",
+                    session_id, e
+                );
+                summary.sessions_skipped.push(session_id.clone());
+                continue;
+            }
+        };
+
+        if !session.events.is_empty() {
+            let batch = session_batch(&schema, &session)?;
+            writer
+                .write(&batch)
+                .map_err(|e| format!("Failed to write Parquet batch: {:?}", e))?;
+        }
+
+        summary.rows_written += session.events.len();
+        summary.sessions_exported += 1;
+    }
+
+    writer
+        .close()
+        .map_err(|e| format!("Failed to finalize Parquet file: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📊 Parquet export: {} rows from {} sessions → {}",
+        summary.rows_written, summary.sessions_exported, summary.output_path
+    );
+
+    Ok(summary)
+}
+
+/// Arrow schema for exported events (see module docs).
+fn event_schema() -> SchemaRef {
+    let timestamp = DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()));
+
+    Arc::new(Schema::new(vec![
+        Field::new("session_id", DataType::Utf8, false),
+        Field::new("session_started_at", timestamp.clone(), false),
+        Field::new("event_index", DataType::UInt32, false),
+        Field::new("event_id", DataType::Utf8, false),
+        Field::new("timestamp", timestamp, false),
+        Field::new("event_type", DataType::Utf8, false),
+        Field::new("action_category", DataType::Utf8, false),
+        Field::new("description", DataType::Utf8, false),
+        Field::new("button", DataType::Utf8, true),
+        Field::new("key", DataType::Utf8, true),
+        Field::new("duration_seconds", DataType::Float64, true),
+        Field::new("response_latency_ms", DataType::UInt64, true),
+        Field::new("x", DataType::Int32, true),
+        Field::new("y", DataType::Int32, true),
+        Field::new("display_id", DataType::UInt32, true),
+        Field::new("full_screen_path", DataType::Utf8, true),
+        Field::new("event_data", DataType::Utf8, false),
+    ]))
+}
+
+/// Builds one record batch containing every event of a session.
+fn session_batch(schema: &SchemaRef, session: &RecordingSession) -> Result<RecordBatch, String> {
+    let mut session_id = StringBuilder::new();
+    let mut session_started_at = TimestampMicrosecondBuilder::new();
+    let mut event_index = UInt32Builder::new();
+    let mut event_id = StringBuilder::new();
+    let mut timestamp = TimestampMicrosecondBuilder::new();
+    let mut event_type = StringBuilder::new();
+    let mut action_category = StringBuilder::new();
+    let mut description = StringBuilder::new();
+    let mut button = StringBuilder::new();
+    let mut key = StringBuilder::new();
+    let mut duration_seconds = Float64Builder::new();
+    let mut response_latency_ms = UInt64Builder::new();
+    let mut x = Int32Builder::new();
+    let mut y = Int32Builder::new();
+    let mut display_id = UInt32Builder::new();
+    let mut full_screen_path = StringBuilder::new();
+    let mut event_data = StringBuilder::new();
+
+    for (index, event) in session.events.iter().enumerate() {
+        let columns = EventColumns::from_event(event);
+
+        session_id.append_value(&session.session_id);
+        session_started_at.append_value(session.started_at.timestamp_micros());
+        event_index.append_value(index as u32);
+        event_id.append_value(event.id.to_string());
+        timestamp.append_value(event.timestamp.timestamp_micros());
+        event_type.append_value(&columns.type_name);
+        action_category.append_value(&event.action_category);
+        description.append_value(event.description());
+        button.append_option(columns.button.as_deref());
+        key.append_option(columns.key.as_deref());
+        duration_seconds.append_option(columns.duration_seconds);
+        response_latency_ms.append_option(event.response_latency_ms);
+        x.append_option(event.position.as_ref().map(|p| p.x));
+        y.append_option(event.position.as_ref().map(|p| p.y));
+        display_id.append_option(
+            event
+                .position
+                .as_ref()
+                .and_then(|p| p.normalized.as_ref())
+                .map(|n| n.display_id),
+        );
+        full_screen_path.append_option(event.screenshots.full_screen.as_deref());
+        event_data.append_value(&columns.data);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(session_id.finish()),
+        Arc::new(session_started_at.finish().with_timezone("UTC")),
+        Arc::new(event_index.finish()),
+        Arc::new(event_id.finish()),
+        Arc::new(timestamp.finish().with_timezone("UTC")),
+        Arc::new(event_type.finish()),
+        Arc::new(action_category.finish()),
+        Arc::new(description.finish()),
+        Arc::new(button.finish()),
+        Arc::new(key.finish()),
+        Arc::new(duration_seconds.finish()),
+        Arc::new(response_latency_ms.finish()),
+        Arc::new(x.finish()),
+        Arc::new(y.finish()),
+        Arc::new(display_id.finish()),
+        Arc::new(full_screen_path.finish()),
+        Arc::new(event_data.finish()),
+    ];
+
+    RecordBatch::try_new(schema.clone(), columns)
+        .map_err(|e| format!("Failed to build Parquet batch: {:?}", e))
+}
+
+/// Type-specific column values of one event.
+struct EventColumns {
+    /// Serde tag of the event type (`"Click"`, `"KeyPress"`, ...)
+    type_name: String,
+    /// Full `event_type` serialized as JSON
+    data: String,
+    button: Option<String>,
+    key: Option<String>,
+    duration_seconds: Option<f64>,
+}
+
+impl EventColumns {
+    fn from_event(event: &Event) -> Self {
+        let data = serde_json::to_value(&event.event_type).unwrap_or_default();
+        let mut columns = EventColumns {
+            type_name: data
+                .get("type")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            data: data.to_string(),
+            button: None,
+            key: None,
+            duration_seconds: None,
+        };
+
+        match &event.event_type {
+            EventType::Click {
+                button,
+                duration_ms,
+            } => {
+                columns.button = Some(format!("{:?}", button));
+                columns.duration_seconds = duration_ms.map(|ms| ms as f64 / 1000.0);
+            }
+            EventType::KeyPress {
+                key, duration_ms, ..
+            } => {
+                columns.key = Some(key.clone());
+                columns.duration_seconds = duration_ms.map(|ms| ms as f64 / 1000.0);
+            }
+            EventType::Shortcut { key, .. } => columns.key = Some(key.clone()),
+            EventType::TextInput { .. }
+            | EventType::MouseTrail { .. }
+            | EventType::Scroll { .. }
+            | EventType::Marker { .. }
+            | EventType::AppSwitch { .. }
+            | EventType::Capture { .. }
+            | EventType::ManualStep { .. }
+            | EventType::CaptureError { .. } => {}
+            EventType::Wait {
+                duration_seconds, ..
+            }
+            | EventType::Idle { duration_seconds }
+            | EventType::Hover { duration_seconds }
+            | EventType::Paused { duration_seconds }
+            | EventType::CredentialsEntered { duration_seconds }
+            | EventType::Lifecycle {
+                duration_seconds, ..
+            }
+            | EventType::RecorderRestart {
+                gap_seconds: duration_seconds,
+            } => columns.duration_seconds = Some(*duration_seconds),
+        }
+
+        columns
+    }
+}
@@ -0,0 +1,359 @@
+//! # Export Presets - Repeat Publishing in One Call
+//!
+//! Publishing the same kind of document again ("Confluence page", "Jira
+//! repro", "Training PDF") means picking the same format, filters, image
+//! size, and destination every time. A preset bundles those choices under a
+//! name, and `export_with_preset()` applies them to a session.
+//!
+//! ## Storage
+//! ```text
+//! recordings/export_presets.json
+//! ```
+//! One list for all sessions, keyed by name (case-insensitive).
+//!
+//! ## Credentials
+//! Presets are plain JSON next to the recordings, so they never hold
+//! secrets: `destination.credential` names the environment variable the
+//! token is read from by whoever uploads the document. `export_with_preset()`
+//! reports whether it is set, so a missing token shows up before publishing.
+//!
+//! ## Formats
+//! Markdown presets write the checklist document (see `checklist` module).
+//! Presets for other formats are rejected when saved, until the format has
+//! a writer.
+//!
+//! Screenshots are downscaled and watermarked copies when `images.max_width`
+//! or the configured watermark asks for it (see `watermark` module), and
+//! never carry metadata (see `clean` module).
+
+use crate::export::checklist::{self, Checklist};
+use crate::export::{self, clean, ExportFormat, ExportOptions};
+use crate::frame_diff;
+use crate::parts;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// All saved presets.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PresetIndex {
+    pub presets: Vec<ExportPreset>,
+}
+
+/// Named export settings (see module docs).
+///
+/// # Example
+/// ```json
+/// {
+///   "name": "Jira repro",
+///   "format": "markdown",
+///   "filter": {"categories": ["interaction", "text_input"]},
+///   "images": {"max_width": 1280},
+///   "destination": {
+///     "directory": "/Users/me/Jira",
+///     "url": "https://example.atlassian.net/browse/OPS",
+///     "credential": "JIRA_API_TOKEN"
+///   }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportPreset {
+    pub name: String,
+    pub format: ExportFormat,
+    /// Format options (the Markdown checklist always links its screenshots)
+    #[serde(default)]
+    pub options: ExportOptions,
+    #[serde(default)]
+    pub filter: PresetFilter,
+    #[serde(default)]
+    pub images: ImageProfile,
+    #[serde(default)]
+    pub destination: Destination,
+}
+
+/// Steps a preset exports.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PresetFilter {
+    /// Action categories to keep (empty = all steps)
+    pub categories: Vec<String>,
+}
+
+/// Screenshots of a preset's documents.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ImageProfile {
+    /// Downscale wider screenshots to this width (`None` = link the originals)
+    pub max_width: Option<u32>,
+}
+
+/// Where a preset's documents go.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Destination {
+    /// Output directory (`None` = the session directory)
+    pub directory: Option<String>,
+    /// Page or project the document is published to, for the uploader
+    pub url: Option<String>,
+    /// Environment variable holding the destination's token (see module docs)
+    pub credential: Option<String>,
+}
+
+/// Result of `export_with_preset()`.
+#[derive(Serialize, Debug, Clone)]
+pub struct PresetExport {
+    pub preset: String,
+    /// Path of the written document
+    pub path: String,
+    pub url: Option<String>,
+    /// Whether the preset's credential variable is set (`false` without one)
+    pub credential_available: bool,
+}
+
+/// Location of the preset list.
+pub fn presets_path() -> PathBuf {
+    storage::recordings_root().join("export_presets.json")
+}
+
+/// Saves a preset, replacing the one with the same name.
+///
+/// # Returns
+/// * `Ok(ExportPreset)` - The stored preset (name trimmed)
+/// * `Err(String)` - Error if the name is blank, the format has no writer
+///   yet, or the list couldn't be written
+pub fn save(mut preset: ExportPreset) -> Result<ExportPreset, String> {
+    preset.name = preset.name.trim().to_string();
+    if preset.name.is_empty() {
+        return Err("Preset name must not be empty".to_string());
+    }
+    if preset.images.max_width == Some(0) {
+        return Err("images.max_width must be at least 1".to_string());
+    }
+    check_format(&preset)?;
+
+    update_index(|index| {
+        match index
+            .presets
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(&preset.name))
+        {
+            Some(existing) => *existing = preset.clone(),
+            None => index.presets.push(preset.clone()),
+        }
+        Ok(())
+    })?;
+    Ok(preset)
+}
+
+/// Removes a preset.
+///
+/// # Returns
+/// * `Ok(bool)` - Whether the preset existed
+/// * `Err(String)` - Error if the list couldn't be written
+pub fn remove(name: &str) -> Result<bool, String> {
+    update_index(|index| {
+        let before = index.presets.len();
+        index
+            .presets
+            .retain(|preset| !preset.name.eq_ignore_ascii_case(name.trim()));
+        Ok(index.presets.len() != before)
+    })
+}
+
+/// Lists saved presets by name.
+///
+/// # Returns
+/// * `Ok(Vec<ExportPreset>)` - Saved presets
+/// * `Err(String)` - Error if the preset list exists but can't be read or parsed
+pub fn list() -> Result<Vec<ExportPreset>, String> {
+    let mut presets = load_index()?.presets;
+    presets.sort_by_key(|preset| preset.name.to_lowercase());
+    Ok(presets)
+}
+
+/// Exports a saved session with a preset.
+///
+/// # Arguments
+/// * `session_id` - Session to export (all its parts, see `parts` module)
+/// * `name` - Name of a saved preset
+///
+/// # Returns
+/// * `Ok(PresetExport)` - Where the document was written
+/// * `Err(String)` - Error if the preset doesn't exist, its format has no
+///   writer, or the session couldn't be loaded or written
+pub fn export_with_preset(session_id: &str, name: &str) -> Result<PresetExport, String> {
+    let preset = load_index()?
+        .presets
+        .into_iter()
+        .find(|preset| preset.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("No export preset named {:?}", name))?;
+    // Saved before formats were checked (or edited by hand)
+    check_format(&preset)?;
+
+    let mut parts = parts::load_parts(session_id)?;
+    for part in parts.iter_mut() {
+        frame_diff::materialize(part);
+    }
+    let mut checklist = checklist::build_checklist(&parts);
+    apply_filter(&mut checklist, &preset.filter);
+
+    let output_path = match &preset.destination.directory {
+        Some(directory) => Path::new(directory).join(format!("{}.md", session_id)),
+        None => checklist::default_markdown_path(session_id),
+    };
+    let image_dir = output_path.with_extension("images");
+    if let Some(max_width) = preset.images.max_width {
+        downscale_images(&mut checklist, &image_dir, max_width)?;
+    }
+    export::prepare_step_images(
+        checklist.items.iter_mut().map(|item| &mut item.step),
+        &image_dir,
+    )?;
+    let path = checklist::write_markdown(&checklist, &output_path)?;
+
+    Ok(PresetExport {
+        preset: preset.name,
+        path: path.display().to_string(),
+        url: preset.destination.url,
+        credential_available: preset
+            .destination
+            .credential
+            .is_some_and(|variable| std::env::var_os(variable).is_some()),
+    })
+}
+
+/// Rejects presets whose format `export_with_preset()` has no writer for.
+///
+/// # Returns
+/// * `Ok(())` - The format can be exported
+/// * `Err(String)` - The format isn't available yet
+fn check_format(preset: &ExportPreset) -> Result<(), String> {
+    if preset.format != ExportFormat::Markdown {
+        return Err(format!(
+            "Export preset {:?}: {:?} export isn't available yet",
+            preset.name, preset.format
+        ));
+    }
+    Ok(())
+}
+
+/// Drops the steps the filter excludes; the rest keep their numbers.
+fn apply_filter(checklist: &mut Checklist, filter: &PresetFilter) {
+    if filter.categories.is_empty() {
+        return;
+    }
+    checklist.items.retain(|item| {
+        filter
+            .categories
+            .iter()
+            .any(|category| category.eq_ignore_ascii_case(&item.step.category))
+    });
+}
+
+/// Writes downscaled copies of the wider screenshots into `image_dir` and
+/// links those; `export::prepare_step_images()` then watermarks or cleans
+/// them like any other export.
+///
+/// Screenshots that can't be read keep their original link (a watermark
+/// still fails on them, see `watermark` module).
+fn downscale_images(
+    checklist: &mut Checklist,
+    image_dir: &Path,
+    max_width: u32,
+) -> Result<(), String> {
+    fs::create_dir_all(image_dir)
+        .map_err(|e| format!("Failed to create image directory: {:?}", e))?;
+
+    for item in checklist.items.iter_mut() {
+        let step = &mut item.step;
+        for screenshot in [&mut step.screenshot, &mut step.detail_screenshot]
+            .into_iter()
+            .flatten()
+        {
+            let source = storage::resolve_asset_path(screenshot);
+            let image = match image::open(&source) {
+                Ok(image) if image.width() > max_width => image,
+                Ok(_) => continue,
+                Err(e) => {
+                    eprintln!("⚠️  Linking original screenshot {}: {:?}", screenshot, e);
+                    continue;
+                }
+            };
+            let Some(target) = clean::copy_target(&source, image_dir) else {
+                continue;
+            };
+            clean::save_png(&image.thumbnail(max_width, u32::MAX), &target)?;
+            *screenshot = target.display().to_string();
+        }
+    }
+    Ok(())
+}
+
+/// Loads the preset list (empty if missing, `Err` if invalid).
+fn load_index() -> Result<PresetIndex, String> {
+    storage::read_json_index(&presets_path(), "export presets")
+}
+
+/// Changes the preset list under the index lock (see `storage::update_json_index()`).
+fn update_index<R>(
+    update: impl FnOnce(&mut PresetIndex) -> Result<R, String>,
+) -> Result<R, String> {
+    storage::update_json_index(&presets_path(), "export presets", update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position, RecordingSession};
+
+    #[test]
+    fn test_preset_json_and_category_filter() {
+        let preset: ExportPreset = serde_json::from_str(
+            r#"{"name": "Jira repro", "format": "markdown", "filter": {"categories": ["Interaction"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(preset.images.max_width, None);
+        assert_eq!(preset.destination, Destination::default());
+
+        let mut session = RecordingSession::new("presets-test".to_string());
+        session.add_event(Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(709.0, 328.0)),
+        ));
+        session.add_event(Event::new(
+            EventType::TextInput {
+                text: "INV-1234".to_string(),
+                keystrokes: 8,
+            },
+            None,
+        ));
+        let mut checklist = checklist::build_checklist(&[session]);
+        assert_eq!(checklist.items.len(), 2);
+
+        // Categories match case-insensitively; kept steps keep their numbers
+        apply_filter(&mut checklist, &preset.filter);
+        assert_eq!(checklist.items.len(), 1);
+        assert_eq!(checklist.items[0].step.category, "interaction");
+        assert_eq!(checklist.items[0].step.number, 1);
+    }
+
+    #[test]
+    fn test_presets_without_a_writer_are_rejected() {
+        let preset: ExportPreset =
+            serde_json::from_str(r#"{"name": "Training PDF", "format": "pdf"}"#).unwrap();
+        // Rejected before the preset list is touched
+        assert!(save(preset.clone()).is_err());
+        assert!(check_format(&preset).is_err());
+
+        let markdown = ExportPreset {
+            format: ExportFormat::Markdown,
+            ..preset
+        };
+        assert!(check_format(&markdown).is_ok());
+    }
+}
@@ -0,0 +1,366 @@
+//! # Redline Export - What Changed Between Two Versions of a Procedure
+//!
+//! When a procedure is re-recorded (new UI, changed process), reviewers need
+//! to see what changed rather than re-read every step. This exporter aligns
+//! the steps of the original and the updated session and marks each one as
+//! unchanged, added, removed, or changed.
+//!
+//! ## Alignment
+//! Steps are matched by a longest common subsequence over their *shape*:
+//! category plus title with standalone numbers masked, so "Clicked left
+//! button at position (709, 328)" matches the same click made a few pixels
+//! away. Quoted and typed text, and digits within words (`Ctrl+1`, `v2`),
+//! are compared exactly.
+//! - **Matched** steps are `Unchanged`, or `Changed` if the window differs
+//!   (ignoring counters such as "Inbox (3)")
+//! - A **removed** step replaced by an **added** step of the same category
+//!   between the same matched steps (e.g., `Type "v1"` → `Type "v2"`) is
+//!   one `Changed` entry
+//! - Everything else is `Removed` (original only) or `Added` (updated only)
+//!
+//! ## Markdown
+//! One table row per entry with the original and updated screenshots side
+//! by side; removed titles are struck through:
+//! ```markdown
+//! | | Original | Updated |
+//! |---|---|---|
+//! | ✏️ | **3.** ~~Type "v1"~~ | **3.** Type "v2" |
+//! ```
+
+use super::checklist;
+use crate::steps::{self, Step};
+use crate::storage;
+use crate::types::RecordingSession;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How a step differs between the two versions.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepChange {
+    Unchanged,
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One aligned pair of steps.
+#[derive(Serialize, Debug, Clone)]
+pub struct RedlineEntry {
+    pub change: StepChange,
+    /// Step in the original session (`None` if added)
+    pub original: Option<Step>,
+    /// Step in the updated session (`None` if removed)
+    pub updated: Option<Step>,
+}
+
+/// Step-by-step comparison of two sessions.
+#[derive(Serialize, Debug, Clone)]
+pub struct Redline {
+    pub original_session_id: String,
+    pub updated_session_id: String,
+    pub title: String,
+    pub entries: Vec<RedlineEntry>,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+/// Compares the steps of an original session and its re-recorded update.
+pub fn build_redline(original: &RecordingSession, updated: &RecordingSession) -> Redline {
+    let entries = align_steps(steps::group_steps(original), steps::group_steps(updated));
+    let count = |change| {
+        entries
+            .iter()
+            .filter(|entry| entry.change == change)
+            .count()
+    };
+
+    Redline {
+        original_session_id: original.session_id.clone(),
+        updated_session_id: updated.session_id.clone(),
+        title: format!("Changes to {}", session_title(updated)),
+        added: count(StepChange::Added),
+        removed: count(StepChange::Removed),
+        changed: count(StepChange::Changed),
+        entries,
+    }
+}
+
+/// Writes a redline as a Markdown table with side-by-side screenshots.
+///
+/// # Arguments
+/// * `redline` - Comparison to write
+/// * `output_path` - Destination `.md` file
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written file
+/// * `Err(String)` - Error if the file couldn't be written
+pub fn write_markdown(redline: &Redline, output_path: &Path) -> Result<PathBuf, String> {
+    let output_dir = output_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create output directory: {:?}", e))?;
+
+    let markdown = render_markdown(redline, |asset| checklist::image_link(asset, &output_dir));
+    fs::write(output_path, markdown).map_err(|e| format!("Failed to write redline: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🖍️  Redline ({} added, {} removed, {} changed) written to {:?}",
+        redline.added, redline.removed, redline.changed, output_path
+    );
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Default Markdown location: `recordings/[updated-id]/redline_[original-id].md`.
+pub fn default_markdown_path(original_session_id: &str, updated_session_id: &str) -> PathBuf {
+    storage::get_session_dir(updated_session_id).join(format!("redline_{}.md", original_session_id))
+}
+
+/// Aligns two step lists (see module docs).
+fn align_steps(original: Vec<Step>, updated: Vec<Step>) -> Vec<RedlineEntry> {
+    let original_shapes: Vec<String> = original.iter().map(shape).collect();
+    let updated_shapes: Vec<String> = updated.iter().map(shape).collect();
+    let (n, m) = (original.len(), updated.len());
+
+    // lcs[i][j] = common steps of original[i..] and updated[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if original_shapes[i] == updated_shapes[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut entries: Vec<RedlineEntry> = Vec::new();
+    let mut original = original.into_iter().map(Some).collect::<Vec<_>>();
+    let mut updated = updated.into_iter().map(Some).collect::<Vec<_>>();
+    // Entries since the last matched pair (where unpaired removals live)
+    let mut run_start = 0;
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && original_shapes[i] == updated_shapes[j] {
+            let (before, after) = (original[i].take().unwrap(), updated[j].take().unwrap());
+            let window = |step: &Step| step.window_title.as_deref().map(mask_numbers);
+            let change = if window(&before) == window(&after) {
+                StepChange::Unchanged
+            } else {
+                StepChange::Changed
+            };
+            entries.push(RedlineEntry {
+                change,
+                original: Some(before),
+                updated: Some(after),
+            });
+            i += 1;
+            j += 1;
+            run_start = entries.len();
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            // Removals first, so a replacement reads as removed-then-added
+            entries.push(RedlineEntry {
+                change: StepChange::Removed,
+                original: original[i].take(),
+                updated: None,
+            });
+            i += 1;
+        } else {
+            let after = updated[j].take().unwrap();
+            // A removal replaced by an addition of the same kind is an edit
+            let replaced = entries[run_start..].iter_mut().find(|entry| {
+                entry.change == StepChange::Removed
+                    && entry
+                        .original
+                        .as_ref()
+                        .is_some_and(|before| before.category == after.category)
+            });
+            match replaced {
+                Some(entry) => {
+                    entry.change = StepChange::Changed;
+                    entry.updated = Some(after);
+                }
+                None => entries.push(RedlineEntry {
+                    change: StepChange::Added,
+                    original: None,
+                    updated: Some(after),
+                }),
+            }
+            j += 1;
+        }
+    }
+
+    entries
+}
+
+/// Category, title with standalone numbers masked, and typed text.
+fn shape(step: &Step) -> String {
+    format!(
+        "{}:{}:{}",
+        step.category,
+        mask_numbers(&step.title),
+        step.typed_text.as_deref().unwrap_or_default()
+    )
+}
+
+/// Replaces standalone numbers outside quotes with `#`.
+///
+/// Coordinates, durations, and counters vary between takes of the same
+/// step; numbers within words (`Ctrl+1`, `v2`) and quoted text don't.
+fn mask_numbers(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut quoted = false;
+    let mut previous: Option<char> = None;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let standalone = !quoted && !previous.is_some_and(|p| p.is_alphanumeric() || p == '+');
+        let negative = c == '-' && chars.peek().is_some_and(char::is_ascii_digit);
+        if standalone && (c.is_ascii_digit() || negative) {
+            while chars
+                .peek()
+                .is_some_and(|next| next.is_ascii_digit() || *next == '.')
+            {
+                previous = chars.next();
+            }
+            masked.push('#');
+            continue;
+        }
+        if c == '"' {
+            quoted = !quoted;
+        }
+        masked.push(c);
+        previous = Some(c);
+    }
+    masked
+}
+
+fn session_title(session: &RecordingSession) -> String {
+    session.title.clone().unwrap_or_else(|| {
+        format!(
+            "workflow recorded {}",
+            session.started_at.format("%Y-%m-%d %H:%M UTC")
+        )
+    })
+}
+
+/// Renders the Markdown document; `link` maps stored screenshot paths to link targets.
+fn render_markdown(redline: &Redline, link: impl Fn(&str) -> String) -> String {
+    let mut markdown = format!(
+        "# {}\n\n{} added, {} removed, {} changed.\n\n| | Original | Updated |\n|---|---|---|\n",
+        redline.title, redline.added, redline.removed, redline.changed
+    );
+
+    for entry in &redline.entries {
+        let symbol = match entry.change {
+            StepChange::Unchanged => "",
+            StepChange::Added => "➕",
+            StepChange::Removed => "➖",
+            StepChange::Changed => "✏️",
+        };
+        let struck = matches!(entry.change, StepChange::Removed | StepChange::Changed);
+        markdown.push_str(&format!(
+            "| {} | {} | {} |\n",
+            symbol,
+            cell(entry.original.as_ref(), struck, &link),
+            cell(entry.updated.as_ref(), false, &link)
+        ));
+    }
+
+    markdown
+}
+
+/// One table cell: step number, title, window, and screenshot.
+fn cell(step: Option<&Step>, struck: bool, link: &impl Fn(&str) -> String) -> String {
+    let Some(step) = step else {
+        return String::new();
+    };
+    // Pipes would end the cell
+    let title = step.title.replace('|', "\\|");
+    let mut cell = if struck {
+        format!("**{}.** ~~{}~~", step.number, title)
+    } else {
+        format!("**{}.** {}", step.number, title)
+    };
+    if let Some(window_title) = &step.window_title {
+        cell.push_str(&format!("<br>*{}*", window_title.replace('|', "\\|")));
+    }
+    if let Some(screenshot) = &step.screenshot {
+        cell.push_str(&format!(
+            "<br>![Step {}]({})",
+            step.number,
+            link(screenshot)
+        ));
+    }
+    cell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position};
+
+    fn session(id: &str, clicks: &[f64], typed: &str) -> RecordingSession {
+        let mut session = RecordingSession::new(id.to_string());
+        for x in clicks {
+            session.add_event(Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(*x, 10.0)),
+            ));
+        }
+        session.add_event(Event::new(
+            EventType::TextInput {
+                text: typed.to_string(),
+                keystrokes: typed.len(),
+            },
+            None,
+        ));
+        session
+    }
+
+    #[test]
+    fn test_redline_aligns_steps() {
+        let original = session("original", &[100.0, 200.0], "v1");
+        // Clicks moved a few pixels, one click dropped, text changed
+        let updated = session("updated", &[104.0], "v2");
+
+        let redline = build_redline(&original, &updated);
+        let changes: Vec<StepChange> = redline.entries.iter().map(|e| e.change).collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                StepChange::Unchanged,
+                StepChange::Removed,
+                StepChange::Changed
+            ]
+        );
+        assert_eq!((redline.added, redline.removed, redline.changed), (0, 1, 1));
+
+        let markdown = render_markdown(&redline, |asset| asset.to_string());
+        assert!(markdown.contains("~~Type \"v1\"~~ | **2.** Type \"v2\""));
+    }
+
+    #[test]
+    fn test_only_volatile_numbers_are_masked() {
+        assert_eq!(
+            mask_numbers("Clicked left button at position (-731, 328)"),
+            "Clicked left button at position (#, #)"
+        );
+        assert_eq!(
+            mask_numbers("Paused for 2.5 seconds"),
+            "Paused for # seconds"
+        );
+        assert_eq!(mask_numbers("Inbox (3) - Mail"), "Inbox (#) - Mail");
+        assert_eq!(mask_numbers("Pressed Ctrl+1"), "Pressed Ctrl+1");
+        assert_eq!(mask_numbers("Type \"order 1042\""), "Type \"order 1042\"");
+    }
+}
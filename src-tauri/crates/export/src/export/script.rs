@@ -0,0 +1,667 @@
+//! # Script Export - Test Skeletons from Recordings
+//!
+//! Turns a session into the skeleton of an automated UI test, so QA teams
+//! can bootstrap a test from a recording instead of writing it from scratch.
+//!
+//! ## Frameworks
+//! - **Playwright** (`[session-id].spec.ts`): `@playwright/test` in TypeScript
+//! - **Selenium** (`test_[session-id].py`): Python with `selenium` 4, runnable by pytest
+//!
+//! ## Mapping (one action per step, see `steps` module)
+//! | Step | Playwright | Selenium |
+//! |------|------------|----------|
+//! | Click on a known element | `getByRole('button', { name })` / `getByText()` | XPath on tag and text/`aria-label` |
+//! | Click without element | `page.mouse.click(x, y)` + TODO | `ActionChains` offset click + TODO |
+//! | Back/forward button | `page.goBack()` / `goForward()` | `driver.back()` / `forward()` |
+//! | Typing | `keyboard.type()` | `ActionChains.send_keys()` |
+//! | Special key, shortcut | `keyboard.press('Meta+KeyS')` | `key_down` / `send_keys` / `key_up` |
+//! | Scroll | `mouse.wheel()` | `window.scrollBy()` |
+//! | Loading wait after a step | `waitForLoadState('networkidle')` | `WebDriverWait` on `document.readyState` |
+//! | Credentials | TODO (nothing typed was recorded) | TODO |
+//! | Other steps | Comment | Comment |
+//!
+//! Browser window titles become title assertions where the page changes
+//! (`"Invoice #1234 — Google Chrome"` → `"Invoice #1234"`).
+//!
+//! ## Limitations
+//! Recordings have no URLs, so the script starts at a TODO `goto()`.
+//! Coordinates are screen positions, not page positions: every coordinate
+//! click is marked TODO to be replaced with a locator.
+
+use crate::export::checklist;
+use crate::parts;
+use crate::steps::{self, Step};
+use crate::storage;
+use crate::types::{Event, EventType, MouseButton, WaitCause};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Browser app names, for window titles that are page titles.
+const BROWSERS: &[&str] = &[
+    "Google Chrome",
+    "Chromium",
+    "Safari",
+    "Firefox",
+    "Microsoft Edge",
+    "Brave Browser",
+    "Arc",
+];
+
+/// Pixels per recorded wheel tick.
+const PIXELS_PER_SCROLL_TICK: i64 = 40;
+
+/// Test framework of a generated script.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptFramework {
+    Playwright,
+    Selenium,
+}
+
+/// Element to click, from the accessibility info of the click.
+#[derive(Debug, Clone, PartialEq)]
+struct Locator {
+    /// ARIA role (e.g., `"button"`)
+    role: Option<&'static str>,
+    name: String,
+}
+
+/// What a step does, independent of the framework.
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Click {
+        locator: Option<Locator>,
+        x: i32,
+        y: i32,
+        right: bool,
+    },
+    Back,
+    Forward,
+    Type(String),
+    /// Key with its modifiers (`rdev` names, e.g., `["Meta"]`, `"KeyS"`)
+    Press {
+        modifiers: Vec<String>,
+        key: String,
+    },
+    Scroll(i64),
+    Credentials,
+    /// Nothing to automate; the step title is the comment
+    Comment,
+}
+
+/// Writes a test script generated from a saved session.
+///
+/// # Arguments
+/// * `session_id` - Saved session (all parts of a multi-part procedure)
+/// * `output_path` - Destination file (see `default_script_path()`)
+/// * `framework` - Playwright or Selenium
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written script
+/// * `Err(String)` - Error if the session couldn't be loaded or the file written
+pub fn export_script(
+    session_id: &str,
+    output_path: &Path,
+    framework: ScriptFramework,
+) -> Result<PathBuf, String> {
+    let parts = parts::load_parts(session_id)?;
+    let checklist = checklist::build_checklist(&parts);
+    let steps: Vec<Step> = checklist.items.into_iter().map(|item| item.step).collect();
+    let events: Vec<&Event> = parts.iter().flat_map(|part| &part.events).collect();
+
+    let script = render(&checklist.title, &steps, &events, framework);
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {:?}", e))?;
+    }
+    fs::write(output_path, script).map_err(|e| format!("Failed to write script: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🧪 {:?} script with {} steps written to {:?}",
+        framework,
+        steps.len(),
+        output_path
+    );
+
+    Ok(output_path.to_path_buf())
+}
+
+/// Default location: `recordings/[session-id]/[session-id].spec.ts` or `test_[session-id].py`.
+pub fn default_script_path(session_id: &str, framework: ScriptFramework) -> PathBuf {
+    let filename = match framework {
+        ScriptFramework::Playwright => format!("{}.spec.ts", session_id),
+        ScriptFramework::Selenium => format!("test_{}.py", session_id.replace('-', "_")),
+    };
+    storage::get_session_dir(session_id).join(filename)
+}
+
+fn render(title: &str, steps: &[Step], events: &[&Event], framework: ScriptFramework) -> String {
+    let by_id: HashMap<uuid::Uuid, &Event> =
+        events.iter().map(|event| (event.id, *event)).collect();
+    let mut body: Vec<String> = Vec::new();
+    let mut section = None;
+    let mut page_title: Option<String> = None;
+
+    for step in steps {
+        let Some(first) = step.event_ids.first().and_then(|id| by_id.get(id)) else {
+            continue;
+        };
+        if let Some(name) = &step.section {
+            if section != Some(name) {
+                section = Some(name);
+                body.push(String::new());
+                body.push(comment(framework, &format!("--- {} ---", name)));
+            }
+        }
+
+        let title_now = page_title_of(first);
+        if title_now.is_some() && title_now != page_title {
+            if let Some(expected) = &title_now {
+                body.push(title_assertion(framework, expected));
+            }
+            page_title = title_now;
+        }
+
+        body.push(String::new());
+        body.push(comment(
+            framework,
+            &format!("Step {}: {}", step.number, step.title),
+        ));
+        for action in actions(step, first) {
+            body.extend(action_lines(framework, &action));
+        }
+
+        let loading = steps::pause_after(events, step)
+            .filter(|pause| pause.cause == Some(WaitCause::Loading));
+        if let Some(pause) = loading {
+            body.push(wait_for_load(framework, pause.seconds));
+        }
+    }
+
+    match framework {
+        ScriptFramework::Playwright => playwright_file(title, &body),
+        ScriptFramework::Selenium => selenium_file(title, &body),
+    }
+}
+
+/// The actions of a step: its first event, then its typing (a click into a
+/// field joined with what was typed there is both).
+fn actions(step: &Step, first: &Event) -> Vec<Action> {
+    let typing = step.typed_text.clone().map(Action::Type);
+    match (action(first), typing) {
+        (action, Some(typing)) if matches!(action, Action::Click { .. }) => vec![action, typing],
+        (_, Some(typing)) => vec![typing],
+        (action, None) => vec![action],
+    }
+}
+
+fn action(first: &Event) -> Action {
+    match &first.event_type {
+        EventType::Click { button, .. } => {
+            let position = first.position.as_ref();
+            match button {
+                MouseButton::Back => Action::Back,
+                MouseButton::Forward => Action::Forward,
+                button => Action::Click {
+                    locator: first.element.as_ref().and_then(|element| {
+                        let name = element.label.clone().filter(|label| !label.is_empty())?;
+                        Some(Locator {
+                            role: element.role.as_deref().and_then(aria_role),
+                            name,
+                        })
+                    }),
+                    x: position.map_or(0, |p| p.x),
+                    y: position.map_or(0, |p| p.y),
+                    right: *button == MouseButton::Right,
+                },
+            }
+        }
+        EventType::KeyPress { key, .. } => Action::Press {
+            modifiers: Vec::new(),
+            key: key.clone(),
+        },
+        EventType::Shortcut { modifiers, key } => Action::Press {
+            modifiers: modifiers.clone(),
+            key: key.clone(),
+        },
+        EventType::Scroll { delta_y, .. } => Action::Scroll(-delta_y * PIXELS_PER_SCROLL_TICK),
+        EventType::CredentialsEntered { .. } => Action::Credentials,
+        _ => Action::Comment,
+    }
+}
+
+/// ARIA role of a platform accessibility role.
+fn aria_role(role: &str) -> Option<&'static str> {
+    Some(match role {
+        "AXButton" | "button" | "push button" => "button",
+        "AXLink" | "link" => "link",
+        "AXTextField" | "AXTextArea" | "AXSearchField" | "edit" => "textbox",
+        "AXCheckBox" | "check box" => "checkbox",
+        "AXRadioButton" | "radio button" => "radio",
+        "AXPopUpButton" | "AXComboBox" | "combo box" => "combobox",
+        "AXMenuItem" | "menu item" => "menuitem",
+        "AXTab" | "AXTabButton" | "tab item" => "tab",
+        _ => return None,
+    })
+}
+
+/// Page title of a browser event (`None` for other apps).
+fn page_title_of(event: &Event) -> Option<String> {
+    let app = event.app_name.as_deref()?;
+    if !BROWSERS.contains(&app) {
+        return None;
+    }
+    let title = event.window_title.as_deref()?;
+    [" — ", " - "]
+        .iter()
+        .find_map(|separator| title.strip_suffix(&format!("{}{}", separator, app)))
+        .map(str::to_string)
+        .filter(|title| !title.is_empty())
+}
+
+fn comment(framework: ScriptFramework, text: &str) -> String {
+    let text = text.replace('\n', " ");
+    match framework {
+        ScriptFramework::Playwright => format!("// {}", text),
+        ScriptFramework::Selenium => format!("# {}", text),
+    }
+}
+
+fn title_assertion(framework: ScriptFramework, title: &str) -> String {
+    match framework {
+        ScriptFramework::Playwright => {
+            format!("await expect(page).toHaveTitle({});", js_string(title))
+        }
+        ScriptFramework::Selenium => format!("assert driver.title == {}", py_string(title)),
+    }
+}
+
+fn wait_for_load(framework: ScriptFramework, seconds: f64) -> String {
+    match framework {
+        ScriptFramework::Playwright => format!(
+            "await page.waitForLoadState('networkidle'); // Loading took {:.1}s when recorded",
+            seconds
+        ),
+        ScriptFramework::Selenium => format!(
+            "WebDriverWait(driver, {}).until(lambda d: d.execute_script(\"return document.readyState\") == \"complete\")  # Loading took {:.1}s when recorded",
+            (seconds * 2.0).ceil().max(10.0) as u64,
+            seconds
+        ),
+    }
+}
+
+fn action_lines(framework: ScriptFramework, action: &Action) -> Vec<String> {
+    match framework {
+        ScriptFramework::Playwright => playwright_lines(action),
+        ScriptFramework::Selenium => selenium_lines(action),
+    }
+}
+
+fn playwright_lines(action: &Action) -> Vec<String> {
+    match action {
+        Action::Click {
+            locator,
+            x,
+            y,
+            right,
+        } => {
+            let options = if *right { "{ button: 'right' }" } else { "" };
+            match locator {
+                Some(Locator {
+                    role: Some(role),
+                    name,
+                }) => vec![format!(
+                    "await page.getByRole('{}', {{ name: {} }}).click({});",
+                    role,
+                    js_string(name),
+                    options
+                )],
+                Some(Locator { role: None, name }) => vec![format!(
+                    "await page.getByText({}).click({});",
+                    js_string(name),
+                    options
+                )],
+                None => vec![
+                    "// TODO: replace the screen coordinates with a locator".to_string(),
+                    if *right {
+                        format!("await page.mouse.click({}, {}, {});", x, y, options)
+                    } else {
+                        format!("await page.mouse.click({}, {});", x, y)
+                    },
+                ],
+            }
+        }
+        Action::Back => vec!["await page.goBack();".to_string()],
+        Action::Forward => vec!["await page.goForward();".to_string()],
+        Action::Type(text) => vec![format!("await page.keyboard.type({});", js_string(text))],
+        Action::Press { modifiers, key } => {
+            let combo: Vec<String> = modifiers
+                .iter()
+                .cloned()
+                .chain(std::iter::once(playwright_key(key)))
+                .collect();
+            vec![format!("await page.keyboard.press('{}');", combo.join("+"))]
+        }
+        Action::Scroll(pixels) => vec![format!("await page.mouse.wheel(0, {});", pixels)],
+        Action::Credentials => vec![
+            "// TODO: enter credentials (not recorded), e.g., from environment variables"
+                .to_string(),
+        ],
+        Action::Comment => Vec::new(),
+    }
+}
+
+fn selenium_lines(action: &Action) -> Vec<String> {
+    match action {
+        Action::Click {
+            locator,
+            x,
+            y,
+            right,
+        } => match locator {
+            Some(locator) => {
+                let find = format!(
+                    "driver.find_element(By.XPATH, {})",
+                    py_string(&xpath(locator))
+                );
+                vec![if *right {
+                    format!("ActionChains(driver).context_click({}).perform()", find)
+                } else {
+                    format!("{}.click()", find)
+                }]
+            }
+            None => vec![
+                "# TODO: replace the screen coordinates with a locator".to_string(),
+                format!(
+                    "ActionChains(driver).move_to_element_with_offset(driver.find_element(By.TAG_NAME, \"body\"), {}, {}).{}().perform()",
+                    x,
+                    y,
+                    if *right { "context_click" } else { "click" }
+                ),
+            ],
+        },
+        Action::Back => vec!["driver.back()".to_string()],
+        Action::Forward => vec!["driver.forward()".to_string()],
+        Action::Type(text) => vec![format!(
+            "ActionChains(driver).send_keys({}).perform()",
+            py_string(text)
+        )],
+        Action::Press { modifiers, key } => {
+            let modifiers: Vec<&str> = modifiers
+                .iter()
+                .map(|modifier| selenium_modifier(modifier))
+                .collect();
+            let mut chain = String::from("ActionChains(driver)");
+            for modifier in &modifiers {
+                chain.push_str(&format!(".key_down({})", modifier));
+            }
+            chain.push_str(&format!(".send_keys({})", selenium_key(key)));
+            for modifier in modifiers.iter().rev() {
+                chain.push_str(&format!(".key_up({})", modifier));
+            }
+            chain.push_str(".perform()");
+            vec![chain]
+        }
+        Action::Scroll(pixels) => vec![format!(
+            "driver.execute_script(\"window.scrollBy(0, {})\")",
+            pixels
+        )],
+        Action::Credentials => vec![
+            "# TODO: enter credentials (not recorded), e.g., from environment variables"
+                .to_string(),
+        ],
+        Action::Comment => Vec::new(),
+    }
+}
+
+fn playwright_file(title: &str, body: &[String]) -> String {
+    let mut script = String::from("import { test, expect } from '@playwright/test';\n\n");
+    script.push_str(&format!(
+        "// Generated by FlowTrace from the recording \"{}\".\n",
+        title.replace('\n', " ")
+    ));
+    script.push_str(&format!(
+        "test({}, async ({{ page }}) => {{\n",
+        js_string(title)
+    ));
+    script.push_str("  // TODO: the recording has no URLs; open the page the workflow starts on\n");
+    script.push_str("  await page.goto('https://example.com');\n");
+    for line in body {
+        push_indented(&mut script, "  ", line);
+    }
+    script.push_str("});\n");
+    script
+}
+
+fn selenium_file(title: &str, body: &[String]) -> String {
+    let mut script = String::from(
+        "from selenium import webdriver\n\
+         from selenium.webdriver.common.action_chains import ActionChains\n\
+         from selenium.webdriver.common.by import By\n\
+         from selenium.webdriver.common.keys import Keys\n\
+         from selenium.webdriver.support.ui import WebDriverWait\n\n\n",
+    );
+    script.push_str(&format!(
+        "# Generated by FlowTrace from the recording \"{}\".\n",
+        title.replace('\n', " ")
+    ));
+    script.push_str(&format!("def test_{}():\n", python_identifier(title)));
+    script.push_str("    driver = webdriver.Chrome()\n");
+    script.push_str("    try:\n");
+    script.push_str(
+        "        # TODO: the recording has no URLs; open the page the workflow starts on\n",
+    );
+    script.push_str("        driver.get(\"https://example.com\")\n");
+    for line in body {
+        push_indented(&mut script, "        ", line);
+    }
+    script.push_str("    finally:\n");
+    script.push_str("        driver.quit()\n");
+    script
+}
+
+fn push_indented(script: &mut String, indent: &str, line: &str) {
+    if !line.is_empty() {
+        script.push_str(indent);
+        script.push_str(line);
+    }
+    script.push('\n');
+}
+
+/// Playwright key name of an `rdev` key (`"Return"` → `"Enter"`, `"Num1"` → `"Digit1"`).
+fn playwright_key(key: &str) -> String {
+    match key {
+        "Return" => "Enter".to_string(),
+        "UpArrow" => "ArrowUp".to_string(),
+        "DownArrow" => "ArrowDown".to_string(),
+        "LeftArrow" => "ArrowLeft".to_string(),
+        "RightArrow" => "ArrowRight".to_string(),
+        key => match key.strip_prefix("Num") {
+            Some(digit) if digit.len() == 1 => format!("Digit{}", digit),
+            _ => key.to_string(),
+        },
+    }
+}
+
+/// Selenium `Keys` constant or character of an `rdev` key.
+fn selenium_key(key: &str) -> String {
+    let constant = match key {
+        "Return" => "ENTER",
+        "Escape" => "ESCAPE",
+        "Tab" => "TAB",
+        "Backspace" => "BACKSPACE",
+        "Delete" => "DELETE",
+        "UpArrow" => "UP",
+        "DownArrow" => "DOWN",
+        "LeftArrow" => "LEFT",
+        "RightArrow" => "RIGHT",
+        "Home" => "HOME",
+        "End" => "END",
+        "PageUp" => "PAGE_UP",
+        "PageDown" => "PAGE_DOWN",
+        "Space" => "SPACE",
+        key if key.starts_with('F') && key[1..].parse::<u8>().is_ok() => key,
+        key => {
+            let character = key
+                .strip_prefix("Key")
+                .or_else(|| key.strip_prefix("Num"))
+                .unwrap_or(key);
+            return py_string(&character.to_lowercase());
+        }
+    };
+    format!("Keys.{}", constant)
+}
+
+fn selenium_modifier(modifier: &str) -> &'static str {
+    match modifier {
+        "Control" => "Keys.CONTROL",
+        "Meta" => "Keys.COMMAND",
+        "Alt" => "Keys.ALT",
+        _ => "Keys.SHIFT",
+    }
+}
+
+/// XPath of an element by tag and visible text or accessible name.
+fn xpath(locator: &Locator) -> String {
+    let tag = match locator.role {
+        Some("button") => "button",
+        Some("link") => "a",
+        Some("textbox") | Some("checkbox") | Some("radio") => "input",
+        Some("combobox") => "select",
+        _ => "*",
+    };
+    let name = xpath_literal(&locator.name);
+    format!(
+        "//{}[normalize-space()={} or @aria-label={} or @placeholder={}]",
+        tag, name, name, name
+    )
+}
+
+/// XPath string literal (XPath 1.0 has no escapes, hence `concat()`).
+fn xpath_literal(text: &str) -> String {
+    if !text.contains('\'') {
+        format!("'{}'", text)
+    } else if !text.contains('"') {
+        format!("\"{}\"", text)
+    } else {
+        let pieces: Vec<String> = text
+            .split('\'')
+            .map(|piece| format!("'{}'", piece))
+            .collect();
+        format!("concat({})", pieces.join(", \"'\", "))
+    }
+}
+
+fn js_string(text: &str) -> String {
+    format!(
+        "'{}'",
+        text.replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('\n', "\\n")
+    )
+}
+
+fn py_string(text: &str) -> String {
+    format!(
+        "\"{}\"",
+        text.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// `"Create an invoice"` → `"create_an_invoice"`.
+fn python_identifier(text: &str) -> String {
+    let identifier: String = text
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if identifier.is_empty() {
+        "recorded_workflow".to_string()
+    } else {
+        identifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Position, RecordingSession, UiElement};
+
+    #[test]
+    fn test_steps_become_locators_or_coordinate_todos() {
+        let mut session = RecordingSession::new("script-test".to_string());
+        let click = |element: Option<UiElement>| {
+            let mut event = Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(709.0, 328.0)),
+            );
+            event.app_name = Some("Google Chrome".to_string());
+            event.window_title = Some("Invoices — Google Chrome".to_string());
+            if let Some(element) = element {
+                event = event.with_element(element);
+            }
+            event
+        };
+        session.add_event(click(Some(UiElement {
+            role: Some("AXButton".to_string()),
+            label: Some("Save".to_string()),
+            value: None,
+        })));
+        session.add_event(Event::new(
+            EventType::Wait {
+                duration_seconds: 3.0,
+                cause: Some(WaitCause::Loading),
+            },
+            None,
+        ));
+        session.add_event(click(None));
+        session.add_event(Event::new(
+            EventType::Shortcut {
+                modifiers: vec!["Meta".to_string()],
+                key: "KeyS".to_string(),
+            },
+            None,
+        ));
+
+        let steps = steps::group_steps(&session);
+        let events: Vec<&Event> = session.events.iter().collect();
+
+        let playwright = render("Save invoice", &steps, &events, ScriptFramework::Playwright);
+        assert!(playwright.contains("test('Save invoice', async ({ page }) => {"));
+        assert!(playwright.contains("  await expect(page).toHaveTitle('Invoices');"));
+        assert!(playwright.contains("  await page.getByRole('button', { name: 'Save' }).click();"));
+        assert!(playwright.contains("  await page.waitForLoadState('networkidle');"));
+        assert!(playwright.contains("  // TODO: replace the screen coordinates with a locator\n  await page.mouse.click(709, 328);"));
+        assert!(playwright.contains("  await page.keyboard.press('Meta+KeyS');"));
+        // The title didn't change: asserted once
+        assert_eq!(playwright.matches("toHaveTitle").count(), 1);
+
+        let selenium = render("Save invoice", &steps, &events, ScriptFramework::Selenium);
+        assert!(selenium.contains("def test_save_invoice():"));
+        assert!(selenium.contains(
+            "        driver.find_element(By.XPATH, \"//button[normalize-space()='Save' or @aria-label='Save' or @placeholder='Save']\").click()"
+        ));
+        assert!(selenium.contains(".key_down(Keys.COMMAND).send_keys(\"s\").key_up(Keys.COMMAND)"));
+        assert_eq!(
+            xpath_literal("It's \"here\""),
+            "concat('It', \"'\", 's \"here\"')"
+        );
+    }
+}
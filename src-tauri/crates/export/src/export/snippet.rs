@@ -0,0 +1,294 @@
+//! # Snippet Export - One Step to Paste Anywhere
+//!
+//! Exporting a whole session to show one step in a chat or a ticket is
+//! overkill. `export_event()` writes just that step: its screenshot with the
+//! click marker burned in (see `annotation::draw_click_marker()`) and a
+//! caption, as Markdown or HTML.
+//!
+//! ```text
+//! recordings/[session-id]/snippets/event_[id].png    annotated screenshot
+//! recordings/[session-id]/snippets/event_[id].md     or .html
+//! ```
+//!
+//! ## Screenshot
+//! The event's full screen capture (reconstructed if it was a partial
+//! capture), with the marker at the click's normalized position. Events
+//! without a capture of their own (typing, shortcuts) show their step's
+//! screenshot without a marker; events outside any step (waits, markers) get
+//! a caption only. A configured watermark is stamped last (see `watermark`).
+//!
+//! ## Formats
+//! - **Markdown**: Image link next to the file, caption below
+//! - **HTML**: `<figure>` with the image embedded as a data URL, so the
+//!   snippet is self-contained when pasted into a rich-text editor
+
+use crate::annotation;
+use crate::export::{clean, watermark, ExportFormat};
+use crate::frame_diff;
+use crate::steps;
+use crate::storage;
+use crate::types::Event;
+use base64::Engine;
+use image::DynamicImage;
+use serde::Serialize;
+use std::fs;
+
+/// A written one-step snippet.
+#[derive(Serialize, Debug, Clone)]
+pub struct EventSnippet {
+    pub session_id: String,
+    pub event_id: String,
+    /// Step the event belongs to (see `steps` module)
+    pub step_number: Option<usize>,
+    /// Caption as plain text
+    pub caption: String,
+    /// Snippet text, ready to copy
+    pub document: String,
+    /// Path of the written `.md` / `.html` file
+    pub path: String,
+    /// Path of the annotated screenshot (`None` for caption-only snippets)
+    pub image_path: Option<String>,
+}
+
+/// Caption parts of a snippet.
+struct Caption<'a> {
+    step_number: Option<usize>,
+    text: &'a str,
+    window_title: Option<&'a str>,
+}
+
+/// Writes the snippet of one event of a saved session (see module docs).
+///
+/// # Arguments
+/// * `session_id` - Saved session
+/// * `event_id` - Event to export
+/// * `format` - `Markdown` or `Html`
+///
+/// # Returns
+/// * `Ok(EventSnippet)` - Snippet text and written files
+/// * `Err(String)` - Error if the format isn't supported, the event doesn't
+///   exist, or the files couldn't be written
+pub fn export_event(
+    session_id: &str,
+    event_id: &str,
+    format: ExportFormat,
+) -> Result<EventSnippet, String> {
+    let extension = match format {
+        ExportFormat::Markdown => "md",
+        ExportFormat::Html => "html",
+        other => return Err(format!("{:?} isn't available for single events", other)),
+    };
+    let session = storage::load_session(session_id)?;
+    let event = session
+        .events
+        .iter()
+        .find(|event| event.has_id(event_id))
+        .ok_or_else(|| format!("Event {} not found in session", event_id))?;
+    let step = steps::group_steps(&session)
+        .into_iter()
+        .find(|step| step.event_ids.contains(&event.id));
+
+    let snippet_dir = storage::get_session_dir(session_id).join("snippets");
+    fs::create_dir_all(&snippet_dir)
+        .map_err(|e| format!("Failed to create snippet directory: {:?}", e))?;
+
+    let image = annotated_image(event, step.as_ref())?;
+    let image = match (image, watermark::current()?) {
+        (Some(image), Some(watermark)) => {
+            let mut image = image.into_rgba8();
+            watermark.apply(&mut image);
+            Some(DynamicImage::ImageRgba8(image))
+        }
+        (image, _) => image,
+    };
+    let image_filename = format!("event_{}.png", event_id);
+    let png = match &image {
+        Some(image) => {
+            let png = clean::encode_png(image)?;
+            fs::write(snippet_dir.join(&image_filename), &png)
+                .map_err(|e| format!("Failed to write snippet image: {:?}", e))?;
+            Some(png)
+        }
+        None => None,
+    };
+
+    let description = event.description();
+    let caption = Caption {
+        step_number: step.as_ref().map(|step| step.number),
+        text: step
+            .as_ref()
+            .map_or(description.as_ref(), |step| step.title.as_str()),
+        window_title: step
+            .as_ref()
+            .and_then(|step| step.window_title.as_deref())
+            .or(event.window_title.as_deref()),
+    };
+    let image_src = png.map(|png| match format {
+        ExportFormat::Html => format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(png)
+        ),
+        _ => image_filename.clone(),
+    });
+    let document = render(format, &caption, image_src.as_deref());
+
+    let path = snippet_dir.join(format!("event_{}.{}", event_id, extension));
+    fs::write(&path, &document).map_err(|e| format!("Failed to write snippet: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!("✂️  Snippet of event {} written to {:?}", event_id, path);
+
+    Ok(EventSnippet {
+        session_id: session_id.to_string(),
+        event_id: event_id.to_string(),
+        step_number: caption.step_number,
+        caption: plain_caption(&caption),
+        document,
+        path: path.display().to_string(),
+        image_path: image.map(|_| {
+            storage::session_asset_path(session_id, &format!("snippets/{}", image_filename))
+        }),
+    })
+}
+
+/// The event's screenshot with its click marker, else its step's screenshot.
+fn annotated_image(
+    event: &Event,
+    step: Option<&steps::Step>,
+) -> Result<Option<DynamicImage>, String> {
+    let own = match frame_diff::reconstruct(&event.screenshots)? {
+        Some(frame) => Some(frame),
+        None => match &event.screenshots.full_screen {
+            Some(path) => Some(read(path)?),
+            None => None,
+        },
+    };
+    let Some(frame) = own else {
+        let shared = step.and_then(|step| step.screenshot.as_deref());
+        return shared.map(read).transpose();
+    };
+
+    let mut frame = frame.into_rgba8();
+    let normalized = event
+        .position
+        .as_ref()
+        .and_then(|position| position.normalized.as_ref());
+    if let (Some(normalized), Some(colors)) = (normalized, &event.annotation_colors) {
+        let x = (normalized.x * frame.width() as f64) as u32;
+        let y = (normalized.y * frame.height() as f64) as u32;
+        // About 18 logical px on a 1440px-wide display
+        let radius = (frame.width() / 80).max(12);
+        annotation::draw_click_marker(&mut frame, x, y, radius, colors);
+    }
+    Ok(Some(DynamicImage::ImageRgba8(frame)))
+}
+
+fn read(path: &str) -> Result<DynamicImage, String> {
+    image::open(storage::resolve_asset_path(path))
+        .map_err(|e| format!("Failed to read {}: {:?}", path, e))
+}
+
+/// Renders the snippet text; `image_src` is the link or data URL of the image.
+fn render(format: ExportFormat, caption: &Caption, image_src: Option<&str>) -> String {
+    let alt = caption.step_number.map_or_else(
+        || "Screenshot".to_string(),
+        |number| format!("Step {}", number),
+    );
+
+    match format {
+        ExportFormat::Html => {
+            let mut html = String::from("<figure>\n");
+            if let Some(src) = image_src {
+                html.push_str(&format!(
+                    "  <img src=\"{}\" alt=\"{}\">\n",
+                    escape_html(src),
+                    alt
+                ));
+            }
+            html.push_str("  <figcaption>");
+            if let Some(number) = caption.step_number {
+                html.push_str(&format!("<strong>Step {}.</strong> ", number));
+            }
+            html.push_str(&escape_html(caption.text));
+            if let Some(window_title) = caption.window_title {
+                html.push_str(&format!(" — <em>{}</em>", escape_html(window_title)));
+            }
+            html.push_str("</figcaption>\n</figure>\n");
+            html
+        }
+        _ => {
+            let mut markdown = String::new();
+            if let Some(src) = image_src {
+                markdown.push_str(&format!("![{}]({})\n\n", alt, src));
+            }
+            if let Some(number) = caption.step_number {
+                markdown.push_str(&format!("**Step {}.** ", number));
+            }
+            markdown.push_str(caption.text);
+            if let Some(window_title) = caption.window_title {
+                markdown.push_str(&format!(" — *{}*", window_title));
+            }
+            markdown.push('\n');
+            markdown
+        }
+    }
+}
+
+fn plain_caption(caption: &Caption) -> String {
+    let mut text = match caption.step_number {
+        Some(number) => format!("Step {}. {}", number, caption.text),
+        None => caption.text.to_string(),
+    };
+    if let Some(window_title) = caption.window_title {
+        text.push_str(&format!(" — {}", window_title));
+    }
+    text
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_and_html_snippets() {
+        let caption = Caption {
+            step_number: Some(3),
+            text: "Clicked left button at position (709, 328)",
+            window_title: Some("Invoice <#1234>"),
+        };
+
+        assert_eq!(
+            render(ExportFormat::Markdown, &caption, Some("event_e1.png")),
+            "![Step 3](event_e1.png)\n\n**Step 3.** Clicked left button at position (709, 328) — *Invoice <#1234>*\n"
+        );
+        let html = render(
+            ExportFormat::Html,
+            &caption,
+            Some("data:image/png;base64,AAAA"),
+        );
+        assert!(html.contains("<img src=\"data:image/png;base64,AAAA\" alt=\"Step 3\">"));
+        assert!(html.contains("<em>Invoice &lt;#1234&gt;</em>"));
+
+        // Outside any step: caption only
+        let wait = Caption {
+            step_number: None,
+            text: "Paused for 4.0 seconds",
+            window_title: None,
+        };
+        assert_eq!(
+            render(ExportFormat::Markdown, &wait, None),
+            "Paused for 4.0 seconds\n"
+        );
+        assert_eq!(
+            plain_caption(&caption),
+            "Step 3. Clicked left button at position (709, 328) — Invoice <#1234>"
+        );
+    }
+}
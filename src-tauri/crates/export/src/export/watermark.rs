@@ -0,0 +1,433 @@
+//! # Watermark Export - Marking Screenshots That Leave the App
+//!
+//! Screenshots shared outside the company may have to carry a notice
+//! ("Confidential - Example Corp") or a logo. With `watermark.enabled` in
+//! the recorder config, every exporter that writes images stamps them; the
+//! recorded screenshots stay as they were, so the watermark can change
+//! without re-recording.
+//!
+//! ## Where It Applies
+//! - `snippet`: the annotated screenshot (file and embedded data URL)
+//! - `checklist` / `redline` Markdown and `presets`: watermarked copies are
+//!   written next to the document (`[document].images/[session-id]_[file]`,
+//!   without metadata, see `clean`) and linked instead of the originals
+//!
+//! An export fails rather than linking an unmarked screenshot (e.g., if the
+//! logo or a screenshot can't be read).
+//!
+//! ## Layout
+//! The logo (scaled to the watermark height) and the text on a translucent
+//! dark plate sit side by side in the configured corner, `size` x image
+//! height tall, with `opacity` applied to both. Text is drawn with a built-in
+//! 5x7 pixel font: letters (as capitals), digits, and common punctuation;
+//! other characters show as `?`.
+
+use crate::config::{self, WatermarkConfig, WatermarkPosition};
+use crate::export::clean;
+use crate::steps::Step;
+use crate::storage;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, Rgba, RgbaImage};
+use std::fs;
+use std::path::Path;
+
+/// Glyph cell of the built-in font.
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+/// A loaded watermark, ready to stamp.
+pub struct Watermark {
+    text: Option<String>,
+    logo: Option<RgbaImage>,
+    position: WatermarkPosition,
+    opacity: f32,
+    size: f32,
+}
+
+impl Watermark {
+    /// Loads the configured watermark.
+    ///
+    /// # Returns
+    /// * `Ok(Some(Watermark))` - Watermark to stamp
+    /// * `Ok(None)` - Watermarking is disabled
+    /// * `Err(String)` - Error if the settings are invalid or the logo can't be read
+    pub fn load(config: &WatermarkConfig) -> Result<Option<Self>, String> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        config.validate()?;
+
+        let logo = match config.logo_path.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => Some(
+                image::open(path)
+                    .map_err(|e| format!("Failed to read watermark logo {}: {:?}", path, e))?
+                    .into_rgba8(),
+            ),
+            _ => None,
+        };
+        Ok(Some(Self {
+            text: config
+                .text
+                .as_deref()
+                .map(|text| text.trim().replace('©', "(C)").to_uppercase())
+                .filter(|text| !text.is_empty()),
+            logo,
+            position: config.position,
+            opacity: config.opacity,
+            size: config.size,
+        }))
+    }
+
+    /// Stamps the watermark onto an image (see module docs).
+    pub fn apply(&self, image: &mut RgbaImage) {
+        let (width, height) = image.dimensions();
+        let target_height = ((height as f32 * self.size).round() as u32)
+            .max(GLYPH_HEIGHT + 2)
+            .min(height);
+        let margin = target_height / 2;
+
+        let logo = self.logo.as_ref().map(|logo| {
+            let logo_width = (logo.width() * target_height / logo.height().max(1)).max(1);
+            imageops::resize(logo, logo_width, target_height, FilterType::Triangle)
+        });
+        // Plate: glyphs with one blank cell between them and around the text
+        let scale = (target_height / (GLYPH_HEIGHT + 2)).max(1);
+        let text_size = self.text.as_ref().map(|text| {
+            let columns = text.chars().count() as u32 * (GLYPH_WIDTH + 1) + 1;
+            (columns * scale, (GLYPH_HEIGHT + 2) * scale)
+        });
+
+        let logo_width = logo.as_ref().map_or(0, |logo| logo.width());
+        let gap = if logo.is_some() && text_size.is_some() {
+            margin / 2
+        } else {
+            0
+        };
+        let (plate_width, plate_height) = text_size.unwrap_or((0, 0));
+        let block_width = logo_width + gap + plate_width;
+        let block_height = target_height.max(plate_height);
+
+        let left = match self.position {
+            WatermarkPosition::TopLeft | WatermarkPosition::BottomLeft => margin,
+            _ => width.saturating_sub(block_width + margin),
+        };
+        let top = match self.position {
+            WatermarkPosition::TopLeft | WatermarkPosition::TopRight => margin,
+            _ => height.saturating_sub(block_height + margin),
+        };
+
+        if let Some(logo) = &logo {
+            let logo_top = top + (block_height - logo.height()) / 2;
+            for (x, y, pixel) in logo.enumerate_pixels() {
+                let alpha = self.opacity * pixel[3] as f32 / 255.0;
+                blend(
+                    image,
+                    left + x,
+                    logo_top + y,
+                    [pixel[0], pixel[1], pixel[2]],
+                    alpha,
+                );
+            }
+        }
+        if let Some(text) = &self.text {
+            let plate_left = left + logo_width + gap;
+            let plate_top = top + (block_height - plate_height) / 2;
+            for y in 0..plate_height {
+                for x in 0..plate_width {
+                    blend(
+                        image,
+                        plate_left + x,
+                        plate_top + y,
+                        [0, 0, 0],
+                        self.opacity * 0.5,
+                    );
+                }
+            }
+            for (index, c) in text.chars().enumerate() {
+                let glyph_left = plate_left + (index as u32 * (GLYPH_WIDTH + 1) + 1) * scale;
+                for (row, bits) in glyph(c).iter().enumerate() {
+                    for column in 0..GLYPH_WIDTH {
+                        if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                            continue;
+                        }
+                        let cell_left = glyph_left + column * scale;
+                        let cell_top = plate_top + (row as u32 + 1) * scale;
+                        for dy in 0..scale {
+                            for dx in 0..scale {
+                                blend(
+                                    image,
+                                    cell_left + dx,
+                                    cell_top + dy,
+                                    [0xFF, 0xFF, 0xFF],
+                                    self.opacity,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The configured watermark, if watermarking is enabled.
+///
+/// # Returns
+/// * `Ok(Option<Watermark>)` - Watermark to stamp (`None` = disabled)
+/// * `Err(String)` - Error if the logo can't be read
+pub fn current() -> Result<Option<Watermark>, String> {
+    Watermark::load(&config::watermark())
+}
+
+/// Writes watermarked copies of step screenshots into `image_dir` and links those.
+///
+/// # Returns
+/// * `Ok(())` - Every screenshot of the steps now points at a stamped copy
+/// * `Err(String)` - Error if a screenshot can't be read or a copy written
+pub fn stamp_steps<'a>(
+    steps: impl IntoIterator<Item = &'a mut Step>,
+    image_dir: &Path,
+    watermark: &Watermark,
+) -> Result<(), String> {
+    fs::create_dir_all(image_dir)
+        .map_err(|e| format!("Failed to create image directory: {:?}", e))?;
+
+    for step in steps {
+        for screenshot in [&mut step.screenshot, &mut step.detail_screenshot]
+            .into_iter()
+            .flatten()
+        {
+            let source = storage::resolve_asset_path(screenshot);
+            let Some(target) = clean::copy_target(&source, image_dir) else {
+                continue;
+            };
+            let mut image = image::open(&source)
+                .map_err(|e| format!("Failed to read screenshot {}: {:?}", screenshot, e))?
+                .into_rgba8();
+            watermark.apply(&mut image);
+            clean::save_png(&DynamicImage::ImageRgba8(image), &target)?;
+            *screenshot = target.display().to_string();
+        }
+    }
+    Ok(())
+}
+
+/// Mixes a color into one pixel (ignored outside the image).
+fn blend(image: &mut RgbaImage, x: u32, y: u32, color: [u8; 3], alpha: f32) {
+    if x >= image.width() || y >= image.height() {
+        return;
+    }
+    let pixel = image.get_pixel_mut(x, y);
+    let alpha = alpha.clamp(0.0, 1.0);
+    let mix =
+        |under: u8, over: u8| (over as f32 * alpha + under as f32 * (1.0 - alpha)).round() as u8;
+    *pixel = Rgba([
+        mix(pixel[0], color[0]),
+        mix(pixel[1], color[1]),
+        mix(pixel[2], color[2]),
+        pixel[3],
+    ]);
+}
+
+/// Rows of a character in the built-in font (bit 4 = leftmost column).
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0; 7],
+        'A' => [
+            0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        'B' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110,
+        ],
+        'C' => [
+            0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110,
+        ],
+        'D' => [
+            0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100,
+        ],
+        'E' => [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111,
+        ],
+        'F' => [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+        'G' => [
+            0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111,
+        ],
+        'H' => [
+            0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        'I' => [
+            0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        'J' => [
+            0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100,
+        ],
+        'K' => [
+            0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001,
+        ],
+        'L' => [
+            0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111,
+        ],
+        'M' => [
+            0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001,
+        ],
+        'N' => [
+            0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001,
+        ],
+        'O' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'P' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+        'Q' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101,
+        ],
+        'R' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001,
+        ],
+        'S' => [
+            0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110,
+        ],
+        'T' => [
+            0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100,
+        ],
+        'U' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'V' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100,
+        ],
+        'W' => [
+            0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010,
+        ],
+        'X' => [
+            0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001,
+        ],
+        'Y' => [
+            0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100,
+        ],
+        'Z' => [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111,
+        ],
+        '0' => [
+            0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110,
+        ],
+        '1' => [
+            0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        '2' => [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111,
+        ],
+        '3' => [
+            0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110,
+        ],
+        '4' => [
+            0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010,
+        ],
+        '5' => [
+            0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110,
+        ],
+        '6' => [
+            0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110,
+        ],
+        '7' => [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000,
+        ],
+        '8' => [
+            0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110,
+        ],
+        '9' => [
+            0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100,
+        ],
+        '.' => [
+            0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100,
+        ],
+        ',' => [
+            0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000,
+        ],
+        ':' => [
+            0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000,
+        ],
+        '-' => [
+            0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000,
+        ],
+        '_' => [
+            0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111,
+        ],
+        '/' => [
+            0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000,
+        ],
+        '(' => [
+            0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010,
+        ],
+        ')' => [
+            0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000,
+        ],
+        '&' => [
+            0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101,
+        ],
+        '@' => [
+            0b01110, 0b10001, 0b00001, 0b01101, 0b10101, 0b10101, 0b01110,
+        ],
+        '#' => [
+            0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010,
+        ],
+        '+' => [
+            0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000,
+        ],
+        '!' => [
+            0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100,
+        ],
+        '\'' => [
+            0b01100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000,
+        ],
+        _ => [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100,
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_is_stamped_in_the_configured_corner() {
+        let config = WatermarkConfig {
+            enabled: true,
+            text: Some("Confidential".to_string()),
+            opacity: 1.0,
+            size: 0.1,
+            ..WatermarkConfig::default()
+        };
+        let watermark = Watermark::load(&config).unwrap().unwrap();
+        assert_eq!(watermark.text.as_deref(), Some("CONFIDENTIAL"));
+
+        let white = Rgba([0xFF, 0xFF, 0xFF, 0xFF]);
+        let mut image = RgbaImage::from_pixel(400, 200, white);
+        watermark.apply(&mut image);
+
+        // Bottom right: the plate darkens pixels there, the rest is untouched
+        let (half_width, half_height) = (image.width() / 2, image.height() / 2);
+        let changed: Vec<(u32, u32)> = image
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| **pixel != white)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert!(!changed.is_empty());
+        assert!(changed
+            .iter()
+            .all(|(x, y)| *x >= half_width && *y >= half_height));
+
+        assert!(Watermark::load(&WatermarkConfig::default())
+            .unwrap()
+            .is_none());
+        let empty = WatermarkConfig {
+            enabled: true,
+            ..WatermarkConfig::default()
+        };
+        assert!(Watermark::load(&empty).is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! # Importer Module - Session Archive Import
+//!
+//! Brings recordings produced on another machine (or exported earlier) back
+//! into the local recordings directory.
+//!
+//! ## Supported Sources
+//! - **Session folder**: A directory containing `session.json` and screenshots
+//! - **Session archive**: A `.flowtrace.zip` file with the same contents
+//!
+//! ## Import Pipeline
+//! ```text
+//! Dropped path → [Extract ZIP] → Validate session.json → Resolve id conflict
+//!              → Copy files → Rewrite screenshot paths → session.json
+//! ```
+//!
+//! ## Untrusted Input
+//! Recordings arrive from colleagues and the internet, so nothing in them is
+//! trusted:
+//! - `session.json` is size-limited (`storage::MAX_SESSION_FILE_BYTES`)
+//! - Session ids must be plain identifiers (`storage::is_valid_session_id`)
+//! - Screenshot paths are reduced to their file name
+//! - Archive entries must stay inside the extraction directory, and entry
+//!   count and extracted size are capped (zip bombs fail instead of filling the disk)
+//! - Symlinks in session folders are never followed or copied
+//! - Sessions must pass the `integrity` checks (missing screenshots, invalid
+//!   or oversized durations, newer schema versions are rejected)
+//!
+//! Fuzz targets for the parser and the extractor live in `src-tauri/fuzz`
+//! (`cargo fuzz run session_json`, `cargo fuzz run session_archive`).
+//!
+//! ## Conflict Handling
+//! Session ids are UUIDs, so a duplicate id almost always means the same
+//! recording is being imported twice:
+//! - **Same recording** (identical `started_at` and event count): skipped
+//! - **Different recording**: imported under a freshly generated session id
+
+use crate::integrity;
+use crate::storage;
+use crate::types::RecordingSession;
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+
+/// File extension used for exported session archives.
+pub const ARCHIVE_EXTENSION: &str = ".flowtrace.zip";
+
+/// Most entries an archive may contain (a session has ~3 per click).
+const MAX_ARCHIVE_ENTRIES: usize = 50_000;
+
+/// Largest single extracted file (screenshots are ~2-10 MB).
+const MAX_ENTRY_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Largest total size an archive may extract to.
+const MAX_EXTRACTED_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Result of importing a single dropped path.
+#[derive(Serialize, Debug, Clone)]
+pub struct ImportOutcome {
+    /// Session id the recording is stored under after import
+    pub session_id: String,
+    /// Session id found in the imported `session.json`
+    pub original_session_id: String,
+    pub status: ImportStatus,
+    pub event_count: usize,
+}
+
+/// How an import was resolved.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    /// Imported under its original session id
+    Imported,
+    /// Session id already existed with different content; imported under a new id
+    Renamed,
+    /// Identical session already present; nothing was copied
+    AlreadyPresent,
+}
+
+/// Progress update emitted while an import is running.
+///
+/// Sent to the frontend as the `import-progress` Tauri event.
+#[derive(Serialize, Debug, Clone)]
+pub struct ImportProgress {
+    pub source: String,
+    pub stage: String,
+    pub files_copied: usize,
+    pub files_total: usize,
+}
+
+/// Imports a session folder or `.flowtrace.zip` archive into the recordings directory.
+///
+/// # Arguments
+/// * `source` - Dropped path (directory or archive)
+/// * `on_progress` - Callback invoked at each pipeline stage and after every copied file
+///
+/// # Returns
+/// * `Ok(ImportOutcome)` - Final session id and how conflicts were resolved
+/// * `Err(String)` - Error if the source is not a valid session
+///
+/// # Cleanup
+/// Archives are extracted into a temporary `.import-[uuid]` directory inside
+/// the recordings root, which is removed whether or not the import succeeds.
+pub fn import_path<F>(source: &Path, mut on_progress: F) -> Result<ImportOutcome, String>
+where
+    F: FnMut(ImportProgress),
+{
+    let source_label = source.display().to_string();
+    let mut report = |stage: &str, files_copied: usize, files_total: usize| {
+        on_progress(ImportProgress {
+            source: source_label.clone(),
+            stage: stage.to_string(),
+            files_copied,
+            files_total,
+        })
+    };
+
+    if source.is_dir() {
+        report("validating", 0, 0);
+        return import_session_dir(source, &mut report);
+    }
+
+    if !is_archive(source) {
+        return Err(format!(
+            "Unsupported import source (expected a session folder or {} file): {}",
+            ARCHIVE_EXTENSION,
+            source.display()
+        ));
+    }
+
+    report("extracting", 0, 0);
+    let temp_dir = storage::recordings_root().join(format!(".import-{}", uuid::Uuid::new_v4()));
+    let result = extract_archive(source, &temp_dir).and_then(|extracted| {
+        report("validating", 0, 0);
+        import_session_dir(&extracted, &mut report)
+    });
+
+    // Always remove the extraction directory, even on failure
+    let _ = fs::remove_dir_all(&temp_dir);
+
+    result
+}
+
+/// Returns `true` if the path looks like a session archive.
+pub fn is_archive(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| {
+            let name = name.to_lowercase();
+            name.ends_with(ARCHIVE_EXTENSION) || name.ends_with(".zip")
+        })
+        .unwrap_or(false)
+}
+
+/// Validates a session directory and copies it into the recordings root.
+fn import_session_dir<F>(source_dir: &Path, report: &mut F) -> Result<ImportOutcome, String>
+where
+    F: FnMut(&str, usize, usize),
+{
+    let mut session = storage::read_session_file(&source_dir.join("session.json"))?;
+    validate_for_import(&session, source_dir)?;
+
+    let original_session_id = session.session_id.clone();
+    let event_count = session.events.len();
+
+    // Resolve duplicate session ids
+    let mut status = ImportStatus::Imported;
+    if storage::get_session_dir(&session.session_id).exists() {
+        match storage::load_session(&session.session_id) {
+            Ok(existing) if is_same_recording(&existing, &session) => {
+                report("done", 0, 0);
+                return Ok(ImportOutcome {
+                    session_id: original_session_id.clone(),
+                    original_session_id,
+                    status: ImportStatus::AlreadyPresent,
+                    event_count,
+                });
+            }
+            _ => {
+                session.session_id = uuid::Uuid::new_v4().to_string();
+                status = ImportStatus::Renamed;
+            }
+        }
+    }
+
+    // Copy every file except session.json, which is rewritten below
+    let files = collect_files(source_dir)?;
+    let files_total = files.len();
+    let target_dir = storage::get_session_dir(&session.session_id);
+    let mut copy_into_place = || -> Result<(), String> {
+        fs::create_dir_all(&target_dir)
+            .map_err(|e| format!("Failed to create session directory: {:?}", e))?;
+
+        report("copying", 0, files_total);
+        for (index, relative) in files.iter().enumerate() {
+            let destination = target_dir.join(relative);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directory {:?}: {:?}", parent, e))?;
+            }
+            fs::copy(source_dir.join(relative), &destination)
+                .map_err(|e| format!("Failed to copy {:?}: {:?}", relative, e))?;
+            report("copying", index + 1, files_total);
+        }
+
+        // Point screenshot paths at the new location
+        rewrite_screenshot_paths(&mut session);
+        storage::save_session(&session)?;
+        Ok(())
+    };
+
+    // The target directory is always new (duplicates were renamed above), so
+    // a failed copy leaves nothing behind as a half-imported session
+    if let Err(e) = copy_into_place() {
+        let _ = fs::remove_dir_all(&target_dir);
+        return Err(e);
+    }
+
+    report("done", files_total, files_total);
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📥 Imported session {} ({} events, {:?})",
+        session.session_id, event_count, status
+    );
+
+    Ok(ImportOutcome {
+        session_id: session.session_id,
+        original_session_id,
+        status,
+        event_count,
+    })
+}
+
+/// Rejects sessions that can't be safely imported.
+///
+/// # Checks
+/// - Session id is a plain identifier that can't escape the recordings directory
+/// - The session passes `integrity::validate()`: every referenced screenshot
+///   exists in the source directory, durations and the schema version are valid
+fn validate_for_import(session: &RecordingSession, source_dir: &Path) -> Result<(), String> {
+    let id = &session.session_id;
+    if !storage::is_valid_session_id(id) {
+        return Err(format!("Invalid session id in session.json: {:?}", id));
+    }
+
+    integrity::validate(session, |path| {
+        asset_file_name(path).is_ok_and(|filename| source_dir.join(filename).is_file())
+    })
+    .into_result()?;
+
+    Ok(())
+}
+
+/// Two sessions are treated as the same recording if they started at the
+/// same instant and contain the same number of events.
+fn is_same_recording(a: &RecordingSession, b: &RecordingSession) -> bool {
+    a.started_at == b.started_at && a.events.len() == b.events.len()
+}
+
+/// Rewrites every screenshot path so it points into the session's own directory.
+fn rewrite_screenshot_paths(session: &mut RecordingSession) {
+    let session_id = session.session_id.clone();
+    let rewrite = |path: &mut Option<String>| {
+        if let Some(old) = path.as_ref() {
+            if let Ok(filename) = asset_file_name(old) {
+                *path = Some(storage::session_asset_path(&session_id, &filename));
+            }
+        }
+    };
+
+    for event in session.events.iter_mut() {
+        rewrite(&mut event.screenshots.full_screen);
+        rewrite(&mut event.screenshots.window_crop);
+        rewrite(&mut event.screenshots.click_crop);
+        if let Some(patch) = event.screenshots.full_screen_patch.as_mut() {
+            if let Ok(filename) = asset_file_name(&patch.base) {
+                patch.base = storage::session_asset_path(&session_id, &filename);
+            }
+        }
+    }
+}
+
+/// Extracts the file name from a stored screenshot path.
+fn asset_file_name(path: &str) -> Result<String, String> {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.to_string())
+        .ok_or_else(|| format!("Invalid screenshot path: {:?}", path))
+}
+
+/// Lists all files below `dir` (relative paths), excluding the top-level `session.json`.
+fn collect_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(relative_dir) = pending.pop() {
+        let entries = fs::read_dir(dir.join(&relative_dir))
+            .map_err(|e| format!("Failed to read directory: {:?}", e))?;
+
+        for entry in entries.flatten() {
+            let relative = relative_dir.join(entry.file_name());
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(_) => continue,
+            };
+
+            if file_type.is_dir() {
+                pending.push(relative);
+            } else if file_type.is_file() && relative != Path::new("session.json") {
+                files.push(relative);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Extracts an archive and returns the directory that contains `session.json`.
+///
+/// Archives may hold the session files at the root or inside a single
+/// top-level folder (e.g., `[session-id]/session.json`).
+///
+/// # Security
+/// Entry names are sanitized with `enclosed_name()`, so entries like
+/// `../../etc/passwd` are rejected rather than written outside `destination`.
+/// Entry count and sizes are capped (`MAX_ARCHIVE_ENTRIES`, `MAX_ENTRY_BYTES`,
+/// `MAX_EXTRACTED_BYTES`); sizes are enforced on the bytes actually written,
+/// not the sizes the archive claims.
+fn extract_archive(archive_path: &Path, destination: &Path) -> Result<PathBuf, String> {
+    let file =
+        fs::File::open(archive_path).map_err(|e| format!("Failed to open archive: {:?}", e))?;
+    extract_archive_from(file, destination)
+}
+
+/// Extracts an archive from any seekable reader (see `extract_archive()`).
+pub fn extract_archive_from<R: Read + Seek>(
+    reader: R,
+    destination: &Path,
+) -> Result<PathBuf, String> {
+    let mut archive =
+        zip::ZipArchive::new(reader).map_err(|e| format!("Failed to read archive: {:?}", e))?;
+    if archive.len() > MAX_ARCHIVE_ENTRIES {
+        return Err(format!(
+            "Archive has too many entries ({}, limit {})",
+            archive.len(),
+            MAX_ARCHIVE_ENTRIES
+        ));
+    }
+
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create extraction directory: {:?}", e))?;
+
+    let mut extracted_bytes: u64 = 0;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read archive entry: {:?}", e))?;
+
+        let relative = entry
+            .enclosed_name()
+            .ok_or_else(|| format!("Archive contains unsafe path: {:?}", entry.name()))?;
+        let output_path = destination.join(relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&output_path)
+                .map_err(|e| format!("Failed to create directory: {:?}", e))?;
+            continue;
+        }
+
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {:?}", e))?;
+        }
+        if entry.size() > MAX_ENTRY_BYTES {
+            return Err(format!(
+                "Archive entry {:?} is too large ({} bytes)",
+                entry.name(),
+                entry.size()
+            ));
+        }
+        let mut output = fs::File::create(&output_path)
+            .map_err(|e| format!("Failed to create extracted file: {:?}", e))?;
+        // One byte past the limit tells an oversized entry from one exactly at it
+        let written = std::io::copy(&mut (&mut entry).take(MAX_ENTRY_BYTES + 1), &mut output)
+            .map_err(|e| format!("Failed to extract archive entry: {:?}", e))?;
+        extracted_bytes += written;
+        if written > MAX_ENTRY_BYTES || extracted_bytes > MAX_EXTRACTED_BYTES {
+            return Err(format!(
+                "Archive expands beyond the size limit (at entry {:?})",
+                entry.name()
+            ));
+        }
+    }
+
+    if destination.join("session.json").is_file() {
+        return Ok(destination.to_path_buf());
+    }
+
+    // Fall back to a single top-level folder containing session.json
+    let candidates: Vec<PathBuf> = fs::read_dir(destination)
+        .map_err(|e| format!("Failed to read extraction directory: {:?}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("session.json").is_file())
+        .collect();
+
+    match candidates.as_slice() {
+        [single] => Ok(single.clone()),
+        [] => Err("Archive does not contain a session.json".to_string()),
+        _ => Err("Archive contains more than one session".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, FramePatch, MouseButton, Position};
+
+    /// Source directory holding the given screenshot files.
+    fn source_dir_with(files: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("flowtrace-import-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        for file in files {
+            fs::write(dir.join(file), b"png").unwrap();
+        }
+        dir
+    }
+
+    fn click_with_full_screen(path: &str) -> Event {
+        Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(10.0, 20.0)),
+        )
+        .with_screenshots(Some(path.to_string()), None, None)
+    }
+
+    #[test]
+    fn test_rejects_unsafe_session_ids() {
+        let source_dir = std::env::temp_dir();
+        let too_long = "a".repeat(129);
+
+        for id in [
+            "",
+            "..",
+            "../escape",
+            "nested/id",
+            "C:\\temp",
+            too_long.as_str(),
+        ] {
+            let session = RecordingSession::new(id.to_string());
+            assert!(
+                validate_for_import(&session, &source_dir).is_err(),
+                "accepted {:?}",
+                id
+            );
+        }
+
+        let session = RecordingSession::new("f2e904d2-286e-484c-83e8-5949bd8697f1".to_string());
+        assert!(validate_for_import(&session, &source_dir).is_ok());
+    }
+
+    #[test]
+    fn test_asset_paths_cannot_escape_the_session() {
+        let source_dir = source_dir_with(&["event_a_full.png"]);
+        let session_id = "f2e904d2-286e-484c-83e8-5949bd8697f1";
+
+        // Only the file name counts, and it must exist in the source
+        let mut session = RecordingSession::new(session_id.to_string());
+        session.add_event(click_with_full_screen("../../../../etc/passwd"));
+        assert!(validate_for_import(&session, &source_dir).is_err());
+
+        let mut session = RecordingSession::new(session_id.to_string());
+        session.add_event(click_with_full_screen(
+            "../../other-session/event_a_full.png",
+        ));
+        assert!(validate_for_import(&session, &source_dir).is_ok());
+
+        rewrite_screenshot_paths(&mut session);
+        assert_eq!(
+            session.events[0].screenshots.full_screen.as_deref(),
+            Some(storage::session_asset_path(session_id, "event_a_full.png").as_str())
+        );
+
+        let _ = fs::remove_dir_all(&source_dir);
+    }
+
+    #[test]
+    fn test_patch_base_must_be_in_the_session() {
+        let source_dir = source_dir_with(&["event_b_full.png", "event_base_full.png"]);
+        let session_id = "f2e904d2-286e-484c-83e8-5949bd8697f1";
+        let with_base = |base: &str| {
+            let mut session = RecordingSession::new(session_id.to_string());
+            let mut click = click_with_full_screen("event_b_full.png");
+            click.screenshots.full_screen_patch = Some(FramePatch {
+                base: base.to_string(),
+                x: 640,
+                y: 360,
+            });
+            session.add_event(click);
+            session
+        };
+
+        for base in ["/etc/passwd", "../other-session/event_b_other.png", ""] {
+            let error = validate_for_import(&with_base(base), &source_dir).unwrap_err();
+            assert!(error.contains("partial capture base"), "{}", error);
+        }
+
+        let mut session = with_base("/elsewhere/event_base_full.png");
+        assert!(validate_for_import(&session, &source_dir).is_ok());
+        rewrite_screenshot_paths(&mut session);
+        let patch = session.events[0].screenshots.full_screen_patch.as_ref();
+        assert_eq!(
+            patch.map(|patch| patch.base.as_str()),
+            Some(storage::session_asset_path(session_id, "event_base_full.png").as_str())
+        );
+
+        let _ = fs::remove_dir_all(&source_dir);
+    }
+
+    #[test]
+    fn test_rejects_oversized_durations() {
+        let source_dir = std::env::temp_dir();
+        let with_duration = |event_type: EventType| {
+            let mut session =
+                RecordingSession::new("f2e904d2-286e-484c-83e8-5949bd8697f1".to_string());
+            session.add_event(Event::new(event_type, None));
+            session
+        };
+
+        for duration_seconds in [1e300, f64::MAX, f64::INFINITY, 365.0 * 24.0 * 3600.0] {
+            for event_type in [
+                EventType::Wait {
+                    duration_seconds,
+                    cause: None,
+                },
+                EventType::CredentialsEntered { duration_seconds },
+                EventType::RecorderRestart {
+                    gap_seconds: duration_seconds,
+                },
+            ] {
+                let error =
+                    validate_for_import(&with_duration(event_type), &source_dir).unwrap_err();
+                assert!(error.contains("invalid duration"), "{}", error);
+            }
+        }
+
+        // A paused weekend is still a plausible recording
+        let session = with_duration(EventType::Paused {
+            duration_seconds: 3.0 * 24.0 * 3600.0,
+        });
+        assert!(validate_for_import(&session, &source_dir).is_ok());
+    }
+}
@@ -0,0 +1,12 @@
+//! # FlowTrace Export - Sessions In and Out
+//!
+//! - **`export`**: Session export formats (Parquet with the `parquet` feature)
+//! - **`annotation`**: Contrast-aware marker colors on exported screenshots
+//! - **`importer`**: Session archive import
+
+pub mod annotation;
+pub mod export;
+pub mod importer;
+
+use flowtrace_analysis::{frame_diff, integrity, ocr, parts, steps};
+use flowtrace_core::{config, storage, types};
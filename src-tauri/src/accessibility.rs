@@ -0,0 +1,375 @@
+//! # Accessibility Module - UI Element Under the Click
+//!
+//! Resolves the UI element at a click position through the platform
+//! accessibility API, so events can say *what* was clicked ("Clicked button
+//! 'Submit'") instead of only where.
+//!
+//! ## Platform Support
+//! - **macOS**: `AXUIElementCopyElementAtPosition` on the system-wide element,
+//!   then `AXRole` / `AXTitle` (or `AXDescription`) / `AXValue`. Uses the
+//!   Accessibility permission the event listener already requires.
+//! - **Other platforms**: No element (events keep the position-based description)
+//!
+//! `focused_element()` resolves the element with keyboard focus the same way
+//! (`AXFocusedUIElement`), e.g., to detect password fields.
+//!
+//! ## Scroll Position
+//! `scroll_position_at()` walks up from the element at the click to the
+//! enclosing `AXScrollArea` (a web page, document, or settings pane) and
+//! reads the `AXValue` of its scroll bars (0.0 at the top/left, 1.0 at the
+//! bottom/right), so documentation can say "scrolled ~60% down" where the
+//! click coordinates alone say nothing. Areas that can't scroll have no
+//! scroll bars and give no position.
+//!
+//! ## Privacy
+//! Values of secure text fields (passwords) are never read, and other values
+//! are truncated to `MAX_VALUE_CHARS`.
+
+use crate::types::{ScrollPosition, UiElement};
+
+/// Longest element value stored (e.g., the contents of a large text area).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const MAX_VALUE_CHARS: usize = 200;
+
+/// Returns the UI element at a global logical point, if the platform exposes one.
+///
+/// # Arguments
+/// * `x`, `y` - Click position in global logical coordinates (points on macOS)
+#[cfg(target_os = "macos")]
+pub fn element_at(x: f64, y: f64) -> Option<UiElement> {
+    macos::element_at(x, y)
+}
+
+/// Returns the UI element at a global logical point, if the platform exposes one.
+///
+/// Not implemented on this platform.
+#[cfg(not(target_os = "macos"))]
+pub fn element_at(_x: f64, _y: f64) -> Option<UiElement> {
+    None
+}
+
+/// Returns the UI element that has keyboard focus, if the platform exposes one.
+#[cfg(target_os = "macos")]
+pub fn focused_element() -> Option<UiElement> {
+    macos::focused_element()
+}
+
+/// Returns the UI element that has keyboard focus, if the platform exposes one.
+///
+/// Not implemented on this platform.
+#[cfg(not(target_os = "macos"))]
+pub fn focused_element() -> Option<UiElement> {
+    None
+}
+
+/// Returns the scroll position of the area around a global logical point,
+/// if the platform exposes one (see module docs).
+#[cfg(target_os = "macos")]
+pub fn scroll_position_at(x: f64, y: f64) -> Option<ScrollPosition> {
+    macos::scroll_position_at(x, y)
+}
+
+/// Returns the scroll position of the area around a global logical point,
+/// if the platform exposes one.
+///
+/// Not implemented on this platform.
+#[cfg(not(target_os = "macos"))]
+pub fn scroll_position_at(_x: f64, _y: f64) -> Option<ScrollPosition> {
+    None
+}
+
+/// Builds a `ScrollPosition` from scroll bar values (0.0-1.0), ignoring invalid ones.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn build_scroll_position(vertical: Option<f64>, horizontal: Option<f64>) -> Option<ScrollPosition> {
+    let percent = |value: Option<f64>| {
+        value
+            .filter(|value| value.is_finite())
+            .map(|value| (value.clamp(0.0, 1.0) * 100.0).round() as u8)
+    };
+    let position = ScrollPosition {
+        vertical_percent: percent(vertical),
+        horizontal_percent: percent(horizontal),
+    };
+    (position != ScrollPosition::default()).then_some(position)
+}
+
+/// Builds a `UiElement`, dropping empty strings and sensitive or oversized values.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn build_element(
+    role: Option<String>,
+    label: Option<String>,
+    value: Option<String>,
+) -> Option<UiElement> {
+    let non_empty = |text: Option<String>| text.filter(|text| !text.trim().is_empty());
+    let role = non_empty(role);
+    let label = non_empty(label);
+    let value = if role.as_deref() == Some("AXSecureTextField") {
+        None
+    } else {
+        non_empty(value).map(|value| value.chars().take(MAX_VALUE_CHARS).collect())
+    };
+
+    if role.is_none() && label.is_none() {
+        return None;
+    }
+    Some(UiElement { role, label, value })
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{build_element, build_scroll_position};
+    use crate::types::{ScrollPosition, UiElement};
+    use std::ffi::{c_char, c_void, CString};
+
+    type CFTypeRef = *const c_void;
+    type AXError = i32;
+
+    const AX_ERROR_SUCCESS: AXError = 0;
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+    /// Upper bound for waiting on an unresponsive application (seconds)
+    const MESSAGING_TIMEOUT: f32 = 0.25;
+    /// `kCFNumberDoubleType`
+    const CF_NUMBER_DOUBLE_TYPE: isize = 13;
+    /// Most ancestors searched for the enclosing scroll area
+    const MAX_SCROLL_AREA_DEPTH: usize = 12;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> CFTypeRef;
+        fn AXUIElementSetMessagingTimeout(element: CFTypeRef, timeout: f32) -> AXError;
+        fn AXUIElementCopyElementAtPosition(
+            application: CFTypeRef,
+            x: f32,
+            y: f32,
+            element: *mut CFTypeRef,
+        ) -> AXError;
+        fn AXUIElementCopyAttributeValue(
+            element: CFTypeRef,
+            attribute: CFTypeRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithCString(
+            allocator: CFTypeRef,
+            c_str: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFStringGetTypeID() -> usize;
+        fn CFNumberGetTypeID() -> usize;
+        fn CFNumberGetValue(number: CFTypeRef, number_type: isize, value: *mut c_void) -> u8;
+        fn CFGetTypeID(cf: CFTypeRef) -> usize;
+        fn CFStringGetLength(string: CFTypeRef) -> isize;
+        fn CFStringGetMaximumSizeForEncoding(length: isize, encoding: u32) -> isize;
+        fn CFStringGetCString(
+            string: CFTypeRef,
+            buffer: *mut c_char,
+            buffer_size: isize,
+            encoding: u32,
+        ) -> u8;
+        fn CFRelease(cf: CFTypeRef);
+    }
+
+    pub fn element_at(x: f64, y: f64) -> Option<UiElement> {
+        // SAFETY: Create/Copy functions return owned (+1) references or null;
+        // each non-null reference is released exactly once.
+        unsafe {
+            let system_wide = AXUIElementCreateSystemWide();
+            if system_wide.is_null() {
+                return None;
+            }
+            AXUIElementSetMessagingTimeout(system_wide, MESSAGING_TIMEOUT);
+
+            let mut element: CFTypeRef = std::ptr::null();
+            let status =
+                AXUIElementCopyElementAtPosition(system_wide, x as f32, y as f32, &mut element);
+            CFRelease(system_wide);
+            if status != AX_ERROR_SUCCESS || element.is_null() {
+                return None;
+            }
+
+            let resolved = describe(element);
+            CFRelease(element);
+            resolved
+        }
+    }
+
+    pub fn focused_element() -> Option<UiElement> {
+        // SAFETY: Same ownership rules as `element_at()`
+        unsafe {
+            let system_wide = AXUIElementCreateSystemWide();
+            if system_wide.is_null() {
+                return None;
+            }
+            AXUIElementSetMessagingTimeout(system_wide, MESSAGING_TIMEOUT);
+
+            let element = copy_attribute(system_wide, "AXFocusedUIElement");
+            CFRelease(system_wide);
+            let element = element?;
+
+            let resolved = describe(element);
+            CFRelease(element);
+            resolved
+        }
+    }
+
+    pub fn scroll_position_at(x: f64, y: f64) -> Option<ScrollPosition> {
+        // SAFETY: Same ownership rules as `element_at()`; every ancestor is
+        // released before moving on to its parent
+        unsafe {
+            let system_wide = AXUIElementCreateSystemWide();
+            if system_wide.is_null() {
+                return None;
+            }
+            AXUIElementSetMessagingTimeout(system_wide, MESSAGING_TIMEOUT);
+
+            let mut element: CFTypeRef = std::ptr::null();
+            let status =
+                AXUIElementCopyElementAtPosition(system_wide, x as f32, y as f32, &mut element);
+            CFRelease(system_wide);
+            if status != AX_ERROR_SUCCESS || element.is_null() {
+                return None;
+            }
+
+            for _ in 0..MAX_SCROLL_AREA_DEPTH {
+                if string_attribute(element, "AXRole").as_deref() == Some("AXScrollArea") {
+                    let position = build_scroll_position(
+                        scroll_bar_value(element, "AXVerticalScrollBar"),
+                        scroll_bar_value(element, "AXHorizontalScrollBar"),
+                    );
+                    CFRelease(element);
+                    return position;
+                }
+                let parent = copy_attribute(element, "AXParent");
+                CFRelease(element);
+                element = parent?;
+            }
+            CFRelease(element);
+            None
+        }
+    }
+
+    /// Reads the value of a scroll area's scroll bar (0.0-1.0).
+    unsafe fn scroll_bar_value(area: CFTypeRef, name: &str) -> Option<f64> {
+        let scroll_bar = copy_attribute(area, name)?;
+        let value = copy_attribute(scroll_bar, "AXValue");
+        CFRelease(scroll_bar);
+        let value = value?;
+
+        let mut number = 0.0f64;
+        let read = CFGetTypeID(value) == CFNumberGetTypeID()
+            && CFNumberGetValue(
+                value,
+                CF_NUMBER_DOUBLE_TYPE,
+                &mut number as *mut f64 as *mut c_void,
+            ) != 0;
+        CFRelease(value);
+        read.then_some(number)
+    }
+
+    /// Reads role, label, and value of an element (doesn't release it).
+    unsafe fn describe(element: CFTypeRef) -> Option<UiElement> {
+        let role = string_attribute(element, "AXRole");
+        let label = string_attribute(element, "AXTitle")
+            .filter(|title| !title.is_empty())
+            .or_else(|| string_attribute(element, "AXDescription"));
+        let value = if role.as_deref() == Some("AXSecureTextField") {
+            None // Never read password fields
+        } else {
+            string_attribute(element, "AXValue")
+        };
+
+        build_element(role, label, value)
+    }
+
+    /// Copies an attribute value; the caller must release it.
+    unsafe fn copy_attribute(element: CFTypeRef, name: &str) -> Option<CFTypeRef> {
+        let name = CString::new(name).ok()?;
+        let attribute =
+            CFStringCreateWithCString(std::ptr::null(), name.as_ptr(), CF_STRING_ENCODING_UTF8);
+        if attribute.is_null() {
+            return None;
+        }
+
+        let mut value: CFTypeRef = std::ptr::null();
+        let status = AXUIElementCopyAttributeValue(element, attribute, &mut value);
+        CFRelease(attribute);
+        if status != AX_ERROR_SUCCESS || value.is_null() {
+            return None;
+        }
+        Some(value)
+    }
+
+    /// Reads a string-valued attribute (non-string values are ignored).
+    unsafe fn string_attribute(element: CFTypeRef, name: &str) -> Option<String> {
+        let value = copy_attribute(element, name)?;
+
+        let text = if CFGetTypeID(value) == CFStringGetTypeID() {
+            cf_string_to_string(value)
+        } else {
+            None
+        };
+        CFRelease(value);
+        text
+    }
+
+    unsafe fn cf_string_to_string(string: CFTypeRef) -> Option<String> {
+        let length = CFStringGetLength(string);
+        let capacity = CFStringGetMaximumSizeForEncoding(length, CF_STRING_ENCODING_UTF8) + 1;
+        let mut buffer = vec![0 as c_char; capacity.max(1) as usize];
+        if CFStringGetCString(
+            string,
+            buffer.as_mut_ptr(),
+            capacity,
+            CF_STRING_ENCODING_UTF8,
+        ) == 0
+        {
+            return None;
+        }
+        let bytes: Vec<u8> = buffer
+            .into_iter()
+            .take_while(|&byte| byte != 0)
+            .map(|byte| byte as u8)
+            .collect();
+        String::from_utf8(bytes).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secure_field_value_is_dropped() {
+        let password = build_element(
+            Some("AXSecureTextField".to_string()),
+            Some("Password".to_string()),
+            Some("hunter2".to_string()),
+        )
+        .unwrap();
+        assert_eq!(password.value, None);
+
+        let long =
+            build_element(Some("AXTextArea".to_string()), None, Some("x".repeat(500))).unwrap();
+        assert_eq!(long.value.unwrap().len(), MAX_VALUE_CHARS);
+
+        assert!(build_element(None, Some(" ".to_string()), None).is_none());
+    }
+
+    #[test]
+    fn test_scroll_bar_values_become_percentages() {
+        let position = build_scroll_position(Some(0.604), Some(1.7)).unwrap();
+        assert_eq!(position.vertical_percent, Some(60));
+        assert_eq!(position.horizontal_percent, Some(100));
+        assert_eq!(position.summary().as_deref(), Some("scrolled ~60% down"));
+
+        let bottom = build_scroll_position(Some(0.97), None).unwrap();
+        assert_eq!(bottom.summary().as_deref(), Some("scrolled to the bottom"));
+        let top = build_scroll_position(Some(0.0), None).unwrap();
+        assert_eq!(top.summary(), None);
+
+        assert!(build_scroll_position(None, Some(f64::NAN)).is_none());
+    }
+}
@@ -0,0 +1,153 @@
+//! # Adaptive Wait Module - Wait Threshold Learned from the User's Pace
+//!
+//! A fixed 2-second threshold fits nobody exactly: a slow, deliberate user
+//! pauses that long between most actions (every step gets a Wait), while a
+//! fast one may stall for 1.5 seconds on a page that didn't load and get
+//! none.
+//!
+//! With `waits.adaptive.enabled`, the threshold follows the user's cadence:
+//!
+//! ```text
+//! threshold = median(recent gaps between actions) × factor,
+//!             clamped to [min_threshold_seconds, max_threshold_seconds]
+//! ```
+//!
+//! ## Gaps
+//! - Measured between consecutive input events (see `check_and_insert_wait_event()`)
+//! - Gaps under `MIN_GAP_SECONDS` (keystrokes within a word, double clicks)
+//!   are ignored, so the cadence is the one between actions, not the typing speed
+//! - Idle gaps and sleep/lock time aren't counted
+//! - The last `WINDOW` gaps are kept in memory
+//!
+//! ## Persistence
+//! When a recording is saved, the median is written to
+//! `waits.adaptive.learned` in the recorder config; the next recording uses
+//! it until it has `min_samples` gaps of its own. Until then (first
+//! recording), `waits.threshold_seconds` applies.
+
+use crate::config::{self, LearnedCadence, WaitConfig};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Gaps shorter than this are within an action (typing, double clicks).
+const MIN_GAP_SECONDS: f64 = 0.3;
+
+/// Number of recent gaps the median is taken over.
+const WINDOW: usize = 200;
+
+/// Recent gaps between actions, oldest first.
+static GAPS: Lazy<Mutex<VecDeque<f64>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// Wait threshold in seconds: learned if enabled and known, else `waits.threshold_seconds`.
+pub fn threshold_seconds() -> f64 {
+    threshold_with(&config::waits(), &GAPS.lock().unwrap())
+}
+
+/// Counts a gap between two input events (see module docs).
+pub fn observe_gap(seconds: f64) {
+    if !seconds.is_finite() || seconds < MIN_GAP_SECONDS {
+        return;
+    }
+    let mut gaps = GAPS.lock().unwrap();
+    if gaps.len() == WINDOW {
+        gaps.pop_front();
+    }
+    gaps.push_back(seconds);
+}
+
+/// Writes the current cadence to the recorder config (no-op if adaptive
+/// waits are off or fewer than `min_samples` gaps were seen).
+///
+/// # Returns
+/// * `Ok(())` - Saved, or nothing to save
+/// * `Err(String)` - Error if the config file couldn't be written
+pub fn save_learned() -> Result<(), String> {
+    let mut config = config::current();
+    let adaptive = &mut config.waits.adaptive;
+    let gaps = GAPS.lock().unwrap().clone();
+    if !adaptive.enabled || gaps.len() < adaptive.min_samples {
+        return Ok(());
+    }
+    let Some(median_gap_seconds) = median(&gaps) else {
+        return Ok(());
+    };
+    adaptive.learned = Some(LearnedCadence {
+        median_gap_seconds,
+        samples: gaps.len(),
+    });
+
+    #[cfg(debug_assertions)]
+    println!(
+        "⏱️  Learned wait cadence: median gap {:.2}s over {} gaps",
+        median_gap_seconds,
+        gaps.len()
+    );
+
+    config::update(config)
+}
+
+fn threshold_with(config: &WaitConfig, gaps: &VecDeque<f64>) -> f64 {
+    let adaptive = &config.adaptive;
+    if !adaptive.enabled {
+        return config.threshold_seconds;
+    }
+    let median_gap = if gaps.len() >= adaptive.min_samples {
+        median(gaps)
+    } else {
+        adaptive
+            .learned
+            .as_ref()
+            .map(|learned| learned.median_gap_seconds)
+    };
+    match median_gap {
+        // Not `clamp()`: a hand-edited config with inverted bounds mustn't panic
+        Some(median_gap) => (median_gap * adaptive.factor)
+            .max(adaptive.min_threshold_seconds)
+            .min(adaptive.max_threshold_seconds),
+        None => config.threshold_seconds,
+    }
+}
+
+fn median(gaps: &VecDeque<f64>) -> Option<f64> {
+    let mut sorted: Vec<f64> = gaps.iter().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+    let middle = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((sorted[middle - 1] + sorted[middle]) / 2.0),
+        _ => Some(sorted[middle]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_follows_cadence_within_bounds() {
+        let mut config = WaitConfig::default();
+        config.adaptive.min_samples = 4;
+        let slow: VecDeque<f64> = [1.0, 1.5, 2.0, 2.5, 9.0].into_iter().collect();
+
+        // Off: the fixed threshold, whatever the cadence
+        assert_eq!(threshold_with(&config, &slow), 2.0);
+
+        config.adaptive.enabled = true;
+        assert_eq!(threshold_with(&config, &VecDeque::new()), 2.0);
+        // Median 2.0s × 3 = 6.0s
+        assert_eq!(threshold_with(&config, &slow), 6.0);
+        let fast: VecDeque<f64> = [0.4, 0.5, 0.5, 0.6].into_iter().collect();
+        assert_eq!(threshold_with(&config, &fast), 1.5);
+        let glacial: VecDeque<f64> = [10.0; 4].into_iter().collect();
+        assert_eq!(threshold_with(&config, &glacial), 8.0);
+
+        // Too few gaps of its own: the learned cadence
+        config.adaptive.learned = Some(LearnedCadence {
+            median_gap_seconds: 0.75,
+            samples: 120,
+        });
+        let few: VecDeque<f64> = [5.0].into_iter().collect();
+        assert_eq!(threshold_with(&config, &few), 2.25);
+    }
+}
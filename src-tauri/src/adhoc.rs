@@ -0,0 +1,209 @@
+//! # Ad-hoc Captures Module - Screenshots Outside a Recording
+//!
+//! The spike-test `capture_screenshot` command saves loose
+//! `recordings/screenshot_YYYYMMDD_HHMMSS.png` files that no session
+//! references, so they can't be listed, exported, or cleaned up like
+//! recordings. `import_loose_screenshots()` moves them into one synthetic
+//! session with a `Capture` event per file.
+//!
+//! On-demand window snapshots (`add_window_capture()`, see `window_capture`
+//! module) are collected in the same session.
+//!
+//! ## Rules
+//! - The session id is fixed (`ADHOC_SESSION_ID`): later imports append to
+//!   the same session instead of creating one per run
+//! - Event timestamps come from the file name (UTC, as written by
+//!   `screenshot::capture_full_screen()`); events stay in time order
+//! - Files are moved, not copied, to `event_[id]_full.png`; anything not
+//!   named like a spike screenshot is left alone
+//!
+//! ## Example
+//! ```text
+//! recordings/screenshot_20260201_154311.png
+//!   → recordings/adhoc-captures/event_[id]_full.png
+//!     {"type": "Capture", "original_file": "screenshot_20260201_154311.png"}
+//! ```
+
+use crate::types::{Event, EventType, RecordingSession};
+use crate::window_capture::WindowInfo;
+use crate::{anonymize, remote_desktop, screenshot, storage};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Session that collects all ad-hoc captures.
+pub const ADHOC_SESSION_ID: &str = "adhoc-captures";
+
+/// Title of the synthetic session.
+const ADHOC_TITLE: &str = "Ad-hoc captures";
+
+/// Result of one import run.
+#[derive(Serialize, Debug, Clone)]
+pub struct AdHocImport {
+    pub session_id: String,
+    /// Screenshots moved into the session
+    pub imported: usize,
+    /// Loose screenshots that couldn't be moved (left in place)
+    pub failed: Vec<String>,
+}
+
+/// Moves loose spike screenshots into the ad-hoc captures session.
+///
+/// # Returns
+/// * `Ok(AdHocImport)` - Counts (`imported: 0` if there was nothing to move)
+/// * `Err(String)` - Error if the existing session couldn't be loaded or the
+///   updated one saved (moved files are put back in that case)
+pub fn import_loose_screenshots() -> Result<AdHocImport, String> {
+    let root = storage::recordings_root();
+    let mut loose: Vec<(DateTime<Utc>, String)> = fs::read_dir(&root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    Some((capture_time(&name)?, name))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    loose.sort();
+
+    let mut report = AdHocImport {
+        session_id: ADHOC_SESSION_ID.to_string(),
+        imported: 0,
+        failed: Vec::new(),
+    };
+    if loose.is_empty() {
+        return Ok(report);
+    }
+
+    let mut session = load_or_create()?;
+    let session_dir = storage::get_session_dir(ADHOC_SESSION_ID);
+    fs::create_dir_all(&session_dir)
+        .map_err(|e| format!("Failed to create session directory: {:?}", e))?;
+
+    // (original, moved) so a failed save can undo the moves
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (timestamp, name) in loose {
+        let mut event = Event::new(
+            EventType::Capture {
+                original_file: Some(name.clone()),
+            },
+            None,
+        );
+        event.timestamp = timestamp;
+
+        let filename = format!("event_{}_full.png", event.id);
+        let (from, to) = (root.join(&name), session_dir.join(&filename));
+        if let Err(e) = fs::rename(&from, &to) {
+            eprintln!("⚠️  Failed to move {}: {:?}", name, e);
+            report.failed.push(name);
+            continue;
+        }
+        moved.push((from, to));
+        event.screenshots.full_screen =
+            Some(storage::session_asset_path(ADHOC_SESSION_ID, &filename));
+        session.add_event(event);
+        report.imported += 1;
+    }
+
+    if let Err(e) = save(&mut session) {
+        for (from, to) in moved {
+            let _ = fs::rename(to, from);
+        }
+        return Err(e);
+    }
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🗂️  Moved {} loose screenshot(s) into {}",
+        report.imported, ADHOC_SESSION_ID
+    );
+
+    Ok(report)
+}
+
+/// Captures one window into the ad-hoc captures session.
+///
+/// # Returns
+/// * `Ok(Event)` - The recorded `Capture` event (screenshot in `window_crop`)
+/// * `Err(String)` - Error if the window couldn't be captured or the session saved
+pub fn add_window_capture(window: &WindowInfo) -> Result<Event, String> {
+    let mut session = load_or_create()?;
+
+    let mut event = Event::new(
+        EventType::Capture {
+            original_file: None,
+        },
+        None,
+    );
+    event.app_name = Some(window.app_name.clone()).filter(|name| !name.is_empty());
+    event.window_title = Some(window.title.clone()).filter(|title| !title.is_empty());
+    event.remote_desktop = remote_desktop::is_remote_client(&window.app_name);
+    anonymize::apply(&mut event);
+    event.screenshots.window_crop = Some(screenshot::capture_window_for_event(
+        ADHOC_SESSION_ID,
+        &event.id.to_string(),
+        window,
+    )?);
+    session.add_event(event.clone());
+    save(&mut session)?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🪟 Captured window '{}' into {}",
+        window.title, ADHOC_SESSION_ID
+    );
+
+    Ok(event)
+}
+
+/// Loads the ad-hoc captures session, or starts it.
+fn load_or_create() -> Result<RecordingSession, String> {
+    if storage::get_session_dir(ADHOC_SESSION_ID)
+        .join("session.json")
+        .is_file()
+    {
+        return storage::load_session(ADHOC_SESSION_ID);
+    }
+    let mut session = RecordingSession::new(ADHOC_SESSION_ID.to_string());
+    session.title = Some(ADHOC_TITLE.to_string());
+    Ok(session)
+}
+
+/// Puts events in time order, spans the session over them, and saves it.
+fn save(session: &mut RecordingSession) -> Result<(), String> {
+    session.events.sort_by_key(|event| event.timestamp);
+    if let Some(first) = session.events.first() {
+        session.started_at = session.started_at.min(first.timestamp);
+    }
+    session.stopped_at = session.events.last().map(|event| event.timestamp);
+    storage::save_session(session).map(|_| ())
+}
+
+/// Parses the capture time of a spike screenshot name (`None` for other files).
+fn capture_time(file_name: &str) -> Option<DateTime<Utc>> {
+    let stamp = file_name
+        .strip_prefix("screenshot_")?
+        .strip_suffix(".png")?;
+    NaiveDateTime::parse_from_str(stamp, "%Y%m%d_%H%M%S")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_time_from_file_name() {
+        assert_eq!(
+            capture_time("screenshot_20260201_154311.png"),
+            Some("2026-02-01T15:43:11Z".parse().unwrap())
+        );
+        assert_eq!(capture_time("screenshot_later.png"), None);
+        assert_eq!(capture_time("config.json"), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! # Anonymize Module - Window Titles and App Names Without Project Names
+//!
+//! Window titles carry the names of the documents, customers, and internal
+//! hosts being worked on ("Falcon roadmap.docx - Word", "jira.corp.example.com").
+//! Configured rewrites (`anonymize` in the recorder config) replace them
+//! before an event is recorded, so a shared recording never contains the
+//! originals:
+//!
+//! ```text
+//! focused window → screenshot::stamp_focused_window() → anonymize::apply() → event
+//! ```
+//!
+//! ## Rules
+//! 1. **app_aliases**: An app name equal to a key (case-insensitive) is
+//!    replaced by its alias
+//! 2. **rules**: Regex replacements in order, on window titles, app names,
+//!    or both (`target`)
+//!
+//! App names in `AppSwitch` events are rewritten the same way, so the
+//! event's description never shows the original either. File drags (see
+//! `file_drag` module) get the app rules on their target app and the title
+//! rules on the file name, target window, and target path (`file_path()`). Titles that end up
+//! empty are dropped.
+//!
+//! ## Scope
+//! Only what is stored in events is rewritten. The filter's `allow_apps` /
+//! `deny_apps` still match the real application (see `filter` module), and
+//! text inside screenshots is the `scrub` module's job.
+
+use crate::config::{self, AnonymizeConfig, RewriteTarget};
+use crate::types::{Event, EventType, FileDrag};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::sync::{Arc, Mutex};
+
+/// Compiled anonymization rules.
+pub struct Anonymizer {
+    aliases: Vec<(String, String)>,
+    rules: Vec<(Regex, String, RewriteTarget)>,
+}
+
+/// Configuration the rules were last compiled from, and the result.
+#[allow(clippy::type_complexity)]
+static COMPILED: Lazy<Mutex<Option<(AnonymizeConfig, Option<Arc<Anonymizer>>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+impl Anonymizer {
+    /// Compiles the configured aliases and rules.
+    ///
+    /// # Returns
+    /// * `Ok(Some(Anonymizer))` - Rewrites to apply
+    /// * `Ok(None)` - Nothing configured (names are recorded as they are)
+    /// * `Err(String)` - A pattern is not a valid regular expression
+    pub fn compile(config: &AnonymizeConfig) -> Result<Option<Self>, String> {
+        let aliases: Vec<(String, String)> = config
+            .app_aliases
+            .iter()
+            .filter(|(name, _)| !name.trim().is_empty())
+            .map(|(name, alias)| (name.trim().to_lowercase(), alias.clone()))
+            .collect();
+        let rules = config
+            .rules
+            .iter()
+            .filter(|rule| !rule.pattern.trim().is_empty())
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|regex| (regex, rule.replacement.clone(), rule.target))
+                    .map_err(|e| format!("Invalid anonymize pattern '{}': {}", rule.pattern, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(if aliases.is_empty() && rules.is_empty() {
+            None
+        } else {
+            Some(Self { aliases, rules })
+        })
+    }
+
+    /// Rewritten application name.
+    pub fn app_name(&self, name: &str) -> String {
+        let name = self
+            .aliases
+            .iter()
+            .find(|(original, _)| *original == name.trim().to_lowercase())
+            .map_or(name, |(_, alias)| alias.as_str());
+        self.rewrite(name, RewriteTarget::AppName)
+    }
+
+    /// Rewritten window title.
+    pub fn window_title(&self, title: &str) -> String {
+        self.rewrite(title, RewriteTarget::WindowTitle)
+    }
+
+    fn rewrite(&self, text: &str, target: RewriteTarget) -> String {
+        self.rules
+            .iter()
+            .filter(|(_, _, applies_to)| {
+                *applies_to == target || *applies_to == RewriteTarget::Both
+            })
+            .fold(text.to_string(), |text, (regex, replacement, _)| {
+                regex.replace_all(&text, replacement.as_str()).into_owned()
+            })
+    }
+}
+
+/// Rewrites an event's window title and app names with the configured rules.
+///
+/// Invalid rules are reported once and nothing is rewritten (`set_config`
+/// rejects them, so only a hand-edited config file has any).
+pub fn apply(event: &mut Event) {
+    let Some(anonymizer) = current() else {
+        return;
+    };
+    apply_with(&anonymizer, event);
+}
+
+fn apply_with(anonymizer: &Anonymizer, event: &mut Event) {
+    event.app_name = event
+        .app_name
+        .as_deref()
+        .map(|name| anonymizer.app_name(name));
+    event.window_title = event
+        .window_title
+        .as_deref()
+        .map(|title| anonymizer.window_title(title))
+        .filter(|title| !title.trim().is_empty());
+
+    if let EventType::AppSwitch { from, to } = &mut event.event_type {
+        *from = anonymizer.app_name(from);
+        *to = anonymizer.app_name(to);
+        event.reclassify();
+    }
+}
+
+/// Application name rewritten with the configured rules (unchanged if
+/// none are configured).
+pub fn app_name(name: &str) -> String {
+    match current() {
+        Some(anonymizer) => anonymizer.app_name(name),
+        None => name.to_string(),
+    }
+}
+
+/// Rewrites the names of a file drag with the configured rules (see "Rules").
+///
+/// Applied after the target folder was resolved from the real window title.
+pub fn apply_to_file_drag(drag: &mut FileDrag) {
+    let Some(anonymizer) = current() else {
+        return;
+    };
+    drag.target_app = drag
+        .target_app
+        .as_deref()
+        .map(|name| anonymizer.app_name(name));
+    for text in [&mut drag.file_name, &mut drag.target_window] {
+        *text = text
+            .as_deref()
+            .map(|text| anonymizer.window_title(text))
+            .filter(|text| !text.trim().is_empty());
+    }
+}
+
+/// Rewrites the path a dragged file was dropped to (`FileDrag::target_path`)
+/// with the window title rules, which name the same folders and documents.
+pub fn file_path(path: &str) -> String {
+    match current() {
+        Some(anonymizer) => anonymizer.window_title(path),
+        None => path.to_string(),
+    }
+}
+
+/// The compiled rules of the active configuration.
+fn current() -> Option<Arc<Anonymizer>> {
+    let config = config::anonymize();
+    let mut compiled = COMPILED.lock().unwrap();
+    if let Some((_, anonymizer)) = compiled
+        .as_ref()
+        .filter(|(compiled_from, _)| *compiled_from == config)
+    {
+        return anonymizer.clone();
+    }
+
+    let anonymizer = Anonymizer::compile(&config)
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️  Anonymization rules not applied: {}", e);
+            None
+        })
+        .map(Arc::new);
+    *compiled = Some((config, anonymizer.clone()));
+    anonymizer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RewriteRule;
+
+    #[test]
+    fn test_aliases_and_rules_rewrite_names() {
+        let config = AnonymizeConfig {
+            app_aliases: [("acmedesk".to_string(), "Ticket tool".to_string())].into(),
+            rules: vec![
+                RewriteRule {
+                    pattern: r"^.* - (Microsoft Word)$".to_string(),
+                    replacement: "Document - $1".to_string(),
+                    target: RewriteTarget::WindowTitle,
+                },
+                RewriteRule {
+                    pattern: "Falcon".to_string(),
+                    replacement: "Project".to_string(),
+                    target: RewriteTarget::Both,
+                },
+            ],
+        };
+        let anonymizer = Anonymizer::compile(&config).unwrap().unwrap();
+
+        let mut switch = Event::new(
+            EventType::AppSwitch {
+                from: "AcmeDesk".to_string(),
+                to: "Falcon Viewer".to_string(),
+            },
+            None,
+        );
+        switch.app_name = Some("Microsoft Word".to_string());
+        switch.window_title = Some("Falcon roadmap.docx - Microsoft Word".to_string());
+        apply_with(&anonymizer, &mut switch);
+
+        assert_eq!(
+            switch.window_title.as_deref(),
+            Some("Document - Microsoft Word")
+        );
+        assert_eq!(switch.app_name.as_deref(), Some("Microsoft Word"));
+        assert_eq!(
+            switch.description(),
+            "Switched from Ticket tool to Project Viewer"
+        );
+
+        assert!(Anonymizer::compile(&AnonymizeConfig::default())
+            .unwrap()
+            .is_none());
+        let invalid = AnonymizeConfig {
+            rules: vec![RewriteRule {
+                pattern: "(".to_string(),
+                ..RewriteRule::default()
+            }],
+            ..AnonymizeConfig::default()
+        };
+        assert!(Anonymizer::compile(&invalid).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! # App Switch Module - Focus Change Detection
+//!
+//! Workflow analysis needs to know "switched from Excel to the browser",
+//! including switches no recorded click explains (Cmd+Tab, Alt+Tab, a
+//! dialog stealing focus). The foreground application is polled while a
+//! session is active and an `AppSwitch { from, to }` event is recorded
+//! whenever it changes.
+//!
+//! ## Rules
+//! - The application focused when recording starts is the baseline (no event)
+//! - Changes while paused update the baseline without an event, so resuming
+//!   in another app doesn't report a switch nobody recorded
+//! - Windows without an application name (some system overlays) are ignored
+//!
+//! The event passes through the recording's filter like any other, by the
+//! application switched *to* (see `filter` module).
+
+use crate::focus;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// Foreground application last seen by the watcher.
+static LAST_APP: Lazy<Arc<Mutex<Option<String>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Sets the baseline for the recording that is starting.
+pub fn reset() {
+    *LAST_APP.lock().unwrap() = foreground_app();
+}
+
+/// Checks the foreground application once.
+///
+/// # Returns
+/// * `Some((from, to))` - The foreground application changed since the last check
+/// * `None` - Same application, or it couldn't be determined
+pub fn poll() -> Option<(String, String)> {
+    let current = foreground_app()?;
+    switched(&mut LAST_APP.lock().unwrap(), current)
+}
+
+/// Records `current` as the last application and reports a change.
+fn switched(last: &mut Option<String>, current: String) -> Option<(String, String)> {
+    match last.replace(current.clone()) {
+        Some(previous) if previous != current => Some((previous, current)),
+        _ => None,
+    }
+}
+
+/// Display name of the foreground application (`None` if unknown or unnamed).
+fn foreground_app() -> Option<String> {
+    focus::focused_window()
+        .ok()
+        .map(|window| window.app_name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_reported_once_per_change() {
+        let mut last = None;
+
+        // First sighting is the baseline
+        assert_eq!(switched(&mut last, "Microsoft Excel".to_string()), None);
+        assert_eq!(switched(&mut last, "Microsoft Excel".to_string()), None);
+        assert_eq!(
+            switched(&mut last, "Google Chrome".to_string()),
+            Some(("Microsoft Excel".to_string(), "Google Chrome".to_string()))
+        );
+        assert_eq!(last.as_deref(), Some("Google Chrome"));
+    }
+}
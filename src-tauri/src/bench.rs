@@ -0,0 +1,188 @@
+//! # Bench Module - Synthetic Input Through the Live Pipeline
+//!
+//! Drives synthetic input through the same code a live recording runs,
+//! without touching the OS input or the screen, so `benches/pipeline.rs` can
+//! measure throughput and per-event latency on any machine.
+//!
+//! ## Stages
+//! ```text
+//! SyntheticInput → testing::Recording → handle_event() → record_event() → session
+//!                → screenshot::capture_all_for_event() (frames from a MockCapturer)
+//!                → stop_recording() → storage::save_session()
+//! ```
+//!
+//! Input is injected as `rdev` events (see `testing` module), so the locks,
+//! wait detection, filtering, and classification are the real ones.
+//!
+//! ## Mock Screen
+//! Clicks are captured from an in-memory 2880x1800 frame (a 1440x900
+//! display at 2x) with enough detail that PNG encoding isn't trivially
+//! fast. Crops, encoding, and file writes are the real ones, under the
+//! directory given to `configure_root()` (never the user's recordings).
+//!
+//! Only compiled with the `bench` feature (`cargo bench --features bench`).
+
+use crate::capturer::MockCapturer;
+use crate::display::DisplayGeometry;
+use crate::storage;
+use crate::testing::{self, Recording};
+use image::{Rgba, RgbaImage};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Logical size and scale of the mock display.
+const DISPLAY_WIDTH: u32 = 1440;
+const DISPLAY_HEIGHT: u32 = 900;
+const DISPLAY_SCALE: u32 = 2;
+
+/// The word typed after each click ("flowtrace").
+const WORD: [(rdev::Key, &str); 9] = [
+    (rdev::Key::KeyF, "f"),
+    (rdev::Key::KeyL, "l"),
+    (rdev::Key::KeyO, "o"),
+    (rdev::Key::KeyW, "w"),
+    (rdev::Key::KeyT, "t"),
+    (rdev::Key::KeyR, "r"),
+    (rdev::Key::KeyA, "a"),
+    (rdev::Key::KeyC, "c"),
+    (rdev::Key::KeyE, "e"),
+];
+
+/// One input of a synthetic stream.
+#[derive(Debug, Clone)]
+pub enum SyntheticInput {
+    /// Mouse move, left button press and release at a logical point
+    Click { x: f64, y: f64 },
+    /// Key press and release (`text` = the character typed)
+    Key {
+        key: rdev::Key,
+        text: Option<&'static str>,
+    },
+    /// No input for a while (becomes a `Wait` event)
+    Wait { seconds: f64 },
+}
+
+/// Builds a stream resembling form filling: click a field, type a word,
+/// press Return, pause now and then.
+pub fn synthetic_stream(len: usize) -> Vec<SyntheticInput> {
+    (0..len)
+        .map(|index| {
+            let step = index % (WORD.len() + 3);
+            match step {
+                0 => SyntheticInput::Click {
+                    x: 100.0 + (index % 1200) as f64,
+                    y: 80.0 + (index % 700) as f64,
+                },
+                step if step <= WORD.len() => {
+                    let (key, text) = WORD[step - 1];
+                    SyntheticInput::Key {
+                        key,
+                        text: Some(text),
+                    }
+                }
+                step if step == WORD.len() + 1 => SyntheticInput::Key {
+                    key: rdev::Key::Return,
+                    text: None,
+                },
+                _ => SyntheticInput::Wait { seconds: 2.5 },
+            }
+        })
+        .collect()
+}
+
+/// Points the recordings directory at `[dir]/recordings`, for the whole run.
+///
+/// # Returns
+/// The recordings directory now in use
+pub fn configure_root(dir: &Path) -> PathBuf {
+    storage::configure_root(dir)
+}
+
+/// A recording fed by synthetic input.
+///
+/// One exists at a time (the pipeline state is global, see `testing`
+/// module): `new()` waits until the previous one is finished or dropped.
+pub struct Pipeline {
+    recording: Recording,
+    session_id: String,
+}
+
+impl Pipeline {
+    /// Starts a synthetic recording.
+    ///
+    /// # Arguments
+    /// * `session_id` - Id of the session (saved under `[root]/[session-id]/`)
+    /// * `capture` - Whether clicks capture screenshots from the mock screen;
+    ///   `false` records the paths only (nothing is encoded or written)
+    ///
+    /// # Returns
+    /// * `Ok(Pipeline)` - Feed it with `process()`
+    /// * `Err(String)` - Error if the session couldn't be installed
+    pub fn new(session_id: &str, capture: bool) -> Result<Self, String> {
+        let recording = if capture {
+            testing::begin_with_capturer(session_id, Arc::new(mock_screen()))?
+        } else {
+            testing::begin(session_id, true)?
+        };
+        Ok(Self {
+            recording,
+            session_id: session_id.to_string(),
+        })
+    }
+
+    /// Injects one input into `handle_event()`, returning once it is recorded.
+    pub fn process(&self, input: &SyntheticInput) {
+        match input {
+            SyntheticInput::Click { x, y } => self.recording.click_at(*x, *y),
+            SyntheticInput::Key {
+                key,
+                text: Some(text),
+            } => self.recording.type_key(*key, text),
+            SyntheticInput::Key { key, text: None } => {
+                self.recording.inject(rdev::EventType::KeyPress(*key));
+                self.recording.inject(rdev::EventType::KeyRelease(*key));
+            }
+            SyntheticInput::Wait { seconds } => self.recording.elapse(*seconds),
+        }
+    }
+
+    /// Number of events recorded so far.
+    pub fn event_count(&self) -> usize {
+        self.recording.event_count()
+    }
+
+    /// Stops the recording with `stop_recording()` (post-processing and save).
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the saved `session.json`
+    /// * `Err(String)` - Error if the session couldn't be saved
+    pub fn persist(self) -> Result<PathBuf, String> {
+        self.recording.stop()?;
+        Ok(storage::get_session_dir(&self.session_id).join("session.json"))
+    }
+}
+
+/// A display whose frame has enough detail that PNG encoding isn't trivially fast.
+fn mock_screen() -> MockCapturer {
+    let display = DisplayGeometry {
+        id: 1,
+        origin_x: 0,
+        origin_y: 0,
+        logical_width: DISPLAY_WIDTH,
+        logical_height: DISPLAY_HEIGHT,
+        physical_width: 0,
+        physical_height: 0,
+        reported_scale: DISPLAY_SCALE as f32,
+    };
+    let frame = RgbaImage::from_fn(
+        DISPLAY_WIDTH * DISPLAY_SCALE,
+        DISPLAY_HEIGHT * DISPLAY_SCALE,
+        |x, y| {
+            let shade = ((x / 8) ^ (y / 8)) as u8;
+            Rgba([shade, shade.wrapping_mul(3), 0xF0, 0xFF])
+        },
+    );
+    MockCapturer {
+        screens: vec![(display, frame)],
+    }
+}
@@ -0,0 +1,232 @@
+//! # Bookmarks Module - Pinned Moments Across Sessions
+//!
+//! Lets users pin key events of long recordings ("the config screen",
+//! "where it failed") and jump back to them later, from any session.
+//!
+//! ## Storage
+//! ```text
+//! recordings/bookmarks.json
+//! ```
+//! One index for all sessions, so bookmarks can be listed without loading
+//! every `session.json`. Bookmarks keep only ids, times, and the user's note:
+//! event text stays in the session, where redaction can reach it. An index
+//! that can't be parsed is reported, never replaced by an empty one.
+//!
+//! ## Timeline
+//! `session_timeline()` lists a session's events in playback order with
+//! their bookmark flags, plus the positions of the bookmarked events
+//! (`bookmarked_positions`) for jump-to navigation.
+
+use crate::storage;
+use crate::types::{Event, RecordingSession};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// All bookmarks, across sessions.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BookmarkIndex {
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// One pinned event.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub session_id: String,
+    pub event_id: String,
+    /// When the event happened
+    pub event_timestamp: DateTime<Utc>,
+    /// When it was bookmarked
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A session's events in playback order.
+#[derive(Serialize, Debug, Clone)]
+pub struct Timeline {
+    pub session_id: String,
+    pub items: Vec<TimelineItem>,
+    /// Indices into `items` of the bookmarked events, in order
+    pub bookmarked_positions: Vec<usize>,
+}
+
+/// One event on the timeline.
+#[derive(Serialize, Debug, Clone)]
+pub struct TimelineItem {
+    pub event_id: String,
+    /// Playback time (`edited_timeline` where present)
+    pub timestamp: DateTime<Utc>,
+    pub action_category: String,
+    pub description: String,
+    pub bookmarked: bool,
+}
+
+/// Location of the bookmark index.
+pub fn bookmarks_path() -> PathBuf {
+    storage::recordings_root().join("bookmarks.json")
+}
+
+/// Bookmarks an event, or updates the note of an existing bookmark.
+///
+/// # Arguments
+/// * `session_id` - Session the event belongs to
+/// * `event` - Event to pin
+/// * `note` - Optional note (blank = none)
+///
+/// # Returns
+/// * `Ok(Bookmark)` - The stored bookmark
+/// * `Err(String)` - Error if the index couldn't be read or written
+pub fn add(session_id: &str, event: &Event, note: Option<String>) -> Result<Bookmark, String> {
+    let note = note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    update_index(|index| {
+        Ok(
+            match index.bookmarks.iter_mut().find(|bookmark| {
+                bookmark.session_id == session_id && event.has_id(&bookmark.event_id)
+            }) {
+                Some(existing) => {
+                    existing.note = note;
+                    existing.clone()
+                }
+                None => {
+                    let bookmark = Bookmark {
+                        session_id: session_id.to_string(),
+                        event_id: event.id.to_string(),
+                        event_timestamp: event.timestamp,
+                        created_at: Utc::now(),
+                        note,
+                    };
+                    index.bookmarks.push(bookmark.clone());
+                    bookmark
+                }
+            },
+        )
+    })
+}
+
+/// Removes a bookmark.
+///
+/// # Returns
+/// * `Ok(bool)` - Whether the event was bookmarked
+/// * `Err(String)` - Error if the index couldn't be read or written
+pub fn remove(session_id: &str, event_id: &str) -> Result<bool, String> {
+    update_index(|index| {
+        let before = index.bookmarks.len();
+        index.bookmarks.retain(|bookmark| {
+            !(bookmark.session_id == session_id && bookmark.event_id == event_id)
+        });
+        Ok(index.bookmarks.len() != before)
+    })
+}
+
+/// Lists bookmarks, most recent event first.
+///
+/// # Arguments
+/// * `session_id` - Only this session's bookmarks (`None` = all sessions)
+///
+/// Bookmarks of deleted sessions are left out (and stay in the index).
+///
+/// # Returns
+/// * `Ok(Vec<Bookmark>)` - Matching bookmarks
+/// * `Err(String)` - Error if the index exists but can't be read or parsed
+pub fn list(session_id: Option<&str>) -> Result<Vec<Bookmark>, String> {
+    let mut bookmarks: Vec<Bookmark> = load_index()?
+        .bookmarks
+        .into_iter()
+        .filter(|bookmark| session_id.is_none_or(|id| bookmark.session_id == id))
+        .filter(|bookmark| storage::get_session_dir(&bookmark.session_id).is_dir())
+        .collect();
+
+    bookmarks.sort_by(|a, b| b.event_timestamp.cmp(&a.event_timestamp));
+    Ok(bookmarks)
+}
+
+/// Builds the timeline of a session with its bookmarks marked.
+pub fn session_timeline(session: &RecordingSession, bookmarks: &[Bookmark]) -> Timeline {
+    let bookmarked: HashSet<&str> = bookmarks
+        .iter()
+        .filter(|bookmark| bookmark.session_id == session.session_id)
+        .map(|bookmark| bookmark.event_id.as_str())
+        .collect();
+
+    let mut items: Vec<TimelineItem> = session
+        .events
+        .iter()
+        .zip(session.timeline())
+        .map(|(event, timestamp)| TimelineItem {
+            event_id: event.id.to_string(),
+            timestamp,
+            action_category: event.action_category.clone(),
+            description: event.description().into_owned(),
+            bookmarked: bookmarked.contains(event.id.to_string().as_str()),
+        })
+        .collect();
+    items.sort_by_key(|item| item.timestamp);
+
+    let bookmarked_positions = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.bookmarked)
+        .map(|(position, _)| position)
+        .collect();
+
+    Timeline {
+        session_id: session.session_id.clone(),
+        items,
+        bookmarked_positions,
+    }
+}
+
+/// Loads the bookmark index (empty if missing, `Err` if invalid).
+fn load_index() -> Result<BookmarkIndex, String> {
+    storage::read_json_index(&bookmarks_path(), "bookmarks")
+}
+
+/// Changes the bookmark index under the index lock (see `storage::update_json_index()`).
+fn update_index<R>(
+    update: impl FnOnce(&mut BookmarkIndex) -> Result<R, String>,
+) -> Result<R, String> {
+    storage::update_json_index(&bookmarks_path(), "bookmarks", update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EventType, MouseButton, Position};
+
+    #[test]
+    fn test_timeline_marks_bookmarks() {
+        let mut session = RecordingSession::new("bookmarks-test".to_string());
+        for x in [10.0, 20.0, 30.0] {
+            session.add_event(Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(x, 10.0)),
+            ));
+        }
+        let bookmark = |session_id: &str, event: &Event| Bookmark {
+            session_id: session_id.to_string(),
+            event_id: event.id.to_string(),
+            event_timestamp: event.timestamp,
+            created_at: Utc::now(),
+            note: None,
+        };
+        let bookmarks = vec![
+            bookmark("bookmarks-test", &session.events[2]),
+            // Same event id in another session doesn't count
+            bookmark("other-session", &session.events[0]),
+        ];
+
+        let timeline = session_timeline(&session, &bookmarks);
+
+        assert_eq!(timeline.items.len(), 3);
+        assert_eq!(timeline.bookmarked_positions, vec![2]);
+        assert!(!timeline.items[0].bookmarked);
+    }
+}
@@ -0,0 +1,195 @@
+//! # Comments Module - Review Threads on Steps
+//!
+//! Lightweight review workflow for recorded procedures: reviewers (e.g., a
+//! subject-matter expert) start comment threads on events, reply, and
+//! resolve them before the procedure is published.
+//!
+//! ## Storage
+//! ```text
+//! recordings/[session-id]/comments.json
+//! ```
+//! Kept next to (not inside) `session.json`, so reviewing never rewrites the
+//! recording and comments travel with exported archives.
+//!
+//! ## Threads
+//! - A thread belongs to one event and holds comments in posting order
+//! - Replying to a resolved thread reopens it
+//! - Authors are free text (there are no user accounts)
+
+use crate::storage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// All comment threads of one session.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SessionComments {
+    pub threads: Vec<CommentThread>,
+}
+
+/// A discussion about one event.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommentThread {
+    pub id: String,
+    pub event_id: String,
+    pub comments: Vec<Comment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// One reviewer comment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Comment {
+    pub id: String,
+    pub author: String,
+    pub timestamp: DateTime<Utc>,
+    pub text: String,
+}
+
+impl SessionComments {
+    /// Starts a thread on an event, or replies to an existing thread.
+    ///
+    /// # Arguments
+    /// * `event_id` - Event the comment is about
+    /// * `thread_id` - Thread to reply to (`None` starts a new thread)
+    /// * `author` - Name of the reviewer
+    /// * `text` - Comment text
+    ///
+    /// # Returns
+    /// * `Ok(CommentThread)` - The thread with the new comment
+    /// * `Err(String)` - Error if author or text is blank, or the thread doesn't
+    ///   exist or belongs to another event
+    pub fn add(
+        &mut self,
+        event_id: &str,
+        thread_id: Option<&str>,
+        author: &str,
+        text: &str,
+    ) -> Result<CommentThread, String> {
+        let (author, text) = (author.trim(), text.trim());
+        if author.is_empty() || text.is_empty() {
+            return Err("Comment author and text must not be empty".to_string());
+        }
+        let comment = Comment {
+            id: uuid::Uuid::new_v4().to_string(),
+            author: author.to_string(),
+            timestamp: Utc::now(),
+            text: text.to_string(),
+        };
+
+        let thread = match thread_id {
+            Some(thread_id) => {
+                let thread = self.thread_mut(thread_id)?;
+                if thread.event_id != event_id {
+                    return Err(format!(
+                        "Thread {} belongs to event {}",
+                        thread_id, thread.event_id
+                    ));
+                }
+                thread.resolved_by = None;
+                thread.resolved_at = None;
+                thread
+            }
+            None => {
+                self.threads.push(CommentThread {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    event_id: event_id.to_string(),
+                    comments: Vec::new(),
+                    resolved_by: None,
+                    resolved_at: None,
+                });
+                self.threads.last_mut().unwrap()
+            }
+        };
+
+        thread.comments.push(comment);
+        Ok(thread.clone())
+    }
+
+    /// Marks a thread as resolved.
+    ///
+    /// # Returns
+    /// * `Ok(CommentThread)` - The resolved thread
+    /// * `Err(String)` - Error if the thread doesn't exist
+    pub fn resolve(&mut self, thread_id: &str, resolved_by: &str) -> Result<CommentThread, String> {
+        let thread = self.thread_mut(thread_id)?;
+        thread.resolved_by = Some(resolved_by.trim().to_string()).filter(|name| !name.is_empty());
+        thread.resolved_at = Some(Utc::now());
+        Ok(thread.clone())
+    }
+
+    /// Number of threads still waiting for a resolution.
+    pub fn open_thread_count(&self) -> usize {
+        self.threads
+            .iter()
+            .filter(|thread| thread.resolved_at.is_none())
+            .count()
+    }
+
+    fn thread_mut(&mut self, thread_id: &str) -> Result<&mut CommentThread, String> {
+        self.threads
+            .iter_mut()
+            .find(|thread| thread.id == thread_id)
+            .ok_or_else(|| format!("Comment thread {} not found", thread_id))
+    }
+}
+
+/// Location of a session's comments.
+pub fn comments_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("comments.json")
+}
+
+/// Loads a session's comments (none if the file doesn't exist yet).
+///
+/// # Returns
+/// * `Ok(SessionComments)` - All threads of the session
+/// * `Err(String)` - Error if the file exists but can't be read or parsed
+pub fn load(session_id: &str) -> Result<SessionComments, String> {
+    storage::read_json_index(&comments_path(session_id), "comments")
+}
+
+/// Loads a session's comments, applies `update`, and saves them via a
+/// temporary file, under the index lock so concurrent reviewers never drop
+/// each other's comments.
+///
+/// # Returns
+/// * `Ok(R)` - What `update` returned
+/// * `Err(String)` - Error from loading, `update`, or saving (nothing is saved)
+pub fn update<R>(
+    session_id: &str,
+    update: impl FnOnce(&mut SessionComments) -> Result<R, String>,
+) -> Result<R, String> {
+    storage::update_json_index(&comments_path(session_id), "comments", update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threads_reply_resolve_and_reopen() {
+        let mut comments = SessionComments::default();
+        let thread = comments
+            .add("event-1", None, "Dana", "Use the bulk import here")
+            .unwrap();
+        comments.resolve(&thread.id, "Sam").expect("thread exists");
+        assert_eq!(comments.open_thread_count(), 0);
+
+        // A reply reopens the thread
+        let thread = comments
+            .add("event-1", Some(&thread.id), "Sam", "Still unclear")
+            .unwrap();
+        assert_eq!(thread.comments.len(), 2);
+        assert_eq!(thread.resolved_at, None);
+        assert_eq!(comments.open_thread_count(), 1);
+
+        assert!(comments
+            .add("event-2", Some(&thread.id), "Sam", "Wrong step")
+            .is_err());
+        assert!(comments.add("event-1", None, " ", "No author").is_err());
+        assert!(comments.resolve("missing", "Sam").is_err());
+    }
+}
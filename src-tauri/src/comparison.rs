@@ -0,0 +1,414 @@
+//! # Comparison Module - Before/After Frames of a Step
+//!
+//! A click is captured at the press, before the UI reacts, so what the
+//! click did only shows in the next capture. For the before/after slider
+//! (and exports that focus on what changed), an event's frame is paired
+//! with the next captured frame of the session, or with the previous one
+//! for the last capture:
+//!
+//! ```text
+//! before = event's full screen       after = next event's full screen
+//! mask   = changed pixels (red, transparent elsewhere)
+//! ```
+//!
+//! ## Alignment
+//! Both frames are written at the before frame's size: an after frame from
+//! another resolution (display change, `max_dimension`) is scaled to it, so
+//! the slider overlays them pixel for pixel. Partial captures are
+//! reconstructed (see `frame_diff` module).
+//!
+//! ## Changed Regions
+//! Frames are compared in `frame_diff::TILE_SIZE` tiles. Touching changed
+//! tiles are merged into regions (largest first), e.g., a dialog that opened
+//! and a counter that ticked are two regions.
+//!
+//! ## Cache
+//! ```text
+//! recordings/[session-id]/comparisons/[event-id].json       EventComparison
+//! recordings/[session-id]/comparisons/[event-id]_before.png
+//! recordings/[session-id]/comparisons/[event-id]_after.png
+//! recordings/[session-id]/comparisons/[event-id]_mask.png
+//! ```
+//! Computed on first request and reused until `session.json` changes.
+//! Redaction deletes every comparison with a redacted event on either side
+//! (`remove_for_events()`).
+
+use crate::frame_diff::{self, Rect, TILE_SIZE};
+use crate::storage;
+use crate::types::{Event, RecordingSession};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Color of changed pixels in the mask.
+const MASK_COLOR: Rgba<u8> = Rgba([255, 0, 64, 160]);
+
+/// One side of a comparison.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ComparisonFrame {
+    /// Event the frame was captured for
+    pub event_id: String,
+    /// Stored path of the aligned PNG
+    pub path: String,
+}
+
+/// A changed area in the aligned frames (physical pixels).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ChangedRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Before/after frames of an event and what changed between them.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventComparison {
+    pub session_id: String,
+    pub event_id: String,
+    pub before: ComparisonFrame,
+    pub after: ComparisonFrame,
+    /// Stored path of the difference mask (same size as the frames)
+    pub mask_path: String,
+    pub width: u32,
+    pub height: u32,
+    /// Share of tiles (0-1) that changed
+    pub changed_ratio: f64,
+    /// Changed areas, largest first (see "Changed Regions")
+    pub changed_regions: Vec<ChangedRegion>,
+}
+
+/// Returns the comparison of an event of a saved session (cached, see module docs).
+///
+/// # Returns
+/// * `Ok(EventComparison)` - Aligned frames, mask, and changed regions
+/// * `Err(String)` - Error if the event doesn't exist, has no full-screen
+///   capture, is the only capture, or the frames couldn't be read or written
+pub fn get_event_comparison(session_id: &str, event_id: &str) -> Result<EventComparison, String> {
+    let dir = comparison_dir(session_id);
+    let cached = dir.join(format!("{}.json", event_id));
+    if is_current(&cached, session_id) {
+        let comparison = fs::read_to_string(&cached)
+            .ok()
+            .and_then(|json_data| serde_json::from_str(&json_data).ok());
+        if let Some(comparison) = comparison {
+            return Ok(comparison);
+        }
+    }
+
+    let session = storage::load_session(session_id)?;
+    let (before_event, after_event) = pair(&session, event_id)?;
+    let before = frame(before_event)?;
+    let after = frame(after_event)?;
+    let (after, mask, changed_ratio, regions) = compare(&before, &after);
+
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create comparison directory: {:?}", e))?;
+    let save = |image: &RgbaImage, suffix: &str| -> Result<String, String> {
+        let filename = format!("{}_{}.png", event_id, suffix);
+        image
+            .save_with_format(dir.join(&filename), ImageFormat::Png)
+            .map_err(|e| format!("Failed to write comparison image: {:?}", e))?;
+        Ok(storage::session_asset_path(
+            session_id,
+            &format!("comparisons/{}", filename),
+        ))
+    };
+    let comparison = EventComparison {
+        session_id: session_id.to_string(),
+        event_id: event_id.to_string(),
+        before: ComparisonFrame {
+            event_id: before_event.id.to_string(),
+            path: save(&before, "before")?,
+        },
+        after: ComparisonFrame {
+            event_id: after_event.id.to_string(),
+            path: save(&after, "after")?,
+        },
+        mask_path: save(&mask, "mask")?,
+        width: before.width(),
+        height: before.height(),
+        changed_ratio,
+        changed_regions: regions
+            .into_iter()
+            .map(|(x, y, width, height)| ChangedRegion {
+                x,
+                y,
+                width,
+                height,
+            })
+            .collect(),
+    };
+
+    let json_data = serde_json::to_string_pretty(&comparison)
+        .map_err(|e| format!("Failed to serialize comparison: {:?}", e))?;
+    fs::write(&cached, json_data).map_err(|e| format!("Failed to write comparison: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🔍 Comparison of event {}: {:.0}% changed in {} regions",
+        event_id,
+        comparison.changed_ratio * 100.0,
+        comparison.changed_regions.len()
+    );
+
+    Ok(comparison)
+}
+
+/// Deletes the cached comparisons that show any of the events (see "Cache").
+///
+/// # Returns
+/// * `Ok(usize)` - Number of comparisons deleted
+/// * `Err(String)` - Error if a cached file couldn't be deleted
+pub fn remove_for_events(session_id: &str, event_ids: &HashSet<String>) -> Result<usize, String> {
+    let dir = comparison_dir(session_id);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(0);
+    };
+
+    // Keyed by the requested event, which may be the after side of another
+    let mut stale: HashSet<String> = event_ids.clone();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(id) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        let shows_event = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json_data| serde_json::from_str::<EventComparison>(&json_data).ok())
+            .map_or(true, |comparison| {
+                [
+                    &comparison.event_id,
+                    &comparison.before.event_id,
+                    &comparison.after.event_id,
+                ]
+                .into_iter()
+                .any(|id| event_ids.contains(id))
+            });
+        if shows_event {
+            stale.insert(id.to_string());
+        }
+    }
+
+    let mut removed = 0;
+    for id in &stale {
+        for suffix in [".json", "_before.png", "_after.png", "_mask.png"] {
+            match fs::remove_file(dir.join(format!("{}{}", id, suffix))) {
+                Ok(()) if suffix == ".json" => removed += 1,
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to delete comparison: {:?}", e)),
+            }
+        }
+    }
+    Ok(removed)
+}
+
+fn comparison_dir(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("comparisons")
+}
+
+/// Whether a cached file was written after `session.json`.
+fn is_current(path: &Path, session_id: &str) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let session_json = storage::get_session_dir(session_id).join("session.json");
+    match (modified(path), modified(&session_json)) {
+        (Some(cached), Some(session)) => cached >= session,
+        _ => false,
+    }
+}
+
+/// The event and the capture it is compared with (next, else previous).
+fn pair<'a>(
+    session: &'a RecordingSession,
+    event_id: &str,
+) -> Result<(&'a Event, &'a Event), String> {
+    let captured: Vec<&Event> = session
+        .events
+        .iter()
+        .filter(|event| event.screenshots.full_screen.is_some())
+        .collect();
+    let index = match captured.iter().position(|event| event.has_id(event_id)) {
+        Some(index) => index,
+        None if session.events.iter().any(|event| event.has_id(event_id)) => {
+            return Err(format!("Event {} has no full-screen capture", event_id));
+        }
+        None => return Err(format!("Event {} not found", event_id)),
+    };
+    match (captured.get(index + 1), index.checked_sub(1)) {
+        (Some(next), _) => Ok((captured[index], next)),
+        (None, Some(previous)) => Ok((captured[previous], captured[index])),
+        (None, None) => Err("Session has no other capture to compare with".to_string()),
+    }
+}
+
+fn frame(event: &Event) -> Result<RgbaImage, String> {
+    if let Some(frame) = frame_diff::reconstruct(&event.screenshots)? {
+        return Ok(frame.into_rgba8());
+    }
+    let path = event
+        .screenshots
+        .full_screen
+        .as_deref()
+        .ok_or("Event has no full-screen capture")?;
+    image::open(storage::resolve_asset_path(path))
+        .map(DynamicImage::into_rgba8)
+        .map_err(|e| format!("Failed to read {}: {:?}", path, e))
+}
+
+/// Aligns `after` to `before` and diffs them.
+///
+/// # Returns
+/// The aligned after frame, the mask, the changed tile ratio, and the changed regions
+fn compare(before: &RgbaImage, after: &RgbaImage) -> (RgbaImage, RgbaImage, f64, Vec<Rect>) {
+    let (width, height) = before.dimensions();
+    let after = if after.dimensions() == (width, height) {
+        after.clone()
+    } else {
+        image::imageops::resize(after, width, height, FilterType::Triangle)
+    };
+
+    let tiles = frame_diff::changed_tiles(before, &after);
+    let mut mask = RgbaImage::new(width, height);
+    for &(tile_x, tile_y, tile_width, tile_height) in &tiles {
+        for y in tile_y..tile_y + tile_height {
+            for x in tile_x..tile_x + tile_width {
+                if before.get_pixel(x, y) != after.get_pixel(x, y) {
+                    mask.put_pixel(x, y, MASK_COLOR);
+                }
+            }
+        }
+    }
+
+    let total = (width.div_ceil(TILE_SIZE) as f64) * (height.div_ceil(TILE_SIZE) as f64);
+    let changed_ratio = if total == 0.0 {
+        0.0
+    } else {
+        tiles.len() as f64 / total
+    };
+    (after, mask, changed_ratio, regions(&tiles))
+}
+
+/// Merges touching tiles (including diagonally) into bounding boxes, largest first.
+fn regions(tiles: &[Rect]) -> Vec<Rect> {
+    let mut unvisited: Vec<Rect> = tiles.to_vec();
+    let mut regions = Vec::new();
+    let touches = |a: &Rect, b: &Rect| {
+        a.0 <= b.0 + b.2 && b.0 <= a.0 + a.2 && a.1 <= b.1 + b.3 && b.1 <= a.1 + a.3
+    };
+
+    while let Some(seed) = unvisited.pop() {
+        let mut component = vec![seed];
+        let mut next = 0;
+        while next < component.len() {
+            let tile = component[next];
+            let (touching, rest): (Vec<Rect>, Vec<Rect>) = unvisited
+                .into_iter()
+                .partition(|other| touches(&tile, other));
+            component.extend(touching);
+            unvisited = rest;
+            next += 1;
+        }
+        let left = component.iter().map(|tile| tile.0).min().unwrap_or(0);
+        let top = component.iter().map(|tile| tile.1).min().unwrap_or(0);
+        let right = component
+            .iter()
+            .map(|tile| tile.0 + tile.2)
+            .max()
+            .unwrap_or(0);
+        let bottom = component
+            .iter()
+            .map(|tile| tile.1 + tile.3)
+            .max()
+            .unwrap_or(0);
+        regions.push((left, top, right - left, bottom - top));
+    }
+
+    regions.sort_by_key(|region| std::cmp::Reverse(region.2 as u64 * region.3 as u64));
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_become_mask_and_separate_regions() {
+        let before = RgbaImage::from_pixel(160, 96, Rgba([255, 255, 255, 255]));
+        let mut after = before.clone();
+        // A dialog (tiles 2-5 × 1-3) and a one-pixel counter far from it
+        for y in 20..60 {
+            for x in 40..90 {
+                after.put_pixel(x, y, Rgba([30, 30, 30, 255]));
+            }
+        }
+        after.put_pixel(150, 90, Rgba([0, 0, 0, 255]));
+
+        let (aligned, mask, changed_ratio, regions) = compare(&before, &after);
+        assert_eq!(aligned.dimensions(), (160, 96));
+        assert_eq!(*mask.get_pixel(50, 30), MASK_COLOR);
+        assert_eq!(mask.get_pixel(5, 5).0[3], 0);
+        assert_eq!(regions, vec![(32, 16, 64, 48), (144, 80, 16, 16)]);
+        // 12 + 1 of 60 tiles
+        assert_eq!(changed_ratio, 13.0 / 60.0);
+
+        // Another resolution is scaled to the before frame
+        let small = RgbaImage::from_pixel(80, 48, Rgba([255, 255, 255, 255]));
+        let (aligned, _, changed_ratio, _) = compare(&before, &small);
+        assert_eq!(aligned.dimensions(), (160, 96));
+        assert_eq!(changed_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_comparisons_showing_redacted_events_are_removed() {
+        let session_id = "comparison-remove-test";
+        let dir = comparison_dir(session_id);
+        fs::create_dir_all(&dir).unwrap();
+        let cache = |event_id: &str, before: &str, after: &str| {
+            let frame = |event_id: &str| ComparisonFrame {
+                event_id: event_id.to_string(),
+                path: String::new(),
+            };
+            let comparison = EventComparison {
+                session_id: session_id.to_string(),
+                event_id: event_id.to_string(),
+                before: frame(before),
+                after: frame(after),
+                mask_path: String::new(),
+                width: 1,
+                height: 1,
+                changed_ratio: 0.0,
+                changed_regions: Vec::new(),
+            };
+            fs::write(
+                dir.join(format!("{}.json", event_id)),
+                serde_json::to_string(&comparison).unwrap(),
+            )
+            .unwrap();
+            for suffix in ["before", "after", "mask"] {
+                fs::write(dir.join(format!("{}_{}.png", event_id, suffix)), b"png").unwrap();
+            }
+        };
+        // "a" shows the redacted "b" as its after frame; "c" doesn't show it
+        cache("a", "a", "b");
+        cache("b", "b", "c");
+        cache("c", "c", "d");
+
+        let redacted: HashSet<String> = ["b".to_string()].into();
+        assert_eq!(remove_for_events(session_id, &redacted), Ok(2));
+        assert!(!dir.join("a_after.png").exists());
+        assert!(!dir.join("b.json").exists());
+        assert!(dir.join("c.json").exists());
+        assert!(dir.join("c_mask.png").exists());
+
+        fs::remove_dir_all(storage::get_session_dir(session_id)).unwrap();
+    }
+}
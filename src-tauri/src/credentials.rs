@@ -0,0 +1,88 @@
+//! # Credentials Module - Password Entry Detection
+//!
+//! Detects when the user is about to type a secret, so keyboard capture can
+//! be suspended automatically and a `CredentialsEntered` placeholder recorded
+//! instead of the keystrokes.
+//!
+//! ## Signals (any one is enough)
+//! | Signal | Source | Platform |
+//! |--------|--------|----------|
+//! | Secure keyboard entry is on | `IsSecureEventInputEnabled()` (password fields in Safari, Chrome, Terminal) | macOS |
+//! | Focused element is a password field | `AXSecureTextField` role (see `accessibility` module) | macOS |
+//! | OS authentication dialog is focused | Foreground app name (`AUTH_DIALOG_APPS`) | All |
+//!
+//! ## When It Runs
+//! Checking is an accessibility IPC plus a window query, so it isn't done
+//! per key. The credential watcher polls it while recording, and the first
+//! key press after a click, Tab, Return, or shortcut re-checks immediately
+//! (focus may have moved into a password field).
+//!
+//! ## Limitations
+//! Web pages that draw their own password inputs without the platform role
+//! aren't detected; use the `keys` capture flag for those.
+
+use crate::{accessibility, focus};
+
+/// Foreground applications that only show authentication prompts.
+const AUTH_DIALOG_APPS: &[&str] = &[
+    "SecurityAgent",                       // macOS admin password prompt
+    "coreautha",                           // macOS Touch ID / password sheet
+    "loginwindow",                         // macOS lock screen
+    "CredentialUIBroker",                  // Windows credential prompt
+    "Consent",                             // Windows UAC prompt
+    "polkit-gnome-authentication-agent-1", // Linux (GNOME) authentication
+];
+
+/// Returns `true` if the user is currently entering a password or other secret.
+pub fn entry_active() -> bool {
+    secure_input_enabled() || focused_password_field() || auth_dialog_focused()
+}
+
+/// Whether the focused element is a password field.
+fn focused_password_field() -> bool {
+    accessibility::focused_element()
+        .is_some_and(|element| element.role.as_deref() == Some("AXSecureTextField"))
+}
+
+/// Whether an OS authentication prompt is the foreground window.
+fn auth_dialog_focused() -> bool {
+    focus::focused_window().is_ok_and(|window| is_auth_dialog_app(&window.app_name))
+}
+
+fn is_auth_dialog_app(app_name: &str) -> bool {
+    AUTH_DIALOG_APPS
+        .iter()
+        .any(|app| app.eq_ignore_ascii_case(app_name))
+}
+
+/// Whether an application has enabled secure keyboard entry.
+#[cfg(target_os = "macos")]
+fn secure_input_enabled() -> bool {
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn IsSecureEventInputEnabled() -> u8;
+    }
+
+    // SAFETY: Takes no arguments and only reads global input state
+    unsafe { IsSecureEventInputEnabled() != 0 }
+}
+
+/// Whether an application has enabled secure keyboard entry.
+///
+/// Not available on this platform.
+#[cfg(not(target_os = "macos"))]
+fn secure_input_enabled() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_dialog_apps_match_case_insensitively() {
+        assert!(is_auth_dialog_app("SecurityAgent"));
+        assert!(is_auth_dialog_app("credentialuibroker"));
+        assert!(!is_auth_dialog_app("Safari"));
+    }
+}
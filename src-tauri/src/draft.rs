@@ -0,0 +1,282 @@
+//! # Draft Module - Polishing a Copy of a Recording
+//!
+//! A recording is evidence of what was done; documentation often needs a
+//! little more (a step done on paper, a different order, two clicks shown
+//! as one step). `duplicate()` copies a saved session into a *draft*, and
+//! only drafts can be edited:
+//!
+//! | Edit | Function |
+//! |------|----------|
+//! | Move an event (and its step) | `move_event()` |
+//! | Add a step with text and an image | `insert_manual_step()` |
+//! | Split or join steps | `set_grouping()` (see `steps` module) |
+//!
+//! ## Copies
+//! The draft gets its own id and directory, with copies of every screenshot,
+//! so editing it (or redacting, scrubbing, re-recording a step) never
+//! touches the recording. Event ids are kept; `draft_of` names the original.
+//!
+//! ## Timestamps
+//! As with re-recorded steps (see `splice` module), recorded timestamps stay
+//! as they were and the edited order is stored in `edited_timeline`: a moved
+//! event takes the gap before it along, and a manual step is inserted
+//! without adding time.
+
+use crate::splice;
+use crate::storage;
+use crate::types::{Event, EventType, GroupingRule, RecordingSession, StepGrouping};
+use chrono::Duration;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Saves an editable copy of a saved session (see module docs).
+///
+/// # Returns
+/// * `Ok(RecordingSession)` - The saved draft
+/// * `Err(String)` - Error if the session couldn't be loaded, or the draft
+///   or its screenshots couldn't be written
+pub fn duplicate(session_id: &str) -> Result<RecordingSession, String> {
+    let mut draft = storage::load_session(session_id)?;
+    let draft_id = uuid::Uuid::new_v4().to_string();
+    let draft_dir = storage::get_session_dir(&draft_id);
+    fs::create_dir_all(&draft_dir)
+        .map_err(|e| format!("Failed to create draft directory: {:?}", e))?;
+
+    // Patches share their base frame: copy each file once
+    let mut copied: HashMap<String, String> = HashMap::new();
+    let mut copy = |path: &mut String| -> Result<(), String> {
+        if let Some(known) = copied.get(path.as_str()) {
+            *path = known.clone();
+            return Ok(());
+        }
+        let source = storage::resolve_asset_path(path);
+        let Some(filename) = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            return Ok(());
+        };
+        fs::copy(&source, draft_dir.join(&filename))
+            .map_err(|e| format!("Failed to copy screenshot {}: {:?}", path, e))?;
+        let target = storage::session_asset_path(&draft_id, &filename);
+        copied.insert(path.clone(), target.clone());
+        *path = target;
+        Ok(())
+    };
+    for event in draft.events.iter_mut() {
+        let screenshots = &mut event.screenshots;
+        for path in [
+            &mut screenshots.full_screen,
+            &mut screenshots.window_crop,
+            &mut screenshots.click_crop,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            copy(path)?;
+        }
+        if let Some(patch) = screenshots.full_screen_patch.as_mut() {
+            copy(&mut patch.base)?;
+        }
+    }
+
+    draft.draft_of = Some(session_id.to_string());
+    draft.session_id = draft_id;
+    storage::save_session(&draft)?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📝 Draft {} created from {} ({} screenshots copied)",
+        draft.session_id,
+        session_id,
+        copied.len()
+    );
+    Ok(draft)
+}
+
+/// Loads a saved draft for editing.
+///
+/// # Returns
+/// * `Ok(RecordingSession)` - The draft
+/// * `Err(String)` - Error if it can't be loaded or isn't a draft
+pub fn load_draft(session_id: &str) -> Result<RecordingSession, String> {
+    let session = storage::load_session(session_id)?;
+    if session.draft_of.is_none() {
+        return Err(format!(
+            "Session {} is not a draft; duplicate it first",
+            session_id
+        ));
+    }
+    Ok(session)
+}
+
+/// Moves an event before another one, or to the end.
+///
+/// # Arguments
+/// * `draft` - Draft to edit
+/// * `event_id` - Event to move
+/// * `before_event_id` - Event it is placed before (`None` = last)
+///
+/// # Returns
+/// * `Ok(())` - Draft edited in place (edited timeline rebuilt)
+/// * `Err(String)` - Error if either event isn't part of the draft
+pub fn move_event(
+    draft: &mut RecordingSession,
+    event_id: &str,
+    before_event_id: Option<&str>,
+) -> Result<(), String> {
+    let from = position(draft, event_id)?;
+    if let Some(before) = before_event_id {
+        position(draft, before)?;
+    }
+
+    let mut gaps = splice::gaps(draft);
+    let event = draft.events.remove(from);
+    let gap = gaps.remove(from);
+    let to = match before_event_id {
+        Some(before) => position(draft, before)?,
+        None => draft.events.len(),
+    };
+    draft.events.insert(to, event);
+    gaps.insert(to, gap);
+    splice::set_timeline(draft, gaps);
+    Ok(())
+}
+
+/// Inserts a `ManualStep` event with its text and an optional image.
+///
+/// # Arguments
+/// * `draft` - Draft to edit
+/// * `text` - Instruction text of the step
+/// * `image_path` - Image file to show (any format `image` reads; stored as PNG)
+/// * `before_event_id` - Event it is placed before (`None` = last)
+///
+/// # Returns
+/// * `Ok(Event)` - The inserted event
+/// * `Err(String)` - Error if the text is blank, the image can't be read, or
+///   `before_event_id` isn't part of the draft
+pub fn insert_manual_step(
+    draft: &mut RecordingSession,
+    text: &str,
+    image_path: Option<&str>,
+    before_event_id: Option<&str>,
+) -> Result<Event, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Manual step text must not be empty".to_string());
+    }
+    let to = match before_event_id {
+        Some(before) => position(draft, before)?,
+        None => draft.events.len(),
+    };
+
+    let mut event = Event::new(
+        EventType::ManualStep {
+            text: text.to_string(),
+        },
+        None,
+    );
+    if let Some(image_path) = image_path {
+        let image = image::open(Path::new(image_path))
+            .map_err(|e| format!("Failed to read image {}: {:?}", image_path, e))?;
+        let filename = format!("event_{}_full.png", event.id);
+        image
+            .save(storage::get_session_dir(&draft.session_id).join(&filename))
+            .map_err(|e| format!("Failed to write manual step image: {:?}", e))?;
+        event.screenshots.full_screen =
+            Some(storage::session_asset_path(&draft.session_id, &filename));
+    }
+
+    let mut gaps = splice::gaps(draft);
+    let timeline = draft.timeline();
+    event.timestamp = match to {
+        0 => draft.started_at,
+        _ => timeline[to - 1],
+    };
+    draft.events.insert(to, event.clone());
+    gaps.insert(to, Duration::zero());
+    splice::set_timeline(draft, gaps);
+    Ok(event)
+}
+
+/// Sets or clears the grouping rule of an event (see `steps` module).
+///
+/// # Returns
+/// * `Ok(())` - Draft edited in place
+/// * `Err(String)` - Error if the event isn't part of the draft
+pub fn set_grouping(
+    draft: &mut RecordingSession,
+    event_id: &str,
+    rule: Option<GroupingRule>,
+) -> Result<(), String> {
+    let event_id = draft.events[position(draft, event_id)?].id;
+    draft
+        .step_grouping
+        .retain(|grouping| grouping.event_id != event_id);
+    if let Some(rule) = rule {
+        draft.step_grouping.push(StepGrouping { event_id, rule });
+    }
+    Ok(())
+}
+
+fn position(draft: &RecordingSession, event_id: &str) -> Result<usize, String> {
+    draft
+        .events
+        .iter()
+        .position(|event| event.has_id(event_id))
+        .ok_or_else(|| format!("Event {} not found in session", event_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::steps;
+    use crate::types::{MouseButton, Position};
+
+    #[test]
+    fn test_reorder_insert_and_regroup() {
+        let start = chrono::Utc::now() - Duration::hours(1);
+        let mut draft = RecordingSession::new("draft-test".to_string());
+        draft.started_at = start;
+        for offset in [1, 4, 9] {
+            let mut click = Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(offset as f64, 0.0)),
+            );
+            click.timestamp = start + Duration::seconds(offset);
+            draft.add_event(click);
+        }
+        let ids: Vec<String> = draft
+            .events
+            .iter()
+            .map(|event| event.id.to_string())
+            .collect();
+        let second_id = draft.events[1].id;
+
+        // The last click moves first and takes its 5s gap along
+        move_event(&mut draft, &ids[2], Some(&ids[0])).unwrap();
+        let offsets: Vec<i64> = draft
+            .timeline()
+            .iter()
+            .map(|time| (*time - start).num_seconds())
+            .collect();
+        assert_eq!(offsets, vec![5, 6, 9]);
+
+        let manual = insert_manual_step(&mut draft, " Sign the form ", None, None).unwrap();
+        assert_eq!(draft.events.last().unwrap().id, manual.id);
+        assert_eq!((draft.timeline()[3] - start).num_seconds(), 9);
+
+        set_grouping(&mut draft, &manual.id.to_string(), Some(GroupingRule::Join)).unwrap();
+        let grouped = steps::group_steps(&draft);
+        assert_eq!(grouped.len(), 3);
+        assert!(grouped[2].title.ends_with("; Sign the form"));
+        assert_eq!(grouped[2].event_ids, vec![second_id, manual.id]);
+
+        assert!(insert_manual_step(&mut draft, "  ", None, None).is_err());
+        assert!(move_event(&mut draft, "missing", None).is_err());
+    }
+}
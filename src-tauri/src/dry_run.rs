@@ -0,0 +1,228 @@
+//! # Dry Run Module - Rehearsing a Recording
+//!
+//! Before an important live demo, a dry run checks that everything a
+//! recording needs works, without leaving anything behind. The session runs
+//! through the full pipeline (listener, wait detection, classification,
+//! filters, screenshot capture and encoding), but:
+//!
+//! - Screenshots are encoded in memory and counted, not written
+//! - No checkpoints, OCR index, `session.json`, or preview track are written
+//! - `stop_recording()` returns a summary, and `stop_dry_run()` the report
+//!
+//! ## Report
+//! What would have been saved (events per type, screenshots and their
+//! encoded size, displays captured), what failed (capture errors), and
+//! warnings for the usual setup problems:
+//!
+//! | Warning | Likely cause |
+//! |---------|--------------|
+//! | No input received | Accessibility permission missing (macOS), or nothing was clicked |
+//! | Clicks without screenshots | Screen Recording permission missing, or captures skipped by config |
+//! | Capture errors | Screen Recording permission, display disconnected, scrub rules without OCR |
+//!
+//! ## Scope
+//! Dry-run state is tied to the session id, so a real recording is never
+//! mistaken for one.
+
+use crate::types::RecordingSession;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Most capture errors kept in a report (the rest are only counted).
+const MAX_ERRORS: usize = 20;
+
+/// Outcome of a dry run (see module docs).
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct DryRunReport {
+    pub session_id: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub event_count: usize,
+    /// Events per type (`"Click"`, `"Wait"`, ...) as they would have been saved
+    pub events_by_type: BTreeMap<String, usize>,
+    /// Screenshots that would have been written
+    pub screenshot_count: usize,
+    /// Their encoded size in bytes
+    pub screenshot_bytes: u64,
+    /// Displays a screenshot was taken of
+    pub displays: Vec<u32>,
+    /// Events whose capture was skipped (display config or capture flags)
+    pub skipped_captures: usize,
+    pub capture_error_count: usize,
+    /// First `MAX_ERRORS` capture errors
+    pub capture_errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// The dry run in progress (`None` when not rehearsing).
+static ACTIVE: Lazy<Mutex<Option<DryRunReport>>> = Lazy::new(|| Mutex::new(None));
+
+/// Report of the last finished dry run, until taken.
+static FINISHED: Lazy<Mutex<Option<DryRunReport>>> = Lazy::new(|| Mutex::new(None));
+
+/// Marks a session as a dry run (before it starts recording).
+pub fn begin(session_id: &str) {
+    *FINISHED.lock().unwrap() = None;
+    *ACTIVE.lock().unwrap() = Some(DryRunReport {
+        session_id: session_id.to_string(),
+        ..DryRunReport::default()
+    });
+}
+
+/// Forgets a dry run that couldn't start.
+pub fn cancel(session_id: &str) {
+    let mut active = ACTIVE.lock().unwrap();
+    if active
+        .as_ref()
+        .is_some_and(|report| report.session_id == session_id)
+    {
+        *active = None;
+    }
+}
+
+/// Whether a session is a dry run (nothing of it may be written).
+pub fn covers(session_id: &str) -> bool {
+    ACTIVE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|report| report.session_id == session_id)
+}
+
+/// Whether any dry run is in progress.
+pub fn in_progress() -> bool {
+    ACTIVE.lock().unwrap().is_some()
+}
+
+/// Counts a screenshot that would have been written.
+pub fn record_screenshot(session_id: &str, display_id: Option<u32>, bytes: usize) {
+    update(session_id, |report| {
+        report.screenshot_count += 1;
+        report.screenshot_bytes += bytes as u64;
+        if let Some(id) = display_id.filter(|id| !report.displays.contains(id)) {
+            report.displays.push(id);
+        }
+    });
+}
+
+/// Counts an event whose capture was skipped.
+pub fn record_skipped(session_id: &str) {
+    update(session_id, |report| report.skipped_captures += 1);
+}
+
+/// Notes a failed capture.
+pub fn record_error(session_id: &str, error: &str) {
+    update(session_id, |report| {
+        report.capture_error_count += 1;
+        if report.capture_errors.len() < MAX_ERRORS {
+            report.capture_errors.push(error.to_string());
+        }
+    });
+}
+
+/// Ends the dry run of a stopped session and keeps its report for `take_report()`.
+///
+/// # Returns
+/// The report, or `None` if the session isn't a dry run
+pub fn finish(session: &RecordingSession) -> Option<DryRunReport> {
+    let mut active = ACTIVE.lock().unwrap();
+    if !active
+        .as_ref()
+        .is_some_and(|report| report.session_id == session.session_id)
+    {
+        return None;
+    }
+    let mut report = active.take()?;
+    drop(active);
+
+    report.started_at = Some(session.started_at);
+    report.stopped_at = session.stopped_at;
+    report.event_count = session.events.len();
+    for event in &session.events {
+        *report
+            .events_by_type
+            .entry(event.event_type.name().to_string())
+            .or_default() += 1;
+    }
+    report.warnings = warnings(&report);
+
+    *FINISHED.lock().unwrap() = Some(report.clone());
+    Some(report)
+}
+
+/// Takes the report of the last finished dry run.
+pub fn take_report() -> Option<DryRunReport> {
+    FINISHED.lock().unwrap().take()
+}
+
+fn update(session_id: &str, apply: impl FnOnce(&mut DryRunReport)) {
+    if let Some(report) = ACTIVE
+        .lock()
+        .unwrap()
+        .as_mut()
+        .filter(|report| report.session_id == session_id)
+    {
+        apply(report);
+    }
+}
+
+/// Setup problems a report points to (see module docs).
+fn warnings(report: &DryRunReport) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let input_events = report
+        .events_by_type
+        .iter()
+        .filter(|(name, _)| !matches!(name.as_str(), "Wait" | "Idle" | "Lifecycle"))
+        .map(|(_, count)| count)
+        .sum::<usize>();
+    if input_events == 0 {
+        warnings
+            .push("No input was received: check the Accessibility permission (macOS)".to_string());
+    }
+
+    let clicks = report.events_by_type.get("Click").copied().unwrap_or(0);
+    if clicks > 0 && report.screenshot_count == 0 {
+        warnings.push(format!(
+            "{} clicks but no screenshots: check the Screen Recording permission (macOS) and display overrides",
+            clicks
+        ));
+    }
+    if report.capture_error_count > 0 {
+        warnings.push(format!(
+            "{} screenshot captures failed (see capture_errors)",
+            report.capture_error_count
+        ));
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warnings_point_to_missing_permissions() {
+        let report = |events: &[(&str, usize)], screenshot_count: usize| DryRunReport {
+            events_by_type: events
+                .iter()
+                .map(|(name, count)| (name.to_string(), *count))
+                .collect(),
+            screenshot_count,
+            ..DryRunReport::default()
+        };
+
+        // Only timing events: the listener saw nothing
+        let no_input = warnings(&report(&[("Wait", 2)], 0));
+        assert_eq!(no_input.len(), 1);
+        assert!(no_input[0].contains("Accessibility"));
+
+        let no_screenshots = warnings(&report(&[("Click", 3)], 0));
+        assert_eq!(no_screenshots.len(), 1);
+        assert!(no_screenshots[0].contains("Screen Recording"));
+
+        assert!(warnings(&report(&[("Click", 3), ("KeyPress", 5)], 9)).is_empty());
+    }
+}
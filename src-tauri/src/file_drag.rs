@@ -0,0 +1,290 @@
+//! # File Drag Module - Files Dragged Out of Finder/Explorer
+//!
+//! Dragging a file from a file manager into an upload form, a mail, or
+//! another folder is one of the most common steps of a procedure, but to
+//! the input hook it is only a press and a release at two points. This
+//! module turns such a click into "Dragged file 'report.pdf' from Finder to
+//! Google Chrome" (see `FileDrag`).
+//!
+//! ## Inference
+//! A left click becomes a file drag at its release when:
+//! - It was pressed in a file manager (`FILE_MANAGERS`, by app name)
+//! - On a named item (the element label is the file name), not on the
+//!   window's chrome (`CHROME_ROLES`)
+//! - The release is at least `MIN_DRAG_PIXELS` away from the press
+//!
+//! The target is the front-most window under the release (see
+//! `window_capture::list_windows()`), FlowTrace's own windows excluded.
+//!
+//! ## Filesystem Correlation
+//! A drop on another file manager window usually names the target folder
+//! in its title ("Invoices", or a full path in Explorer). The folder is
+//! resolved below the home directory (`target_folder()`) and polled for up
+//! to `WATCH_TIMEOUT` until the file shows up there with a fresh change
+//! time; its path is then stored as `target_path`. Copies that take longer
+//! (large files, network drives) go without one.
+//!
+//! ## Platform Support
+//! Windows under the cursor are only listed on macOS; elsewhere the target
+//! is unknown and the step reads "Dragged file 'report.pdf' out of Explorer".
+
+use crate::types::{Event, EventType, FileDrag, MouseButton, Position};
+use crate::window_capture::WindowInfo;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// File manager app names (case-insensitive).
+const FILE_MANAGERS: &[&str] = &[
+    "Finder",
+    "Explorer",
+    "File Explorer",
+    "Windows Explorer",
+    "Files",
+    "Nautilus",
+    "Dolphin",
+    "Thunar",
+    "Nemo",
+];
+
+/// Roles of a file manager's own controls: dragging them doesn't move a file.
+const CHROME_ROLES: &[&str] = &[
+    "AXWindow",
+    "AXToolbar",
+    "AXButton",
+    "AXMenuButton",
+    "AXPopUpButton",
+    "AXScrollArea",
+    "AXScrollBar",
+    "AXSplitter",
+    "AXSplitGroup",
+    "AXTabGroup",
+];
+
+/// Shortest press-to-release distance of a drag (a click wobbles a few pixels).
+const MIN_DRAG_PIXELS: f64 = 8.0;
+
+/// How long a dropped file is waited for in the target folder.
+const WATCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Interval between checks of the target folder.
+const WATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Whether an app is a file manager.
+pub fn is_file_manager(app_name: &str) -> bool {
+    FILE_MANAGERS
+        .iter()
+        .any(|manager| manager.eq_ignore_ascii_case(app_name.trim()))
+}
+
+/// Infers a file drag from a recorded click and its release (see "Inference").
+///
+/// # Arguments
+/// * `click` - The recorded click
+/// * `release_x`, `release_y` - Cursor position at the release (global logical)
+/// * `windows` - On-screen windows, front-most first; only listed if the
+///   click qualifies, since listing them calls into the window server
+///
+/// # Returns
+/// `Some(FileDrag)` without a `target_path` (see "Filesystem Correlation")
+pub fn infer(
+    click: &Event,
+    release_x: f64,
+    release_y: f64,
+    windows: impl FnOnce() -> Vec<WindowInfo>,
+) -> Option<FileDrag> {
+    if !matches!(
+        click.event_type,
+        EventType::Click {
+            button: MouseButton::Left,
+            ..
+        }
+    ) {
+        return None;
+    }
+    let source_app = click
+        .app_name
+        .as_deref()
+        .filter(|app| is_file_manager(app))?;
+    let element = click.element.as_ref().filter(|element| {
+        !element
+            .role
+            .as_deref()
+            .is_some_and(|role| CHROME_ROLES.contains(&role))
+    })?;
+    let file_name = element.label.clone().filter(|label| !label.is_empty())?;
+    let press = click.position.as_ref()?;
+    if (release_x - press.x as f64).hypot(release_y - press.y as f64) < MIN_DRAG_PIXELS {
+        return None;
+    }
+
+    let own_process = std::process::id() as u64;
+    let target = windows().into_iter().find(|window| {
+        window.process_id != own_process
+            && (window.x..window.x + window.width).contains(&release_x)
+            && (window.y..window.y + window.height).contains(&release_y)
+    });
+    Some(FileDrag {
+        file_name: Some(file_name),
+        source_app: source_app.to_string(),
+        target_app: target
+            .as_ref()
+            .map(|window| window.app_name.clone())
+            .filter(|app| !app.is_empty()),
+        target_window: target
+            .map(|window| window.title)
+            .filter(|title| !title.is_empty()),
+        drop_position: Position::new(release_x, release_y),
+        target_path: None,
+    })
+}
+
+/// Folder a drop on a file manager window went to, from the window title.
+///
+/// # Arguments
+/// * `drag` - Inferred drag (with real, not anonymized, names)
+/// * `home` - The user's home directory
+///
+/// # Returns
+/// The title as an absolute folder path, or a folder of that name in the
+/// home directory, Desktop, Documents, or Downloads; `None` for drops on
+/// other apps and folders that can't be told from their title ("Recents")
+pub fn target_folder(drag: &FileDrag, home: &Path) -> Option<PathBuf> {
+    let target_app = drag.target_app.as_deref()?;
+    if !is_file_manager(target_app) {
+        return None;
+    }
+    let title = drag.target_window.as_deref()?.trim();
+    if Path::new(title).is_absolute() {
+        return Some(PathBuf::from(title)).filter(|folder| folder.is_dir());
+    }
+    [
+        home.to_path_buf(),
+        home.join("Desktop"),
+        home.join("Documents"),
+        home.join("Downloads"),
+    ]
+    .into_iter()
+    .map(|parent| parent.join(title))
+    .find(|folder| folder.is_dir())
+}
+
+/// The user's home directory.
+pub fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Polls a folder until a dropped file appears in it (blocks up to `WATCH_TIMEOUT`).
+///
+/// # Returns
+/// Path of the file if it appeared, or was changed, since the call
+pub fn wait_for_file(folder: &Path, file_name: &str) -> Option<PathBuf> {
+    // The drop may have been handled before the watch started
+    let since = SystemTime::now() - Duration::from_secs(1);
+    let path = folder.join(file_name);
+    let deadline = SystemTime::now() + WATCH_TIMEOUT;
+    while SystemTime::now() < deadline {
+        if fs::metadata(&path).is_ok_and(|metadata| changed_since(&metadata, since)) {
+            return Some(path);
+        }
+        std::thread::sleep(WATCH_INTERVAL);
+    }
+    None
+}
+
+/// Whether a file was created, moved, or written since `since`.
+///
+/// Moving a file keeps its modification time, so Unix uses the status
+/// change time, which a rename updates.
+#[cfg(unix)]
+fn changed_since(metadata: &fs::Metadata, since: SystemTime) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let changed = SystemTime::UNIX_EPOCH + Duration::from_secs(metadata.ctime().max(0) as u64);
+    // `ctime()` has whole seconds here
+    changed + Duration::from_secs(1) >= since
+}
+
+/// Whether a file was created, moved, or written since `since`.
+#[cfg(not(unix))]
+fn changed_since(metadata: &fs::Metadata, since: SystemTime) -> bool {
+    [metadata.created(), metadata.modified()]
+        .into_iter()
+        .flatten()
+        .any(|time| time >= since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UiElement;
+
+    fn window(app_name: &str, title: &str, x: f64) -> WindowInfo {
+        WindowInfo {
+            id: 1,
+            title: title.to_string(),
+            app_name: app_name.to_string(),
+            process_id: 42,
+            x,
+            y: 0.0,
+            width: 500.0,
+            height: 500.0,
+        }
+    }
+
+    #[test]
+    fn test_drag_from_finder_names_file_and_target() {
+        let mut click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(100.0, 100.0)),
+        );
+        click.app_name = Some("Finder".to_string());
+        click.element = Some(UiElement {
+            role: Some("AXImage".to_string()),
+            label: Some("report.pdf".to_string()),
+            value: None,
+        });
+        let windows = || {
+            vec![
+                window("Finder", "Downloads", 0.0),
+                window("Google Chrome", "Upload files — Google Chrome", 600.0),
+            ]
+        };
+
+        // A wobble is still a click
+        assert!(infer(&click, 103.0, 102.0, windows).is_none());
+
+        let drag = infer(&click, 800.0, 200.0, windows).unwrap();
+        assert_eq!(drag.file_name.as_deref(), Some("report.pdf"));
+        assert_eq!(drag.target_app.as_deref(), Some("Google Chrome"));
+        assert_eq!(
+            drag.describe(),
+            "Dragged file 'report.pdf' from Finder to Google Chrome"
+        );
+        assert!(target_folder(&drag, Path::new("/")).is_none());
+
+        // Another Finder window: the folder is resolved below home
+        let home = std::env::temp_dir().join(format!("file-drag-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(home.join("Documents/Invoices")).unwrap();
+        let mut to_folder = drag.clone();
+        to_folder.target_app = Some("Finder".to_string());
+        to_folder.target_window = Some("Invoices".to_string());
+        assert_eq!(
+            to_folder.describe(),
+            "Dragged file 'report.pdf' to 'Invoices' in Finder"
+        );
+        assert_eq!(
+            target_folder(&to_folder, &home),
+            Some(home.join("Documents/Invoices"))
+        );
+        fs::remove_dir_all(&home).unwrap();
+
+        // Dragging the window by its toolbar moves no file
+        click.element.as_mut().unwrap().role = Some("AXToolbar".to_string());
+        assert!(infer(&click, 800.0, 200.0, windows).is_none());
+    }
+}
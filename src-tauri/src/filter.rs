@@ -0,0 +1,174 @@
+//! # Filter Module - Choosing Which Events Are Recorded
+//!
+//! Applies a `config::FilterConfig` to every event before it is added to the
+//! active session, e.g., to record clicks only or to skip wait events.
+//!
+//! ## Scope
+//! - **Global**: `filter` in the recorder config, used by every recording
+//! - **Per session**: A filter passed to `start_recording()` replaces the
+//!   global one for that recording
+//!
+//! ## Application Rules
+//! `allow_apps` / `deny_apps` match the application focused when the event
+//! is added, by display name (`"Google Chrome"`) or process name
+//! (`"chrome"`), so typing in a password manager or clicking around an
+//! unrelated app is never recorded. If the focused application can't be
+//! determined, an allowlist drops the event and a denylist keeps it.
+//!
+//! ## Always Kept
+//! `Paused`, `Idle`, `CredentialsEntered`, `Lifecycle`, and `RecorderRestart`
+//! events mark intervals where capture was suspended or the user or machine
+//! was unavailable; dropping them would make the timeline look continuous, so
+//! filters never apply to them. Neither do they to `CaptureError` events,
+//! which say events may be missing. `Marker` events are inserted deliberately
+//! and are kept too.
+//!
+//! Clicks are filtered before their screenshots are captured, so excluded
+//! clicks cost nothing.
+
+use crate::config::FilterConfig;
+use crate::focus;
+use crate::types::{Event, EventType};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// Filter of the active recording.
+static ACTIVE: Lazy<Arc<Mutex<FilterConfig>>> =
+    Lazy::new(|| Arc::new(Mutex::new(FilterConfig::default())));
+
+/// Sets the filter for the recording that is starting.
+pub fn activate(filter: FilterConfig) {
+    *ACTIVE.lock().unwrap() = filter;
+}
+
+/// Returns `true` if the active recording keeps `event`.
+pub fn allows(event: &Event) -> bool {
+    // Clone so the lock isn't held while the focused window is queried
+    let filter = ACTIVE.lock().unwrap().clone();
+    let app_names = if filter.allow_apps.is_empty() && filter.deny_apps.is_empty() {
+        Vec::new()
+    } else {
+        focused_app_names(event)
+    };
+
+    matches_filter(&filter, event, &app_names)
+}
+
+/// Names of the focused application: the event's `app_name` plus the
+/// current foreground window's display and process name.
+fn focused_app_names(event: &Event) -> Vec<String> {
+    let mut names: Vec<String> = event.app_name.iter().cloned().collect();
+    if let Ok(window) = focus::focused_window() {
+        names.push(window.app_name);
+        if let Some(stem) = window.process_path.file_stem() {
+            names.push(stem.to_string_lossy().to_string());
+        }
+    }
+    names.retain(|name| !name.is_empty());
+    names
+}
+
+fn matches_filter(filter: &FilterConfig, event: &Event, app_names: &[String]) -> bool {
+    if matches!(
+        event.event_type,
+        EventType::Paused { .. }
+            | EventType::Idle { .. }
+            | EventType::CredentialsEntered { .. }
+            | EventType::Lifecycle { .. }
+            | EventType::RecorderRestart { .. }
+            | EventType::CaptureError { .. }
+            | EventType::Marker { .. }
+    ) {
+        return true;
+    }
+
+    let matches = |entry: &String| {
+        entry.eq_ignore_ascii_case(event.event_type.name())
+            || entry.eq_ignore_ascii_case(&event.action_category)
+    };
+    let matches_app = |entry: &String| {
+        app_names
+            .iter()
+            .any(|name| entry.eq_ignore_ascii_case(name))
+    };
+
+    (filter.include.is_empty() || filter.include.iter().any(matches))
+        && !filter.exclude.iter().any(matches)
+        && (filter.allow_apps.is_empty() || filter.allow_apps.iter().any(matches_app))
+        && !filter.deny_apps.iter().any(matches_app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MouseButton, Position};
+
+    #[test]
+    fn test_include_and_exclude_by_type_or_category() {
+        let click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(1.0, 1.0)),
+        );
+        let wait = Event::new(
+            EventType::Wait {
+                duration_seconds: 3.0,
+                cause: None,
+            },
+            None,
+        );
+        let paused = Event::new(
+            EventType::Paused {
+                duration_seconds: 3.0,
+            },
+            None,
+        );
+
+        let clicks_only = FilterConfig {
+            include: vec!["Click".to_string()],
+            ..Default::default()
+        };
+        assert!(matches_filter(&clicks_only, &click, &[]));
+        assert!(!matches_filter(&clicks_only, &wait, &[]));
+        assert!(matches_filter(&clicks_only, &paused, &[]));
+
+        let no_waits = FilterConfig {
+            exclude: vec!["wait".to_string()],
+            ..Default::default()
+        };
+        assert!(matches_filter(&no_waits, &click, &[]));
+        assert!(!matches_filter(&no_waits, &wait, &[]));
+    }
+
+    #[test]
+    fn test_allow_and_deny_by_focused_app() {
+        let click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(1.0, 1.0)),
+        );
+        let chrome = ["Google Chrome".to_string(), "chrome".to_string()];
+        let password_manager = ["1Password".to_string()];
+
+        let chrome_only = FilterConfig {
+            allow_apps: vec!["CHROME".to_string()],
+            ..Default::default()
+        };
+        assert!(matches_filter(&chrome_only, &click, &chrome));
+        assert!(!matches_filter(&chrome_only, &click, &password_manager));
+        // Unknown focus: an allowlist can't be satisfied
+        assert!(!matches_filter(&chrome_only, &click, &[]));
+
+        let no_secrets = FilterConfig {
+            deny_apps: vec!["1password".to_string()],
+            ..Default::default()
+        };
+        assert!(matches_filter(&no_secrets, &click, &chrome));
+        assert!(!matches_filter(&no_secrets, &click, &password_manager));
+        assert!(matches_filter(&no_secrets, &click, &[]));
+    }
+}
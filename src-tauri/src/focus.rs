@@ -0,0 +1,133 @@
+//! # Focus Module - Foreground Window Detection
+//!
+//! Window crops, app context (`app_name` / `window_title`), app filters, and
+//! app switch detection all need the foreground window. `active-win-pos-rs`
+//! is used while the Accessibility permission is granted; without it, the
+//! front-most application window in the window list (see `window_capture`
+//! module) stands in, so those features mostly keep working instead of
+//! failing outright.
+//!
+//! ## Fallback Accuracy
+//! The front-most normal window is usually the focused one, but a floating
+//! window of another app can win, and the process path is unknown (app
+//! filters match the display name only). Events that used the fallback are
+//! counted in the session's `capture_stats`, so reviewers know window crops
+//! and app names may be off.
+
+use crate::types::CaptureStats;
+use crate::window_capture;
+use active_win_pos_rs::{get_active_window, ActiveWindow, WindowPosition};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Events of the active recording whose window came from the fallback.
+static FALLBACK_EVENTS: AtomicU32 = AtomicU32::new(0);
+
+/// Whether the Accessibility permission was missing during the active recording.
+static ACCESSIBILITY_DENIED: AtomicBool = AtomicBool::new(false);
+
+/// Clears the stats for the recording that is starting.
+pub fn reset() {
+    FALLBACK_EVENTS.store(0, Ordering::SeqCst);
+    ACCESSIBILITY_DENIED.store(!accessibility_trusted(), Ordering::SeqCst);
+}
+
+/// Returns the foreground window (falling back to the window list).
+pub fn focused_window() -> Result<ActiveWindow, String> {
+    detect().map(|(window, _)| window)
+}
+
+/// Returns the foreground window for an event, counting fallback lookups.
+pub fn focused_window_for_event() -> Result<ActiveWindow, String> {
+    let (window, fallback) = detect()?;
+    if fallback {
+        FALLBACK_EVENTS.fetch_add(1, Ordering::SeqCst);
+    }
+    Ok(window)
+}
+
+/// Capture stats of the active recording (`None` if nothing was degraded).
+pub fn stats() -> Option<CaptureStats> {
+    let stats = CaptureStats {
+        window_fallbacks: FALLBACK_EVENTS.load(Ordering::SeqCst),
+        accessibility_denied: ACCESSIBILITY_DENIED.load(Ordering::SeqCst),
+    };
+    (stats.window_fallbacks > 0 || stats.accessibility_denied).then_some(stats)
+}
+
+/// Foreground window and whether it came from the fallback.
+fn detect() -> Result<(ActiveWindow, bool), String> {
+    if accessibility_trusted() {
+        if let Ok(window) = get_active_window() {
+            return Ok((window, false));
+        }
+    } else {
+        ACCESSIBILITY_DENIED.store(true, Ordering::SeqCst);
+    }
+
+    window_capture::list_windows()
+        .into_iter()
+        .next()
+        .map(|window| (active_window(window), true))
+        .ok_or_else(|| "Failed to get the foreground window".to_string())
+}
+
+fn active_window(window: window_capture::WindowInfo) -> ActiveWindow {
+    ActiveWindow {
+        title: window.title,
+        process_path: PathBuf::new(), // Not in the window list
+        app_name: window.app_name,
+        window_id: window.id.to_string(),
+        process_id: window.process_id,
+        position: WindowPosition {
+            x: window.x,
+            y: window.y,
+            width: window.width,
+            height: window.height,
+        },
+    }
+}
+
+/// Whether the process has the Accessibility permission.
+#[cfg(target_os = "macos")]
+fn accessibility_trusted() -> bool {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> u8;
+    }
+    // SAFETY: No arguments; only reads the process's trust state
+    unsafe { AXIsProcessTrusted() != 0 }
+}
+
+/// Whether the process has the Accessibility permission.
+///
+/// No such permission on this platform.
+#[cfg(not(target_os = "macos"))]
+fn accessibility_trusted() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_list_entry_becomes_active_window() {
+        let window = active_window(window_capture::WindowInfo {
+            id: 7,
+            title: "Invoice #1234".to_string(),
+            app_name: "Google Chrome".to_string(),
+            process_id: 42,
+            x: 10.0,
+            y: 20.0,
+            width: 800.0,
+            height: 600.0,
+        });
+
+        assert_eq!(window.window_id, "7");
+        assert_eq!(window.process_id, 42);
+        assert_eq!(window.app_name, "Google Chrome");
+        assert_eq!(window.position.width, 800.0);
+        assert!(window.process_path.file_stem().is_none());
+    }
+}
@@ -0,0 +1,543 @@
+//! # Follow Module - Guided Replay of a Recorded Procedure
+//!
+//! "Follow mode" turns a recording into a trainer: an always-on-top overlay
+//! shows the next step (title + click crop), and the step advances when the
+//! user performs a matching action.
+//!
+//! ## Flow
+//! ```text
+//! start_follow(session) → steps::group_steps() → overlay shows step 1
+//! rdev event → handle_event() → follow::observe() → match? → next step
+//!                                                 → `follow-step` Tauri event
+//! ```
+//!
+//! ## Matching
+//! | Step | Completed by |
+//! |------|--------------|
+//! | Click | Same button within `CLICK_TOLERANCE` logical px of the recorded click |
+//! | Key / Shortcut | Same key with the same Ctrl/Alt/Cmd modifiers |
+//! | Typing | As many typing keys as recorded (`TextInput` keystrokes), or the next step's action |
+//!
+//! Layouts drift between runs (window positions, scrolled lists), so a user
+//! who is ahead can also complete the step *after* the current one, which
+//! skips the current step. `skip_step()` covers everything else.
+//!
+//! ## Timing
+//! Each step's baseline is how long it took in the recording (time since
+//! the previous step). A step is *slow* when the operator needs more than
+//! `SLOW_FACTOR` times the baseline and at least `SLOW_MIN_EXTRA_SECONDS`
+//! more; the overlay gets the resulting budget to warn while the step is
+//! still open. When follow mode finishes or is stopped, a training report
+//! is written to:
+//!
+//! ```text
+//! recordings/[session-id]/training/report_[YYYYMMDD_HHMMSS].json
+//! ```
+
+use crate::postprocess;
+use crate::steps::{self, Step};
+use crate::storage;
+use crate::types::{EventType, MouseButton, RecordingSession};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Maximum distance (logical px) between a recorded and a performed click.
+const CLICK_TOLERANCE: f64 = 50.0;
+
+/// A step is slow when it takes more than this multiple of its baseline...
+const SLOW_FACTOR: f64 = 2.0;
+
+/// ...and at least this many seconds longer (short steps vary a lot).
+const SLOW_MIN_EXTRA_SECONDS: f64 = 5.0;
+
+/// Label of the overlay window.
+pub const OVERLAY_LABEL: &str = "follow-overlay";
+
+/// A user action observed while following.
+#[derive(Debug, Clone)]
+pub enum ObservedAction {
+    Click { button: MouseButton, x: f64, y: f64 },
+    Key { key: String, modifiers: Vec<String> },
+}
+
+/// What the overlay displays; sent as the `follow-step` Tauri event.
+#[derive(Serialize, Debug, Clone)]
+pub struct FollowState {
+    pub session_id: String,
+    /// 1-based number of the step to perform next
+    pub step_number: usize,
+    pub total_steps: usize,
+    /// Instruction for the next step (`None` once finished)
+    pub title: Option<String>,
+    /// Click crop of the next step as a `data:image/png;base64,...` URL
+    pub image: Option<String>,
+    /// Seconds after which the next step counts as slow (`None` without baseline)
+    pub budget_seconds: Option<f64>,
+    /// Steps completed so far that took significantly longer than recorded
+    pub slow_steps: usize,
+    pub finished: bool,
+}
+
+/// Operator timing of one follow session, compared against the recording.
+#[derive(Serialize, Debug, Clone)]
+pub struct TrainingReport {
+    pub session_id: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// `false` if follow mode was stopped before the last step
+    pub completed: bool,
+    pub steps: Vec<StepTiming>,
+    pub slow_steps: usize,
+    pub skipped_steps: usize,
+}
+
+/// Timing of one step in a training report.
+#[derive(Serialize, Debug, Clone)]
+pub struct StepTiming {
+    pub number: usize,
+    pub title: String,
+    /// Time the step took in the recording
+    pub baseline_seconds: Option<f64>,
+    /// Time the operator took (`None` if skipped)
+    pub actual_seconds: Option<f64>,
+    pub skipped: bool,
+    pub slow: bool,
+}
+
+/// Action a step expects from the user.
+#[derive(Debug, Clone)]
+enum ExpectedAction {
+    Click {
+        button: MouseButton,
+        x: f64,
+        y: f64,
+    },
+    Key {
+        key: String,
+        modifiers: Vec<String>,
+    },
+    Typing {
+        keys: usize,
+    },
+    /// Steps that can't be matched automatically (skip manually)
+    Manual,
+}
+
+struct FollowStep {
+    step: Step,
+    expected: ExpectedAction,
+    /// Time the step took in the recording
+    baseline_seconds: Option<f64>,
+}
+
+/// Active follow session.
+struct FollowSession {
+    session_id: String,
+    steps: Vec<FollowStep>,
+    /// Index of the step to perform next
+    current: usize,
+    /// Typing keys seen for the current step
+    typed: usize,
+    started_at: DateTime<Utc>,
+    /// When the current step became the next one to perform
+    step_started_at: DateTime<Utc>,
+    /// Timings of the steps done (or skipped) so far
+    timings: Vec<StepTiming>,
+}
+
+/// Follow session in progress, if any.
+static FOLLOW: Lazy<Arc<Mutex<Option<FollowSession>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Starts following a saved session.
+///
+/// # Returns
+/// * `Ok(FollowState)` - First step to perform
+/// * `Err(String)` - Error if the session couldn't be loaded or has no steps
+pub fn start(session_id: &str) -> Result<FollowState, String> {
+    let session = storage::load_session(session_id)?;
+    let steps = follow_steps(&session);
+    if steps.is_empty() {
+        return Err("Session has no steps to follow".to_string());
+    }
+
+    let now = Utc::now();
+    let follow = FollowSession {
+        session_id: session_id.to_string(),
+        steps,
+        current: 0,
+        typed: 0,
+        started_at: now,
+        step_started_at: now,
+        timings: Vec::new(),
+    };
+    let state = follow.state();
+    *FOLLOW.lock().unwrap() = Some(follow);
+
+    #[cfg(debug_assertions)]
+    println!("🧭 Follow mode started: {} steps", state.total_steps);
+
+    Ok(state)
+}
+
+/// Ends follow mode.
+///
+/// # Returns
+/// The training report if follow mode was stopped before the last step
+/// (finished sessions write theirs when the last step is done)
+pub fn stop() -> Option<TrainingReport> {
+    let follow = FOLLOW.lock().unwrap().take()?;
+    if follow.current >= follow.steps.len() {
+        return None;
+    }
+    let report = follow.report(false);
+    save_report(&report);
+    Some(report)
+}
+
+/// Returns `true` while a follow session is active.
+pub fn is_active() -> bool {
+    FOLLOW
+        .lock()
+        .map(|follow| follow.is_some())
+        .unwrap_or(false)
+}
+
+/// Current overlay state, if following.
+pub fn current_state() -> Option<FollowState> {
+    FOLLOW.lock().unwrap().as_ref().map(FollowSession::state)
+}
+
+/// Skips the current step (e.g., when automatic matching can't detect it).
+pub fn skip_step() -> Option<FollowState> {
+    let mut lock = FOLLOW.lock().unwrap();
+    let follow = lock.as_mut()?;
+    if follow.current >= follow.steps.len() {
+        return Some(follow.state());
+    }
+    follow.skip_current();
+    follow.step_started_at = Utc::now();
+    Some(follow.state())
+}
+
+/// Feeds a user action into follow mode.
+///
+/// # Returns
+/// The new state if the action completed a step, `None` otherwise
+pub fn observe(action: &ObservedAction) -> Option<FollowState> {
+    let mut lock = FOLLOW.lock().ok()?;
+    let follow = lock.as_mut()?;
+    if follow.current >= follow.steps.len() {
+        return None;
+    }
+
+    let current = &follow.steps[follow.current].expected;
+    if let ExpectedAction::Typing { keys } = current {
+        if is_typing(action) {
+            follow.typed += 1;
+            if follow.typed >= *keys {
+                follow.complete_current();
+                return Some(follow.state());
+            }
+            return None;
+        }
+    }
+
+    if matches(current, action) {
+        follow.complete_current();
+    } else if follow
+        .steps
+        .get(follow.current + 1)
+        .is_some_and(|next| matches(&next.expected, action))
+    {
+        // User is ahead: they performed the step after the current one
+        follow.skip_current();
+        follow.complete_current();
+    } else {
+        return None;
+    }
+
+    Some(follow.state())
+}
+
+impl FollowSession {
+    /// Records the current step as done and moves to the next one.
+    ///
+    /// After a skip-ahead, the timing still runs from `step_started_at`,
+    /// which `skip_current()` leaves untouched (the operator was working on
+    /// both steps).
+    fn complete_current(&mut self) {
+        let now = Utc::now();
+        let actual = (now - self.step_started_at).num_milliseconds() as f64 / 1000.0;
+        let baseline = self.steps[self.current].baseline_seconds;
+        let slow = baseline.is_some_and(|baseline| actual > slow_budget(baseline));
+
+        #[cfg(debug_assertions)]
+        if slow {
+            println!(
+                "🐢 Follow step {} took {:.1}s (recorded: {:.1}s)",
+                self.current + 1,
+                actual,
+                baseline.unwrap_or_default()
+            );
+        }
+
+        self.record_timing(Some(actual), false, slow);
+        self.step_started_at = now;
+    }
+
+    /// Records the current step as skipped and moves to the next one.
+    fn skip_current(&mut self) {
+        self.record_timing(None, true, false);
+    }
+
+    fn record_timing(&mut self, actual_seconds: Option<f64>, skipped: bool, slow: bool) {
+        let follow_step = &self.steps[self.current];
+        self.timings.push(StepTiming {
+            number: follow_step.step.number,
+            title: follow_step.step.title.clone(),
+            baseline_seconds: follow_step.baseline_seconds,
+            actual_seconds,
+            skipped,
+            slow,
+        });
+        self.current += 1;
+        self.typed = 0;
+
+        #[cfg(debug_assertions)]
+        println!(
+            "🧭 Follow mode: step {}/{}",
+            self.current + 1,
+            self.steps.len()
+        );
+
+        if self.current >= self.steps.len() && save_report(&self.report(true)).is_some() {
+            #[cfg(debug_assertions)]
+            println!("🎓 Follow mode finished, training report saved");
+        }
+    }
+
+    /// Builds the training report for the steps so far.
+    fn report(&self, completed: bool) -> TrainingReport {
+        TrainingReport {
+            session_id: self.session_id.clone(),
+            started_at: self.started_at,
+            ended_at: Utc::now(),
+            completed,
+            steps: self.timings.clone(),
+            slow_steps: self.timings.iter().filter(|timing| timing.slow).count(),
+            skipped_steps: self.timings.iter().filter(|timing| timing.skipped).count(),
+        }
+    }
+
+    fn state(&self) -> FollowState {
+        let next = self.steps.get(self.current);
+        FollowState {
+            session_id: self.session_id.clone(),
+            step_number: self.current + 1,
+            total_steps: self.steps.len(),
+            title: next.map(|next| next.step.title.clone()),
+            image: next
+                .and_then(|next| next.step.detail_screenshot.as_deref())
+                .and_then(image_data_url),
+            budget_seconds: next.and_then(|next| next.baseline_seconds).map(slow_budget),
+            slow_steps: self.timings.iter().filter(|timing| timing.slow).count(),
+            finished: next.is_none(),
+        }
+    }
+}
+
+/// Groups a session into steps and derives the action each one expects.
+fn follow_steps(session: &RecordingSession) -> Vec<FollowStep> {
+    let timeline = session.timeline();
+    let mut previous_end = session.started_at;
+
+    steps::group_steps(session)
+        .into_iter()
+        .map(|step| {
+            // Baseline: from the end of the previous step to the end of this one
+            let end = step
+                .event_ids
+                .last()
+                .and_then(|id| session.events.iter().position(|event| &event.id == id))
+                .map(|index| timeline[index]);
+            let baseline_seconds = end.map(|end| {
+                let seconds = (end - previous_end).num_milliseconds() as f64 / 1000.0;
+                previous_end = end;
+                seconds.max(0.0)
+            });
+
+            let first = step
+                .event_ids
+                .first()
+                .and_then(|id| session.events.iter().find(|event| &event.id == id));
+            let expected = match first.map(|event| (&event.event_type, &event.position)) {
+                _ if step.category == "text_input" => ExpectedAction::Typing {
+                    keys: keystrokes(session, &step),
+                },
+                Some((EventType::Click { button, .. }, Some(position))) => ExpectedAction::Click {
+                    button: button.clone(),
+                    x: position.x as f64,
+                    y: position.y as f64,
+                },
+                Some((EventType::KeyPress { key, .. }, _)) => ExpectedAction::Key {
+                    key: key.clone(),
+                    modifiers: Vec::new(),
+                },
+                Some((EventType::Shortcut { modifiers, key }, _)) => ExpectedAction::Key {
+                    key: key.clone(),
+                    modifiers: modifiers.clone(),
+                },
+                _ => ExpectedAction::Manual,
+            };
+            FollowStep {
+                step,
+                expected,
+                baseline_seconds,
+            }
+        })
+        .collect()
+}
+
+/// Keystrokes that make up a typing step.
+fn keystrokes(session: &RecordingSession, step: &Step) -> usize {
+    session
+        .events
+        .iter()
+        .filter(|event| step.event_ids.contains(&event.id))
+        .map(|event| match &event.event_type {
+            EventType::TextInput { keystrokes, .. } => *keystrokes,
+            _ => 1,
+        })
+        .sum()
+}
+
+fn matches(expected: &ExpectedAction, action: &ObservedAction) -> bool {
+    match (expected, action) {
+        (
+            ExpectedAction::Click { button, x, y },
+            ObservedAction::Click {
+                button: clicked,
+                x: clicked_x,
+                y: clicked_y,
+            },
+        ) => button == clicked && (x - clicked_x).hypot(y - clicked_y) <= CLICK_TOLERANCE,
+        (
+            ExpectedAction::Key { key, modifiers },
+            ObservedAction::Key {
+                key: pressed,
+                modifiers: held,
+            },
+        ) => key == pressed && without_shift(modifiers) == without_shift(held),
+        _ => false,
+    }
+}
+
+/// Keys that count as typing (same rule as `steps` grouping).
+fn is_typing(action: &ObservedAction) -> bool {
+    match action {
+        ObservedAction::Key { key, modifiers } => {
+            without_shift(modifiers).is_empty()
+                && (postprocess::typed_char(key, false).is_some()
+                    || matches!(key.as_str(), "Backspace" | "Delete"))
+        }
+        ObservedAction::Click { .. } => false,
+    }
+}
+
+fn without_shift(modifiers: &[String]) -> Vec<&String> {
+    modifiers
+        .iter()
+        .filter(|modifier| *modifier != "Shift")
+        .collect()
+}
+
+/// Seconds after which a step with the given baseline counts as slow.
+fn slow_budget(baseline_seconds: f64) -> f64 {
+    (baseline_seconds * SLOW_FACTOR).max(baseline_seconds + SLOW_MIN_EXTRA_SECONDS)
+}
+
+/// Writes a training report into the session's `training` directory.
+///
+/// # Returns
+/// Path of the report, or `None` if it couldn't be written (logged)
+fn save_report(report: &TrainingReport) -> Option<PathBuf> {
+    let dir = storage::get_session_dir(&report.session_id).join("training");
+    let path = dir.join(format!(
+        "report_{}.json",
+        report.started_at.format("%Y%m%d_%H%M%S")
+    ));
+
+    let result = fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create training directory: {:?}", e))
+        .and_then(|_| {
+            serde_json::to_string_pretty(report)
+                .map_err(|e| format!("Failed to serialize training report: {:?}", e))
+        })
+        .and_then(|json_data| {
+            fs::write(&path, json_data)
+                .map_err(|e| format!("Failed to write training report: {:?}", e))
+        });
+
+    match result {
+        Ok(()) => Some(path),
+        Err(e) => {
+            eprintln!("⚠️  {}", e);
+            None
+        }
+    }
+}
+
+/// Reads a stored screenshot as a PNG data URL for the overlay webview.
+fn image_data_url(asset: &str) -> Option<String> {
+    let bytes = fs::read(storage::resolve_asset_path(asset)).ok()?;
+    Some(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str) -> ObservedAction {
+        ObservedAction::Key {
+            key: name.to_string(),
+            modifiers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_click_tolerance_and_typing_keys() {
+        let click = ExpectedAction::Click {
+            button: MouseButton::Left,
+            x: 100.0,
+            y: 100.0,
+        };
+        let near = ObservedAction::Click {
+            button: MouseButton::Left,
+            x: 130.0,
+            y: 120.0,
+        };
+        let far = ObservedAction::Click {
+            button: MouseButton::Left,
+            x: 300.0,
+            y: 100.0,
+        };
+
+        assert!(matches(&click, &near));
+        assert_eq!(slow_budget(2.0), 7.0);
+        assert_eq!(slow_budget(20.0), 40.0);
+        assert!(!matches(&click, &far));
+        assert!(is_typing(&key("KeyA")));
+        assert!(!is_typing(&ObservedAction::Key {
+            key: "KeyS".to_string(),
+            modifiers: vec!["Meta".to_string()],
+        }));
+    }
+}
@@ -0,0 +1,69 @@
+//! # Fuzz Module - Entry Points for cargo-fuzz
+//!
+//! Exposes the parsers that handle untrusted input to the fuzz targets in
+//! `src-tauri/fuzz` (only compiled with the `fuzzing` feature).
+//!
+//! ```text
+//! cd src-tauri
+//! cargo +nightly fuzz run session_json
+//! cargo +nightly fuzz run session_archive
+//! ```
+//!
+//! Each entry point must never panic, hang, or write outside its scratch
+//! directory, whatever the input.
+
+use crate::importer;
+use crate::steps;
+use crate::storage;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+/// Parses arbitrary bytes as `session.json` and runs what a loaded session goes through.
+pub fn session_json(data: &[u8]) {
+    let Ok(json_data) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(session) = storage::parse_session(json_data) else {
+        return;
+    };
+
+    // Loaded sessions are grouped into steps and saved again
+    let _ = storage::is_valid_session_id(&session.session_id);
+    let _ = steps::group_steps(&session);
+    serde_json::to_string(&session).expect("a parsed session must serialize");
+}
+
+/// Extracts arbitrary bytes as a session archive into a scratch directory.
+///
+/// # Panics
+/// If any extracted file ends up outside the scratch directory.
+pub fn session_archive(data: &[u8]) {
+    let scratch = std::env::temp_dir().join(format!("flowtrace-fuzz-{}", uuid::Uuid::new_v4()));
+
+    let _ = importer::extract_archive_from(Cursor::new(data), &scratch);
+    assert_contained(&scratch, &scratch);
+
+    let _ = fs::remove_dir_all(&scratch);
+}
+
+/// Asserts that every path below `dir` resolves inside `root`.
+fn assert_contained(dir: &Path, root: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+
+    for entry in entries.flatten() {
+        let path = fs::canonicalize(entry.path()).unwrap_or_else(|_| entry.path());
+        assert!(
+            path.starts_with(&root),
+            "extracted {:?} outside {:?}",
+            path,
+            root
+        );
+        if path.is_dir() {
+            assert_contained(&path, &root);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! # Idle Module - Away-From-Keyboard Detection
+//!
+//! A `Wait` is a short pause between two actions (reading, a page load). A
+//! gap without any input longer than `idle.threshold_seconds` (5 minutes by
+//! default) means the user stepped away: it is recorded as one `Idle` event
+//! instead, timestamped when input stopped, so a coffee break doesn't show up
+//! as a very long wait on the next step. Sleep and screen lock time is not
+//! counted (see `lifecycle` module).
+//!
+//! ## Auto-Pause
+//! With `idle.auto_pause` enabled, a watcher thread in `lib.rs` doesn't wait
+//! for the next input: once the threshold is reached it records the `Idle`
+//! event, pauses the recording as if the user had pressed Pause, and notifies
+//! the frontend with the `recording-auto-paused` Tauri event (payload:
+//! `AutoPause`). Capture continues only after the user resumes.
+
+use crate::config::IdleConfig;
+use crate::types::{Event, EventType};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Payload of the `recording-auto-paused` event.
+#[derive(Serialize, Debug, Clone)]
+pub struct AutoPause {
+    /// Time without input when the recording was paused
+    pub idle_seconds: f64,
+    /// Result message of `pause_recording()` (or its error)
+    pub message: String,
+}
+
+/// Returns the `Idle` event for a gap without input, if it is long enough.
+///
+/// # Arguments
+/// * `config` - Idle settings (`threshold_seconds: None` disables detection)
+/// * `last_input` - When the last input was received
+/// * `idle_seconds` - Length of the gap, sleep and screen lock excluded
+///
+/// # Returns
+/// * `Some(Event)` - `Idle` event timestamped at `last_input`
+/// * `None` - Detection is off or the gap is shorter than the threshold
+pub fn idle_event(
+    config: &IdleConfig,
+    last_input: DateTime<Utc>,
+    idle_seconds: f64,
+) -> Option<Event> {
+    let threshold_seconds = config.threshold_seconds?;
+    if idle_seconds < threshold_seconds {
+        return None;
+    }
+
+    let mut event = Event::new(
+        EventType::Idle {
+            duration_seconds: idle_seconds,
+        },
+        None,
+    );
+    event.timestamp = last_input;
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_event_only_past_threshold() {
+        let last_input = "2026-02-01T15:43:18Z".parse::<DateTime<Utc>>().unwrap();
+        let mut config = IdleConfig {
+            threshold_seconds: Some(300.0),
+            auto_pause: false,
+        };
+
+        assert!(idle_event(&config, last_input, 299.9).is_none());
+
+        let event = idle_event(&config, last_input, 612.3).unwrap();
+        assert!(matches!(
+            event.event_type,
+            EventType::Idle { duration_seconds } if duration_seconds == 612.3
+        ));
+        assert_eq!(event.timestamp, last_input);
+        assert_eq!(event.action_category, "idle");
+
+        config.threshold_seconds = None;
+        assert!(idle_event(&config, last_input, 612.3).is_none());
+    }
+}
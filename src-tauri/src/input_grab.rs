@@ -0,0 +1,197 @@
+//! # Input Grab Module - Blocking the User's Input During Replay
+//!
+//! A screenshot refresh (see `refresh` module) drives the real mouse and
+//! keyboard, so a nudge of the mouse or a stray key press mid-replay lands
+//! in the application and corrupts the run. While a replay suppresses
+//! input, an `rdev::grab()` event tap swallows physical input and lets only
+//! the replay's own synthetic events through.
+//!
+//! ## Telling Synthetic From Physical Input
+//! `rdev` doesn't mark simulated events, so the replay announces each one
+//! with `expect()` right before sending it. A grabbed event passes if it
+//! matches an announcement from the last `EXPECT_WINDOW` (mouse moves within
+//! a pixel); each announcement lets one event through.
+//!
+//! ## Escape
+//! Escape is swallowed like any other key, and stops the refresh from the
+//! grab callback.
+//!
+//! ## Platforms
+//! macOS only (`rdev`'s `unstable_grab` feature), with the Accessibility
+//! permission recording needs anyway. Elsewhere, or if the event tap can't
+//! be created, `begin()` returns `false` and the replay runs unguarded.
+//!
+//! `rdev::grab()` never returns, so the tap is created on first use and kept
+//! for the rest of the process; outside a replay it passes everything through.
+
+#[cfg(target_os = "macos")]
+use crate::refresh;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long an announced synthetic event may take to reach the tap.
+const EXPECT_WINDOW: Duration = Duration::from_millis(250);
+
+/// Whether physical input is being swallowed.
+static SUPPRESSING: AtomicBool = AtomicBool::new(false);
+
+/// Synthetic inputs announced by the replay and not seen by the tap yet.
+static EXPECTED: Lazy<Mutex<Vec<(rdev::EventType, Instant)>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Starts swallowing physical input.
+///
+/// # Returns
+/// `true` if input is suppressed until `end()`; `false` if grabbing isn't
+/// available (the replay runs unguarded)
+pub fn begin() -> bool {
+    if !tap_running() {
+        return false;
+    }
+    EXPECTED.lock().unwrap().clear();
+    SUPPRESSING.store(true, Ordering::SeqCst);
+    true
+}
+
+/// Lets physical input through again.
+pub fn end() {
+    SUPPRESSING.store(false, Ordering::SeqCst);
+    EXPECTED.lock().unwrap().clear();
+}
+
+/// Announces a synthetic input that must pass the tap.
+pub fn expect(event_type: &rdev::EventType) {
+    if SUPPRESSING.load(Ordering::SeqCst) {
+        EXPECTED.lock().unwrap().push((*event_type, Instant::now()));
+    }
+}
+
+/// Grab callback: `Some` passes the event on, `None` swallows it.
+#[cfg(target_os = "macos")]
+fn on_grabbed(event: rdev::Event) -> Option<rdev::Event> {
+    if !SUPPRESSING.load(Ordering::SeqCst)
+        || take_expected(
+            &mut EXPECTED.lock().unwrap(),
+            &event.event_type,
+            Instant::now(),
+        )
+    {
+        return Some(event);
+    }
+
+    if let rdev::EventType::KeyPress(rdev::Key::Escape) = event.event_type {
+        refresh::stop();
+    }
+    None
+}
+
+/// Removes the announcement matching a grabbed event, if any (see module docs).
+///
+/// # Returns
+/// `true` if the event is one of the replay's own
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn take_expected(
+    expected: &mut Vec<(rdev::EventType, Instant)>,
+    event_type: &rdev::EventType,
+    now: Instant,
+) -> bool {
+    expected.retain(|(_, at)| now.duration_since(*at) <= EXPECT_WINDOW);
+    match expected
+        .iter()
+        .position(|(announced, _)| same_input(announced, event_type))
+    {
+        Some(index) => {
+            expected.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn same_input(announced: &rdev::EventType, grabbed: &rdev::EventType) -> bool {
+    match (announced, grabbed) {
+        // Positions come back rounded to the pixel grid
+        (rdev::EventType::MouseMove { x, y }, rdev::EventType::MouseMove { x: gx, y: gy }) => {
+            (x - gx).abs() <= 1.0 && (y - gy).abs() <= 1.0
+        }
+        _ => announced == grabbed,
+    }
+}
+
+/// Creates the event tap unless it exists, and reports whether it is running.
+#[cfg(target_os = "macos")]
+fn tap_running() -> bool {
+    /// How long a failing `rdev::grab()` takes at most to return its error.
+    const STARTUP_TIMEOUT: Duration = Duration::from_millis(200);
+
+    static STARTED: AtomicBool = AtomicBool::new(false);
+    static FAILED: AtomicBool = AtomicBool::new(false);
+
+    if STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_ok()
+    {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            // Blocks for the rest of the process unless the tap can't be created
+            if let Err(e) = rdev::grab(on_grabbed) {
+                let _ = sender.send(format!("{:?}", e));
+            }
+        });
+        if let Ok(error) = receiver.recv_timeout(STARTUP_TIMEOUT) {
+            eprintln!("⚠️  Input suppression unavailable: {}", error);
+            FAILED.store(true, Ordering::SeqCst);
+        }
+    }
+    !FAILED.load(Ordering::SeqCst)
+}
+
+/// Creates the event tap unless it exists, and reports whether it is running.
+///
+/// Not supported on this platform.
+#[cfg(not(target_os = "macos"))]
+fn tap_running() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_announced_input_passes() {
+        let now = Instant::now();
+        let click = rdev::EventType::ButtonPress(rdev::Button::Left);
+        let mut expected = vec![
+            (rdev::EventType::MouseMove { x: 709.4, y: 328.0 }, now),
+            (click, now),
+        ];
+
+        // The replay's own move, rounded by the OS
+        assert!(take_expected(
+            &mut expected,
+            &rdev::EventType::MouseMove { x: 709.0, y: 328.0 },
+            now
+        ));
+        // A physical move elsewhere
+        assert!(!take_expected(
+            &mut expected,
+            &rdev::EventType::MouseMove { x: 20.0, y: 40.0 },
+            now
+        ));
+        // Each announcement passes one event
+        assert!(take_expected(&mut expected, &click, now));
+        assert!(!take_expected(&mut expected, &click, now));
+
+        // Announcements expire
+        expected.push((click, now));
+        assert!(!take_expected(
+            &mut expected,
+            &click,
+            now + EXPECT_WINDOW * 2
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! # Journal Module - Streaming Events to Disk
+//!
+//! Checkpoints (see `resume` module) rewrite `session.json` every few
+//! seconds, so a crash still loses the events since the last one. While a
+//! checkpointed recording runs, every event is also appended to its journal
+//! the moment it is recorded:
+//!
+//! ```text
+//! recordings/[session-id]/events.jsonl     one event (session.json format) per line
+//! ```
+//!
+//! ## Amendments
+//! Some events are completed after they were recorded (click and key hold
+//! durations, key repeats, merged scroll ticks, response latency, marker
+//! labels). The amended event is appended again; when the journal is read,
+//! a later line with the same `id` replaces the earlier one, in the place of
+//! the first. A line cut off by the crash is skipped.
+//!
+//! ## Lifecycle
+//! - `open()` when checkpointing begins (the first checkpoint holds all
+//!   earlier events, so an existing journal is truncated)
+//! - `close()` when the recording stops; once `stop_recording()` has written
+//!   the final `session.json` (header and post-processed events), the
+//!   journal is deleted with `remove()`
+//! - Recovery (`resume` module) merges the journal over the last checkpoint
+//!
+//! Lock order: CURRENT_SESSION before the journal (events are appended
+//! while the session is locked).
+
+use crate::storage;
+use crate::types::{Event, RecordingSession};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The journal being written.
+struct Journal {
+    session_id: String,
+    file: File,
+}
+
+static JOURNAL: Lazy<Mutex<Option<Journal>>> = Lazy::new(|| Mutex::new(None));
+
+/// Location of a session's journal.
+pub fn journal_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("events.jsonl")
+}
+
+/// Starts journaling the events of a session (see module docs).
+///
+/// # Returns
+/// * `Ok(())` - Events of `session_id` are appended from now on
+/// * `Err(String)` - Error if the journal couldn't be created
+pub fn open(session_id: &str) -> Result<(), String> {
+    let session_dir = storage::get_session_dir(session_id);
+    fs::create_dir_all(&session_dir)
+        .map_err(|e| format!("Failed to create session directory: {:?}", e))?;
+    let file = File::create(journal_path(session_id))
+        .map_err(|e| format!("Failed to create event journal: {:?}", e))?;
+
+    *JOURNAL.lock().unwrap() = Some(Journal {
+        session_id: session_id.to_string(),
+        file,
+    });
+    Ok(())
+}
+
+/// Appends a new or amended event of the journaled session.
+///
+/// Events of other sessions are ignored. A failed write is reported once
+/// and stops the journal (checkpoints continue).
+pub fn append(session_id: &str, event: &Event) {
+    let mut journal = JOURNAL.lock().unwrap();
+    let Some(open) = journal
+        .as_mut()
+        .filter(|open| open.session_id == session_id)
+    else {
+        return;
+    };
+
+    let written = serde_json::to_string(event)
+        .map_err(|e| format!("{:?}", e))
+        .and_then(|mut line| {
+            line.push('\n');
+            open.file
+                .write_all(line.as_bytes())
+                .map_err(|e| format!("{:?}", e))
+        });
+    if let Err(e) = written {
+        eprintln!("⚠️  Event journal stopped, failed to append: {}", e);
+        *journal = None;
+    }
+}
+
+/// Stops journaling a session (the file stays until `remove()`).
+pub fn close(session_id: &str) {
+    let mut journal = JOURNAL.lock().unwrap();
+    if journal
+        .as_ref()
+        .is_some_and(|open| open.session_id == session_id)
+    {
+        *journal = None;
+    }
+}
+
+/// Deletes a session's journal once its final `session.json` is saved.
+pub fn remove(session_id: &str) {
+    close(session_id);
+    let _ = fs::remove_file(journal_path(session_id));
+}
+
+/// Events of a session's journal, amendments applied (see module docs).
+///
+/// # Returns
+/// Events in recording order; empty if there is no journal
+pub fn read(session_id: &str) -> Vec<Event> {
+    let Ok(contents) = fs::read_to_string(journal_path(session_id)) else {
+        return Vec::new();
+    };
+    replay(&contents)
+}
+
+/// Applies a session's journal to its last checkpoint.
+///
+/// # Returns
+/// Number of events added or amended
+pub fn merge_into(session: &mut RecordingSession) -> usize {
+    let mut merged = 0;
+    for event in read(&session.session_id) {
+        match session.events.iter_mut().find(|known| known.id == event.id) {
+            Some(known) => {
+                if serde_json::to_value(&*known).ok() != serde_json::to_value(&event).ok() {
+                    *known = event;
+                    merged += 1;
+                }
+            }
+            None => {
+                session.add_event(event);
+                merged += 1;
+            }
+        }
+    }
+    merged
+}
+
+fn replay(contents: &str) -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::new();
+    let mut positions: HashMap<uuid::Uuid, usize> = HashMap::new();
+
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(event) = serde_json::from_str::<Event>(line) else {
+            continue; // Cut off by the crash
+        };
+        match positions.get(&event.id) {
+            Some(&position) => events[position] = event,
+            None => {
+                positions.insert(event.id, events.len());
+                events.push(event);
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EventType, MouseButton, Position};
+
+    #[test]
+    fn test_replay_applies_amendments_in_place() {
+        let click = Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(709.0, 328.0)),
+        );
+        let mut held = click.clone();
+        held.set_click_duration(96);
+        let marker = Event::new(
+            EventType::Marker {
+                label: Some("Submit".to_string()),
+            },
+            None,
+        );
+
+        let line = |event: &Event| serde_json::to_string(event).unwrap();
+        let journal = format!(
+            "{}\n{}\n{}\n{}",
+            line(&click),
+            line(&marker),
+            line(&held),
+            // Cut off mid-write
+            &line(&marker)[..20]
+        );
+
+        let events = replay(&journal);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, click.id);
+        assert!(matches!(
+            events[0].event_type,
+            EventType::Click {
+                duration_ms: Some(96),
+                ..
+            }
+        ));
+        assert_eq!(events[1].id, marker.id);
+    }
+}
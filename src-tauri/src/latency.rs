@@ -0,0 +1,182 @@
+//! # Latency Module - Click Response Time
+//!
+//! How long did the application take to react to a click? With
+//! `latency.enabled`, the display a click was captured on is sampled every
+//! `sample_interval_ms` after the click until it meaningfully differs from
+//! the click's capture. The time from the press to that sample is stored as
+//! the click's `response_latency_ms`, so UX and performance teams get
+//! per-step responsiveness straight from real usage recordings.
+//!
+//! ## Meaningful Change
+//! Frames are compared in `frame_diff::TILE_SIZE` tiles. A blinking caret or
+//! a ticking clock changes a handful of tiles; a sample counts as the
+//! response once at least `min_changed_ratio` of all tiles differ.
+//!
+//! ## Accuracy
+//! - Resolution is the sampling interval plus one capture (30-100ms at 4K)
+//! - Measured from the press: for controls that act on release, subtract
+//!   the click's `duration_ms`
+//! - The reference is the click's own capture, so a change that happened
+//!   before it was taken isn't seen (the next change counts instead)
+//! - No change within `timeout_ms`, the next click, or the end of the
+//!   recording leaves the field unset
+//!
+//! Sampling runs on its own thread and measures one click at a time: a new
+//! click ends the previous measurement, whose response it would blur anyway.
+
+use crate::buffer_pool;
+use crate::capturer::{self, ScreenCapturer};
+use crate::config::{self, LatencyConfig};
+use crate::display::DisplayGeometry;
+use crate::frame_diff::{self, TILE_SIZE};
+use chrono::{DateTime, Utc};
+use image::RgbaImage;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Shortest accepted sampling interval (lower values would capture back to back).
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Incremented for every measurement; a running one stops when it changes.
+static MEASUREMENT: AtomicU64 = AtomicU64::new(0);
+
+/// The screen as captured for a click, before scrubbing.
+#[derive(Debug, Clone)]
+pub struct ReferenceFrame {
+    display_id: u32,
+    frame: RgbaImage,
+}
+
+impl ReferenceFrame {
+    /// Copies a click's capture (into a pooled buffer) for `measure()`.
+    pub fn new(display_id: u32, frame: &RgbaImage) -> Option<Self> {
+        let mut pixels = buffer_pool::take(frame.as_raw().len());
+        pixels.extend_from_slice(frame.as_raw());
+        Some(Self {
+            display_id,
+            frame: RgbaImage::from_raw(frame.width(), frame.height(), pixels)?,
+        })
+    }
+}
+
+/// Starts measuring the response to a click in the background.
+///
+/// # Arguments
+/// * `reference` - The click's capture
+/// * `pressed_at` - Time of the press (the click event's timestamp)
+/// * `config` - Sampling settings
+/// * `on_measured` - Called with the latency in milliseconds once the
+///   screen changed (not called if it didn't)
+pub fn measure(
+    reference: ReferenceFrame,
+    pressed_at: DateTime<Utc>,
+    config: &LatencyConfig,
+    on_measured: impl FnOnce(u64) + Send + 'static,
+) {
+    let measurement = MEASUREMENT.fetch_add(1, Ordering::SeqCst) + 1;
+    let config = config.clone();
+
+    std::thread::spawn(move || {
+        if let Some(latency_ms) = first_change(&reference, pressed_at, &config, measurement) {
+            #[cfg(debug_assertions)]
+            println!("⏱️  Click response after {}ms", latency_ms);
+            on_measured(latency_ms);
+        }
+        buffer_pool::give(reference.frame.into_raw());
+    });
+}
+
+/// Samples the reference's display until it changes (see module docs).
+///
+/// # Returns
+/// Milliseconds from `pressed_at` to the first changed sample, or `None` on
+/// timeout, capture failure, or when superseded by a newer measurement
+fn first_change(
+    reference: &ReferenceFrame,
+    pressed_at: DateTime<Utc>,
+    config: &LatencyConfig,
+    measurement: u64,
+) -> Option<u64> {
+    let backend = capturer::default_backend();
+    let display = backend
+        .displays()
+        .ok()?
+        .into_iter()
+        .find(|display| display.id == reference.display_id)?;
+    let interval = Duration::from_millis(config.sample_interval_ms).max(MIN_SAMPLE_INTERVAL);
+    let timeout_ms = config.timeout_ms.min(config::MAX_LATENCY_TIMEOUT_MS);
+    let deadline = pressed_at + chrono::Duration::milliseconds(timeout_ms as i64);
+
+    while Utc::now() < deadline {
+        std::thread::sleep(interval);
+        if MEASUREMENT.load(Ordering::SeqCst) != measurement {
+            return None; // Superseded by the next click
+        }
+
+        // The screen is read at the start of the capture
+        let sampled_at = Utc::now();
+        let frame = capture(backend, &display)?;
+        let changed = changed_ratio(&reference.frame, &frame);
+        buffer_pool::give(frame.into_raw());
+
+        if changed >= config.min_changed_ratio {
+            return Some((sampled_at - pressed_at).num_milliseconds().max(0) as u64);
+        }
+    }
+    None
+}
+
+/// Share of tiles (0-1) that differ between two frames (`1.0` if the sizes differ).
+pub fn changed_ratio(previous: &RgbaImage, current: &RgbaImage) -> f64 {
+    let (width, height) = current.dimensions();
+    if previous.dimensions() != (width, height) {
+        return 1.0;
+    }
+
+    let tiles = (width.div_ceil(TILE_SIZE) as f64) * (height.div_ceil(TILE_SIZE) as f64);
+    if tiles == 0.0 {
+        return 0.0;
+    }
+    frame_diff::changed_tiles(previous, current).len() as f64 / tiles
+}
+
+fn capture(backend: &dyn ScreenCapturer, display: &DisplayGeometry) -> Option<RgbaImage> {
+    backend
+        .capture(display)
+        .map_err(|e| {
+            eprintln!(
+                "⚠️  Failed to sample screen for click latency ({}): {}",
+                backend.name(),
+                e
+            )
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_changed_ratio_counts_tiles() {
+        let previous = RgbaImage::from_pixel(160, 160, Rgba([255, 255, 255, 255]));
+        let mut current = previous.clone();
+        assert_eq!(changed_ratio(&previous, &current), 0.0);
+
+        // A caret: one pixel, one of 100 tiles
+        current.put_pixel(5, 5, Rgba([0, 0, 0, 255]));
+        assert_eq!(changed_ratio(&previous, &current), 0.01);
+
+        // A dialog covering the top half
+        for y in 0..80 {
+            for x in 0..160 {
+                current.put_pixel(x, y, Rgba([40, 40, 40, 255]));
+            }
+        }
+        assert_eq!(changed_ratio(&previous, &current), 0.5);
+
+        let resized = RgbaImage::new(80, 80);
+        assert_eq!(changed_ratio(&previous, &resized), 1.0);
+    }
+}
@@ -6,9 +6,18 @@
 //! ## Architecture
 //!
 //! - **Global State Management**: Uses `Arc<Mutex<>>` for thread-safe shared state
-//! - **Background Event Listener**: `rdev::listen()` runs in a separate thread
+//! - **Background Event Listener**: An `EventSource` (`rdev::listen()` for
+//!   live input) runs in a separate thread
 //! - **Screenshot Integration**: Captures 3 screenshots per click (full, window, click crop)
-//! - **Automatic Wait Detection**: Inserts pause events for gaps > 2 seconds
+//! - **Automatic Wait Detection**: Inserts pause events for gaps > 2 seconds (`waits.threshold_seconds`, or learned with `waits.adaptive`)
+//!
+//! ## Workspace
+//!
+//! - **`flowtrace-core`** (`crates/core`): Session data model, storage, configuration
+//! - **`flowtrace-capture`** (`crates/capture`): Screen capture backends, input sources
+//! - **`flowtrace-analysis`** (`crates/analysis`): Post-processing, steps, integrity, OCR
+//! - **`flowtrace-export`** (`crates/export`): Export formats, archive import
+//! - **This crate** (the Tauri app): Recording pipeline, replays, Tauri commands
 //!
 //! ## Threading Model
 //!
@@ -20,13 +29,63 @@
 //! ```
 
 // Declare modules
-mod event_monitor;
+mod accessibility;
+mod adaptive_wait;
+mod adhoc;
+mod anonymize;
+mod app_switch;
+#[cfg(feature = "bench")]
+pub mod bench;
+mod bookmarks;
+mod comments;
+mod comparison;
+mod credentials;
+mod draft;
+mod dry_run;
+mod file_drag;
+mod filter;
+mod focus;
+mod follow;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+mod idle;
+mod input_grab;
+mod journal;
+mod latency;
+mod lifecycle;
+mod limits;
+mod mouse_trail;
+mod preview;
+mod privacy;
+mod recovery;
+mod refresh;
+mod remote_desktop;
+mod resume;
 mod screenshot;
-mod storage;
-mod types;
+mod scrub;
+mod splice;
+mod stats;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod titling;
+mod wait_cause;
+mod watchdog;
 
+// Shared crates of the workspace (see `crates/`)
+use flowtrace_analysis::{frame_diff, integrity, ocr, parts, postprocess, steps};
+pub use flowtrace_capture::event_source;
+use flowtrace_capture::{
+    capture_flags, capturer, color_profile, error_dialog, event_monitor, window_capture,
+};
+use flowtrace_core::{buffer_pool, config, display, marker, storage, types};
+use flowtrace_export::{annotation, export, importer};
+
+use event_source::EventSource;
 use once_cell::sync::Lazy;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
 use types::{Event, EventType, MouseButton, Position, RecordingSession};
 
 /// Global state for the current recording session.
@@ -39,6 +98,40 @@ use types::{Event, EventType, MouseButton, Position, RecordingSession};
 static CURRENT_SESSION: Lazy<Arc<Mutex<Option<RecordingSession>>>> =
     Lazy::new(|| Arc::new(Mutex::new(None)));
 
+/// Whether the shared `rdev` listener thread has been spawned.
+///
+/// `rdev::listen()` blocks forever and can't be stopped, so the listener is
+/// spawned once per process and reused by every recording.
+static LISTENER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether events should currently be recorded.
+///
+/// Checked at the top of `handle_event()`: while `false`, the listener keeps
+/// running but only tracks mouse position. This is what makes
+/// start → stop → start work within one process lifetime.
+static RECORDING_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Start of the current pause, if the active session is paused.
+///
+/// Set by `pause_recording()`; converted into a `Paused` event by
+/// `resume_recording()` or `stop_recording()`.
+static PAUSED_AT: Lazy<Arc<Mutex<Option<chrono::DateTime<chrono::Utc>>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Step being re-recorded, if the active recording is a replacement snippet.
+///
+/// Set by `rerecord_step()`; `stop_recording()` then splices the captured
+/// events into the saved session instead of saving a new one.
+static RERECORD_TARGET: Lazy<Arc<Mutex<Option<String>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Modifier keys currently held down, tracked from press/release events.
+///
+/// Used to turn key presses like `KeyS` with `MetaLeft` held into a
+/// `Shortcut` event instead of plain typing. Tracked even between
+/// recordings, so a modifier held while starting a recording is known.
+static PRESSED_MODIFIERS: Lazy<Arc<Mutex<Vec<rdev::Key>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
 /// Tracks the last known mouse position from `MouseMove` events.
 ///
 /// **Why needed**: The `rdev` library doesn't provide position data in `ButtonPress` events.
@@ -50,11 +143,104 @@ static LAST_MOUSE_POSITION: Lazy<Arc<Mutex<(f64, f64)>>> =
 
 /// Tracks the timestamp of the last processed event for automatic wait detection.
 ///
-/// Used by `check_and_insert_wait_event()` to detect pauses > 2 seconds.
+/// Used by `check_and_insert_wait_event()` to detect pauses above the wait threshold.
 /// When a significant gap is detected, a synthetic `Wait` event is inserted.
 static LAST_EVENT_TIME: Lazy<Arc<Mutex<Option<chrono::DateTime<chrono::Utc>>>>> =
     Lazy::new(|| Arc::new(Mutex::new(None)));
 
+/// Where and since when the cursor has been resting, for hover detection.
+///
+/// Used by `check_and_insert_hover_event()`. Cleared by clicks and key
+/// presses (the dwell ended with an action, not a hover) and between
+/// recordings.
+static HOVER_ANCHOR: Lazy<Arc<Mutex<Option<(chrono::DateTime<chrono::Utc>, f64, f64)>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Recorded clicks whose button hasn't been released yet: (button, event id, press time).
+///
+/// Paired with the next `ButtonRelease` of the same button to store the
+/// click's `duration_ms`.
+static PENDING_CLICKS: Lazy<Arc<Mutex<Vec<(MouseButton, uuid::Uuid, std::time::SystemTime)>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
+/// A recorded key press whose key hasn't been released yet.
+struct PendingKey {
+    key: rdev::Key,
+    event_id: uuid::Uuid,
+    pressed_at: std::time::SystemTime,
+    /// Press or latest auto-repeat
+    last_seen: std::time::SystemTime,
+}
+
+/// Keys held down since their recorded press.
+///
+/// A press of a key that is still pending is an auto-repeat: it is counted
+/// on the pending event (`repeat_count`) instead of being recorded. The
+/// release stores the key's `duration_ms`.
+static PENDING_KEYS: Lazy<Arc<Mutex<Vec<PendingKey>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
+/// Longest gap between a press and its next auto-repeat (the slowest OS
+/// "delay until repeat" is 2s). Longer gaps mean the release was missed,
+/// e.g., focus moved to a login screen, and the press is a new one.
+const KEY_REPEAT_MAX_GAP: std::time::Duration = std::time::Duration::from_millis(2500);
+
+/// Whether the session limit watcher thread has been spawned (once per process).
+static LIMIT_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the app switch watcher thread has been spawned (once per process).
+static APP_SWITCH_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the credential watcher thread has been spawned (once per process).
+static CREDENTIAL_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the sleep / screen lock watcher thread has been spawned (once per process).
+static LIFECYCLE_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the idle auto-pause watcher thread has been spawned (once per process).
+static IDLE_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the wait cause sampler thread has been spawned (once per process).
+static WAIT_CAUSE_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the dead listener watcher thread has been spawned (once per process).
+static LISTENER_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the checkpoint thread has been spawned (once per process).
+static CHECKPOINT_WATCH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Held while a checkpoint of the active session is being written.
+///
+/// `stop_recording()` waits for it so a stale checkpoint never overwrites
+/// the final save. Lock order: CURRENT_SESSION before this.
+static CHECKPOINT_IN_FLIGHT: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Open `CredentialsEntered` placeholder: its event id and when entry began.
+///
+/// Set while keyboard capture is suspended for a password field (see
+/// `update_credential_entry()`). Lock order: CURRENT_SESSION before this.
+static CREDENTIAL_ENTRY: Lazy<Arc<Mutex<Option<(uuid::Uuid, chrono::DateTime<chrono::Utc>)>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Set when focus may have moved (a click, Tab, Return, or a shortcut).
+///
+/// The next key press re-checks for a password field itself instead of
+/// waiting for the credential watcher; every other key press only reads
+/// `CREDENTIAL_ENTRY` (the check itself is an accessibility IPC plus a
+/// window query, too slow to run per key).
+static CREDENTIAL_RECHECK: AtomicBool = AtomicBool::new(true);
+
+/// Time of the last recorded wheel tick, for merging ticks into one `Scroll` event.
+///
+/// Used by `record_scroll()`; cleared between recordings.
+static LAST_SCROLL_AT: Lazy<Arc<Mutex<Option<chrono::DateTime<chrono::Utc>>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Handle of the running app, for emitting Tauri events from the `rdev` thread.
+///
+/// Set once in `run()`'s setup hook.
+static APP_HANDLE: once_cell::sync::OnceCell<tauri::AppHandle> = once_cell::sync::OnceCell::new();
+
 /// Demo greeting command (from Tauri template).
 ///
 /// # Arguments
@@ -110,6 +296,7 @@ fn start_event_listener() -> Result<String, String> {
 /// - macOS: Screen Recording permission for launching application (Cursor/iTerm2)
 ///
 /// **UI Location**: Available under "Test Functions" collapsible section.
+/// Use `import_loose_screenshots` to move these files into a session.
 #[tauri::command]
 fn capture_screenshot() -> Result<String, String> {
     #[cfg(debug_assertions)]
@@ -126,14 +313,19 @@ fn capture_screenshot() -> Result<String, String> {
 /// Creates a new session with a unique UUID and spawns a background thread
 /// that captures all mouse clicks and keyboard events until `stop_recording()` is called.
 ///
+/// # Arguments
+/// * `filter` - Which events to keep for this recording (e.g., clicks only);
+///   `None` uses the `filter` from the recorder config (see `filter` module)
+/// * `title` - Session title; `None` or blank generates one at stop (see `titling` module)
+///
 /// # Returns
 /// * `Ok(String)` - Success message with session ID
 /// * `Err(String)` - Error if recording already in progress
 ///
 /// # What Gets Captured
-/// - **Mouse clicks**: Left, right, middle button presses with positions
+/// - **Mouse clicks**: Button presses (left, right, middle, back/forward, extra) with positions
 /// - **Keyboard events**: All key presses except modifier-only keys
-/// - **Wait events**: Automatic detection of pauses > 2 seconds
+/// - **Wait events**: Automatic detection of pauses > 2 seconds (configurable)
 /// - **Screenshots**: 3 per click (full screen, window crop, click crop)
 ///
 /// # Session Management
@@ -145,47 +337,713 @@ fn capture_screenshot() -> Result<String, String> {
 /// The event listener runs in a separate thread because `rdev::listen()` blocks.
 /// Events are added to the shared `CURRENT_SESSION` via `Arc<Mutex<>>`.
 ///
-/// # Known Limitation
-/// The listener cannot be gracefully stopped. Workaround: restart the application
-/// to start a new recording session.
+/// The listener is spawned on the first recording only and kept alive for
+/// the rest of the process; `RECORDING_ACTIVE` gates whether its events are
+/// recorded, so sessions can be started again after `stop_recording()`.
 ///
 /// # Permissions Required
 /// - macOS: Accessibility + Screen Recording for launching application
 #[tauri::command]
-fn start_recording() -> Result<String, String> {
+fn start_recording(
+    filter: Option<config::FilterConfig>,
+    title: Option<String>,
+) -> Result<String, String> {
     #[cfg(debug_assertions)]
     println!("🎬 Start recording command called!");
 
+    // Create new session with unique identifier
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let mut session = RecordingSession::new(session_id.clone());
+    session.title = title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+    record_new_session(session, filter)?;
+
+    #[cfg(debug_assertions)]
+    println!("📝 Created recording session: {}", session_id);
+
+    Ok(format!("Recording started with session ID: {}", session_id))
+}
+
+/// Starts recording the next sitting of a stopped session.
+///
+/// The new part is a session of its own, linked to the first part of the
+/// procedure and titled like it; checklist exports render the parts as
+/// "Day 1 / Day 2" sections (see `parts` module).
+///
+/// # Arguments
+/// * `session_id` - Any part of the procedure to continue
+/// * `filter` - Which events to keep (`None` uses the config's `filter`)
+///
+/// # Returns
+/// * `Ok(String)` - Success message with the new part's session ID
+/// * `Err(String)` - Error if the session couldn't be loaded, a part of it
+///   hasn't been stopped, or a recording is already in progress
+#[tauri::command]
+fn continue_session(
+    session_id: String,
+    filter: Option<config::FilterConfig>,
+) -> Result<String, String> {
+    let part = parts::next_part(&session_id)?;
+    let first_title = storage::load_session(&part.first_session_id)
+        .ok()
+        .and_then(|first| first.title);
+
+    let part_id = uuid::Uuid::new_v4().to_string();
+    let number = part.number;
+    let mut session = RecordingSession::new(part_id.clone());
+    session.title = first_title;
+    session.part = Some(part);
+    record_new_session(session, filter)?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📝 Continuing {} as part {}: {}",
+        session_id, number, part_id
+    );
+
+    Ok(format!(
+        "Recording part {} with session ID: {}",
+        number, part_id
+    ))
+}
+
+/// Starts a dry run: a recording that captures everything but saves nothing.
+///
+/// Verifies permissions and configuration before an important recording
+/// (see `dry_run` module). Stop it with `stop_dry_run()` for the report;
+/// `stop_recording()` and the limit watcher stop it too.
+///
+/// # Arguments
+/// * `filter` - Which events to keep (`None` uses the config's `filter`)
+///
+/// # Returns
+/// * `Ok(String)` - Success message with the session ID
+/// * `Err(String)` - Error if a recording is already in progress
+#[tauri::command]
+fn start_dry_run(filter: Option<config::FilterConfig>) -> Result<String, String> {
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let mut session = RecordingSession::new(session_id.clone());
+    session.title = Some("Dry run".to_string());
+
+    // Marked first: nothing of the session may reach the disk
+    dry_run::begin(&session_id);
+    let filter = filter.unwrap_or(config::current().filter);
+    // No checkpoints: a dry run can't be resumed
+    if let Err(e) = activate_session(session, filter) {
+        dry_run::cancel(&session_id);
+        return Err(e);
+    }
+
+    Ok(format!("Dry run started with session ID: {}", session_id))
+}
+
+/// Stops the dry run and returns what would have been captured.
+///
+/// # Returns
+/// * `Ok(DryRunReport)` - Events, screenshots, capture errors, and warnings
+///   (also for a dry run the limit watcher already stopped)
+/// * `Err(String)` - Error if no dry run was recorded
+#[tauri::command]
+fn stop_dry_run() -> Result<dry_run::DryRunReport, String> {
+    if dry_run::in_progress() {
+        stop_recording()?;
+    }
+    dry_run::take_report().ok_or_else(|| "No dry run in progress".to_string())
+}
+
+/// Records into a newly created session (shared by `start_recording()` and
+/// `continue_session()`).
+fn record_new_session(
+    mut session: RecordingSession,
+    filter: Option<config::FilterConfig>,
+) -> Result<(), String> {
+    let config = config::current();
+    if config.timestamps.record_utc_offset {
+        session.utc_offset = Some(chrono::Local::now().format("%:z").to_string());
+    }
+    let filter = filter.unwrap_or(config.filter);
+    activate_session(session.clone(), filter.clone())?;
+    begin_checkpoints(&session, &filter);
+    Ok(())
+}
+
+/// Makes `session` the active recording and starts capturing events.
+///
+/// # Arguments
+/// * `session` - Session to record into
+/// * `filter` - Which events the recording keeps (see `filter` module)
+///
+/// # Returns
+/// * `Ok(())` - Recording into `session`
+/// * `Err(String)` - Error if another recording is already in progress
+fn activate_session(session: RecordingSession, filter: config::FilterConfig) -> Result<(), String> {
+    install_session(session, filter)?;
+
+    ensure_listener_started();
+    ensure_listener_watch_started();
+    ensure_credential_watch_started();
+    ensure_app_switch_watch_started();
+    ensure_lifecycle_watch_started();
+    ensure_idle_watch_started();
+    ensure_wait_cause_watch_started();
+    ensure_limit_watch_started();
+    ensure_checkpoint_watch_started();
+
+    Ok(())
+}
+
+/// Makes `session` the active recording with fresh capture state, without
+/// starting the listener or watcher threads (see `activate_session()`).
+fn install_session(session: RecordingSession, filter: config::FilterConfig) -> Result<(), String> {
     let mut session_lock = CURRENT_SESSION.lock().unwrap();
 
     // Enforce single active session
     if session_lock.is_some() {
         return Err("Recording already in progress".to_string());
     }
-
-    // Create new session with unique identifier
-    let session_id = uuid::Uuid::new_v4().to_string();
-    let session = RecordingSession::new(session_id.clone());
-
-    #[cfg(debug_assertions)]
-    println!("📝 Created recording session: {}", session_id);
+    // The replay's synthetic input would be recorded
+    if refresh::is_active() {
+        return Err("A session is being replayed".to_string());
+    }
 
     *session_lock = Some(session);
     drop(session_lock); // CRITICAL: Release lock before spawning thread to prevent deadlock
 
-    // Start event listener in background thread (rdev::listen blocks forever)
+    // Don't report a gap since the previous session as a Wait
+    if let Ok(mut last_time) = LAST_EVENT_TIME.lock() {
+        *last_time = None;
+    }
+    *PAUSED_AT.lock().unwrap() = None;
+    *HOVER_ANCHOR.lock().unwrap() = None;
+    PENDING_CLICKS.lock().unwrap().clear();
+    PENDING_KEYS.lock().unwrap().clear();
+    mouse_trail::reset();
+    wait_cause::reset();
+    *LAST_SCROLL_AT.lock().unwrap() = None;
+    *CREDENTIAL_ENTRY.lock().unwrap() = None;
+    CREDENTIAL_RECHECK.store(true, Ordering::SeqCst);
+    app_switch::reset();
+    focus::reset();
+    lifecycle::reset();
+    watchdog::reset();
+    capture_flags::reset();
+    filter::activate(filter);
+    marker::activate(&config::current().marker);
+    RECORDING_ACTIVE.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Makes the active recording resumable after a restart (see `resume` and
+/// `journal` modules).
+///
+/// Failures are logged: the recording works, it just can't be resumed.
+fn begin_checkpoints(session: &RecordingSession, filter: &config::FilterConfig) {
+    if let Err(e) = resume::begin(session, filter) {
+        eprintln!("⚠️  Failed to checkpoint recording: {}", e);
+        return;
+    }
+    if let Err(e) = journal::open(&session.session_id) {
+        eprintln!("⚠️  Failed to journal recording: {}", e);
+    }
+}
+
+/// Spawns the thread that checkpoints the active recording unless it is already running.
+///
+/// Only sessions registered with `begin_checkpoints()` are written, when
+/// `resume::due()` says so (`checkpoints` in the recorder config).
+fn ensure_checkpoint_watch_started() {
+    if CHECKPOINT_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        std::thread::sleep(resume::CHECKPOINT_POLL);
+
+        if CURRENT_SESSION.lock().unwrap().is_none() {
+            continue;
+        }
+        // No file I/O while holding the session, input handlers wait for it
+        let Some(state) = resume::load() else {
+            continue;
+        };
+        let schedule = config::checkpoints();
+
+        let session_lock = CURRENT_SESSION.lock().unwrap();
+        let Some(session) = session_lock.as_ref().filter(|session| {
+            session.session_id == state.session_id
+                && resume::due(&state, session.events.len(), chrono::Utc::now(), &schedule)
+        }) else {
+            continue;
+        };
+        let session = session.clone();
+        let _in_flight = CHECKPOINT_IN_FLIGHT.lock().unwrap();
+        drop(session_lock); // Writing can take a while for long sessions
+
+        match resume::checkpoint(&session) {
+            Ok(true) => {
+                #[cfg(debug_assertions)]
+                println!("💾 Checkpointed {} events", session.events.len());
+            }
+            Ok(false) => {}
+            Err(e) => eprintln!("⚠️  Failed to checkpoint recording: {}", e),
+        }
+    });
+}
+
+/// Spawns the shared listener thread unless it is already running.
+///
+/// The thread runs the OS event source (`event_source::RdevSource`) and
+/// hands each event to `handle_event()`. If `rdev::listen()` fails (e.g., missing Accessibility permission), the
+/// flag is reset so the next `start_recording()` tries again. The thread's
+/// exit, however it happens, is reported to the watchdog (see `watchdog`
+/// module).
+fn ensure_listener_started() {
+    if LISTENER_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    let source: Box<dyn EventSource> = Box::new(event_source::RdevSource);
+
+    // Start event listener in background thread (the OS source blocks forever)
     std::thread::spawn(move || {
         #[cfg(debug_assertions)]
-        println!("👂 Starting integrated event listener...");
+        println!(
+            "👂 Starting integrated event listener ({})...",
+            source.name()
+        );
 
-        if let Err(e) = rdev::listen(move |event| {
-            handle_event(event);
-        }) {
-            eprintln!("❌ Event listener error: {:?}", e);
+        let mut guard = watchdog::ListenerGuard::default();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            source.run(Box::new(|event| {
+                watchdog::beat();
+                // A panic must neither end capture nor unwind into the OS callback
+                let handled =
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| handle_event(event)));
+                if let Err(payload) = handled {
+                    recover_from_panic(watchdog::panic_message(payload.as_ref()));
+                }
+            }))
+        }));
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                eprintln!("❌ Event listener error: {}", e);
+                guard.fail(e);
+            }
+            Err(payload) => guard.panicked(watchdog::panic_message(payload.as_ref())),
         }
+        // Restartable before the watchdog hears of it (it may respawn the listener)
+        LISTENER_STARTED.store(false, Ordering::SeqCst);
+        drop(guard);
     });
+}
 
-    Ok(format!("Recording started with session ID: {}", session_id))
+/// Keeps capture going after `handle_event()` panicked (see `watchdog` module).
+///
+/// Clears the poison the panic left on the recorder's locks (their data is
+/// kept as the panic left it) and records a `CaptureError` event, once per
+/// repeated message.
+fn recover_from_panic(message: String) {
+    eprintln!("❌ Event handler panicked: {}", message);
+
+    CURRENT_SESSION.clear_poison();
+    PAUSED_AT.clear_poison();
+    RERECORD_TARGET.clear_poison();
+    PRESSED_MODIFIERS.clear_poison();
+    LAST_MOUSE_POSITION.clear_poison();
+    LAST_EVENT_TIME.clear_poison();
+    HOVER_ANCHOR.clear_poison();
+    PENDING_CLICKS.clear_poison();
+    PENDING_KEYS.clear_poison();
+    CREDENTIAL_ENTRY.clear_poison();
+    LAST_SCROLL_AT.clear_poison();
+
+    if watchdog::should_report(&message) {
+        record_capture_error(message);
+    }
+}
+
+/// Adds a `CaptureError` event to the active recording.
+fn record_capture_error(message: String) {
+    if !RECORDING_ACTIVE.load(Ordering::SeqCst) {
+        return;
+    }
+    let error_event = Event::new(EventType::CaptureError { message }, None);
+    if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        add_filtered(session, error_event);
+    }
+}
+
+/// Spawns the thread that reports a dead listener unless it is already running.
+///
+/// A listener ended by a panic in `rdev` is respawned (see `watchdog`
+/// module); otherwise the active session is marked as truncated. It stays
+/// open so the events recorded so far can still be saved with
+/// `stop_recording()`.
+///
+/// # Emitted Tauri Events
+/// - `recording-listener-died` - `ListenerFailure` with the listener's error
+fn ensure_listener_watch_started() {
+    /// How often the listener is checked.
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+    if LISTENER_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let Some(failure) = watchdog::take_failure() else {
+            continue;
+        };
+
+        if CURRENT_SESSION.lock().unwrap().is_none() {
+            continue; // Between recordings: the next start restarts it
+        }
+        if failure.panicked && watchdog::respawn_allowed() {
+            record_capture_error(failure.error.clone());
+            ensure_listener_started();
+            continue;
+        }
+
+        let mut session_lock = CURRENT_SESSION.lock().unwrap();
+        let Some(session) = session_lock.as_mut() else {
+            continue;
+        };
+        session.truncated = Some(failure.truncation(session.started_at));
+        drop(session_lock);
+
+        eprintln!(
+            "⚠️  Event listener died mid-recording ({}); no more events are recorded",
+            failure.error
+        );
+        if let Some(app) = APP_HANDLE.get() {
+            let _ = app.emit("recording-listener-died", failure);
+        }
+    });
+}
+
+/// Spawns the thread that watches for password entry unless it is already running.
+///
+/// Secure keyboard entry hides keystrokes from `rdev` entirely, so entering
+/// and leaving a password field can't be detected from key events alone.
+fn ensure_credential_watch_started() {
+    /// How often focus is checked while recording.
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(300);
+
+    if CREDENTIAL_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if RECORDING_ACTIVE.load(Ordering::SeqCst) {
+            update_credential_entry();
+        }
+    });
+}
+
+/// Spawns the thread that records `AppSwitch` events unless it is already running.
+///
+/// Focus changes made from the keyboard or by the OS produce no mouse event,
+/// so the foreground application is polled (see `app_switch` module).
+fn ensure_app_switch_watch_started() {
+    /// How often the foreground application is checked while a session is active.
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+    if APP_SWITCH_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if CURRENT_SESSION.lock().unwrap().is_none() {
+            continue;
+        }
+        // Polled while paused too, to keep the baseline current
+        let Some((from, to)) = app_switch::poll() else {
+            continue;
+        };
+        if !RECORDING_ACTIVE.load(Ordering::SeqCst) {
+            continue;
+        }
+
+        #[cfg(debug_assertions)]
+        println!("🔀 Switched from {} to {}", from, to);
+
+        let mut switch_event = Event::new(EventType::AppSwitch { from, to }, None);
+        screenshot::stamp_focused_window(&mut switch_event);
+        if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+            add_filtered(session, switch_event);
+        }
+    });
+}
+
+/// Spawns the thread that records `Lifecycle` events unless it is already running.
+///
+/// Sleep is noticed as a gap between polls, so the thread polls while a
+/// session exists, paused or not (see `lifecycle` module).
+fn ensure_lifecycle_watch_started() {
+    /// How often sleep and screen lock are checked.
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+    if LIFECYCLE_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if CURRENT_SESSION.lock().unwrap().is_some() {
+            record_lifecycle_events();
+        }
+    });
+}
+
+/// Adds the sleep / screen lock intervals that just ended to the session.
+///
+/// Intervals while paused are dropped: the `Paused` event already covers them.
+fn record_lifecycle_events() {
+    let events = lifecycle::poll();
+    if events.is_empty() || !RECORDING_ACTIVE.load(Ordering::SeqCst) {
+        return;
+    }
+
+    if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        for event in events {
+            #[cfg(debug_assertions)]
+            println!("💤 {}", event.description());
+            add_filtered(session, event);
+        }
+    }
+}
+
+/// Spawns the thread that samples the screen between inputs unless it is already running.
+///
+/// Only samples with `wait_causes.enabled` (re-read on every sample) while
+/// recording and not paused; see the `wait_cause` module.
+fn ensure_wait_cause_watch_started() {
+    if WAIT_CAUSE_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        let wait_causes = config::wait_causes();
+        std::thread::sleep(wait_cause::sample_interval(&wait_causes));
+
+        let paused = PAUSED_AT.lock().unwrap().is_some();
+        if !wait_causes.enabled || !RECORDING_ACTIVE.load(Ordering::SeqCst) || paused {
+            wait_cause::reset();
+            continue;
+        }
+        let (x, y) = *LAST_MOUSE_POSITION.lock().unwrap();
+        wait_cause::sample(x, y, &wait_causes);
+    });
+}
+
+/// Spawns the thread that pauses idle recordings unless it is already running.
+///
+/// Only acts with `idle.auto_pause` enabled (re-read on every check); see the
+/// `idle` module. Without it, idle gaps are recorded on the next input by
+/// `check_and_insert_wait_event()`.
+///
+/// # Emitted Tauri Events
+/// - `recording-auto-paused` - `AutoPause` with the idle time and the pause result
+fn ensure_idle_watch_started() {
+    /// How often the time since the last input is checked.
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+    if IDLE_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let idle_config = config::idle();
+        if !idle_config.auto_pause || !RECORDING_ACTIVE.load(Ordering::SeqCst) {
+            continue;
+        }
+
+        let now = chrono::Utc::now();
+        let mut last_time_lock = LAST_EVENT_TIME.lock().unwrap();
+        let Some(last_time) = *last_time_lock else {
+            continue;
+        };
+        let idle_seconds = (now - last_time).num_milliseconds() as f64 / 1000.0
+            - lifecycle::inactive_seconds(last_time, now);
+        let Some(idle_event) = idle::idle_event(&idle_config, last_time, idle_seconds) else {
+            continue;
+        };
+
+        // Lock order: LAST_EVENT_TIME before CURRENT_SESSION
+        if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+            add_filtered(session, idle_event);
+        }
+        // The gap is recorded; the next input must not become a Wait or Idle too
+        *last_time_lock = None;
+        drop(last_time_lock);
+
+        // Exactly as if the user had pressed Pause
+        let message = pause_recording().unwrap_or_else(|e| e);
+        eprintln!(
+            "⚠️  Recording paused automatically after {:.0}s idle: {}",
+            idle_seconds, message
+        );
+        if let Some(app) = APP_HANDLE.get() {
+            let _ = app.emit(
+                "recording-auto-paused",
+                idle::AutoPause {
+                    idle_seconds,
+                    message,
+                },
+            );
+        }
+    });
+}
+
+/// Spawns the thread that stops recordings at their limits unless it is already running.
+///
+/// See the `limits` module; the limits are re-read from the config on every check.
+///
+/// # Emitted Tauri Events
+/// - `recording-auto-stopped` - `AutoStop` with the limit and the stop result
+fn ensure_limit_watch_started() {
+    /// How often the active recording is checked.
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+    if LIMIT_WATCH_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return; // Already running
+    }
+
+    std::thread::spawn(|| loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let limits = config::current().limits;
+        let reason = CURRENT_SESSION
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|session| {
+                limits::reached(
+                    &limits,
+                    session.started_at,
+                    session.events.len(),
+                    chrono::Utc::now(),
+                )
+            });
+        let Some(reason) = reason else {
+            continue;
+        };
+
+        // Exactly as if the user had pressed Stop
+        let message = stop_recording().unwrap_or_else(|e| e);
+        eprintln!(
+            "⚠️  Recording stopped automatically ({:?}): {}",
+            reason, message
+        );
+        if let Some(app) = APP_HANDLE.get() {
+            let _ = app.emit(
+                "recording-auto-stopped",
+                limits::AutoStop { reason, message },
+            );
+        }
+    });
+}
+
+/// Suspends keyboard capture while credentials are entered (see `credentials` module).
+///
+/// Called by the credential watcher and by the first key press after a
+/// possible focus change (see `CREDENTIAL_RECHECK`). Entering a
+/// password field adds a `CredentialsEntered` placeholder (timestamped at
+/// that moment, so it sorts before later clicks); leaving it sets the
+/// placeholder's duration.
+///
+/// # Returns
+/// `true` while credentials are being entered (keys must not be recorded)
+fn update_credential_entry() -> bool {
+    let active = credentials::entry_active();
+    let entering = CREDENTIAL_ENTRY.lock().unwrap().is_some();
+    if active == entering {
+        return active;
+    }
+
+    if active {
+        let mut placeholder = Event::new(
+            EventType::CredentialsEntered {
+                duration_seconds: 0.0,
+            },
+            None,
+        );
+        screenshot::stamp_focused_window(&mut placeholder);
+
+        let mut session_lock = CURRENT_SESSION.lock().unwrap();
+        let Some(session) = session_lock.as_mut() else {
+            return active;
+        };
+        let mut entry = CREDENTIAL_ENTRY.lock().unwrap();
+        if entry.is_none() {
+            #[cfg(debug_assertions)]
+            println!("🔐 Credential entry detected, keyboard capture suspended");
+            *entry = Some((placeholder.id, placeholder.timestamp));
+            record_event(session, placeholder);
+        }
+    } else if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        finish_credential_entry(session);
+    }
+    active
+}
+
+/// Closes the open `CredentialsEntered` placeholder, if any, with its duration.
+///
+/// The caller holds the CURRENT_SESSION lock (lock order).
+fn finish_credential_entry(session: &mut RecordingSession) {
+    let Some((event_id, since)) = CREDENTIAL_ENTRY.lock().unwrap().take() else {
+        return;
+    };
+    let Some(placeholder) = session.events.iter_mut().rev().find(|e| e.id == event_id) else {
+        return;
+    };
+
+    let duration_seconds = (chrono::Utc::now() - since).num_milliseconds() as f64 / 1000.0;
+    let mut closed = Event::new(EventType::CredentialsEntered { duration_seconds }, None);
+    closed.id = event_id;
+    closed.timestamp = since;
+    closed.app_name = placeholder.app_name.take();
+    closed.window_title = placeholder.window_title.take();
+    closed.remote_desktop = placeholder.remote_desktop;
+    *placeholder = closed;
+    journal::append(&session.session_id, placeholder);
+
+    #[cfg(debug_assertions)]
+    println!("🔓 Credential entry finished ({:.1}s)", duration_seconds);
 }
 
 /// Stops the current recording session and saves events to disk.
@@ -213,39 +1071,1584 @@ fn start_recording() -> Result<String, String> {
 /// ```
 ///
 /// # Note
-/// The background event listener continues running (known limitation).
-/// To start a new recording, restart the application.
+/// The background event listener keeps running but stops recording
+/// immediately; a new recording can be started right away. Also called by
+/// the limit watcher when a recording reaches its configured `limits`.
+/// The session is taken out of `CURRENT_SESSION` first, so saving and the
+/// privacy review don't hold the lock the event callback needs.
 #[tauri::command]
 fn stop_recording() -> Result<String, String> {
     #[cfg(debug_assertions)]
     println!("⏹️  Stop recording command called!");
 
-    let mut session_lock = CURRENT_SESSION.lock().unwrap();
+    // Stop recording events before the session is taken
+    RECORDING_ACTIVE.store(false, Ordering::SeqCst);
+
+    // Lock order: RERECORD_TARGET before CURRENT_SESSION (as in rerecord_step)
+    let rerecord_target = RERECORD_TARGET.lock().unwrap().take();
+    // Taken out so the slow work below (saving, privacy review, titling)
+    // doesn't block the event callback and every other command
+    let session = CURRENT_SESSION.lock().unwrap().take();
+
+    match session {
+        Some(mut session) => {
+            // Let a checkpoint being written finish before the final save
+            drop(CHECKPOINT_IN_FLIGHT.lock().unwrap());
+            // Events from here on are post-processing, saved with the session
+            journal::close(&session.session_id);
+            if let Some(trail_event) = mouse_trail::take_event() {
+                add_filtered(&mut session, trail_event);
+            }
+            finish_credential_entry(&mut session);
+            // Stopping while locked closes the lock interval
+            if let Some(lock_event) = lifecycle::finish() {
+                if PAUSED_AT.lock().unwrap().is_none() {
+                    add_filtered(&mut session, lock_event);
+                }
+            }
+            // Stopping while paused closes the pause interval
+            if let Some(paused_event) = take_paused_event() {
+                session.add_event(paused_event);
+            }
+            session.stop();
+            session.capture_stats = focus::stats();
+            // Merge keystrokes into readable TextInput events
+            postprocess::aggregate_text_input(&mut session);
+            postprocess::normalize_waits(&mut session, &config::current().waits);
+            let event_count = session.events.len();
+
+            // Rehearsal: report instead of saving (see `dry_run` module)
+            if let Some(report) = dry_run::finish(&session) {
+                return Ok(format!(
+                    "Dry run stopped. {} events, {} screenshots ({} bytes) would have been saved; {} warnings",
+                    event_count,
+                    report.screenshot_count,
+                    report.screenshot_bytes,
+                    report.warnings.len()
+                ));
+            }
+
+            // Replacement snippet: splice into the saved session instead
+            if let Some(event_id) = rerecord_target {
+                return finish_rerecord(session, &event_id);
+            }
+
+            if session.title.is_none() {
+                session.title = titling::generate(&session);
+            }
+
+            // Save to disk as JSON
+            match storage::save_session(&session) {
+                Ok(path) => {
+                    #[cfg(debug_assertions)]
+                    println!(
+                        "✅ Recording stopped. {} events saved to: {:?}",
+                        event_count, path
+                    );
+                    resume::clear(&session.session_id);
+                    journal::remove(&session.session_id);
+
+                    if let Err(e) = adaptive_wait::save_learned() {
+                        eprintln!("⚠️  Failed to save learned wait cadence: {}", e);
+                    }
+
+                    // Flag sensitive events before the session is shared
+                    if let Err(e) = privacy::review_saved(&session) {
+                        eprintln!("⚠️  Failed to run privacy review: {}", e);
+                    }
+
+                    // Decoding every screenshot takes a while; don't hold up the stop
+                    let preview_config = config::current().preview;
+                    if preview_config.enabled {
+                        let session = session.clone();
+                        std::thread::spawn(move || {
+                            if let Err(e) = preview::generate(&session, &preview_config) {
+                                eprintln!("⚠️  Failed to generate preview track: {}", e);
+                            }
+                        });
+                    }
+
+                    // Opt-in: roll the finished session into the usage stats
+                    // (reads every saved session, so off the stop path too)
+                    if config::current().telemetry.enabled {
+                        std::thread::spawn(|| {
+                            if let Err(e) = stats::aggregate_usage_stats(None) {
+                                eprintln!("⚠️  Failed to update usage stats: {}", e);
+                            }
+                        });
+                    }
+
+                    Ok(format!(
+                        "Recording stopped. {} events captured. Saved to: {}",
+                        event_count,
+                        path.display()
+                    ))
+                }
+                Err(e) => Err(format!("Failed to save recording: {}", e)),
+            }
+        }
+        None => Err("No recording in progress".to_string()),
+    }
+}
+
+/// Re-records a single step of a saved session.
+///
+/// Starts a short recording whose events replace `event_id` when
+/// `stop_recording()` is called, so one botched step can be fixed without
+/// redoing the whole session. Screenshots go into the original session's
+/// directory; the replaced step's screenshots are left for `gc_storage`.
+///
+/// # Arguments
+/// * `session_id` - Saved session to edit
+/// * `event_id` - Step to replace
+///
+/// # Returns
+/// * `Ok(String)` - Success message
+/// * `Err(String)` - Error if the step doesn't exist or a recording is in progress
+///
+/// # Timestamps
+/// Recorded timestamps are kept; the adjusted order in time is written to the
+/// session's `edited_timeline` (see `splice` module).
+#[tauri::command]
+fn rerecord_step(session_id: String, event_id: String) -> Result<String, String> {
+    let session = storage::load_session(&session_id)?;
+    if !session.events.iter().any(|event| event.has_id(&event_id)) {
+        return Err(format!("Event {} not found in session", event_id));
+    }
+
+    // Snippet shares the session id so screenshots land in the same directory.
+    // Holding the target lock keeps stop_recording() from seeing the snippet
+    // before it is marked as one (it would overwrite the saved session).
+    let mut rerecord_lock = RERECORD_TARGET.lock().unwrap();
+    activate_session(RecordingSession::new(session_id), config::current().filter)?;
+    *rerecord_lock = Some(event_id.clone());
+
+    #[cfg(debug_assertions)]
+    println!("🔁 Re-recording step {}", event_id);
+
+    Ok(format!(
+        "Re-recording step {}. Stop recording to splice it in.",
+        event_id
+    ))
+}
+
+/// Splices a finished replacement snippet into its saved session.
+fn finish_rerecord(snippet: RecordingSession, event_id: &str) -> Result<String, String> {
+    let mut session = storage::load_session(&snippet.session_id)?;
+    let inserted = snippet.events.len();
+
+    splice::splice_step(&mut session, event_id, snippet.events)?;
+    let path =
+        storage::save_session(&session).map_err(|e| format!("Failed to save recording: {}", e))?;
+
+    Ok(format!(
+        "Step replaced with {} events. Saved to: {}",
+        inserted,
+        path.display()
+    ))
+}
+
+/// Copies a saved session into an editable draft (see `draft` module).
+///
+/// # Returns
+/// * `Ok(RecordingSession)` - The draft, with its own id and screenshots
+/// * `Err(String)` - Error if the session can't be loaded or copied
+#[tauri::command]
+fn duplicate_session(session_id: String) -> Result<RecordingSession, String> {
+    draft::duplicate(&session_id)
+}
+
+/// Moves an event of a draft before another event, or to the end.
+///
+/// # Arguments
+/// * `session_id` - Draft to edit
+/// * `event_id` - Event to move
+/// * `before_event_id` - Event to place it before (`None` = last)
+///
+/// # Returns
+/// * `Ok(Vec<Step>)` - The draft's steps after the move
+/// * `Err(String)` - Error if the session isn't a draft or an event doesn't exist
+#[tauri::command]
+fn move_draft_event(
+    session_id: String,
+    event_id: String,
+    before_event_id: Option<String>,
+) -> Result<Vec<steps::Step>, String> {
+    let mut session = draft::load_draft(&session_id)?;
+    draft::move_event(&mut session, &event_id, before_event_id.as_deref())?;
+    storage::save_session(&session)?;
+    Ok(steps::group_steps(&session))
+}
+
+/// Adds a step that wasn't recorded to a draft.
+///
+/// # Arguments
+/// * `session_id` - Draft to edit
+/// * `text` - Instruction text of the step
+/// * `image_path` - Image to show with it, copied into the draft
+/// * `before_event_id` - Event to place it before (`None` = last)
+///
+/// # Returns
+/// * `Ok(Event)` - The inserted `ManualStep` event
+/// * `Err(String)` - Error if the session isn't a draft, the text is blank,
+///   or the image can't be read
+#[tauri::command]
+fn insert_manual_step(
+    session_id: String,
+    text: String,
+    image_path: Option<String>,
+    before_event_id: Option<String>,
+) -> Result<Event, String> {
+    let mut session = draft::load_draft(&session_id)?;
+    let event = draft::insert_manual_step(
+        &mut session,
+        &text,
+        image_path.as_deref(),
+        before_event_id.as_deref(),
+    )?;
+    storage::save_session(&session)?;
+    Ok(event)
+}
+
+/// Splits or joins steps of a draft at an event (see `steps` module).
+///
+/// # Arguments
+/// * `session_id` - Draft to edit
+/// * `event_id` - Event the rule applies to
+/// * `rule` - `"split"` or `"join"`; `None` restores the automatic grouping
+///
+/// # Returns
+/// * `Ok(Vec<Step>)` - The draft's steps with the rule applied
+/// * `Err(String)` - Error if the session isn't a draft or the event doesn't exist
+#[tauri::command]
+fn set_step_grouping(
+    session_id: String,
+    event_id: String,
+    rule: Option<types::GroupingRule>,
+) -> Result<Vec<steps::Step>, String> {
+    let mut session = draft::load_draft(&session_id)?;
+    draft::set_grouping(&mut session, &event_id, rule)?;
+    storage::save_session(&session)?;
+    Ok(steps::group_steps(&session))
+}
+
+/// Temporarily suspends capture without ending the active session.
+///
+/// Nothing is recorded while paused (e.g., while entering a password). The
+/// paused interval is stored as a single `Paused` event on resume or stop,
+/// rather than as a Wait event.
+///
+/// # Returns
+/// * `Ok(String)` - Success message
+/// * `Err(String)` - Error if no recording is in progress or it is already paused
+#[tauri::command]
+fn pause_recording() -> Result<String, String> {
+    if CURRENT_SESSION.lock().unwrap().is_none() {
+        return Err("No recording in progress".to_string());
+    }
+
+    let mut paused_at = PAUSED_AT.lock().unwrap();
+    if paused_at.is_some() {
+        return Err("Recording is already paused".to_string());
+    }
+
+    RECORDING_ACTIVE.store(false, Ordering::SeqCst);
+    *paused_at = Some(chrono::Utc::now());
+    drop(paused_at); // Lock order: CURRENT_SESSION before PAUSED_AT
+
+    // Movement before the pause belongs before the Paused event
+    if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        if let Some(trail_event) = mouse_trail::take_event() {
+            add_filtered(session, trail_event);
+        }
+        // Nothing is captured while paused anyway
+        finish_credential_entry(session);
+    }
+
+    #[cfg(debug_assertions)]
+    println!("⏸️  Recording paused");
+
+    Ok("Recording paused".to_string())
+}
+
+/// Resumes capture after `pause_recording()`.
+///
+/// Adds a `Paused` event covering the interval (timestamped at the start of
+/// the pause). The first action after resuming never produces a Wait event
+/// for the paused time.
+///
+/// # Returns
+/// * `Ok(String)` - Success message with the pause duration
+/// * `Err(String)` - Error if no recording is in progress or it isn't paused
+#[tauri::command]
+fn resume_recording() -> Result<String, String> {
+    let mut session_lock = CURRENT_SESSION.lock().unwrap();
+    let session = session_lock
+        .as_mut()
+        .ok_or_else(|| "No recording in progress".to_string())?;
+
+    let paused_event = take_paused_event().ok_or_else(|| "Recording is not paused".to_string())?;
+    let description = paused_event.description().into_owned();
+    record_event(session, paused_event);
+    drop(session_lock);
+
+    if let Ok(mut last_time) = LAST_EVENT_TIME.lock() {
+        *last_time = None;
+    }
+    *HOVER_ANCHOR.lock().unwrap() = None;
+    RECORDING_ACTIVE.store(true, Ordering::SeqCst);
+
+    #[cfg(debug_assertions)]
+    println!("▶️  Recording resumed ({})", description);
+
+    Ok(format!("Recording resumed. {}", description))
+}
+
+/// Ends the current pause, if any, and returns it as a `Paused` event.
+fn take_paused_event() -> Option<Event> {
+    let paused_at = PAUSED_AT.lock().unwrap().take()?;
+    let duration_seconds = (chrono::Utc::now() - paused_at).num_milliseconds() as f64 / 1000.0;
+
+    let mut event = Event::new(EventType::Paused { duration_seconds }, None);
+    event.timestamp = paused_at;
+    Some(event)
+}
+
+/// Captures one window, focused or not, into the "Ad-hoc captures" session.
+///
+/// # Arguments
+/// * `pattern` - Window title pattern (case-insensitive regular expression;
+///   plain text matches anywhere in the title)
+///
+/// # Returns
+/// * `Ok(Event)` - `Capture` event with the window in `screenshots.window_crop`
+/// * `Err(String)` - Invalid pattern, no matching on-screen window, or the
+///   capture failed (macOS only for now, see `window_capture` module)
+#[tauri::command]
+fn capture_window_by_title(pattern: String) -> Result<Event, String> {
+    let window = window_capture::find_window(&pattern)?;
+    adhoc::add_window_capture(&window)
+}
+
+/// Returns the recording interrupted by a restart of FlowTrace, if any.
+///
+/// # Returns
+/// `None` if there is nothing to resume (or a recording is in progress)
+#[tauri::command]
+fn get_interrupted_recording() -> Option<resume::ActiveRecording> {
+    if CURRENT_SESSION.lock().unwrap().is_some() {
+        return None;
+    }
+    resume::load()
+}
+
+/// Continues the recording interrupted by a restart (see `resume` module).
+///
+/// New events are appended to the same session after a `RecorderRestart`
+/// event; the recording's filter is restored.
+///
+/// # Returns
+/// * `Ok(String)` - Success message with session ID
+/// * `Err(String)` - Error if there is nothing to resume, its checkpoint
+///   can't be read, or a recording is already in progress
+#[tauri::command]
+fn resume_interrupted_recording() -> Result<String, String> {
+    if CURRENT_SESSION.lock().unwrap().is_some() {
+        return Err("Recording already in progress".to_string());
+    }
+    let (session, filter) = resume::take_resumable()?;
+    let session_id = session.session_id.clone();
+    activate_session(session.clone(), filter.clone())?;
+    begin_checkpoints(&session, &filter);
+
+    #[cfg(debug_assertions)]
+    println!("🔁 Resumed recording session: {}", session_id);
+
+    Ok(format!("Recording resumed with session ID: {}", session_id))
+}
+
+/// Saves the recording interrupted by a restart as it was last checkpointed.
+///
+/// # Returns
+/// * `Ok(String)` - Id of the finished session
+/// * `Err(String)` - Error if there is nothing to finish or it couldn't be saved
+#[tauri::command]
+fn finish_interrupted_recording() -> Result<String, String> {
+    if CURRENT_SESSION.lock().unwrap().is_some() {
+        return Err("Recording already in progress".to_string());
+    }
+    resume::finish_interrupted()
+}
+
+/// Lists saved sessions that have events but were never stopped (see `recovery` module).
+#[tauri::command]
+fn list_unfinished_sessions() -> Vec<recovery::UnfinishedSession> {
+    let active_session_id = CURRENT_SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|session| session.session_id.clone());
+    recovery::scan(active_session_id.as_deref())
+}
+
+/// Finalizes a session cut off by a crash or force-quit.
+///
+/// The stop time is the last event's timestamp; events journaled after the
+/// last checkpoint are included.
+///
+/// # Returns
+/// * `Ok(String)` - Success message with the event count
+/// * `Err(String)` - Error if the session is being recorded, was stopped
+///   normally, or couldn't be saved
+#[tauri::command]
+fn recover_session(session_id: String) -> Result<String, String> {
+    if CURRENT_SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|session| session.session_id == session_id)
+    {
+        return Err("Session is being recorded".to_string());
+    }
+    let session = recovery::recover(&session_id)?;
+    Ok(format!(
+        "Session {} recovered with {} events",
+        session_id,
+        session.events.len()
+    ))
+}
+
+/// Moves loose `capture_screenshot` files into the "Ad-hoc captures" session.
+///
+/// # Returns
+/// * `Ok(AdHocImport)` - Session id, number of screenshots moved, and any left in place
+/// * `Err(String)` - Error if the session couldn't be loaded or saved
+///
+/// See `adhoc` module for naming and timestamp rules.
+#[tauri::command]
+fn import_loose_screenshots() -> Result<adhoc::AdHocImport, String> {
+    adhoc::import_loose_screenshots()
+}
+
+/// Lists saved sessions for the session browser.
+///
+/// # Returns
+/// Id, title, start/stop time, event count, and size on disk of each
+/// session, most recent first
+#[tauri::command]
+fn list_sessions() -> Vec<storage::SessionSummary> {
+    storage::list_sessions()
+}
+
+/// Loads a saved session for review and playback.
+///
+/// Screenshot paths are absolute, so every event's images can be shown as
+/// they are. Partial captures (see `frame_diff` module) keep their
+/// `full_screen_patch`: the viewer draws the patch at `x`, `y` over its
+/// `base`. Nothing is written to disk.
+///
+/// # Returns
+/// * `Ok(RecordingSession)` - The session with all its events
+/// * `Err(String)` - Error if the id is invalid, or `session.json` is missing
+///   or not a valid session
+#[tauri::command]
+fn load_session(session_id: String) -> Result<RecordingSession, String> {
+    let mut session = storage::load_session(&session_id)?;
+    storage::resolve_screenshot_paths(&mut session);
+    Ok(session)
+}
+
+/// Checks a saved session for missing screenshots and inconsistent data.
+///
+/// The same checks run before exports and imports (see `integrity` module).
+///
+/// # Returns
+/// * `Ok(IntegrityReport)` - Findings with severity, check, and event id;
+///   `valid` is `false` if any is an error
+/// * `Err(String)` - Error if the session couldn't be loaded
+#[tauri::command]
+fn validate_session(session_id: String) -> Result<integrity::IntegrityReport, String> {
+    integrity::validate_saved(&session_id)
+}
+
+/// Reports (and optionally deletes) orphaned screenshots and temporary files.
+///
+/// # Arguments
+/// * `delete` - `false` for a dry run, `true` to delete what was found
+///
+/// # Returns
+/// * `Ok(GcReport)` - Files found, bytes reclaimable, whether they were deleted
+/// * `Err(String)` - Error if the recordings directory couldn't be scanned
+///
+/// The active recording session (if any) is never touched, and no session
+/// is while a refresh or replay is running.
+#[tauri::command]
+fn gc_storage(delete: bool) -> Result<storage::GcReport, String> {
+    let active_session_id = CURRENT_SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|session| session.session_id.clone());
+
+    storage::gc_storage(delete, active_session_id.as_deref(), refresh::is_active())
+}
+
+/// Estimates the output size and duration of an export before running it.
+///
+/// Lets users decide between embedding and linking images, e.g., when the
+/// recipient has an email attachment limit.
+///
+/// # Arguments
+/// * `session_id` - Saved session to estimate
+/// * `format` - `"markdown"` (the step-by-step document) or `"zip"`
+/// * `options` - Optional `ExportOptions` (defaults: link images)
+///
+/// # Returns
+/// * `Ok(ExportEstimate)` - Projected document/total bytes and duration
+/// * `Err(String)` - Error if the session couldn't be loaded, or the format
+///   has no session exporter (`"html"`, `"pdf"`)
+#[tauri::command]
+fn estimate_export(
+    session_id: String,
+    format: export::ExportFormat,
+    options: Option<export::ExportOptions>,
+) -> Result<export::ExportEstimate, String> {
+    export::estimate_export(&session_id, format, &options.unwrap_or_default())
+}
+
+/// Returns a saved session as a follow-along checklist (JSON for the frontend).
+///
+/// Sessions recorded over several sittings include all their parts, one
+/// section per day (see `parts` module). Partial captures come with their
+/// `screenshot_patch` (see `steps` module).
+///
+/// # Returns
+/// * `Ok(Checklist)` - Grouped steps with expected screenshots, all unticked
+/// * `Err(String)` - Error if the session couldn't be loaded
+#[tauri::command]
+fn get_checklist(session_id: String) -> Result<export::checklist::Checklist, String> {
+    let parts = parts::load_parts(&session_id)?;
+    Ok(export::checklist::build_checklist(&parts))
+}
+
+/// Writes a saved session as a Markdown task list.
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `output_path` - Destination `.md` file (defaults to `recordings/[session-id]/checklist.md`)
+///
+/// # Returns
+/// * `Ok(String)` - Path of the written file
+/// * `Err(String)` - Error if the session couldn't be loaded, a screenshot
+///   couldn't be watermarked, or the file written
+///
+/// Linked screenshots are watermarked or metadata-free copies when needed
+/// (see `export::prepare_step_images()`).
+#[tauri::command]
+fn export_checklist_markdown(
+    session_id: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    integrity::ensure_exportable(&session_id)?;
+    let mut parts = parts::load_parts(&session_id)?;
+    for part in parts.iter_mut() {
+        frame_diff::materialize(part);
+    }
+    let mut checklist = export::checklist::build_checklist(&parts);
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| export::checklist::default_markdown_path(&session_id));
+    export::prepare_step_images(
+        checklist.items.iter_mut().map(|item| &mut item.step),
+        &output_path.with_extension("images"),
+    )?;
+
+    export::checklist::write_markdown(&checklist, &output_path)
+        .map(|path| path.display().to_string())
+}
+
+/// Packages a saved session and its screenshots into one ZIP archive.
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `output_path` - Destination file (defaults to
+///   `recordings/[session-id]/[session-id].flowtrace.zip`)
+///
+/// # Returns
+/// * `Ok(String)` - Path of the written archive
+/// * `Err(String)` - Error if the session couldn't be loaded or failed
+///   validation, or the archive couldn't be written
+///
+/// The archive can be imported again by dropping it on the window (see
+/// `importer` module).
+#[tauri::command]
+fn export_session_zip(session_id: String, output_path: Option<String>) -> Result<String, String> {
+    integrity::ensure_exportable(&session_id)?;
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| export::archive::default_archive_path(&session_id));
+    export::archive::export_session_zip(&session_id, &output_path)
+        .map(|path| path.display().to_string())
+}
+
+/// Writes a saved session as an LLM-ready context pack (`manifest.json`,
+/// `steps.jsonl`, optional `images/`).
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `output_dir` - Pack directory (defaults to `recordings/[session-id]/context-pack/`)
+/// * `options` - Optional `ContextPackOptions` (defaults: no images)
+///
+/// # Returns
+/// * `Ok(ContextPackExport)` - Pack directory, step and image counts
+/// * `Err(String)` - Error if the session couldn't be loaded or failed
+///   validation, or the pack couldn't be written
+#[tauri::command]
+fn export_context_pack(
+    session_id: String,
+    output_dir: Option<String>,
+    options: Option<export::context_pack::ContextPackOptions>,
+) -> Result<export::context_pack::ContextPackExport, String> {
+    integrity::ensure_exportable(&session_id)?;
+    let output_dir = output_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(|| export::context_pack::default_pack_dir(&session_id));
+    export::context_pack::export_context_pack(
+        &session_id,
+        &output_dir,
+        &options.unwrap_or_default(),
+    )
+}
+
+/// Exports a saved session as a step-by-step Markdown document.
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `output_path` - Destination file (defaults to `recordings/[session-id]/document.md`)
+/// * `options` - Export options (`embed_images` for a self-contained file)
+///
+/// # Returns
+/// * `Ok(String)` - Path of the written document
+/// * `Err(String)` - Error if the session couldn't be loaded or failed
+///   validation, or the document couldn't be written
+#[tauri::command]
+fn export_markdown_document(
+    session_id: String,
+    output_path: Option<String>,
+    options: Option<export::ExportOptions>,
+) -> Result<String, String> {
+    integrity::ensure_exportable(&session_id)?;
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| export::document::default_document_path(&session_id));
+    export::document::export_document(&session_id, &output_path, &options.unwrap_or_default())
+        .map(|path| path.display().to_string())
+}
+
+/// Copies steps of a saved session to the system clipboard.
+///
+/// # Arguments
+/// * `session_id` - Saved session
+/// * `range` - Steps to copy by number, inclusive (all steps if omitted)
+/// * `format` - `"markdown"` or `"text"`
+///
+/// # Returns
+/// * `Ok(ClipboardCopy)` - The copied text and number of steps
+/// * `Err(String)` - Error if the session couldn't be loaded or failed
+///   validation, the range selects no step, or the clipboard is unavailable
+#[tauri::command]
+fn copy_steps_to_clipboard(
+    session_id: String,
+    range: Option<export::clipboard::StepRange>,
+    format: export::clipboard::ClipboardFormat,
+) -> Result<export::clipboard::ClipboardCopy, String> {
+    integrity::ensure_exportable(&session_id)?;
+    export::clipboard::copy_steps_to_clipboard(&session_id, range, format)
+}
+
+/// Exports the event timeline of a saved session as CSV.
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `output_path` - Destination file (defaults to `recordings/[session-id]/events.csv`)
+///
+/// # Returns
+/// * `Ok(String)` - Path of the written file
+/// * `Err(String)` - Error if the session couldn't be loaded or failed
+///   validation, or the file couldn't be written
+#[tauri::command]
+fn export_csv(session_id: String, output_path: Option<String>) -> Result<String, String> {
+    integrity::ensure_exportable(&session_id)?;
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| export::csv::default_csv_path(&session_id));
+    export::csv::export_csv(&session_id, &output_path).map(|path| path.display().to_string())
+}
+
+/// Generates a Playwright or Selenium test skeleton from a saved session.
+///
+/// # Arguments
+/// * `session_id` - Saved session to export
+/// * `framework` - `"playwright"` or `"selenium"`
+/// * `output_path` - Destination file (defaults to `recordings/[session-id]/[session-id].spec.ts`
+///   or `test_[session-id].py`)
+///
+/// # Returns
+/// * `Ok(String)` - Path of the written script
+/// * `Err(String)` - Error if the session couldn't be loaded or failed
+///   validation, or the file couldn't be written
+#[tauri::command]
+fn export_test_script(
+    session_id: String,
+    framework: export::script::ScriptFramework,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    integrity::ensure_exportable(&session_id)?;
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| export::script::default_script_path(&session_id, framework));
+    export::script::export_script(&session_id, &output_path, framework)
+        .map(|path| path.display().to_string())
+}
+
+/// Exports one event as a snippet: annotated screenshot plus caption.
+///
+/// # Arguments
+/// * `session_id` - Saved session
+/// * `event_id` - Event to export
+/// * `format` - `"markdown"` or `"html"` (image embedded)
+///
+/// # Returns
+/// * `Ok(EventSnippet)` - Snippet text to copy and the written files
+///   (`recordings/[session-id]/snippets/`)
+/// * `Err(String)` - Error if the session failed validation, the event
+///   doesn't exist, the format isn't supported, or the files couldn't be written
+#[tauri::command]
+fn export_event(
+    session_id: String,
+    event_id: String,
+    format: export::ExportFormat,
+) -> Result<export::snippet::EventSnippet, String> {
+    integrity::ensure_exportable(&session_id)?;
+    export::snippet::export_event(&session_id, &event_id, format)
+}
+
+/// Compares a saved session with its re-recorded update, step by step.
+///
+/// # Arguments
+/// * `original_session_id` - Earlier version of the procedure
+/// * `updated_session_id` - Re-recorded version
+///
+/// Partial captures come with their `screenshot_patch` (see `steps` module).
+///
+/// # Returns
+/// * `Ok(Redline)` - Aligned steps marked unchanged/added/removed/changed
+/// * `Err(String)` - Error if either session couldn't be loaded
+#[tauri::command]
+fn compare_sessions(
+    original_session_id: String,
+    updated_session_id: String,
+) -> Result<export::redline::Redline, String> {
+    let original = storage::load_session(&original_session_id)?;
+    let updated = storage::load_session(&updated_session_id)?;
+    Ok(export::redline::build_redline(&original, &updated))
+}
+
+/// Writes the comparison of two sessions as a redline Markdown document.
+///
+/// # Arguments
+/// * `original_session_id` - Earlier version of the procedure
+/// * `updated_session_id` - Re-recorded version
+/// * `output_path` - Destination `.md` file (defaults to
+///   `recordings/[updated-id]/redline_[original-id].md`)
+///
+/// # Returns
+/// * `Ok(String)` - Path of the written file
+/// * `Err(String)` - Error if a session couldn't be loaded or failed
+///   validation, a screenshot couldn't be watermarked, or the file written
+#[tauri::command]
+fn export_redline_markdown(
+    original_session_id: String,
+    updated_session_id: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    integrity::ensure_exportable(&original_session_id)?;
+    integrity::ensure_exportable(&updated_session_id)?;
+    // The document links files, so partial captures need their full frames
+    let mut original = storage::load_session(&original_session_id)?;
+    let mut updated = storage::load_session(&updated_session_id)?;
+    frame_diff::materialize(&mut original);
+    frame_diff::materialize(&mut updated);
+    let mut redline = export::redline::build_redline(&original, &updated);
+    let output_path = output_path.map(PathBuf::from).unwrap_or_else(|| {
+        export::redline::default_markdown_path(&original_session_id, &updated_session_id)
+    });
+    export::prepare_step_images(
+        redline
+            .entries
+            .iter_mut()
+            .flat_map(|entry| [entry.original.as_mut(), entry.updated.as_mut()])
+            .flatten(),
+        &output_path.with_extension("images"),
+    )?;
+
+    export::redline::write_markdown(&redline, &output_path).map(|path| path.display().to_string())
+}
+
+/// Exports events from saved sessions into a single Parquet file.
+///
+/// Requires the `parquet` Cargo feature (`cargo build --features parquet`).
+///
+/// # Arguments
+/// * `output_path` - Destination `.parquet` file
+/// * `session_ids` - Sessions to include (defaults to all saved sessions)
+///
+/// # Returns
+/// * `Ok(ParquetExportSummary)` - Rows written and skipped sessions (as JSON)
+/// * `Err(String)` - Error if a session failed validation, writing failed,
+///   or the feature is disabled
+#[tauri::command]
+fn export_parquet(
+    output_path: String,
+    session_ids: Option<Vec<String>>,
+) -> Result<serde_json::Value, String> {
+    #[cfg(feature = "parquet")]
+    {
+        let session_ids = session_ids.unwrap_or_else(storage::list_session_ids);
+        for session_id in &session_ids {
+            integrity::ensure_exportable(session_id)?;
+        }
+        let summary =
+            export::parquet::export_parquet(std::path::Path::new(&output_path), &session_ids)?;
+        serde_json::to_value(summary).map_err(|e| format!("Failed to serialize summary: {:?}", e))
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    {
+        let _ = (output_path, session_ids);
+        Err(
+            "Parquet export is not available: FlowTrace was built without the `parquet` feature"
+                .to_string(),
+        )
+    }
+}
+
+/// Saves a named export preset, replacing the one with the same name.
+///
+/// # Arguments
+/// * `preset` - Format, filters, image profile, and destination (see `export::presets`)
+///
+/// # Returns
+/// * `Ok(ExportPreset)` - The stored preset
+/// * `Err(String)` - Error if the name is blank or the presets couldn't be written
+#[tauri::command]
+fn save_export_preset(
+    preset: export::presets::ExportPreset,
+) -> Result<export::presets::ExportPreset, String> {
+    export::presets::save(preset)
+}
+
+/// Deletes an export preset.
+///
+/// # Returns
+/// * `Ok(bool)` - Whether the preset existed
+/// * `Err(String)` - Error if the presets couldn't be written
+#[tauri::command]
+fn delete_export_preset(name: String) -> Result<bool, String> {
+    export::presets::remove(&name)
+}
+
+/// Lists saved export presets by name.
+///
+/// # Returns
+/// * `Ok(Vec<ExportPreset>)` - Saved presets
+/// * `Err(String)` - Error if the preset list can't be read
+#[tauri::command]
+fn list_export_presets() -> Result<Vec<export::presets::ExportPreset>, String> {
+    export::presets::list()
+}
+
+/// Exports a saved session with a named preset in one call.
+///
+/// # Arguments
+/// * `session_id` - Session to export
+/// * `preset` - Name of a saved preset (e.g., `"Jira repro"`)
+///
+/// # Returns
+/// * `Ok(PresetExport)` - Written document, destination URL, and whether its credential is set
+/// * `Err(String)` - Error if the preset is unknown or unsupported, the
+///   session failed validation, or the export failed
+#[tauri::command]
+fn export_with_preset(
+    session_id: String,
+    preset: String,
+) -> Result<export::presets::PresetExport, String> {
+    integrity::ensure_exportable(&session_id)?;
+    export::presets::export_with_preset(&session_id, &preset)
+}
+
+/// Searches the OCR-indexed screen text of saved sessions.
+///
+/// Requires `ocr.enabled` in the recorder config while recording; sessions
+/// recorded without it have no index and never match.
+///
+/// # Arguments
+/// * `query` - Text to find (case-insensitive), e.g., `"quota exceeded"`
+/// * `session_ids` - Sessions to search (defaults to all saved sessions)
+///
+/// # Returns
+/// Matching events with timestamps and surrounding text
+#[tauri::command]
+fn search_screen_text(
+    query: String,
+    session_ids: Option<Vec<String>>,
+) -> Vec<ocr::ScreenTextMatch> {
+    let session_ids = session_ids.unwrap_or_else(storage::list_session_ids);
+    ocr::search_screen_text(&query, &session_ids)
+}
+
+/// Flags events of a saved session whose OCR text contains error keywords.
+///
+/// Complements the capture-time error dialog heuristics (window title, size,
+/// red iconography), which run before OCR results exist.
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Ids of all events flagged with `error_dialog_detected`
+/// * `Err(String)` - Error if the session couldn't be loaded or saved
+#[tauri::command]
+fn detect_error_dialogs(session_id: String) -> Result<Vec<String>, String> {
+    let mut session = storage::load_session(&session_id)?;
+    let flagged = ocr::error_keyword_events(&session_id);
+
+    for event in session.events.iter_mut() {
+        if flagged.contains(&event.id.to_string()) {
+            event.error_dialog_detected = true;
+        }
+    }
+    storage::save_session(&session)?;
+
+    Ok(session
+        .events
+        .iter()
+        .filter(|event| event.error_dialog_detected)
+        .map(|event| event.id.to_string())
+        .collect())
+}
+
+/// Inserts a `Marker` event into the active recording (same as the hotkey).
+///
+/// # Arguments
+/// * `label` - Optional text such as `"bug here"` (can be set later with `label_marker`)
+///
+/// # Returns
+/// * `Ok(String)` - Id of the marker event
+/// * `Err(String)` - Error if no recording is in progress
+#[tauri::command]
+fn add_marker(label: Option<String>) -> Result<String, String> {
+    insert_marker(label).map(|event| event.id.to_string())
+}
+
+/// Sets or clears the label of a marker, during or after the recording.
+///
+/// # Arguments
+/// * `session_id` - Session containing the marker (active or saved)
+/// * `event_id` - Marker event to label
+/// * `label` - New label (`None` or blank removes it)
+///
+/// # Returns
+/// * `Ok(String)` - The marker's updated description
+/// * `Err(String)` - Error if the event doesn't exist or isn't a marker
+#[tauri::command]
+fn label_marker(
+    session_id: String,
+    event_id: String,
+    label: Option<String>,
+) -> Result<String, String> {
+    let label = marker::normalize_label(label);
+    let relabel = |session: &mut RecordingSession| {
+        let event = session
+            .events
+            .iter_mut()
+            .find(|event| event.has_id(&event_id))
+            .ok_or_else(|| format!("Event {} not found in session {}", event_id, session_id))?;
+        if !event.set_marker_label(label.clone()) {
+            return Err(format!("Event {} is not a marker", event_id));
+        }
+        journal::append(&session.session_id, event);
+        Ok(event.description().into_owned())
+    };
+
+    // Markers of the active recording are labelled in memory
+    if let Some(session) = CURRENT_SESSION
+        .lock()
+        .unwrap()
+        .as_mut()
+        .filter(|session| session.session_id == session_id)
+    {
+        return relabel(session);
+    }
+
+    let mut session = storage::load_session(&session_id)?;
+    let description = relabel(&mut session)?;
+    storage::save_session(&session)?;
+    Ok(description)
+}
+
+/// Bookmarks an event so it can be found again from any session.
+///
+/// Bookmarking an already bookmarked event updates its note.
+///
+/// # Arguments
+/// * `session_id` - Session containing the event (active or saved)
+/// * `event_id` - Event to pin
+/// * `note` - Optional note such as `"where the export fails"`
+///
+/// # Returns
+/// * `Ok(Bookmark)` - The stored bookmark
+/// * `Err(String)` - Error if the event doesn't exist or the index couldn't be written
+#[tauri::command]
+fn bookmark_event(
+    session_id: String,
+    event_id: String,
+    note: Option<String>,
+) -> Result<bookmarks::Bookmark, String> {
+    let find = |session: &RecordingSession| {
+        session
+            .events
+            .iter()
+            .find(|event| event.has_id(&event_id))
+            .cloned()
+            .ok_or_else(|| format!("Event {} not found in session {}", event_id, session_id))
+    };
+
+    // Events of the active recording aren't saved yet
+    let active_event = CURRENT_SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|session| session.session_id == session_id)
+        .map(find);
+    let event = match active_event {
+        Some(event) => event?,
+        None => find(&storage::load_session(&session_id)?)?,
+    };
+
+    bookmarks::add(&session_id, &event, note)
+}
+
+/// Removes the bookmark of an event.
+///
+/// # Returns
+/// * `Ok(bool)` - Whether the event was bookmarked
+/// * `Err(String)` - Error if the index couldn't be written
+#[tauri::command]
+fn remove_bookmark(session_id: String, event_id: String) -> Result<bool, String> {
+    bookmarks::remove(&session_id, &event_id)
+}
+
+/// Lists bookmarks for jump-to navigation, most recent event first.
+///
+/// # Arguments
+/// * `session_id` - Only this session's bookmarks (`None` = all sessions)
+///
+/// # Returns
+/// * `Ok(Vec<Bookmark>)` - Matching bookmarks
+/// * `Err(String)` - Error if the bookmark index can't be read
+#[tauri::command]
+fn list_bookmarks(session_id: Option<String>) -> Result<Vec<bookmarks::Bookmark>, String> {
+    bookmarks::list(session_id.as_deref())
+}
+
+/// Returns a saved session's events in playback order, bookmarks marked.
+///
+/// # Returns
+/// * `Ok(Timeline)` - Events plus the positions of bookmarked ones
+/// * `Err(String)` - Error if the session or the bookmark index couldn't be loaded
+#[tauri::command]
+fn get_timeline(session_id: String) -> Result<bookmarks::Timeline, String> {
+    let session = storage::load_session(&session_id)?;
+    Ok(bookmarks::session_timeline(
+        &session,
+        &bookmarks::list(Some(&session_id))?,
+    ))
+}
+
+/// Returns the preview track for scrubbing a saved session's timeline.
+///
+/// Written when the recording stopped; regenerated here if missing or older
+/// than `session.json` (see `preview` module).
+///
+/// # Returns
+/// * `Ok(PreviewTrack)` - Downscaled frames and event density buckets
+/// * `Err(String)` - Error if the session couldn't be loaded or the track written
+#[tauri::command]
+fn get_preview_track(session_id: String) -> Result<preview::PreviewTrack, String> {
+    preview::load_or_generate(&session_id, &config::current().preview)
+}
+
+/// Returns the before/after frames of an event and what changed between them.
+///
+/// The event's capture is paired with the next one (the screen the click
+/// led to), or the previous one for the last capture; see `comparison` module.
+///
+/// # Arguments
+/// * `session_id` - Saved session
+/// * `event_id` - Event with a full-screen capture
+///
+/// # Returns
+/// * `Ok(EventComparison)` - Aligned frames, difference mask, and changed regions
+/// * `Err(String)` - Error if the event doesn't exist or has nothing to compare with,
+///   or the frames couldn't be read or written
+#[tauri::command]
+fn get_event_comparison(
+    session_id: String,
+    event_id: String,
+) -> Result<comparison::EventComparison, String> {
+    comparison::get_event_comparison(&session_id, &event_id)
+}
+
+/// Adds a review comment to an event of a saved session.
+///
+/// # Arguments
+/// * `session_id` - Session under review
+/// * `event_id` - Event the comment is about
+/// * `author` - Reviewer name
+/// * `text` - Comment text
+/// * `thread_id` - Thread to reply to (`None` starts a new thread)
+///
+/// # Returns
+/// * `Ok(CommentThread)` - The thread with the new comment
+/// * `Err(String)` - Error if the event or thread doesn't exist, or saving fails
+#[tauri::command]
+fn add_comment(
+    session_id: String,
+    event_id: String,
+    author: String,
+    text: String,
+    thread_id: Option<String>,
+) -> Result<comments::CommentThread, String> {
+    let session = storage::load_session(&session_id)?;
+    if !session.events.iter().any(|event| event.has_id(&event_id)) {
+        return Err(format!(
+            "Event {} not found in session {}",
+            event_id, session_id
+        ));
+    }
+
+    comments::update(&session_id, |session_comments| {
+        session_comments.add(&event_id, thread_id.as_deref(), &author, &text)
+    })
+}
+
+/// Marks a comment thread as resolved (a later reply reopens it).
+///
+/// # Arguments
+/// * `session_id` - Session under review
+/// * `thread_id` - Thread to resolve
+/// * `resolved_by` - Reviewer name (blank = unknown)
+///
+/// # Returns
+/// * `Ok(CommentThread)` - The resolved thread
+/// * `Err(String)` - Error if the thread doesn't exist or saving fails
+#[tauri::command]
+fn resolve_comment_thread(
+    session_id: String,
+    thread_id: String,
+    resolved_by: String,
+) -> Result<comments::CommentThread, String> {
+    comments::update(&session_id, |session_comments| {
+        session_comments.resolve(&thread_id, &resolved_by)
+    })
+}
+
+/// Returns all comment threads of a session, resolved ones included.
+///
+/// # Returns
+/// * `Ok(SessionComments)` - Threads in the order they were started
+/// * `Err(String)` - Error if the comments file can't be read
+#[tauri::command]
+fn list_comments(session_id: String) -> Result<comments::SessionComments, String> {
+    comments::load(&session_id)
+}
+
+/// Runs the privacy detectors over a saved session.
+///
+/// A report is already written when a recording stops; calling this again
+/// picks up screen text that the OCR worker indexed since.
+///
+/// # Returns
+/// * `Ok(PrivacyReport)` - Potentially sensitive events with the reason for each
+/// * `Err(String)` - Error if the session couldn't be loaded or the report written
+#[tauri::command]
+fn get_privacy_report(session_id: String) -> Result<privacy::PrivacyReport, String> {
+    let session = storage::load_session(&session_id)?;
+    privacy::review_saved(&session)
+}
+
+/// Redacts events of a saved session before it is shared.
+///
+/// Typed text, window titles, and clicked element details are removed and
+/// the events' screenshots and screen text deleted. Pass the report's
+/// `flagged_event_ids` to scrub everything it found in one call.
+///
+/// # Arguments
+/// * `session_id` - Saved session to edit
+/// * `event_ids` - Events to redact
+///
+/// # Returns
+/// * `Ok(RedactionSummary)` - Events redacted, screenshots deleted, unknown ids
+/// * `Err(String)` - Error if the session couldn't be loaded or saved
+#[tauri::command]
+fn redact_events(
+    session_id: String,
+    event_ids: Vec<String>,
+) -> Result<privacy::RedactionSummary, String> {
+    privacy::redact(&session_id, &event_ids)
+}
+
+/// Starts follow mode: a guided replay of a saved session.
+///
+/// Opens a small always-on-top overlay window that shows the next step with
+/// its click crop. The overlay advances (via the `follow-step` Tauri event)
+/// when the user performs the matching action; see the `follow` module.
+///
+/// # Arguments
+/// * `session_id` - Saved session to follow
+///
+/// # Returns
+/// * `Ok(FollowState)` - First step to perform
+/// * `Err(String)` - Error if the session has no steps or the overlay couldn't be opened
+///
+/// # Note
+/// Async so the overlay window is created off the main thread (creating
+/// windows from synchronous commands deadlocks on Windows).
+#[tauri::command]
+async fn start_follow(
+    app: tauri::AppHandle,
+    session_id: String,
+) -> Result<follow::FollowState, String> {
+    if refresh::is_active() {
+        return Err("A session is being replayed".to_string());
+    }
+    let state = follow::start(&session_id)?;
+
+    if app.get_webview_window(follow::OVERLAY_LABEL).is_none() {
+        tauri::WebviewWindowBuilder::new(
+            &app,
+            follow::OVERLAY_LABEL,
+            tauri::WebviewUrl::App("index.html#follow".into()),
+        )
+        .title("Follow")
+        .inner_size(360.0, 300.0)
+        .always_on_top(true)
+        .decorations(false)
+        .skip_taskbar(true)
+        .build()
+        .map_err(|e| format!("Failed to open follow overlay: {:?}", e))?;
+    }
+
+    // Actions are observed through the shared listener
+    ensure_listener_started();
+
+    Ok(state)
+}
+
+/// Ends follow mode and closes the overlay window.
+///
+/// # Returns
+/// * `Ok(Some(TrainingReport))` - Step timings if stopped before the last step
+///   (also saved under `recordings/[session-id]/training/`)
+/// * `Ok(None)` - Not following, or already finished (report saved on finish)
+/// * `Err(String)` - Error if the overlay couldn't be closed
+#[tauri::command]
+fn stop_follow(app: tauri::AppHandle) -> Result<Option<follow::TrainingReport>, String> {
+    let report = follow::stop();
+    if let Some(window) = app.get_webview_window(follow::OVERLAY_LABEL) {
+        window
+            .close()
+            .map_err(|e| format!("Failed to close follow overlay: {:?}", e))?;
+    }
+    Ok(report)
+}
+
+/// Returns the step the follow overlay should display (`None` if not following).
+#[tauri::command]
+fn get_follow_state() -> Option<follow::FollowState> {
+    follow::current_state()
+}
+
+/// Skips the current follow step, for actions that can't be detected automatically.
+#[tauri::command]
+fn skip_follow_step() -> Option<follow::FollowState> {
+    follow::skip_step()
+}
+
+/// Re-captures the screenshots of a saved session by replaying it.
+///
+/// Replays clicks, keys, typing, and scrolling with synthetic input on a
+/// background thread and captures new screenshots for every click (see
+/// `refresh` module). Events keep their recorded data; only screenshots change.
+/// Pressing Escape or calling `stop_refresh` stops the replay.
+///
+/// # Arguments
+/// * `session_id` - Saved session to refresh
+/// * `suppress_input` - Block the user's mouse and keyboard during the replay
+///   (default `true`; macOS only, see `input_grab` module)
+///
+/// # Emitted Tauri Events
+/// - `refresh-progress` - `RefreshProgress` after every replayed event
+/// - `refresh-finished` - `RefreshReport` once the replay ended
+/// - `refresh-failed` - `{ session_id, error }` if the session couldn't be saved
+///
+/// # Returns
+/// * `Ok(usize)` - Number of events that will be replayed
+/// * `Err(String)` - Error if the session couldn't be loaded, or a recording,
+///   follow mode, or another refresh is active
+#[tauri::command]
+fn refresh_screenshots(
+    app: tauri::AppHandle,
+    session_id: String,
+    suppress_input: Option<bool>,
+) -> Result<usize, String> {
+    let session = storage::load_session(&session_id)?;
+    refresh::begin()?;
+    if CURRENT_SESSION.lock().unwrap().is_some() || follow::is_active() {
+        refresh::finish();
+        return Err("Stop recording and follow mode before refreshing screenshots".to_string());
+    }
+
+    // Escape is observed through the shared listener
+    ensure_listener_started();
+
+    let total_events = session.events.len();
+    std::thread::spawn(move || {
+        let progress_app = app.clone();
+        let report = refresh::run(session, suppress_input.unwrap_or(true), |progress| {
+            let _ = progress_app.emit("refresh-progress", progress);
+        });
+        refresh::finish();
+
+        match report {
+            Ok(report) => {
+                let _ = app.emit("refresh-finished", report);
+            }
+            Err(e) => {
+                eprintln!("❌ Screenshot refresh failed for {}: {}", session_id, e);
+                let _ = app.emit(
+                    "refresh-failed",
+                    serde_json::json!({
+                        "session_id": session_id,
+                        "error": e,
+                    }),
+                );
+            }
+        }
+    });
+
+    Ok(total_events)
+}
+
+/// Stops a running screenshot refresh after its current input.
+#[tauri::command]
+fn stop_refresh() {
+    refresh::stop();
+}
+
+/// Replays a saved session with synthetic input, as recorded (basic RPA).
+///
+/// Clicks, keys, typing, and scrolling are sent on a background thread;
+/// recorded waits are honored, scaled by `speed` (see `refresh` module).
+/// Nothing is captured or saved. Pressing Escape or calling `stop_replay`
+/// stops the replay.
+///
+/// # Arguments
+/// * `session_id` - Saved session to replay
+/// * `speed` - Speed factor for waits (default `1.0`, `2.0` waits half as long)
+/// * `suppress_input` - Block the user's mouse and keyboard during the replay
+///   (default `true`; macOS only, see `input_grab` module)
+///
+/// # Emitted Tauri Events
+/// - `replay-progress` - `ReplayProgress` after every replayed event
+/// - `replay-finished` - `ReplayReport` once the replay ended
+///
+/// # Returns
+/// * `Ok(usize)` - Number of events that will be replayed
+/// * `Err(String)` - Error if the session couldn't be loaded, the speed is out
+///   of range, or a recording, follow mode, or another replay is active
+#[tauri::command]
+fn replay_session(
+    app: tauri::AppHandle,
+    session_id: String,
+    speed: Option<f64>,
+    suppress_input: Option<bool>,
+) -> Result<usize, String> {
+    let speed = refresh::validate_speed(speed)?;
+    let session = storage::load_session(&session_id)?;
+    refresh::begin()?;
+    if CURRENT_SESSION.lock().unwrap().is_some() || follow::is_active() {
+        refresh::finish();
+        return Err("Stop recording and follow mode before replaying a session".to_string());
+    }
+
+    // Escape is observed through the shared listener
+    ensure_listener_started();
+
+    let total_events = session.events.len();
+    std::thread::spawn(move || {
+        let progress_app = app.clone();
+        let report = refresh::replay(session, speed, suppress_input.unwrap_or(true), |progress| {
+            let _ = progress_app.emit("replay-progress", progress);
+        });
+        refresh::finish();
+        let _ = app.emit("replay-finished", report);
+    });
+
+    Ok(total_events)
+}
+
+/// Stops a running session replay after its current input.
+#[tauri::command]
+fn stop_replay() {
+    refresh::stop();
+}
+
+/// Aggregates saved sessions into the anonymized usage stats file.
+///
+/// Opt-in: fails unless `telemetry.enabled` is set in the recorder config.
+/// Only sessions not yet counted are added, so this is safe to call repeatedly.
+///
+/// # Returns
+/// * `Ok(UsageStats)` - Counts per category, event type, and hour of day
+/// * `Err(String)` - Error if telemetry is disabled or the stats file couldn't be written
+#[tauri::command]
+fn aggregate_usage_stats() -> Result<stats::UsageStats, String> {
+    let active_session_id = CURRENT_SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|session| session.session_id.clone());
+
+    stats::aggregate_usage_stats(active_session_id.as_deref())
+}
+
+/// Summarizes a saved session by foreground application.
+///
+/// # Returns
+/// * `Ok(Vec<AppUsage>)` - Events and time per application, most used first
+/// * `Err(String)` - Error if the session couldn't be loaded
+#[tauri::command]
+fn get_app_usage(session_id: String) -> Result<Vec<stats::AppUsage>, String> {
+    let session = storage::load_session(&session_id)?;
+    Ok(stats::app_usage(&session))
+}
+
+/// Returns the current recorder configuration.
+#[tauri::command]
+fn get_config() -> config::RecorderConfig {
+    config::current()
+}
+
+/// Returns which kinds of input the recording currently captures.
+#[tauri::command]
+fn get_capture_flags() -> capture_flags::CaptureFlags {
+    capture_flags::current()
+}
+
+/// Switches kinds of input on or off for the active recording.
+///
+/// Takes effect with the next event, without pausing the recording
+/// (e.g., `keys: false` while typing a password). Flags reset to the
+/// defaults whenever a recording starts.
+///
+/// # Returns
+/// The flags now in effect
+#[tauri::command]
+fn set_capture_flags(flags: capture_flags::CaptureFlags) -> capture_flags::CaptureFlags {
+    capture_flags::set(flags);
+    #[cfg(debug_assertions)]
+    println!("🎚️  Capture flags: {:?}", flags);
+    flags
+}
+
+/// Replaces and persists the recorder configuration.
+///
+/// Changes take effect immediately, including for an active recording
+/// (e.g., per-display capture overrides apply to the next click).
+///
+/// # Returns
+/// * `Ok(())` - Saved to `config.json` in the recordings directory
+/// * `Err(String)` - Error if a scrub or anonymize pattern, the marker hotkey, or a
+///   threshold, interval, or ratio is out of range, or the config file couldn't be written
+#[tauri::command]
+fn set_config(config: config::RecorderConfig) -> Result<(), String> {
+    scrub::ScrubRules::compile(&config.scrub)?;
+    anonymize::Anonymizer::compile(&config.anonymize)?;
+    config.waits.validate()?;
+    config.idle.validate()?;
+    config.wait_causes.validate()?;
+    config.preview.validate()?;
+    config.checkpoints.validate()?;
+    config.remote_desktop.validate()?;
+    config.watermark.validate()?;
+    config.partial_capture.validate()?;
+    config.mouse_trail.validate()?;
+    config.ocr.validate()?;
+    config.limits.validate()?;
+    config.latency.validate()?;
+    config.marker.validate()?;
+    config::update(config)
+}
 
-    match session_lock.take() {
-        Some(mut session) => {
-            session.stop();
-            let event_count = session.events.len();
+/// Reports where sessions are stored (see `storage` module).
+///
+/// # Returns
+/// * `Ok(StorageInfo)` - Directory in use, the default, and whether it was chosen
+/// * `Err(String)` - Error if the app data directory can't be determined
+#[tauri::command]
+fn get_storage_location(app: tauri::AppHandle) -> Result<storage::StorageInfo, String> {
+    let app_data_dir = app_data_dir(&app)?;
+    Ok(storage::storage_info(&app_data_dir))
+}
 
-            // Save to disk as JSON
-            match storage::save_session(&session) {
-                Ok(path) => {
-                    #[cfg(debug_assertions)]
-                    println!(
-                        "✅ Recording stopped. {} events saved to: {:?}",
-                        event_count, path
-                    );
-                    Ok(format!(
-                        "Recording stopped. {} events captured. Saved to: {}",
-                        event_count,
-                        path.display()
-                    ))
-                }
-                Err(e) => Err(format!("Failed to save recording: {}", e)),
-            }
-        }
-        None => Err("No recording in progress".to_string()),
+/// Chooses the directory sessions are stored in, and switches to it.
+///
+/// The settings in use move along to the new directory's `config.json`
+/// (one already there is kept as `config.json.bak`). Existing sessions are
+/// not moved.
+///
+/// # Arguments
+/// * `recordings_dir` - Absolute directory (`None` = `[app data]/recordings`)
+///
+/// # Returns
+/// * `Ok(StorageInfo)` - The directory now in use
+/// * `Err(String)` - Error if a recording or refresh is running, the path is
+///   relative, or it or the config file couldn't be written
+#[tauri::command]
+fn set_storage_location(
+    app: tauri::AppHandle,
+    recordings_dir: Option<String>,
+) -> Result<storage::StorageInfo, String> {
+    if CURRENT_SESSION.lock().unwrap().is_some() {
+        return Err("Can't move the recordings directory while recording".to_string());
+    }
+    if refresh::is_active() {
+        return Err("A session is being replayed".to_string());
     }
+    let app_data_dir = app_data_dir(&app)?;
+    let recordings_dir = recordings_dir
+        .filter(|dir| !dir.trim().is_empty())
+        .map(PathBuf::from);
+    let settings = config::current();
+    storage::set_location(&app_data_dir, recordings_dir)?;
+    config::carry_over(settings)?;
+    Ok(storage::storage_info(&app_data_dir))
+}
+
+/// Platform app-data directory (Tauri's path resolver).
+fn app_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {:?}", e))
 }
 
 /// Detects significant pauses between user actions and inserts synthetic Wait events.
@@ -256,9 +2659,14 @@ fn stop_recording() -> Result<String, String> {
 ///
 /// # Algorithm
 /// 1. Calculate time difference between now and last event
-/// 2. If gap >= 2.0 seconds → create synthetic Wait event
-/// 3. Add Wait event to current session
-/// 4. Update last event timestamp to now
+/// 2. If gap >= `waits.threshold_seconds` (default 2.0) → record any sleep /
+///    screen lock that just ended and subtract its time (see `lifecycle` module)
+/// 3. If the remaining gap is still >= threshold → create synthetic Wait event,
+///    or an `Idle` event if it is >= `idle.threshold_seconds` (see `idle` module)
+/// 4. Attribute the Wait to loading / reading / away from the screen samples
+///    and a change of the focused window (see `wait_cause` module)
+/// 5. Add Wait / Idle event to current session
+/// 6. Update last event timestamp to now
 ///
 /// # Why This Matters
 /// Wait events provide context about user behavior:
@@ -280,39 +2688,78 @@ fn stop_recording() -> Result<String, String> {
 /// - `CURRENT_SESSION` - Adds Wait event to recording
 ///
 /// # Tuning
-/// - Threshold: 2.0 seconds (configurable via `WAIT_THRESHOLD_SECONDS`)
-/// - Too low: Noisy with many short waits
-/// - Too high: Miss meaningful pauses
+/// - Threshold: `waits.threshold_seconds` in the recorder config (default 2.0),
+///   read for every event so `set_config` applies to an active recording;
+///   learned from the user's pace with `waits.adaptive` (see `adaptive_wait` module)
+/// - Too low: Noisy with many short waits (raise it for slow page loads)
+/// - Too high: Miss meaningful pauses (lower it for fast data entry)
 fn check_and_insert_wait_event() {
-    /// Minimum pause duration (seconds) to trigger a Wait event.
-    /// Tuned to capture meaningful pauses without noise.
-    const WAIT_THRESHOLD_SECONDS: f64 = 2.0;
-
+    let threshold_seconds = adaptive_wait::threshold_seconds();
     let now = chrono::Utc::now();
 
     // Acquire lock and check last event time
     if let Ok(mut last_time_lock) = LAST_EVENT_TIME.lock() {
         if let Some(last_time) = *last_time_lock {
             // Calculate time gap in seconds (convert from milliseconds)
-            let duration = (now - last_time).num_milliseconds() as f64 / 1000.0;
+            let mut duration = (now - last_time).num_milliseconds() as f64 / 1000.0;
+
+            // Time asleep or locked is a Lifecycle event, not a Wait. The
+            // watcher may not have noticed the wake yet, so check now.
+            if duration >= threshold_seconds {
+                record_lifecycle_events();
+                duration -= lifecycle::inactive_seconds(last_time, now);
+            }
+
+            // No input for minutes: the user was away, not pausing
+            let idle_event = if duration >= threshold_seconds {
+                idle::idle_event(&config::idle(), last_time, duration)
+            } else {
+                None
+            };
+            if idle_event.is_none() {
+                adaptive_wait::observe_gap(duration);
+            }
 
             // Only insert Wait event if gap is significant
-            if duration >= WAIT_THRESHOLD_SECONDS {
+            if let Some(idle_event) = idle_event {
+                #[cfg(debug_assertions)]
+                println!("💤 Idle detected: {:.1}s without input", duration);
+
+                if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+                    add_filtered(session, idle_event);
+                }
+            } else if duration >= threshold_seconds {
                 #[cfg(debug_assertions)]
                 println!("⏸️  Wait detected: {:.1}s pause", duration);
 
                 // Create synthetic Wait event with calculated duration
-                let wait_event = Event::new(
+                let mut wait_event = Event::new(
                     EventType::Wait {
                         duration_seconds: duration,
+                        cause: None,
                     },
                     None, // No position for Wait events
                 );
+                screenshot::stamp_focused_window(&mut wait_event);
 
                 // Add to current recording session
                 if let Ok(mut session_lock) = CURRENT_SESSION.lock() {
                     if let Some(session) = session_lock.as_mut() {
-                        session.add_event(wait_event);
+                        // A different window without input: something opened or navigated
+                        let window_changed = session.events.last().is_some_and(|previous| {
+                            let same = |a: &Option<String>, b: &Option<String>| {
+                                a.is_none() || b.is_none() || a == b
+                            };
+                            !same(&previous.app_name, &wait_event.app_name)
+                                || !same(&previous.window_title, &wait_event.window_title)
+                        });
+                        wait_event.set_wait_cause(wait_cause::cause(
+                            &config::wait_causes(),
+                            last_time,
+                            now,
+                            window_changed,
+                        ));
+                        add_filtered(session, wait_event);
                         #[cfg(debug_assertions)]
                         println!(
                             "✅ Wait event added to session (total: {})",
@@ -329,6 +2776,83 @@ fn check_and_insert_wait_event() {
     }
 }
 
+/// Detects the cursor resting in one place and inserts synthetic Hover events.
+///
+/// The counterpart of `check_and_insert_wait_event()` for the mouse: `rdev`
+/// reports nothing while the cursor rests, so the dwell is measured when it
+/// moves away again.
+///
+/// # Algorithm
+/// 1. MouseMove within `HOVER_RADIUS` of the anchor → still resting
+/// 2. MouseMove farther away → if resting >= threshold, insert a Hover event
+///    at the anchor position (timestamped when the rest began); the new
+///    position becomes the anchor
+/// 3. Click / key press → clear the anchor (the dwell led to an action)
+///
+/// # Example Timeline
+/// ```text
+/// 10:00:00 - Mouse comes to rest over a menu at (640, 24)
+/// 10:00:03 - Mouse moves away → Insert Hover(3.0s) at (640, 24)
+/// ```
+///
+/// # Tuning
+/// - Threshold: 1.5 seconds (`HOVER_THRESHOLD_SECONDS`); tooltips appear after ~1s
+/// - Radius: 10 logical px (`HOVER_RADIUS`) absorbs hand tremor
+fn check_and_insert_hover_event(event_type: &rdev::EventType) {
+    /// Minimum resting time (seconds) to trigger a Hover event.
+    const HOVER_THRESHOLD_SECONDS: f64 = 1.5;
+    /// Maximum cursor drift (logical px) that still counts as resting.
+    const HOVER_RADIUS: f64 = 10.0;
+
+    let (x, y) = match *event_type {
+        rdev::EventType::MouseMove { x, y } => (x, y),
+        rdev::EventType::ButtonPress(_) | rdev::EventType::KeyPress(_) => {
+            if let Ok(mut anchor) = HOVER_ANCHOR.lock() {
+                *anchor = None;
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    let now = chrono::Utc::now();
+    let Ok(mut anchor_lock) = HOVER_ANCHOR.lock() else {
+        return;
+    };
+
+    if let Some((since, anchor_x, anchor_y)) = *anchor_lock {
+        if (x - anchor_x).hypot(y - anchor_y) <= HOVER_RADIUS {
+            return; // Still resting
+        }
+
+        let duration = (now - since).num_milliseconds() as f64 / 1000.0;
+        if duration >= HOVER_THRESHOLD_SECONDS {
+            #[cfg(debug_assertions)]
+            println!(
+                "🫧 Hover detected: {:.1}s at ({}, {})",
+                duration, anchor_x, anchor_y
+            );
+
+            let mut hover_event = Event::new(
+                EventType::Hover {
+                    duration_seconds: duration,
+                },
+                Some(Position::new(anchor_x, anchor_y)),
+            );
+            hover_event.timestamp = since;
+            screenshot::stamp_focused_window(&mut hover_event);
+
+            if let Ok(mut session_lock) = CURRENT_SESSION.lock() {
+                if let Some(session) = session_lock.as_mut() {
+                    add_filtered(session, hover_event);
+                }
+            }
+        }
+    }
+
+    *anchor_lock = Some((now, x, y));
+}
+
 /// Main event handler for all captured system events (clicks, keyboard, mouse moves).
 ///
 /// This function is called by the `rdev` event listener for **every** system event.
@@ -340,18 +2864,31 @@ fn check_and_insert_wait_event() {
 /// ```
 ///
 /// # What Gets Processed
-/// - **MouseMove**: Track position (don't record event itself - too noisy)
+/// - **MouseMove**: Track position (don't record event itself - too noisy);
+///   sampled into `MouseTrail` events when enabled in the config
 /// - **ButtonPress**: Record clicks with screenshots (left, right, middle)
-/// - **KeyPress**: Record keyboard input (filter out modifier-only keys)
-/// - **Other events**: Ignored (button release, wheel, etc.)
+/// - **KeyPress**: Record keyboard input; with Ctrl/Alt/Cmd held → `Shortcut`
+///   (withheld while a password field has focus → `CredentialsEntered`)
+/// - **Modifier press/release**: Track held modifiers (not recorded themselves)
+/// - **Wheel**: Merged into `Scroll` events when the `scroll` capture flag is on
+/// - **ButtonRelease**: Completes the `duration_ms` of the pressed click; a
+///   release away from a press in Finder/Explorer records a file drag
+/// - **KeyRelease**: Completes the `duration_ms` of the pressed key; presses
+///   of a key that wasn't released are auto-repeats (`repeat_count`)
+/// - **Other events**: Ignored
+///
+/// # Capture Flags
+/// Clicks, keys, scrolling, and click screenshots can each be switched off
+/// at runtime (see `capture_flags` module and `set_capture_flags`).
 ///
 /// # Wait Detection
-/// Before processing each event, checks for pauses > 2 seconds and inserts
+/// Before processing each event, checks for pauses above the wait threshold and inserts
 /// synthetic Wait events automatically.
 ///
 /// # Thread Context
-/// Runs in background thread spawned by `start_recording()`.
-/// Uses `Arc<Mutex<>>` for thread-safe access to global state.
+/// Runs in the background listener thread spawned by the first `start_recording()`.
+/// Uses `Arc<Mutex<>>` for thread-safe access to global state, and returns
+/// early (after tracking mouse position) while `RECORDING_ACTIVE` is `false`.
 ///
 /// # Performance Considerations
 /// - **MouseMove**: Not recorded (would generate 100+ events/second)
@@ -361,8 +2898,35 @@ fn check_and_insert_wait_event() {
 /// # Arguments
 /// * `event` - Raw event from `rdev::listen()` containing event type and metadata
 fn handle_event(event: rdev::Event) {
-    // STEP 1: Check for significant time gaps and insert Wait events
+    // Modifier state must stay accurate across sessions and pauses
+    track_modifiers(&event.event_type);
+
+    // Follow mode watches actions whether or not a recording is running
+    if follow::is_active() {
+        observe_follow(&event.event_type);
+    }
+    // Escape stops a screenshot refresh
+    if refresh::is_active() {
+        refresh::observe(&event.event_type);
+    }
+
+    // Listener outlives recordings: between sessions only track mouse
+    // position, so the first click of the next session is accurate
+    if !RECORDING_ACTIVE.load(Ordering::SeqCst) {
+        if let rdev::EventType::MouseMove { x, y } = event.event_type {
+            if let Ok(mut pos) = LAST_MOUSE_POSITION.lock() {
+                *pos = (x, y);
+            }
+        }
+        return;
+    }
+
+    // STEP 1: Check for significant time gaps and insert Wait / Hover events
     check_and_insert_wait_event();
+    check_and_insert_hover_event(&event.event_type);
+
+    // Runtime toggles (e.g., keyboard off while entering credentials)
+    let flags = capture_flags::current();
 
     match event.event_type {
         // STEP 2: Track mouse position (required for clicks, but don't record moves)
@@ -370,20 +2934,28 @@ fn handle_event(event: rdev::Event) {
             if let Ok(mut pos) = LAST_MOUSE_POSITION.lock() {
                 *pos = (x, y);
             }
-            // Early return: MouseMove events are too noisy to record
-            // (Would generate 100+ events per second of mouse movement)
+            // MouseMove events are too noisy to record one by one
+            // (100+ per second); opt-in trails sample them instead
+            if let Some(trail_event) = mouse_trail::sample(x, y) {
+                if let Ok(mut session_lock) = CURRENT_SESSION.lock() {
+                    if let Some(session) = session_lock.as_mut() {
+                        add_filtered(session, trail_event);
+                    }
+                }
+            }
             return;
         }
 
         // STEP 3: Process and record mouse button clicks
         rdev::EventType::ButtonPress(button) => {
-            // Filter: Only capture left, right, middle buttons
-            let mouse_button = match button {
-                rdev::Button::Left => MouseButton::Left,
-                rdev::Button::Right => MouseButton::Right,
-                rdev::Button::Middle => MouseButton::Middle,
-                _ => return, // Ignore trackpad gestures, forward/back buttons, etc.
-            };
+            // A click can focus a password field; the next key press checks
+            CREDENTIAL_RECHECK.store(true, Ordering::SeqCst);
+            if !flags.clicks {
+                return;
+            }
+
+            // Every button is recorded (back/forward and extra buttons included)
+            let mouse_button = mouse_button(button);
 
             // Retrieve last known mouse position from global tracker
             // (rdev doesn't provide position in ButtonPress events)
@@ -392,7 +2964,11 @@ fn handle_event(event: rdev::Event) {
                 *pos
             };
 
-            let position = Position::new(x, y);
+            // Also relative to its display, for consumers with other resolutions
+            let position = match screenshot::display_at(x, y) {
+                Some(display) => Position::new(x, y).with_display(&display),
+                None => Position::new(x, y),
+            };
 
             #[cfg(debug_assertions)]
             println!("🖱️  Click detected at ({}, {})", position.x, position.y);
@@ -405,47 +2981,106 @@ fn handle_event(event: rdev::Event) {
             // Create event with position (will be moved/consumed)
             let mut new_event = Event::new(
                 EventType::Click {
-                    button: mouse_button,
+                    button: mouse_button.clone(),
+                    duration_ms: None,
                 },
                 Some(position),
             );
+            screenshot::stamp_focused_window(&mut new_event);
+            // Resolve the element before the click changes the UI (a remote
+            // window's controls aren't visible to it, see `remote_desktop` module)
+            let element = (!new_event.remote_desktop)
+                .then(|| accessibility::element_at(x, y))
+                .flatten();
+            if let Some(element) = element {
+                new_event = new_event.with_element(element);
+            }
+            if !new_event.remote_desktop {
+                new_event.scroll_position = accessibility::scroll_position_at(x, y);
+            }
+            // Before the (expensive) screenshots
+            if !filter::allows(&new_event) {
+                return;
+            }
+            // Paired with the release to measure how long the button was held
+            PENDING_CLICKS
+                .lock()
+                .unwrap()
+                .push((mouse_button, new_event.id, event.time));
 
             // Screenshot capture and session update
             if let Ok(session_lock) = CURRENT_SESSION.lock() {
                 if let Some(session) = session_lock.as_ref() {
                     let session_id = session.session_id.clone();
-                    let event_id = new_event.id.clone();
+                    let event_id = new_event.id;
+                    let pressed_at = new_event.timestamp;
 
                     // CRITICAL: Drop lock BEFORE screenshot capture
                     // Screenshot can take 100-500ms, holding the lock would block other events
                     drop(session_lock);
 
                     // Capture 3 screenshots: full screen, window crop, click crop
-                    // Note: Window and click crops may be offset on Retina displays (known issue)
-                    match screenshot::capture_all_for_event(
-                        &session_id,
-                        &event_id,
-                        click_x,
-                        click_y,
-                    ) {
-                        Ok((full, window, click)) => {
-                            new_event = new_event.with_screenshots(Some(full), window, click);
+                    let mut latency_reference = None;
+                    let capture = if flags.screenshots {
+                        screenshot::capture_all_for_event(
+                            &session_id,
+                            &event_id.to_string(),
+                            click_x,
+                            click_y,
+                        )
+                    } else {
+                        Ok(None)
+                    };
+                    match capture {
+                        Ok(Some(capture)) => {
+                            // Background, throttled; no-op unless enabled in config
+                            // (a dry run's screenshots were never written)
+                            if !dry_run::covers(&session_id) {
+                                ocr::enqueue(
+                                    &session_id,
+                                    &event_id.to_string(),
+                                    new_event.timestamp,
+                                    &capture.full_screen,
+                                );
+                            }
+                            new_event.error_dialog_detected = capture.error_dialog_detected;
+                            new_event.annotation_colors = capture.annotation_colors;
+                            new_event = new_event.with_screenshots(
+                                Some(capture.full_screen),
+                                capture.window_crop,
+                                capture.click_crop,
+                            );
+                            new_event.screenshots.full_screen_patch = capture.full_screen_patch;
+                            latency_reference = capture.latency_reference;
                             #[cfg(debug_assertions)]
                             println!(
                                 "📸 Screenshots captured for event {} (full + window + click)",
                                 event_id
                             );
                         }
+                        Ok(None) => {
+                            dry_run::record_skipped(&session_id);
+                            #[cfg(debug_assertions)]
+                            println!(
+                                "🙈 Screenshots skipped for event {} (display config or capture flags)",
+                                event_id
+                            );
+                        }
                         Err(e) => {
                             // Non-fatal: Continue recording even if screenshot fails
                             eprintln!("⚠️  Failed to capture screenshots: {}", e);
+                            dry_run::record_error(&session_id, &e);
                         }
                     }
 
                     // Re-acquire lock and add event to session
                     if let Ok(mut session_lock) = CURRENT_SESSION.lock() {
                         if let Some(session) = session_lock.as_mut() {
-                            session.add_event(new_event);
+                            // Path leading to the click comes first
+                            if let Some(trail_event) = mouse_trail::take_event() {
+                                add_filtered(session, trail_event);
+                            }
+                            record_event(session, new_event);
                             #[cfg(debug_assertions)]
                             println!(
                                 "✅ Event added to session (total: {})",
@@ -453,12 +3088,53 @@ fn handle_event(event: rdev::Event) {
                             );
                         }
                     }
+
+                    // Background sampling until the screen responds (see `latency` module)
+                    if let Some(reference) = latency_reference {
+                        latency::measure(
+                            reference,
+                            pressed_at,
+                            &config::current().latency,
+                            move |latency_ms| record_response_latency(event_id, latency_ms),
+                        );
+                    }
                 }
             }
         }
 
         // STEP 4: Process and record keyboard events
         rdev::EventType::KeyPress(key) => {
+            // Convert key enum to string representation (e.g., "KeyA", "Return", "Space")
+            // Computed once: the hotkey check and the recorded event share both
+            let key_str = format!("{:?}", key);
+            let modifiers = held_modifiers();
+
+            // Marker hotkey: works even with keyboard capture off, never recorded as a key
+            if marker::is_hotkey(&modifiers, &key_str) {
+                if let Err(e) = insert_marker(None) {
+                    eprintln!("⚠️  Failed to insert marker: {}", e);
+                }
+                return;
+            }
+            if !flags.keys {
+                return;
+            }
+            // Password fields: keys are withheld, a placeholder is recorded instead
+            let credentials_active = if CREDENTIAL_RECHECK.swap(false, Ordering::SeqCst) {
+                update_credential_entry()
+            } else {
+                CREDENTIAL_ENTRY.lock().unwrap().is_some()
+            };
+            // Tab, Return, and shortcuts can move focus into (or out of) a field
+            if matches!(key, rdev::Key::Tab | rdev::Key::Return)
+                || modifiers.iter().any(|name| name != "Shift")
+            {
+                CREDENTIAL_RECHECK.store(true, Ordering::SeqCst);
+            }
+            if credentials_active {
+                return;
+            }
+
             // Early exit: Only capture if recording is active
             if let Ok(session_lock) = CURRENT_SESSION.lock() {
                 if session_lock.is_none() {
@@ -468,31 +3144,53 @@ fn handle_event(event: rdev::Event) {
                 return; // Failed to acquire lock
             }
 
-            // Convert key enum to string representation (e.g., "KeyA", "Return", "Space")
-            let key_str = format!("{:?}", key);
+            // Auto-repeat of a held key: counted on the original press
+            if record_key_repeat(key, event.time) {
+                return;
+            }
 
             // Filter: Skip modifier-only keys to reduce noise
             // Rationale: Modifier keys alone (Shift, Ctrl, Cmd) don't represent user intent
-            // We only care about the final key combination (e.g., "KeyS" not "ShiftLeft + KeyS")
-            if matches!(
-                key,
-                rdev::Key::ShiftLeft
-                    | rdev::Key::ShiftRight
-                    | rdev::Key::ControlLeft
-                    | rdev::Key::ControlRight
-                    | rdev::Key::Alt
-                    | rdev::Key::AltGr
-                    | rdev::Key::MetaLeft
-                    | rdev::Key::MetaRight
-            ) {
+            // They are tracked by track_modifiers() and attached to the next key instead
+            if modifier_name(key).is_some() {
                 return; // Skip modifier-only presses
             }
 
+            // Ctrl/Alt/Cmd + key is a shortcut; Shift alone is just typing (capitals)
+            let event_type = if modifiers.iter().any(|modifier| modifier != "Shift") {
+                EventType::Shortcut {
+                    modifiers,
+                    key: key_str,
+                }
+            } else {
+                EventType::KeyPress {
+                    key: key_str,
+                    shift: !modifiers.is_empty(),
+                    text: typed_text(&event),
+                    duration_ms: None,
+                    repeat_count: 0,
+                }
+            };
+
             #[cfg(debug_assertions)]
-            println!("⌨️  Key pressed: {}", key_str);
+            println!("⌨️  Key pressed: {:?}", event_type);
 
             // Create event without position (keyboard events aren't location-based)
-            let new_event = Event::new(EventType::KeyPress { key: key_str }, None);
+            let mut new_event = Event::new(event_type, None);
+            screenshot::stamp_focused_window(&mut new_event);
+            if !filter::allows(&new_event) {
+                return;
+            }
+            // Paired with the release (hold duration) and later repeats;
+            // shortcuts repeat as separate events (each Cmd+Z undoes again)
+            if matches!(new_event.event_type, EventType::KeyPress { .. }) {
+                PENDING_KEYS.lock().unwrap().push(PendingKey {
+                    key,
+                    event_id: new_event.id,
+                    pressed_at: event.time,
+                    last_seen: event.time,
+                });
+            }
 
             // Add to session WITHOUT screenshot capture
             // Design decision: Skip screenshots for keyboard events to:
@@ -501,7 +3199,7 @@ fn handle_event(event: rdev::Event) {
             // - Rely on click screenshots for visual context
             if let Ok(mut session_lock) = CURRENT_SESSION.lock() {
                 if let Some(session) = session_lock.as_mut() {
-                    session.add_event(new_event);
+                    record_event(session, new_event);
                     #[cfg(debug_assertions)]
                     println!(
                         "✅ Key event added to session (total: {})",
@@ -511,16 +3209,427 @@ fn handle_event(event: rdev::Event) {
             }
         }
 
-        // STEP 5: Ignore all other event types
+        // STEP 5: Record scrolling (opt-in capture flag)
+        rdev::EventType::Wheel { delta_x, delta_y } => {
+            if flags.scroll {
+                record_scroll(delta_x, delta_y);
+            }
+        }
+
+        // STEP 6: Complete the hold duration of clicks and keys
+        rdev::EventType::ButtonRelease(button) => record_click_release(button, event.time),
+        rdev::EventType::KeyRelease(key) => record_key_duration(key, event.time),
+
+        // STEP 7: Ignore all other event types
         _ => {
             // Explicitly ignored:
-            // - ButtonRelease: We only care about press, not release
-            // - Wheel: Mouse wheel events not relevant for workflow tracking
             // - Other: Any future event types from rdev
         }
     }
 }
 
+/// `rdev::Button::Unknown` codes of the back and forward buttons on this platform.
+#[cfg(target_os = "windows")]
+const BACK_FORWARD_CODES: (u8, u8) = (1, 2); // XBUTTON1, XBUTTON2
+#[cfg(target_os = "macos")]
+const BACK_FORWARD_CODES: (u8, u8) = (3, 4); // CGMouseButton numbers
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const BACK_FORWARD_CODES: (u8, u8) = (8, 9); // X11 buttons
+
+/// Recorded button of an `rdev` button.
+fn mouse_button(button: rdev::Button) -> MouseButton {
+    match button {
+        rdev::Button::Left => MouseButton::Left,
+        rdev::Button::Right => MouseButton::Right,
+        rdev::Button::Middle => MouseButton::Middle,
+        rdev::Button::Unknown(code) if code == BACK_FORWARD_CODES.0 => MouseButton::Back,
+        rdev::Button::Unknown(code) if code == BACK_FORWARD_CODES.1 => MouseButton::Forward,
+        rdev::Button::Unknown(code) => MouseButton::Other(code),
+    }
+}
+
+/// `rdev` button that replays a recorded button (inverse of `mouse_button()`).
+fn rdev_button(button: &MouseButton) -> rdev::Button {
+    match button {
+        MouseButton::Left => rdev::Button::Left,
+        MouseButton::Right => rdev::Button::Right,
+        MouseButton::Middle => rdev::Button::Middle,
+        MouseButton::Back => rdev::Button::Unknown(BACK_FORWARD_CODES.0),
+        MouseButton::Forward => rdev::Button::Unknown(BACK_FORWARD_CODES.1),
+        MouseButton::Other(code) => rdev::Button::Unknown(*code),
+    }
+}
+
+/// Stores how long the screen took to respond to a recorded click.
+fn record_response_latency(event_id: uuid::Uuid, latency_ms: u64) {
+    if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        if let Some(click) = session.events.iter_mut().rev().find(|e| e.id == event_id) {
+            click.response_latency_ms = Some(latency_ms);
+            journal::append(&session.session_id, click);
+        }
+    }
+}
+
+/// Stores how long the button of a recorded click was held, and whether it
+/// dragged a file (see `file_drag` module).
+///
+/// # Arguments
+/// * `button` - Released button
+/// * `released_at` - Time of the release, from `rdev`
+fn record_click_release(button: rdev::Button, released_at: std::time::SystemTime) {
+    let button = mouse_button(button);
+    let pressed = {
+        let mut pending = PENDING_CLICKS.lock().unwrap();
+        let index = pending.iter().position(|(pressed, ..)| *pressed == button);
+        index.map(|index| pending.remove(index))
+    };
+    let Some((_, event_id, pressed_at)) = pressed else {
+        return; // Press wasn't recorded (filtered, clicks off, before the session)
+    };
+
+    let duration_ms = released_at
+        .duration_since(pressed_at)
+        .map(|held| held.as_millis() as u64)
+        .unwrap_or(0);
+    let (x, y) = *LAST_MOUSE_POSITION.lock().unwrap();
+    if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        if let Some(click) = session.events.iter_mut().rev().find(|e| e.id == event_id) {
+            click.set_click_duration(duration_ms);
+            if let Some(mut drag) = file_drag::infer(click, x, y, window_capture::list_windows) {
+                let folder =
+                    file_drag::home_dir().and_then(|home| file_drag::target_folder(&drag, &home));
+                if let (Some(folder), Some(file_name)) = (folder, drag.file_name.clone()) {
+                    watch_dropped_file(&session.session_id, event_id, folder, file_name);
+                }
+                anonymize::apply_to_file_drag(&mut drag);
+                click.set_file_drag(drag);
+            }
+            journal::append(&session.session_id, click);
+        }
+    }
+}
+
+/// Records where a file dropped on a folder window ended up (see
+/// "Filesystem Correlation" in the `file_drag` module), in the background.
+fn watch_dropped_file(session_id: &str, event_id: uuid::Uuid, folder: PathBuf, file_name: String) {
+    let session_id = session_id.to_string();
+    std::thread::spawn(move || {
+        let Some(path) = file_drag::wait_for_file(&folder, &file_name) else {
+            return;
+        };
+        let mut session_lock = CURRENT_SESSION.lock().unwrap();
+        let Some(session) = session_lock
+            .as_mut()
+            .filter(|session| session.session_id == session_id)
+        else {
+            return; // Recording stopped meanwhile
+        };
+        if let Some(click) = session.events.iter_mut().rev().find(|e| e.id == event_id) {
+            if let Some(drag) = click.file_drag.as_mut() {
+                drag.target_path = Some(anonymize::file_path(&path.display().to_string()));
+            }
+            journal::append(&session.session_id, click);
+        }
+    });
+}
+
+/// Counts a press of a key that is still held on its recorded `KeyPress`.
+///
+/// # Returns
+/// `true` if the press was an auto-repeat (and must not be recorded)
+fn record_key_repeat(key: rdev::Key, pressed_at: std::time::SystemTime) -> bool {
+    let event_id = {
+        let mut pending = PENDING_KEYS.lock().unwrap();
+        let Some(index) = pending.iter().position(|held| held.key == key) else {
+            return false;
+        };
+        let gap = pressed_at
+            .duration_since(pending[index].last_seen)
+            .unwrap_or_default();
+        if gap > KEY_REPEAT_MAX_GAP {
+            pending.remove(index); // Release was missed: a new press
+            return false;
+        }
+        pending[index].last_seen = pressed_at;
+        pending[index].event_id
+    };
+
+    if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        if let Some(press) = session.events.iter_mut().rev().find(|e| e.id == event_id) {
+            press.add_key_repeat();
+            journal::append(&session.session_id, press);
+        }
+    }
+    true
+}
+
+/// Stores how long the key of a recorded key press was held.
+///
+/// # Arguments
+/// * `key` - Released key
+/// * `released_at` - Time of the release, from `rdev`
+fn record_key_duration(key: rdev::Key, released_at: std::time::SystemTime) {
+    let pressed = {
+        let mut pending = PENDING_KEYS.lock().unwrap();
+        let index = pending.iter().position(|held| held.key == key);
+        index.map(|index| pending.remove(index))
+    };
+    let Some(pressed) = pressed else {
+        return; // Press wasn't recorded (modifier, hotkey, password field, filtered)
+    };
+
+    let duration_ms = released_at
+        .duration_since(pressed.pressed_at)
+        .map(|held| held.as_millis() as u64)
+        .unwrap_or(0);
+    if let Some(session) = CURRENT_SESSION.lock().unwrap().as_mut() {
+        if let Some(press) = session
+            .events
+            .iter_mut()
+            .rev()
+            .find(|e| e.id == pressed.event_id)
+        {
+            press.set_key_duration(duration_ms);
+            journal::append(&session.session_id, press);
+        }
+    }
+}
+
+/// Appends a `Marker` event to the active recording.
+///
+/// # Emitted Tauri Events
+/// - `marker-added` - The new event, so the frontend can offer to label it
+///
+/// # Returns
+/// * `Ok(Event)` - The inserted marker
+/// * `Err(String)` - Error if no recording is in progress
+fn insert_marker(label: Option<String>) -> Result<Event, String> {
+    let mut session_lock = CURRENT_SESSION.lock().unwrap();
+    let session = session_lock
+        .as_mut()
+        .ok_or_else(|| "No recording in progress".to_string())?;
+
+    // Movement before the marker belongs before it
+    if let Some(trail_event) = mouse_trail::take_event() {
+        add_filtered(session, trail_event);
+    }
+    let mut marker_event = Event::new(
+        EventType::Marker {
+            label: marker::normalize_label(label),
+        },
+        None,
+    );
+    screenshot::stamp_focused_window(&mut marker_event);
+    record_event(session, marker_event.clone());
+    drop(session_lock);
+
+    #[cfg(debug_assertions)]
+    println!("🚩 {}", marker_event.description());
+
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit("marker-added", &marker_event);
+    }
+    Ok(marker_event)
+}
+
+/// Adds an event to the session unless the recording's filter drops it.
+fn add_filtered(session: &mut RecordingSession, event: Event) {
+    if filter::allows(&event) {
+        record_event(session, event);
+    }
+}
+
+/// Adds an event to the active session and its journal (see `journal` module).
+fn record_event(session: &mut RecordingSession, event: Event) {
+    journal::append(&session.session_id, &event);
+    session.add_event(event);
+}
+
+/// Characters a key press produced in the active keyboard layout.
+///
+/// `rdev` resolves them through the OS (`event.unicode`), so shifted
+/// symbols, non-US layouts, and dead-key compositions (`´` + `e` → `é`)
+/// come out right, unlike the key name.
+///
+/// # Returns
+/// `None` for dead keys (the character arrives with the next key) and
+/// non-printing keys (Return, Tab, arrows report control characters)
+fn typed_text(event: &rdev::Event) -> Option<String> {
+    let unicode = event.unicode.as_ref()?;
+    if unicode.is_dead {
+        return None;
+    }
+    let text = match &unicode.name {
+        Some(name) => name.clone(),
+        None => String::from_utf16(&unicode.unicode).ok()?,
+    };
+    Some(text).filter(|text| !text.is_empty() && !text.chars().any(char::is_control))
+}
+
+/// Records a wheel tick, merged into the previous `Scroll` event while scrolling continues.
+///
+/// One scroll gesture produces dozens of ticks; ticks less than
+/// `SCROLL_MERGE_SECONDS` apart are summed into the session's last event as
+/// long as it is still that `Scroll` (any other event starts a new one).
+fn record_scroll(delta_x: i64, delta_y: i64) {
+    /// Maximum gap (seconds) between ticks of one scroll gesture.
+    const SCROLL_MERGE_SECONDS: f64 = 1.0;
+
+    let now = chrono::Utc::now();
+    let continues = LAST_SCROLL_AT
+        .lock()
+        .ok()
+        .and_then(|mut last| last.replace(now))
+        .is_some_and(|last| (now - last).num_milliseconds() as f64 / 1000.0 < SCROLL_MERGE_SECONDS);
+
+    let Ok(mut session_lock) = CURRENT_SESSION.lock() else {
+        return;
+    };
+    let Some(session) = session_lock.as_mut() else {
+        return;
+    };
+
+    if continues {
+        if let Some(last) = session.events.last_mut() {
+            if let EventType::Scroll {
+                delta_x: total_x,
+                delta_y: total_y,
+            } = last.event_type
+            {
+                let mut merged = Event::new(
+                    EventType::Scroll {
+                        delta_x: total_x + delta_x,
+                        delta_y: total_y + delta_y,
+                    },
+                    last.position.take(),
+                );
+                merged.id = last.id;
+                merged.timestamp = last.timestamp;
+                merged.app_name = last.app_name.take();
+                merged.window_title = last.window_title.take();
+                merged.remote_desktop = last.remote_desktop;
+                *last = merged;
+                journal::append(&session.session_id, last);
+                return;
+            }
+        }
+    }
+
+    let (x, y) = *LAST_MOUSE_POSITION.lock().unwrap();
+    let mut scroll_event = Event::new(
+        EventType::Scroll { delta_x, delta_y },
+        Some(Position::new(x, y)),
+    );
+    screenshot::stamp_focused_window(&mut scroll_event);
+    add_filtered(session, scroll_event);
+}
+
+/// Canonical name of a modifier key (left/right variants merged).
+///
+/// # Returns
+/// `Some("Control" | "Alt" | "Shift" | "Meta")`, or `None` for other keys
+fn modifier_name(key: rdev::Key) -> Option<&'static str> {
+    match key {
+        rdev::Key::ControlLeft | rdev::Key::ControlRight => Some("Control"),
+        rdev::Key::Alt | rdev::Key::AltGr => Some("Alt"),
+        rdev::Key::ShiftLeft | rdev::Key::ShiftRight => Some("Shift"),
+        rdev::Key::MetaLeft | rdev::Key::MetaRight => Some("Meta"),
+        _ => None,
+    }
+}
+
+/// Updates `PRESSED_MODIFIERS` from modifier press/release events.
+fn track_modifiers(event_type: &rdev::EventType) {
+    let (key, pressed) = match *event_type {
+        rdev::EventType::KeyPress(key) => (key, true),
+        rdev::EventType::KeyRelease(key) => (key, false),
+        _ => return,
+    };
+    if modifier_name(key).is_none() {
+        return;
+    }
+
+    if let Ok(mut held) = PRESSED_MODIFIERS.lock() {
+        held.retain(|held_key| *held_key != key);
+        if pressed {
+            held.push(key);
+        }
+    }
+}
+
+/// Names of the modifiers currently held, in `Control, Alt, Shift, Meta` order.
+fn held_modifiers() -> Vec<String> {
+    let held = PRESSED_MODIFIERS.lock().unwrap();
+    ["Control", "Alt", "Shift", "Meta"]
+        .into_iter()
+        .filter(|name| held.iter().any(|key| modifier_name(*key) == Some(*name)))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Feeds a click or key press into follow mode and notifies the overlay.
+///
+/// # Emitted Tauri Events
+/// - `follow-step` - `FollowState` whenever a step was completed
+fn observe_follow(event_type: &rdev::EventType) {
+    let action = match *event_type {
+        rdev::EventType::ButtonPress(button) => {
+            let button = mouse_button(button);
+            let (x, y) = *LAST_MOUSE_POSITION.lock().unwrap();
+            follow::ObservedAction::Click { button, x, y }
+        }
+        rdev::EventType::KeyPress(key) if modifier_name(key).is_none() => {
+            follow::ObservedAction::Key {
+                key: format!("{:?}", key),
+                modifiers: held_modifiers(),
+            }
+        }
+        _ => return,
+    };
+
+    if let Some(state) = follow::observe(&action) {
+        if let Some(app) = APP_HANDLE.get() {
+            let _ = app.emit("follow-step", state);
+        }
+    }
+}
+
+/// Imports session folders or `.flowtrace.zip` archives dropped onto the window.
+///
+/// Runs on a background thread so large archives don't block the UI.
+///
+/// # Emitted Tauri Events
+/// - `import-progress` - `ImportProgress` after each stage and copied file
+/// - `import-finished` - `ImportOutcome` once a dropped path was imported
+/// - `import-failed` - `{ source, error }` if validation or copying failed
+fn handle_dropped_paths(app: tauri::AppHandle, paths: Vec<PathBuf>) {
+    std::thread::spawn(move || {
+        for path in paths {
+            #[cfg(debug_assertions)]
+            println!("📥 Importing dropped path: {:?}", path);
+
+            let progress_app = app.clone();
+            match importer::import_path(&path, |progress| {
+                let _ = progress_app.emit("import-progress", progress);
+            }) {
+                Ok(outcome) => {
+                    let _ = app.emit("import-finished", outcome);
+                }
+                Err(e) => {
+                    eprintln!("❌ Import failed for {:?}: {}", path, e);
+                    let _ = app.emit(
+                        "import-failed",
+                        serde_json::json!({
+                            "source": path.display().to_string(),
+                            "error": e,
+                        }),
+                    );
+                }
+            }
+        }
+    });
+}
+
 /// Initializes and runs the Tauri application.
 ///
 /// Sets up the Tauri runtime with all registered commands and plugins.
@@ -532,10 +3641,62 @@ fn handle_event(event: rdev::Event) {
 /// - `capture_screenshot` - Spike testing command
 /// - `start_recording` - **Main**: Start workflow recording
 /// - `stop_recording` - **Main**: Stop and save recording
+/// - `continue_session` - Record the next sitting of a stopped session as a linked part
+/// - `start_dry_run` / `stop_dry_run` - Rehearse a recording without saving anything
+/// - `pause_recording` / `resume_recording` - Suspend capture within a session
+/// - `get_interrupted_recording` / `resume_interrupted_recording` /
+///   `finish_interrupted_recording` - Continue or close a recording after a restart
+/// - `list_unfinished_sessions` / `recover_session` - Finalize sessions a crash
+///   cut off (also emitted as `unfinished-sessions` at startup)
+/// - `get_capture_flags` / `set_capture_flags` - Toggle clicks/keys/scroll/screenshots live
+/// - `add_marker` / `label_marker` - Insert and label manual `Marker` events
+/// - `bookmark_event` / `remove_bookmark` / `list_bookmarks` - Pin events across sessions
+/// - `get_timeline` - Events in playback order with bookmarks marked
+/// - `get_preview_track` - Downscaled frames and event density for timeline scrubbing
+/// - `get_event_comparison` - Aligned before/after frames, difference mask, and changed regions of an event
+/// - `refresh_screenshots` / `stop_refresh` - Re-capture screenshots by replaying a session
+/// - `replay_session` / `stop_replay` - Replay a session with synthetic input (waits scaled by speed)
+/// - `add_comment` / `resolve_comment_thread` / `list_comments` - Review threads on events
+/// - `rerecord_step` - Replace one step of a saved session with a new snippet
+/// - `duplicate_session` - Copy a saved session into an editable draft
+/// - `move_draft_event`, `insert_manual_step`, `set_step_grouping` - Reorder,
+///   extend, and regroup the steps of a draft
+/// - `list_sessions` - Saved sessions with times, event counts, and sizes
+/// - `load_session` - A saved session with absolute screenshot paths, for review
+/// - `validate_session` - Missing screenshots and inconsistent data in a saved session
+/// - `gc_storage` - Clean up orphaned screenshots and temporary files
+/// - `import_loose_screenshots` - Gather spike screenshots into a session
+/// - `capture_window_by_title` - Snapshot a window matching a title pattern
+/// - `estimate_export` - Projected export size before exporting
+/// - `get_config` / `set_config` - Read and persist recorder configuration
+/// - `get_storage_location` / `set_storage_location` - Where sessions are stored
+///   (the app data directory by default)
+/// - `get_checklist` / `export_checklist_markdown` - Follow-along checklist of steps
+/// - `export_session_zip` - A saved session and its screenshots as one `.flowtrace.zip`
+/// - `export_context_pack` - Steps as JSONL (element, screen text, typed text) for LLM pipelines
+/// - `export_markdown_document` - Step-by-step Markdown document (SOP) with screenshots and wait notes
+/// - `copy_steps_to_clipboard` - A range of steps as Markdown or plain text on the clipboard
+/// - `export_csv` - Event timeline as CSV (timestamp, type, category, key/button, position, app, description)
+/// - `export_test_script` - Playwright/Selenium test skeleton from the steps
+/// - `export_event` - One step's annotated screenshot and caption (Markdown/HTML)
+/// - `compare_sessions` / `export_redline_markdown` - What changed between two versions
+/// - `export_parquet` - Columnar export across sessions (`parquet` feature)
+/// - `save_export_preset` / `delete_export_preset` / `list_export_presets` /
+///   `export_with_preset` - Named export settings for repeat publishing
+/// - `aggregate_usage_stats` - Opt-in anonymized usage statistics
+/// - `get_app_usage` - Per-application summary of one session
+/// - `search_screen_text` - Find events by OCR-indexed on-screen text
+/// - `detect_error_dialogs` - Flag events whose screen text shows an error
+/// - `get_privacy_report` / `redact_events` - Review and scrub sensitive events before sharing
+/// - `start_follow` / `stop_follow` - Guided replay overlay for a saved session
+/// - `get_follow_state` / `skip_follow_step` - Follow overlay state and manual advance
 ///
 /// # Plugins
 /// - `tauri_plugin_opener` - Handles file/URL opening
 ///
+/// # Window Events
+/// - File drop: Imports session folders / archives via `handle_dropped_paths()`
+///
 /// # Platform Support
 /// - Primary: macOS (tested on Sonoma 25.2.0)
 /// - Mobile: Conditional compilation via `#[cfg_attr]`
@@ -546,12 +3707,108 @@ fn handle_event(event: rdev::Event) {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            let _ = APP_HANDLE.set(app.handle().clone());
+
+            // Store recordings in the app data directory (see `storage` module)
+            match app_data_dir(app.handle()) {
+                Ok(app_data_dir) => {
+                    storage::configure_root(&app_data_dir);
+                    config::reload();
+                }
+                Err(e) => eprintln!("⚠️  Recording to ./recordings: {}", e),
+            }
+
+            // Offer to finish sessions a crash cut off (see `recovery` module)
+            let handle = app.handle().clone();
+            std::thread::spawn(move || {
+                let unfinished = recovery::scan(None);
+                if !unfinished.is_empty() {
+                    let _ = handle.emit("unfinished-sessions", &unfinished);
+                }
+            });
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) = event {
+                handle_dropped_paths(window.app_handle().clone(), paths.clone());
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             start_event_listener,
             capture_screenshot,
             start_recording,
-            stop_recording
+            stop_recording,
+            continue_session,
+            start_dry_run,
+            stop_dry_run,
+            pause_recording,
+            resume_recording,
+            rerecord_step,
+            duplicate_session,
+            move_draft_event,
+            insert_manual_step,
+            set_step_grouping,
+            list_sessions,
+            load_session,
+            validate_session,
+            gc_storage,
+            import_loose_screenshots,
+            capture_window_by_title,
+            get_interrupted_recording,
+            resume_interrupted_recording,
+            finish_interrupted_recording,
+            list_unfinished_sessions,
+            recover_session,
+            estimate_export,
+            get_checklist,
+            export_checklist_markdown,
+            export_session_zip,
+            export_context_pack,
+            export_markdown_document,
+            copy_steps_to_clipboard,
+            export_csv,
+            export_test_script,
+            export_event,
+            compare_sessions,
+            export_redline_markdown,
+            get_capture_flags,
+            set_capture_flags,
+            add_marker,
+            label_marker,
+            bookmark_event,
+            remove_bookmark,
+            list_bookmarks,
+            get_timeline,
+            get_preview_track,
+            get_event_comparison,
+            refresh_screenshots,
+            stop_refresh,
+            replay_session,
+            stop_replay,
+            add_comment,
+            resolve_comment_thread,
+            list_comments,
+            get_config,
+            set_config,
+            get_storage_location,
+            set_storage_location,
+            export_parquet,
+            save_export_preset,
+            delete_export_preset,
+            list_export_presets,
+            export_with_preset,
+            aggregate_usage_stats,
+            get_app_usage,
+            search_screen_text,
+            detect_error_dialogs,
+            get_privacy_report,
+            redact_events,
+            start_follow,
+            stop_follow,
+            get_follow_state,
+            skip_follow_step
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,209 @@
+//! # Lifecycle Module - Sleep and Screen Lock Detection
+//!
+//! If the machine sleeps or the screen is locked mid-recording, the session
+//! would otherwise show one giant Wait. These intervals are recorded as
+//! `Lifecycle { state, duration_seconds }` events instead (timestamped at the
+//! start, like `Paused`), and Wait events never count time inside them, so
+//! analysis can exclude that time.
+//!
+//! ## Detection
+//! - **Sleep**: The watcher polls about once a second; a wall-clock gap of
+//!   more than `SLEEP_GAP` between two polls means the process was suspended
+//!   (all platforms)
+//! - **Screen lock**: `CGSSessionScreenIsLocked` in the session dictionary on
+//!   macOS; not detected on other platforms
+//!
+//! A lock still open when the recording stops is closed by `finish()`.
+
+use crate::types::{Event, EventType, LifecycleState};
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// Gap between polls that can only be explained by a suspended machine.
+const SLEEP_GAP: Duration = Duration::seconds(10);
+
+/// What the watcher has seen during the active session.
+#[derive(Debug, Default)]
+struct WatchState {
+    last_poll: Option<DateTime<Utc>>,
+    locked_since: Option<DateTime<Utc>>,
+    /// Completed (start, end) intervals, for `inactive_seconds()`
+    intervals: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+static STATE: Lazy<Arc<Mutex<WatchState>>> =
+    Lazy::new(|| Arc::new(Mutex::new(WatchState::default())));
+
+/// Starts watching for the recording that is starting.
+pub fn reset() {
+    *STATE.lock().unwrap() = WatchState {
+        last_poll: Some(Utc::now()),
+        ..WatchState::default()
+    };
+}
+
+/// Checks for sleep and lock transitions once.
+///
+/// # Returns
+/// `Lifecycle` events for intervals that just ended (usually none)
+pub fn poll() -> Vec<Event> {
+    let now = Utc::now();
+    observe(&mut STATE.lock().unwrap(), now, screen_locked())
+}
+
+/// Closes a lock interval that is still open (recording stopped while locked).
+pub fn finish() -> Option<Event> {
+    let mut state = STATE.lock().unwrap();
+    let since = state.locked_since.take()?;
+    Some(interval_event(
+        &mut state,
+        LifecycleState::Locked,
+        since,
+        Utc::now(),
+    ))
+}
+
+/// Seconds of `start..end` the machine was asleep or locked.
+pub fn inactive_seconds(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    let state = STATE.lock().unwrap();
+    let open_lock = state.locked_since.map(|since| (since, end));
+    state
+        .intervals
+        .iter()
+        .copied()
+        .chain(open_lock)
+        .map(|(from, to)| (to.min(end) - from.max(start)).num_milliseconds().max(0))
+        .sum::<i64>() as f64
+        / 1000.0
+}
+
+/// Applies one observation (`locked`: `None` if unknown on this platform).
+fn observe(state: &mut WatchState, now: DateTime<Utc>, locked: Option<bool>) -> Vec<Event> {
+    let mut events = Vec::new();
+
+    if let Some(last) = state.last_poll.filter(|last| now - *last > SLEEP_GAP) {
+        events.push(interval_event(state, LifecycleState::Sleep, last, now));
+    }
+    state.last_poll = Some(now);
+
+    match (locked, state.locked_since) {
+        (Some(true), None) => state.locked_since = Some(now),
+        (Some(false), Some(since)) => {
+            state.locked_since = None;
+            events.push(interval_event(state, LifecycleState::Locked, since, now));
+        }
+        _ => {}
+    }
+
+    events
+}
+
+/// Records a completed interval and builds its event.
+fn interval_event(
+    state: &mut WatchState,
+    lifecycle_state: LifecycleState,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Event {
+    state.intervals.push((start, end));
+    let mut event = Event::new(
+        EventType::Lifecycle {
+            state: lifecycle_state,
+            duration_seconds: (end - start).num_milliseconds() as f64 / 1000.0,
+        },
+        None,
+    );
+    event.timestamp = start;
+    event
+}
+
+/// Whether the screen is locked (`None` if unknown).
+#[cfg(target_os = "macos")]
+fn screen_locked() -> Option<bool> {
+    macos::screen_locked()
+}
+
+/// Whether the screen is locked (`None` if unknown).
+///
+/// Not implemented on this platform.
+#[cfg(not(target_os = "macos"))]
+fn screen_locked() -> Option<bool> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::ffi::{c_char, c_void, CString};
+
+    type CFTypeRef = *const c_void;
+
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGSessionCopyCurrentDictionary() -> CFTypeRef;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFDictionaryGetValue(dict: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+        fn CFBooleanGetValue(boolean: CFTypeRef) -> u8;
+        fn CFStringCreateWithCString(
+            allocator: CFTypeRef,
+            string: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFRelease(cf: CFTypeRef);
+    }
+
+    pub fn screen_locked() -> Option<bool> {
+        let key = CString::new("CGSSessionScreenIsLocked").ok()?;
+        // SAFETY: The session dictionary and the key are owned (+1) and
+        // released exactly once; the looked-up value is borrowed.
+        unsafe {
+            let session = CGSessionCopyCurrentDictionary();
+            if session.is_null() {
+                return None; // No GUI session (e.g., at the login window)
+            }
+            let key =
+                CFStringCreateWithCString(std::ptr::null(), key.as_ptr(), CF_STRING_ENCODING_UTF8);
+            if key.is_null() {
+                CFRelease(session);
+                return None;
+            }
+
+            // The key is only present while locked
+            let value = CFDictionaryGetValue(session, key);
+            let locked = !value.is_null() && CFBooleanGetValue(value) != 0;
+            CFRelease(key);
+            CFRelease(session);
+            Some(locked)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep_and_lock_become_intervals() {
+        let start: DateTime<Utc> = "2026-02-01T15:00:00Z".parse().unwrap();
+        let at = |seconds| start + Duration::seconds(seconds);
+        let mut state = WatchState {
+            last_poll: Some(start),
+            ..WatchState::default()
+        };
+
+        assert!(observe(&mut state, at(1), Some(false)).is_empty());
+        assert!(observe(&mut state, at(2), Some(true)).is_empty());
+        // Asleep while locked, then unlocked
+        let events = observe(&mut state, at(602), Some(true));
+        assert_eq!(events[0].description(), "System asleep for 600.0 seconds");
+        assert_eq!(events[0].timestamp, at(2));
+        let events = observe(&mut state, at(610), Some(false));
+        assert_eq!(events[0].description(), "Screen locked for 608.0 seconds");
+        assert_eq!(events[0].timestamp, at(2));
+    }
+}
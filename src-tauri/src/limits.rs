@@ -0,0 +1,96 @@
+//! # Limits Module - Automatic Stop for Forgotten Recordings
+//!
+//! A recording left running overnight keeps writing ~2.2 MB per click. The
+//! optional `limits` in the recorder config stop it automatically:
+//!
+//! | Limit | Counts |
+//! |-------|--------|
+//! | `max_duration_seconds` | Wall-clock time since the recording started (pauses included) |
+//! | `max_events` | Events in the session (waits and markers included) |
+//!
+//! A watcher thread checks the active recording every second. When a limit
+//! is reached, the session is finalized and saved through `stop_recording()`,
+//! and the frontend is notified with the `recording-auto-stopped` Tauri event
+//! (payload: `AutoStop`).
+
+use crate::config::SessionLimits;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Which limit stopped a recording.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitReached {
+    MaxDuration,
+    MaxEvents,
+}
+
+/// Payload of the `recording-auto-stopped` event.
+#[derive(Serialize, Debug, Clone)]
+pub struct AutoStop {
+    pub reason: LimitReached,
+    /// Result message of `stop_recording()` (or its error)
+    pub message: String,
+}
+
+/// Returns the first limit a recording has reached, if any.
+///
+/// # Arguments
+/// * `limits` - Configured limits (`None` fields are unlimited)
+/// * `started_at` - When the recording started
+/// * `event_count` - Events recorded so far
+/// * `now` - Current time
+pub fn reached(
+    limits: &SessionLimits,
+    started_at: DateTime<Utc>,
+    event_count: usize,
+    now: DateTime<Utc>,
+) -> Option<LimitReached> {
+    let elapsed_seconds = (now - started_at).num_milliseconds() as f64 / 1000.0;
+
+    if limits
+        .max_duration_seconds
+        .is_some_and(|max| elapsed_seconds >= max)
+    {
+        Some(LimitReached::MaxDuration)
+    } else if limits.max_events.is_some_and(|max| event_count >= max) {
+        Some(LimitReached::MaxEvents)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_limits_reached() {
+        let started_at = Utc::now();
+        let an_hour_later = started_at + Duration::hours(1);
+        let limits = SessionLimits {
+            max_duration_seconds: Some(3600.0),
+            max_events: Some(500),
+        };
+
+        assert_eq!(reached(&limits, started_at, 10, started_at), None);
+        assert_eq!(
+            reached(&limits, started_at, 10, an_hour_later),
+            Some(LimitReached::MaxDuration)
+        );
+        assert_eq!(
+            reached(&limits, started_at, 500, started_at),
+            Some(LimitReached::MaxEvents)
+        );
+        assert_eq!(
+            reached(
+                &SessionLimits::default(),
+                started_at,
+                100_000,
+                an_hour_later
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! # Mouse Trail Module - Throttled Cursor Trajectories
+//!
+//! `MouseMove` events are descoped from recording (100+ per second), but
+//! cursor trajectories matter for UX research. When enabled in the recorder
+//! config, positions are sampled at `sample_hz` and stored as compact
+//! `MouseTrail` events holding many points each.
+//!
+//! ## Flushing
+//! Buffered points become one `MouseTrail` event:
+//! - Right before a click is recorded (the path leading to the click)
+//! - When the buffer reaches `max_points`
+//! - When the recording is paused or stopped
+//!
+//! ## JSON
+//! Points are `[offset_ms, x, y]` relative to the event timestamp (first point):
+//! ```json
+//! {"type": "MouseTrail", "points": [[0, 100, 200], [200, 130, 210], [400, 180, 240]]}
+//! ```
+
+use crate::types::{Event, EventType, TrailPoint};
+use crate::{config, screenshot};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// Points sampled since the last flush.
+static TRAIL: Lazy<Arc<Mutex<Vec<(DateTime<Utc>, f64, f64)>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
+/// Records a cursor position if trails are enabled and the sample interval has passed.
+///
+/// # Returns
+/// A `MouseTrail` event if the buffer just filled up, `None` otherwise
+pub fn sample(x: f64, y: f64) -> Option<Event> {
+    let settings = config::mouse_trail();
+    if !settings.enabled || settings.sample_hz <= 0.0 {
+        return None;
+    }
+
+    let now = Utc::now();
+    let interval_ms = (1000.0 / settings.sample_hz) as i64;
+    let mut trail = TRAIL.lock().ok()?;
+    if let Some((last, _, _)) = trail.last() {
+        if (now - *last).num_milliseconds() < interval_ms {
+            return None;
+        }
+    }
+    trail.push((now, x, y));
+
+    if trail.len() >= settings.max_points.max(2) {
+        return build_event(trail.drain(..).collect()).map(with_focused_window);
+    }
+    None
+}
+
+/// Flushes buffered points into a `MouseTrail` event.
+///
+/// # Returns
+/// `None` if fewer than two points were sampled (no movement to show)
+pub fn take_event() -> Option<Event> {
+    let points: Vec<_> = TRAIL.lock().ok()?.drain(..).collect();
+    build_event(points).map(with_focused_window)
+}
+
+/// Discards buffered points (e.g., movement from before a recording started).
+pub fn reset() {
+    if let Ok(mut trail) = TRAIL.lock() {
+        trail.clear();
+    }
+}
+
+fn with_focused_window(mut event: Event) -> Event {
+    screenshot::stamp_focused_window(&mut event);
+    event
+}
+
+fn build_event(points: Vec<(DateTime<Utc>, f64, f64)>) -> Option<Event> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let start = points[0].0;
+    let points = points
+        .into_iter()
+        .map(|(time, x, y)| {
+            TrailPoint(
+                (time - start).num_milliseconds().max(0) as u32,
+                x as i32,
+                y as i32,
+            )
+        })
+        .collect();
+
+    let mut event = Event::new(EventType::MouseTrail { points }, None);
+    event.timestamp = start;
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_points_are_relative_to_first_sample() {
+        let start = Utc::now();
+        let event = build_event(vec![
+            (start, 10.0, 20.0),
+            (start + Duration::milliseconds(200), 15.5, 25.0),
+        ])
+        .unwrap();
+
+        assert_eq!(event.timestamp, start);
+        match event.event_type {
+            EventType::MouseTrail { points } => {
+                assert_eq!(points, vec![TrailPoint(0, 10, 20), TrailPoint(200, 15, 25)]);
+            }
+            other => panic!("expected MouseTrail, got {:?}", other),
+        }
+        assert!(build_event(vec![(start, 1.0, 1.0)]).is_none());
+    }
+}
@@ -0,0 +1,356 @@
+//! # Preview Module - Scrubbable Timeline Track
+//!
+//! Dragging across a session's timeline would decode a 2.2MB screenshot per
+//! position. When a recording stops, a lightweight preview track is written
+//! next to it instead:
+//!
+//! ```text
+//! recordings/[session-id]/preview.json         frames + event density
+//! recordings/[session-id]/preview/[event-id].jpg  320px wide JPEG per frame
+//! ```
+//!
+//! ## Frames
+//! One frame per captured step (click, capture) by default, or at most one
+//! per `preview.frame_interval_seconds`. Each is the event's full screen
+//! (reconstructed if it was a partial capture), downscaled to
+//! `preview.frame_width`. While scrubbing, the frontend shows the last frame
+//! at or before the position.
+//!
+//! ## Density
+//! Events per `preview.bucket_seconds` bucket from the session start, for a
+//! sparkline under the timeline. Timing-only events (Wait, Idle, Paused,
+//! Lifecycle, RecorderRestart) record the absence of activity and aren't
+//! counted.
+//!
+//! ## Staleness
+//! `load_or_generate()` rebuilds the track when `session.json` was written
+//! after it (redaction, re-recording, splicing), or when it is missing.
+//! Redaction also deletes the frames of the redacted events right away
+//! (`remove_frames()`), so their pixels aren't viewable until then.
+
+use crate::config::PreviewConfig;
+use crate::frame_diff;
+use crate::steps;
+use crate::storage;
+use crate::types::{Event, EventType, RecordingSession};
+use chrono::{DateTime, Utc};
+use image::{DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Preview data of one session (`preview.json`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PreviewTrack {
+    pub session_id: String,
+    pub generated_at: DateTime<Utc>,
+    /// Session start to stop (or last event)
+    pub duration_seconds: f64,
+    /// Frames in time order
+    pub frames: Vec<PreviewFrame>,
+    /// Length of one density bucket
+    pub bucket_seconds: f64,
+    /// Events per bucket, from the session start
+    pub density: Vec<usize>,
+}
+
+/// One downscaled frame of the timeline.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PreviewFrame {
+    /// Position on the timeline (seconds after the session start)
+    pub offset_seconds: f64,
+    /// Event whose screenshot the frame shows
+    pub event_id: String,
+    /// Step the event belongs to (see `steps` module)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_number: Option<usize>,
+    /// Stored path of the JPEG
+    pub path: String,
+}
+
+/// Location of a session's preview track.
+pub fn preview_path(session_id: &str) -> PathBuf {
+    storage::get_session_dir(session_id).join("preview.json")
+}
+
+/// Writes the preview track of a saved session (see module docs).
+///
+/// Frames of an earlier track are replaced. Screenshots that can't be read
+/// are left out with a warning.
+///
+/// # Returns
+/// * `Ok(PreviewTrack)` - The written track
+/// * `Err(String)` - Error if the preview files couldn't be written
+pub fn generate(
+    session: &RecordingSession,
+    config: &PreviewConfig,
+) -> Result<PreviewTrack, String> {
+    let frame_dir = storage::get_session_dir(&session.session_id).join("preview");
+    if frame_dir.exists() {
+        fs::remove_dir_all(&frame_dir)
+            .map_err(|e| format!("Failed to clear preview frames: {:?}", e))?;
+    }
+    fs::create_dir_all(&frame_dir)
+        .map_err(|e| format!("Failed to create preview directory: {:?}", e))?;
+
+    let step_numbers = step_numbers(session);
+    let mut frames = Vec::new();
+    for event in select_frames(session, config.frame_interval_seconds) {
+        let mut image = match source_image(event) {
+            Ok(image) => image,
+            Err(e) => {
+                eprintln!("⚠️  Skipping preview frame of event {}: {}", event.id, e);
+                continue;
+            }
+        };
+        if image.width() > config.frame_width {
+            image = image.thumbnail(config.frame_width, u32::MAX);
+        }
+        let filename = format!("{}.jpg", event.id);
+        // JPEG has no alpha channel
+        DynamicImage::ImageRgb8(image.to_rgb8())
+            .save_with_format(frame_dir.join(&filename), ImageFormat::Jpeg)
+            .map_err(|e| format!("Failed to write preview frame: {:?}", e))?;
+
+        frames.push(PreviewFrame {
+            offset_seconds: offset_seconds(session, event.timestamp),
+            event_id: event.id.to_string(),
+            step_number: step_numbers
+                .iter()
+                .find(|(id, _)| *id == event.id)
+                .map(|(_, number)| *number),
+            path: storage::session_asset_path(
+                &session.session_id,
+                &format!("preview/{}", filename),
+            ),
+        });
+    }
+
+    let track = PreviewTrack {
+        session_id: session.session_id.clone(),
+        generated_at: Utc::now(),
+        duration_seconds: duration_seconds(session),
+        frames,
+        bucket_seconds: config.bucket_seconds,
+        density: density(session, config.bucket_seconds),
+    };
+
+    storage::write_json_atomic(&preview_path(&session.session_id), &track, "preview track")?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🎞️  Preview track: {} frames, {} buckets",
+        track.frames.len(),
+        track.density.len()
+    );
+
+    Ok(track)
+}
+
+/// Loads a session's preview track, (re)generating it if missing or stale.
+///
+/// # Returns
+/// * `Ok(PreviewTrack)` - Track matching the saved session
+/// * `Err(String)` - Error if the session couldn't be loaded or the track written
+pub fn load_or_generate(session_id: &str, config: &PreviewConfig) -> Result<PreviewTrack, String> {
+    let path = preview_path(session_id);
+    let modified = |path: &PathBuf| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let session_modified = modified(&storage::get_session_dir(session_id).join("session.json"));
+
+    let current = match (modified(&path), session_modified) {
+        (Some(preview), Some(session)) => preview >= session,
+        _ => false,
+    };
+    if current {
+        let track = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json_data| serde_json::from_str(&json_data).ok());
+        if let Some(track) = track {
+            return Ok(track);
+        }
+    }
+
+    generate(&storage::load_session(session_id)?, config)
+}
+
+/// Deletes the frames of events (e.g., redacted ones) and the track that
+/// lists them; the next `load_or_generate()` writes a new track.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of frames deleted
+/// * `Err(String)` - Error if a frame or the track couldn't be deleted
+pub fn remove_frames(session_id: &str, event_ids: &HashSet<String>) -> Result<usize, String> {
+    let frame_dir = storage::get_session_dir(session_id).join("preview");
+    let mut removed = 0;
+    for event_id in event_ids {
+        match fs::remove_file(frame_dir.join(format!("{}.jpg", event_id))) {
+            Ok(()) => removed += 1,
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to delete preview frame: {:?}", e)),
+        }
+    }
+    if removed > 0 {
+        match fs::remove_file(preview_path(session_id)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to delete preview track: {:?}", e)),
+        }
+    }
+    Ok(removed)
+}
+
+/// Screenshotted events that get a frame, in time order.
+fn select_frames(session: &RecordingSession, interval_seconds: Option<f64>) -> Vec<&Event> {
+    let mut selected: Vec<&Event> = Vec::new();
+    for event in session
+        .events
+        .iter()
+        .filter(|event| source_path(event).is_some())
+    {
+        let due = match (interval_seconds, selected.last()) {
+            (Some(interval), Some(previous)) => {
+                (event.timestamp - previous.timestamp).num_milliseconds() as f64 / 1000.0
+                    >= interval
+            }
+            _ => true,
+        };
+        if due {
+            selected.push(event);
+        }
+    }
+    selected
+}
+
+/// Events per bucket of `bucket_seconds` (see module docs).
+fn density(session: &RecordingSession, bucket_seconds: f64) -> Vec<usize> {
+    let buckets = (duration_seconds(session) / bucket_seconds).ceil().max(1.0) as usize;
+    let mut density = vec![0; buckets];
+
+    for event in &session.events {
+        let timing_only = matches!(
+            event.event_type,
+            EventType::Wait { .. }
+                | EventType::Idle { .. }
+                | EventType::Paused { .. }
+                | EventType::Lifecycle { .. }
+                | EventType::RecorderRestart { .. }
+        );
+        if timing_only {
+            continue;
+        }
+        let bucket = (offset_seconds(session, event.timestamp) / bucket_seconds) as usize;
+        density[bucket.min(buckets - 1)] += 1;
+    }
+    density
+}
+
+/// `(event_id, step number)` of every event that is part of a step.
+fn step_numbers(session: &RecordingSession) -> Vec<(uuid::Uuid, usize)> {
+    steps::group_steps(session)
+        .into_iter()
+        .flat_map(|step| {
+            let number = step.number;
+            step.event_ids.into_iter().map(move |id| (id, number))
+        })
+        .collect()
+}
+
+/// Screenshot a frame is made from: the full screen, else a window snapshot.
+fn source_path(event: &Event) -> Option<&str> {
+    event
+        .screenshots
+        .full_screen
+        .as_deref()
+        .or(event.screenshots.window_crop.as_deref())
+}
+
+fn source_image(event: &Event) -> Result<DynamicImage, String> {
+    if let Some(frame) = frame_diff::reconstruct(&event.screenshots)? {
+        return Ok(frame);
+    }
+    let path = source_path(event).ok_or("Event has no screenshot")?;
+    image::open(storage::resolve_asset_path(path))
+        .map_err(|e| format!("Failed to read {}: {:?}", path, e))
+}
+
+fn duration_seconds(session: &RecordingSession) -> f64 {
+    let end = session
+        .stopped_at
+        .or_else(|| session.events.last().map(|event| event.timestamp))
+        .unwrap_or(session.started_at);
+    offset_seconds(session, end)
+}
+
+fn offset_seconds(session: &RecordingSession, at: DateTime<Utc>) -> f64 {
+    ((at - session.started_at).num_milliseconds() as f64 / 1000.0).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MouseButton, Position};
+
+    #[test]
+    fn test_frames_and_density() {
+        let mut session = RecordingSession::new("preview-test".to_string());
+        session.started_at = "2026-02-01T15:43:00Z".parse().unwrap();
+        session.stopped_at = Some("2026-02-01T15:43:12Z".parse().unwrap());
+        let started_at = session.started_at;
+        let event = |seconds: i64, event_type: EventType, screenshot: bool| {
+            let mut event = Event::new(event_type, Some(Position::new(5.0, 5.0)));
+            event.timestamp = started_at + chrono::Duration::seconds(seconds);
+            if screenshot {
+                event.screenshots.full_screen = Some(format!("event_{}_full.png", seconds));
+            }
+            event
+        };
+        let click = || EventType::Click {
+            button: MouseButton::Left,
+            duration_ms: None,
+        };
+        let wait = EventType::Wait {
+            duration_seconds: 4.0,
+            cause: None,
+        };
+        session.events = vec![
+            event(0, click(), true),
+            event(2, click(), true),
+            event(3, wait, false),
+            event(7, click(), true),
+            event(12, click(), false),
+        ];
+
+        let offsets = |frames: Vec<&Event>| -> Vec<f64> {
+            frames
+                .iter()
+                .map(|event| offset_seconds(&session, event.timestamp))
+                .collect()
+        };
+        assert_eq!(offsets(select_frames(&session, None)), [0.0, 2.0, 7.0]);
+        assert_eq!(offsets(select_frames(&session, Some(5.0))), [0.0, 7.0]);
+
+        // The Wait isn't activity; the click at the very end counts in the last bucket
+        assert_eq!(density(&session, 5.0), [2, 1, 1]);
+    }
+
+    #[test]
+    fn test_removed_frames_invalidate_the_track() {
+        let session_id = "preview-remove-test";
+        let frame_dir = storage::get_session_dir(session_id).join("preview");
+        fs::create_dir_all(&frame_dir).unwrap();
+        for event_id in ["redacted", "kept"] {
+            fs::write(frame_dir.join(format!("{}.jpg", event_id)), b"jpeg").unwrap();
+        }
+        fs::write(preview_path(session_id), "{}").unwrap();
+
+        let redacted: HashSet<String> = ["redacted".to_string(), "unknown".to_string()].into();
+        assert_eq!(remove_frames(session_id, &redacted), Ok(1));
+        assert!(!frame_dir.join("redacted.jpg").exists());
+        assert!(frame_dir.join("kept.jpg").exists());
+        assert!(!preview_path(session_id).exists());
+
+        fs::remove_dir_all(storage::get_session_dir(session_id)).unwrap();
+    }
+}
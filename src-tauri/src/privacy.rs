@@ -0,0 +1,481 @@
+//! # Privacy Module - Pre-Share Review Report
+//!
+//! Runs every privacy detector over a finished session and lists the events
+//! that may reveal something sensitive, so they can be redacted with one
+//! call before the recording is shared.
+//!
+//! ## Detectors
+//! | Kind | Looks at | Flags |
+//! |------|----------|-------|
+//! | `personal_data` | Typed text, window titles, clicked element label/value | Email addresses, card numbers (Luhn-checked), phone numbers, US SSNs, IBANs |
+//! | `screen_text` | OCR index (`ocr` module) | Scrub keywords/patterns or personal data on screen |
+//! | `credentials` | `CredentialsEntered` segments, clicks on password fields | Everything captured while a secret was entered |
+//!
+//! ## Flow
+//! ```text
+//! stop_recording() → review_saved() → privacy_report.json
+//! get_privacy_report(session_id) → review_saved() again (OCR may have finished since)
+//! redact_events(session_id, event_ids) → detach dependent patches + Event::redact() + delete screenshots + drop OCR text, preview frames, and comparisons
+//! ```
+//!
+//! Findings describe *what* was detected, never the matched text itself, so
+//! the report file doesn't become a copy of the data it warns about.
+
+use crate::comparison;
+use crate::config;
+use crate::frame_diff;
+use crate::ocr;
+use crate::preview;
+use crate::scrub::ScrubRules;
+use crate::storage;
+use crate::types::{Event, EventType, RecordingSession};
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+
+/// What a detector found.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    PersonalData,
+    ScreenText,
+    Credentials,
+}
+
+/// One potentially sensitive event.
+#[derive(Serialize, Debug, Clone)]
+pub struct PrivacyFinding {
+    pub event_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub kind: FindingKind,
+    /// What was detected and where (e.g., `"Email address in typed text"`)
+    pub reason: String,
+}
+
+/// Result of a privacy review.
+#[derive(Serialize, Debug, Clone)]
+pub struct PrivacyReport {
+    pub session_id: String,
+    pub generated_at: DateTime<Utc>,
+    /// Findings in event order; an event may appear more than once
+    pub findings: Vec<PrivacyFinding>,
+    /// Distinct flagged event ids, ready to pass to `redact_events`
+    pub flagged_event_ids: Vec<String>,
+}
+
+/// Result of `redact()`.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct RedactionSummary {
+    pub events_redacted: usize,
+    pub screenshots_deleted: usize,
+    /// Partial captures of redacted frames turned into full frames (see `frame_diff::detach()`)
+    pub frames_detached: usize,
+    /// Requested ids that aren't in the session
+    pub unknown_event_ids: Vec<String>,
+}
+
+/// Personal data patterns: (label, regex).
+static PERSONAL_DATA: Lazy<Vec<(&'static str, Regex)>> = Lazy::new(|| {
+    [
+        (
+            "Email address",
+            r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+        ),
+        ("Card number", r"\b\d(?:[ -]?\d){12,18}\b"),
+        ("US social security number", r"\b\d{3}-\d{2}-\d{4}\b"),
+        (
+            "IBAN",
+            r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?\b",
+        ),
+        ("Phone number", r"(?:\+|\b)\d[\d ().-]{7,}\d\b"),
+    ]
+    .into_iter()
+    .map(|(label, pattern)| (label, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+/// Runs all detectors over a session.
+///
+/// # Arguments
+/// * `session` - Session to review
+/// * `rules` - Compiled scrub rules for the screen text check (if configured)
+pub fn review(session: &RecordingSession, rules: Option<&ScrubRules>) -> PrivacyReport {
+    let credential_segments = credential_segments(session);
+    let screen_text: Vec<_> = ocr::load_index(&session.session_id).entries;
+    let mut findings = Vec::new();
+
+    for event in session.events.iter().filter(|event| !event.redacted) {
+        let mut flag = |kind: FindingKind, reason: String| {
+            findings.push(PrivacyFinding {
+                event_id: event.id.to_string(),
+                timestamp: event.timestamp,
+                kind,
+                reason,
+            })
+        };
+
+        for (source, text) in event_texts(event) {
+            if let Some(label) = find_personal_data(&text) {
+                flag(
+                    FindingKind::PersonalData,
+                    format!("{} in {}", label, source),
+                );
+            }
+        }
+
+        for entry in screen_text
+            .iter()
+            .filter(|entry| event.has_id(&entry.event_id))
+        {
+            if rules.is_some_and(|rules| rules.is_match(&entry.text)) {
+                flag(
+                    FindingKind::ScreenText,
+                    "Scrub keyword visible on screen".to_string(),
+                );
+            } else if let Some(label) = find_personal_data(&entry.text) {
+                flag(
+                    FindingKind::ScreenText,
+                    format!("{} visible on screen", label),
+                );
+            }
+        }
+
+        let password_field = event
+            .element
+            .as_ref()
+            .is_some_and(|element| element.role.as_deref() == Some("AXSecureTextField"));
+        if matches!(event.event_type, EventType::CredentialsEntered { .. }) {
+            flag(FindingKind::Credentials, "Password entry".to_string());
+        } else if password_field {
+            flag(
+                FindingKind::Credentials,
+                "Click on a password field".to_string(),
+            );
+        } else if credential_segments
+            .iter()
+            .any(|(start, end)| *start <= event.timestamp && event.timestamp <= *end)
+        {
+            flag(
+                FindingKind::Credentials,
+                "Captured during password entry".to_string(),
+            );
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let flagged_event_ids = findings
+        .iter()
+        .filter(|finding| seen.insert(finding.event_id.clone()))
+        .map(|finding| finding.event_id.clone())
+        .collect();
+
+    PrivacyReport {
+        session_id: session.session_id.clone(),
+        generated_at: Utc::now(),
+        findings,
+        flagged_event_ids,
+    }
+}
+
+/// Reviews a saved session with the configured scrub rules and stores the report.
+///
+/// # Returns
+/// * `Ok(PrivacyReport)` - Report, also written to `recordings/[session-id]/privacy_report.json`
+/// * `Err(String)` - Error if a scrub pattern is invalid or the report couldn't be written
+pub fn review_saved(session: &RecordingSession) -> Result<PrivacyReport, String> {
+    let rules = ScrubRules::compile(&config::current().scrub)?;
+    let report = review(session, rules.as_ref());
+
+    let json_data = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize privacy report: {:?}", e))?;
+    fs::write(
+        storage::get_session_dir(&session.session_id).join("privacy_report.json"),
+        json_data,
+    )
+    .map_err(|e| format!("Failed to write privacy report: {:?}", e))?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🛡️  Privacy review: {} finding(s) in {} event(s)",
+        report.findings.len(),
+        report.flagged_event_ids.len()
+    );
+
+    Ok(report)
+}
+
+/// Redacts events of a saved session and deletes their screenshots.
+///
+/// Partial captures drawn onto a redacted frame become standalone full
+/// frames first, so the rest of the session stays exportable.
+///
+/// # Arguments
+/// * `session_id` - Saved session to edit
+/// * `event_ids` - Events to redact (e.g., `PrivacyReport::flagged_event_ids`)
+///
+/// # Returns
+/// * `Ok(RedactionSummary)` - What was removed
+/// * `Err(String)` - Error if the session couldn't be loaded or saved, or a screenshot deleted
+pub fn redact(session_id: &str, event_ids: &[String]) -> Result<RedactionSummary, String> {
+    let mut session = storage::load_session(session_id)?;
+    let requested: HashSet<String> = event_ids.iter().cloned().collect();
+    let mut summary = RedactionSummary::default();
+
+    // Later partial captures are drawn onto redacted frames: make them whole first
+    let bases: Vec<String> = session
+        .events
+        .iter()
+        .filter(|event| requested.contains(&event.id.to_string()))
+        .filter(|event| event.screenshots.full_screen_patch.is_none())
+        .filter_map(|event| event.screenshots.full_screen.clone())
+        .collect();
+    for base in &bases {
+        summary.frames_detached += frame_diff::detach(&mut session, base, &requested)?;
+    }
+
+    for event in session
+        .events
+        .iter_mut()
+        .filter(|event| requested.contains(&event.id.to_string()))
+    {
+        // A reconstruction exports wrote for a partial capture isn't referenced
+        let reconstruction = event
+            .screenshots
+            .full_screen_patch
+            .as_ref()
+            .map(|_| frame_diff::reconstruction_path(session_id, &event.id.to_string()));
+        let paths = event
+            .screenshots
+            .paths()
+            .into_iter()
+            .chain(reconstruction.as_deref());
+        for path in paths {
+            match fs::remove_file(storage::resolve_asset_path(path)) {
+                Ok(()) => summary.screenshots_deleted += 1,
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to delete screenshot {}: {:?}", path, e)),
+            }
+        }
+        event.redact();
+        summary.events_redacted += 1;
+    }
+
+    let known: HashSet<String> = session
+        .events
+        .iter()
+        .map(|event| event.id.to_string())
+        .collect();
+    summary.unknown_event_ids = event_ids
+        .iter()
+        .filter(|id| !known.contains(id.as_str()))
+        .cloned()
+        .collect();
+
+    ocr::remove_entries(session_id, &requested)?;
+    preview::remove_frames(session_id, &requested)?;
+    comparison::remove_for_events(session_id, &requested)?;
+    storage::save_session(&session)?;
+
+    Ok(summary)
+}
+
+/// Time ranges in which a secret was being entered.
+fn credential_segments(session: &RecordingSession) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    session
+        .events
+        .iter()
+        .filter_map(|event| match event.event_type {
+            EventType::CredentialsEntered { duration_seconds } => Some((
+                event.timestamp,
+                event.timestamp + Duration::milliseconds((duration_seconds * 1000.0) as i64),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Free text recorded with an event, labelled by where it came from.
+fn event_texts(event: &Event) -> Vec<(&'static str, String)> {
+    let mut texts = Vec::new();
+
+    if let EventType::TextInput { text, .. } = &event.event_type {
+        texts.push(("typed text", text.clone()));
+    }
+    if let Some(title) = &event.window_title {
+        texts.push(("window title", title.clone()));
+    }
+    if let Some(element) = &event.element {
+        let fields = [&element.label, &element.value];
+        for text in fields.into_iter().flatten() {
+            texts.push(("clicked element", text.clone()));
+        }
+    }
+
+    texts
+}
+
+/// Returns the label of the first personal data pattern found in `text`.
+pub fn find_personal_data(text: &str) -> Option<&'static str> {
+    PERSONAL_DATA.iter().find_map(|(label, pattern)| {
+        pattern
+            .find_iter(text)
+            .any(|found| plausible(label, found.as_str()))
+            .then_some(*label)
+    })
+}
+
+/// Second check for patterns that also match ordinary numbers.
+fn plausible(label: &str, matched: &str) -> bool {
+    let digits = matched.chars().filter(char::is_ascii_digit).count();
+    match label {
+        "Card number" => luhn_valid(matched),
+        // Dates and version strings have fewer digits than any phone number
+        "Phone number" => (10..=15).contains(&digits),
+        _ => true,
+    }
+}
+
+/// Luhn checksum, to tell card numbers from other long digit runs.
+fn luhn_valid(number: &str) -> bool {
+    let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, &digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_personal_data_detection() {
+        assert_eq!(
+            find_personal_data("Contact jane.doe@example.com"),
+            Some("Email address")
+        );
+        assert_eq!(
+            find_personal_data("Card 4111 1111 1111 1111"),
+            Some("Card number")
+        );
+        // Fails the Luhn check: an order number, not a card
+        assert_ne!(
+            find_personal_data("Order 4111 1111 1111 1112"),
+            Some("Card number")
+        );
+        assert_eq!(
+            find_personal_data("SSN 078-05-1120"),
+            Some("US social security number")
+        );
+        assert_eq!(
+            find_personal_data("DE89 3704 0044 0532 0130 00"),
+            Some("IBAN")
+        );
+        assert_eq!(
+            find_personal_data("Call +1 (415) 555-0132"),
+            Some("Phone number")
+        );
+        assert_eq!(find_personal_data("Invoice #1234 — Google Chrome"), None);
+    }
+
+    #[test]
+    fn test_redacting_a_base_keeps_its_patches_exportable() {
+        use crate::types::{FramePatch, MouseButton, Position};
+        use image::{Rgba, RgbaImage};
+
+        let session_id = "test-redact-base";
+        let dir = storage::get_session_dir(session_id);
+        fs::create_dir_all(&dir).unwrap();
+        let asset = |name: &str| storage::session_asset_path(session_id, name);
+        let click = || {
+            Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(5.0, 5.0)),
+            )
+        };
+
+        // A full frame, a patch kept, and a patch redacted along with its base
+        let mut session = RecordingSession::new(session_id.to_string());
+        let base = asset("event_base_full.png");
+        RgbaImage::from_pixel(64, 32, Rgba([255, 255, 255, 255]))
+            .save(storage::resolve_asset_path(&base))
+            .unwrap();
+        session.add_event(click().with_screenshots(Some(base.clone()), None, None));
+        for name in ["kept", "secret"] {
+            let patch = asset(&format!("event_{}_patch.png", name));
+            RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 255]))
+                .save(storage::resolve_asset_path(&patch))
+                .unwrap();
+            let mut event = click().with_screenshots(Some(patch), None, None);
+            event.screenshots.full_screen_patch = Some(FramePatch {
+                base: base.clone(),
+                x: 16,
+                y: 0,
+            });
+            session.add_event(event);
+        }
+        session.stop();
+        storage::save_session(&session).unwrap();
+        // An earlier export reconstructed both patches
+        frame_diff::materialize(&mut session.clone());
+
+        let (base_id, kept_id, secret_id) = (
+            session.events[0].id.to_string(),
+            session.events[1].id.to_string(),
+            session.events[2].id.to_string(),
+        );
+        let preview_frame = dir.join("preview").join(format!("{}.jpg", base_id));
+        fs::create_dir_all(dir.join("preview")).unwrap();
+        fs::write(&preview_frame, b"jpeg").unwrap();
+        let summary = redact(session_id, &[base_id, secret_id.clone()]).unwrap();
+        assert!(!preview_frame.exists());
+        assert_eq!(summary.events_redacted, 2);
+        assert_eq!(summary.frames_detached, 1);
+
+        let saved = storage::load_session(session_id).unwrap();
+        let kept = &saved.events[1].screenshots;
+        assert!(kept.full_screen_patch.is_none());
+        let frame = image::open(storage::resolve_asset_path(
+            kept.full_screen.as_deref().unwrap(),
+        ))
+        .unwrap()
+        .to_rgba8();
+        assert_eq!(frame.dimensions(), (64, 32));
+        assert_eq!(*frame.get_pixel(20, 5), Rgba([255, 0, 0, 255]));
+        assert!(!storage::resolve_asset_path(&base).exists());
+        assert!(!storage::resolve_asset_path(&asset("event_kept_patch.png")).exists());
+        // The redacted patch's reconstruction showed the redacted frame
+        assert!(
+            !storage::resolve_asset_path(&frame_diff::reconstruction_path(session_id, &secret_id))
+                .exists()
+        );
+        assert_eq!(saved.events[1].id.to_string(), kept_id);
+
+        crate::integrity::ensure_exportable(session_id).unwrap();
+        let archive = std::env::temp_dir().join(format!("{}.flowtrace.zip", uuid::Uuid::new_v4()));
+        crate::export::archive::export_session_zip(session_id, &archive).unwrap();
+        fs::remove_file(&archive).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+//! # Recovery Module - Finishing Sessions That Never Stopped
+//!
+//! A session gets its `stopped_at` when `stop_recording()` saves it. One
+//! that has events but no `stopped_at` was cut off: FlowTrace crashed or was
+//! force-quit, or a resumable recording (see `resume` module) was never
+//! resumed. Such sessions are easy to mistake for broken ones, so they are
+//! listed at startup and can be finalized as they are.
+//!
+//! ## Startup
+//! `run()` scans the recordings directory in the background and emits
+//! `unfinished-sessions` with the list if it isn't empty;
+//! `list_unfinished_sessions` returns the same list on demand.
+//!
+//! ## Recovering
+//! `recover()` applies the session's event journal (see `journal` module),
+//! sets `stopped_at` to the last event's timestamp (`started_at` without
+//! events), and saves the session. If it was the resumable recording, that
+//! state is cleared.
+
+use crate::journal;
+use crate::resume;
+use crate::storage;
+use crate::types::RecordingSession;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// A saved session without `stopped_at` (see module docs).
+#[derive(Serialize, Debug, Clone)]
+pub struct UnfinishedSession {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// Events saved or journaled
+    pub event_count: usize,
+    /// Stop time `recover()` would use
+    pub last_event_at: DateTime<Utc>,
+    /// Whether it can be resumed instead (see `resume` module)
+    pub resumable: bool,
+}
+
+/// Lists saved sessions that have events but were never stopped.
+///
+/// # Arguments
+/// * `active_session_id` - Session being recorded (never listed)
+///
+/// # Returns
+/// Unfinished sessions, oldest first
+pub fn scan(active_session_id: Option<&str>) -> Vec<UnfinishedSession> {
+    let resumable = resume::load().map(|state| state.session_id);
+    let mut unfinished: Vec<UnfinishedSession> = storage::list_session_ids()
+        .into_iter()
+        .filter(|id| Some(id.as_str()) != active_session_id)
+        .filter_map(|id| {
+            let mut session = storage::load_session(&id).ok()?;
+            if session.stopped_at.is_some() {
+                return None;
+            }
+            journal::merge_into(&mut session);
+            summarize(&session, resumable.as_deref() == Some(id.as_str()))
+        })
+        .collect();
+
+    unfinished.sort_by_key(|session| session.started_at);
+    unfinished
+}
+
+/// Finalizes an unfinished session (see module docs).
+///
+/// # Returns
+/// * `Ok(RecordingSession)` - The saved session
+/// * `Err(String)` - Error if it can't be loaded, was already stopped, or
+///   couldn't be saved
+pub fn recover(session_id: &str) -> Result<RecordingSession, String> {
+    let mut session = storage::load_session(session_id)?;
+    if session.stopped_at.is_some() {
+        return Err(format!("Session {} was stopped normally", session_id));
+    }
+    journal::merge_into(&mut session);
+    session.stopped_at = Some(stop_time(&session));
+    storage::save_session(&session)?;
+    journal::remove(session_id);
+    resume::clear(session_id);
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🩹 Recovered session {} ({} events)",
+        session_id,
+        session.events.len()
+    );
+
+    Ok(session)
+}
+
+fn summarize(session: &RecordingSession, resumable: bool) -> Option<UnfinishedSession> {
+    if session.stopped_at.is_some() || session.events.is_empty() {
+        return None;
+    }
+    Some(UnfinishedSession {
+        session_id: session.session_id.clone(),
+        title: session.title.clone(),
+        started_at: session.started_at,
+        event_count: session.events.len(),
+        last_event_at: stop_time(session),
+        resumable,
+    })
+}
+
+/// Timestamp of the last event, or `started_at` without events.
+fn stop_time(session: &RecordingSession) -> DateTime<Utc> {
+    session
+        .events
+        .iter()
+        .map(|event| event.timestamp)
+        .max()
+        .unwrap_or(session.started_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType};
+
+    #[test]
+    fn test_only_sessions_with_events_and_no_stop_are_listed() {
+        let mut session = RecordingSession::new("recovery-test".to_string());
+        assert!(summarize(&session, false).is_none());
+
+        let at = |time: &str| time.parse::<DateTime<Utc>>().unwrap();
+        for time in ["2026-02-01T15:43:10Z", "2026-02-01T15:44:55Z"] {
+            let mut event = Event::new(
+                EventType::Marker {
+                    label: Some("checkpoint".to_string()),
+                },
+                None,
+            );
+            event.timestamp = at(time);
+            session.add_event(event);
+        }
+
+        let unfinished = summarize(&session, true).unwrap();
+        assert_eq!(unfinished.event_count, 2);
+        assert_eq!(unfinished.last_event_at, at("2026-02-01T15:44:55Z"));
+        assert!(unfinished.resumable);
+
+        session.stopped_at = Some(at("2026-02-01T15:45:00Z"));
+        assert!(summarize(&session, false).is_none());
+    }
+}
@@ -0,0 +1,821 @@
+//! # Refresh Module - Re-capturing Screenshots After a UI Change
+//!
+//! A UI refresh leaves documentation screenshots stale while the steps
+//! themselves stay valid. `refresh_screenshots` replays a saved session with
+//! synthetic input and captures new screenshots for every click, keeping the
+//! recorded events (ids, positions, timing, text) as they are.
+//!
+//! ## Replay
+//! | Event | Replayed as |
+//! |-------|-------------|
+//! | Click | Move to the position, wait `SETTLE_DELAY`, **capture**, press + release |
+//! | KeyPress / Shortcut | Key press with the recorded modifiers (auto-repeats included) |
+//! | TextInput | Typed character by character (US layout) |
+//! | Scroll | Wheel event with the recorded deltas at the recorded position |
+//! | Wait | Sleep for the recorded duration (refresh: at most `MAX_WAIT`; replay: divided by the speed, at most `MAX_REPLAY_WAIT`) |
+//! | Idle, Hover, MouseTrail, Marker, Paused, AppSwitch, Capture, ManualStep, Lifecycle, RecorderRestart, CaptureError | Nothing |
+//!
+//! Events that can't be replayed stop the run: `CredentialsEntered` (secrets
+//! are never recorded), redacted typing, keys or characters without a known
+//! key, and waits of an invalid length (infinite, or too long to sleep).
+//! Screenshots captured up to that point are kept.
+//!
+//! ## Plain Replay
+//! `replay_session` runs the same replay without capturing or saving
+//! anything, as a basic way to automate a recorded procedure (RPA). Waits
+//! are scaled by a speed factor (`MIN_SPEED`..=`MAX_SPEED`, e.g., `2.0`
+//! waits half as long); the pauses around clicks and inputs are not, since
+//! the UI needs them to keep up. Only one refresh or replay runs at a time.
+//!
+//! ## Supervision
+//! Replay drives the real mouse and keyboard, so it's meant to be watched:
+//! - It refuses to start while a recording or follow mode is active
+//! - Pressing Escape stops it (replayed Escapes are ignored)
+//! - With input suppression (the default), the user's own mouse and keyboard
+//!   input is swallowed until it ends (see `input_grab` module, macOS only)
+//! - The session is saved once at the end, with the new screenshots of the
+//!   steps reached; their OCR text is indexed again
+//! - Partial captures drawn onto a click's frame are made full frames (and
+//!   saved) before the replay starts, since the recapture overwrites that frame
+
+use crate::frame_diff;
+use crate::input_grab;
+use crate::ocr;
+use crate::postprocess;
+use crate::screenshot;
+use crate::storage;
+use crate::types::{Event, EventType, RecordingSession};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Time for hover effects and animations to finish before a click is captured.
+const SETTLE_DELAY: Duration = Duration::from_millis(600);
+
+/// Longest recorded wait that is replayed in full (loading screens rarely need more).
+const MAX_WAIT: Duration = Duration::from_secs(10);
+
+/// Longest wait of a plain replay, after scaling (longer ones were breaks, not loading).
+const MAX_REPLAY_WAIT: Duration = Duration::from_secs(60 * 60);
+
+/// Slowest and fastest replay speed factors.
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 10.0;
+
+/// Pause after every synthetic input (macOS drops events sent back to back).
+const INPUT_DELAY: Duration = Duration::from_millis(20);
+
+/// Escape presses this soon after a synthetic input are the replay's own.
+const OWN_INPUT_WINDOW: Duration = Duration::from_millis(250);
+
+/// Names of the keys that can be replayed, as recorded (`format!("{:?}", key)`).
+const KEYS: &[(&str, rdev::Key)] = &[
+    ("KeyA", rdev::Key::KeyA),
+    ("KeyB", rdev::Key::KeyB),
+    ("KeyC", rdev::Key::KeyC),
+    ("KeyD", rdev::Key::KeyD),
+    ("KeyE", rdev::Key::KeyE),
+    ("KeyF", rdev::Key::KeyF),
+    ("KeyG", rdev::Key::KeyG),
+    ("KeyH", rdev::Key::KeyH),
+    ("KeyI", rdev::Key::KeyI),
+    ("KeyJ", rdev::Key::KeyJ),
+    ("KeyK", rdev::Key::KeyK),
+    ("KeyL", rdev::Key::KeyL),
+    ("KeyM", rdev::Key::KeyM),
+    ("KeyN", rdev::Key::KeyN),
+    ("KeyO", rdev::Key::KeyO),
+    ("KeyP", rdev::Key::KeyP),
+    ("KeyQ", rdev::Key::KeyQ),
+    ("KeyR", rdev::Key::KeyR),
+    ("KeyS", rdev::Key::KeyS),
+    ("KeyT", rdev::Key::KeyT),
+    ("KeyU", rdev::Key::KeyU),
+    ("KeyV", rdev::Key::KeyV),
+    ("KeyW", rdev::Key::KeyW),
+    ("KeyX", rdev::Key::KeyX),
+    ("KeyY", rdev::Key::KeyY),
+    ("KeyZ", rdev::Key::KeyZ),
+    ("Num0", rdev::Key::Num0),
+    ("Num1", rdev::Key::Num1),
+    ("Num2", rdev::Key::Num2),
+    ("Num3", rdev::Key::Num3),
+    ("Num4", rdev::Key::Num4),
+    ("Num5", rdev::Key::Num5),
+    ("Num6", rdev::Key::Num6),
+    ("Num7", rdev::Key::Num7),
+    ("Num8", rdev::Key::Num8),
+    ("Num9", rdev::Key::Num9),
+    ("Space", rdev::Key::Space),
+    ("BackQuote", rdev::Key::BackQuote),
+    ("Minus", rdev::Key::Minus),
+    ("Equal", rdev::Key::Equal),
+    ("LeftBracket", rdev::Key::LeftBracket),
+    ("RightBracket", rdev::Key::RightBracket),
+    ("SemiColon", rdev::Key::SemiColon),
+    ("Quote", rdev::Key::Quote),
+    ("BackSlash", rdev::Key::BackSlash),
+    ("IntlBackslash", rdev::Key::IntlBackslash),
+    ("Comma", rdev::Key::Comma),
+    ("Dot", rdev::Key::Dot),
+    ("Slash", rdev::Key::Slash),
+    ("Return", rdev::Key::Return),
+    ("Tab", rdev::Key::Tab),
+    ("Backspace", rdev::Key::Backspace),
+    ("Delete", rdev::Key::Delete),
+    ("Escape", rdev::Key::Escape),
+    ("UpArrow", rdev::Key::UpArrow),
+    ("DownArrow", rdev::Key::DownArrow),
+    ("LeftArrow", rdev::Key::LeftArrow),
+    ("RightArrow", rdev::Key::RightArrow),
+    ("Home", rdev::Key::Home),
+    ("End", rdev::Key::End),
+    ("PageUp", rdev::Key::PageUp),
+    ("PageDown", rdev::Key::PageDown),
+    ("Insert", rdev::Key::Insert),
+    ("F1", rdev::Key::F1),
+    ("F2", rdev::Key::F2),
+    ("F3", rdev::Key::F3),
+    ("F4", rdev::Key::F4),
+    ("F5", rdev::Key::F5),
+    ("F6", rdev::Key::F6),
+    ("F7", rdev::Key::F7),
+    ("F8", rdev::Key::F8),
+    ("F9", rdev::Key::F9),
+    ("F10", rdev::Key::F10),
+    ("F11", rdev::Key::F11),
+    ("F12", rdev::Key::F12),
+];
+
+/// One synthetic input (or pause) of the replay.
+#[derive(Debug, Clone, PartialEq)]
+enum ReplayAction {
+    /// Capture the screen at the position, then click
+    Click {
+        button: rdev::Button,
+        x: f64,
+        y: f64,
+    },
+    /// Press `key` `times` times while holding `modifiers`
+    Keys {
+        key: rdev::Key,
+        modifiers: Vec<rdev::Key>,
+        times: u32,
+    },
+    Scroll {
+        x: f64,
+        y: f64,
+        delta_x: i64,
+        delta_y: i64,
+    },
+    Wait(Duration),
+}
+
+/// Progress of a refresh; sent as the `refresh-progress` Tauri event.
+#[derive(Serialize, Debug, Clone)]
+pub struct RefreshProgress {
+    pub session_id: String,
+    /// Events replayed so far
+    pub replayed: usize,
+    pub total_events: usize,
+    /// Clicks with new screenshots so far
+    pub captured: usize,
+}
+
+/// Outcome of a refresh; sent as the `refresh-finished` Tauri event.
+#[derive(Serialize, Debug, Clone)]
+pub struct RefreshReport {
+    pub session_id: String,
+    /// Clicks with new screenshots
+    pub captured: usize,
+    /// `true` if every event was replayed
+    pub completed: bool,
+    /// Why the replay stopped early (Escape, an event that can't be replayed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_reason: Option<String>,
+    /// The user's input was blocked during the replay
+    pub input_suppressed: bool,
+}
+
+/// Progress of a plain replay; sent as the `replay-progress` Tauri event.
+#[derive(Serialize, Debug, Clone)]
+pub struct ReplayProgress {
+    pub session_id: String,
+    /// Events replayed so far
+    pub replayed: usize,
+    pub total_events: usize,
+}
+
+/// Outcome of a plain replay; sent as the `replay-finished` Tauri event.
+#[derive(Serialize, Debug, Clone)]
+pub struct ReplayReport {
+    pub session_id: String,
+    /// Events replayed
+    pub replayed: usize,
+    pub total_events: usize,
+    /// `true` if every event was replayed
+    pub completed: bool,
+    /// Why the replay stopped early (Escape, an event that can't be replayed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_reason: Option<String>,
+    /// The user's input was blocked during the replay
+    pub input_suppressed: bool,
+}
+
+/// Whether a refresh or replay is running.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Set by Escape or `stop()` to end the running refresh or replay.
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Time of the most recent synthetic input.
+static LAST_SIMULATED: Lazy<Mutex<Instant>> = Lazy::new(|| Mutex::new(Instant::now()));
+
+/// Marks a refresh or replay as running.
+///
+/// # Returns
+/// * `Ok(())` - The caller must `run()` (or `replay()`) and then `finish()`
+/// * `Err(String)` - Error if a refresh or replay is already running
+pub fn begin() -> Result<(), String> {
+    ACTIVE
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .map_err(|_| "A session is already being replayed".to_string())?;
+    STOP_REQUESTED.store(false, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Marks the running refresh or replay as finished.
+pub fn finish() {
+    ACTIVE.store(false, Ordering::SeqCst);
+}
+
+/// Whether a refresh or replay is running.
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Asks the running refresh or replay to stop after the current input.
+pub fn stop() {
+    STOP_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Watches the user's input while a refresh or replay runs: Escape stops it.
+pub fn observe(event_type: &rdev::EventType) {
+    if let rdev::EventType::KeyPress(rdev::Key::Escape) = event_type {
+        if LAST_SIMULATED.lock().unwrap().elapsed() > OWN_INPUT_WINDOW {
+            stop();
+        }
+    }
+}
+
+/// Replays a session and replaces the screenshots of every click reached.
+///
+/// # Arguments
+/// * `session` - Saved session to refresh
+/// * `suppress_input` - Block the user's input while replaying (if supported)
+/// * `on_progress` - Called after every replayed event
+///
+/// # Returns
+/// * `Ok(RefreshReport)` - How far the replay got (the session is saved)
+/// * `Err(String)` - Error if a partial capture couldn't be made a full
+///   frame, or the session couldn't be saved
+pub fn run<F>(
+    mut session: RecordingSession,
+    suppress_input: bool,
+    mut on_progress: F,
+) -> Result<RefreshReport, String>
+where
+    F: FnMut(RefreshProgress),
+{
+    // Never diff against a frame of the original recording
+    frame_diff::reset();
+    // Recaptures overwrite frames that later partial captures are drawn onto
+    if detach_recaptured_bases(&mut session)? > 0 {
+        storage::save_session(&session)?;
+    }
+    let input_suppressed = suppress_input && input_grab::begin();
+
+    let total_events = session.events.len();
+    let session_id = session.session_id.clone();
+    let (stopped_reason, captured) = replay_events(
+        &mut session.events,
+        |duration| duration.min(MAX_WAIT),
+        |event, x, y| recapture(&session_id, event, x, y),
+        |replayed, captured| {
+            on_progress(RefreshProgress {
+                session_id: session_id.clone(),
+                replayed,
+                total_events,
+                captured,
+            })
+        },
+    );
+
+    if input_suppressed {
+        input_grab::end();
+    }
+
+    if !captured.is_empty() {
+        ocr::remove_entries(&session.session_id, &captured)?;
+        for event in session
+            .events
+            .iter()
+            .filter(|e| captured.contains(&e.id.to_string()))
+        {
+            if let Some(full_screen) = &event.screenshots.full_screen {
+                ocr::enqueue(
+                    &session.session_id,
+                    &event.id.to_string(),
+                    event.timestamp,
+                    full_screen,
+                );
+            }
+        }
+        storage::save_session(&session)?;
+    }
+
+    #[cfg(debug_assertions)]
+    println!(
+        "🔁 Refreshed {} screenshots of session {}",
+        captured.len(),
+        session.session_id
+    );
+
+    Ok(RefreshReport {
+        session_id: session.session_id,
+        captured: captured.len(),
+        completed: stopped_reason.is_none(),
+        stopped_reason,
+        input_suppressed,
+    })
+}
+
+/// Replays a session as recorded, without capturing (see "Plain Replay").
+///
+/// # Arguments
+/// * `session` - Saved session to replay
+/// * `speed` - Speed factor for recorded waits (`2.0` waits half as long)
+/// * `suppress_input` - Block the user's input while replaying (if supported)
+/// * `on_progress` - Called after every replayed event
+///
+/// # Returns
+/// How far the replay got
+pub fn replay<F>(
+    mut session: RecordingSession,
+    speed: f64,
+    suppress_input: bool,
+    mut on_progress: F,
+) -> ReplayReport
+where
+    F: FnMut(ReplayProgress),
+{
+    let input_suppressed = suppress_input && input_grab::begin();
+    let total_events = session.events.len();
+    let session_id = session.session_id.clone();
+    let mut replayed_events = 0;
+    let (stopped_reason, _) = replay_events(
+        &mut session.events,
+        |duration| {
+            Duration::try_from_secs_f64(duration.as_secs_f64() / speed)
+                .unwrap_or(MAX_REPLAY_WAIT)
+                .min(MAX_REPLAY_WAIT)
+        },
+        |_, _, _| false,
+        |replayed, _| {
+            replayed_events = replayed;
+            on_progress(ReplayProgress {
+                session_id: session_id.clone(),
+                replayed,
+                total_events,
+            })
+        },
+    );
+    if input_suppressed {
+        input_grab::end();
+    }
+
+    #[cfg(debug_assertions)]
+    println!(
+        "▶️  Replayed {}/{} events of session {} at {}x",
+        replayed_events, total_events, session_id, speed
+    );
+
+    ReplayReport {
+        session_id,
+        replayed: replayed_events,
+        total_events,
+        completed: stopped_reason.is_none(),
+        stopped_reason,
+        input_suppressed,
+    }
+}
+
+/// Checks a replay speed factor.
+///
+/// # Returns
+/// * `Ok(f64)` - The factor (`1.0` if none was given)
+/// * `Err(String)` - Error if it is outside `MIN_SPEED`..=`MAX_SPEED`
+pub fn validate_speed(speed: Option<f64>) -> Result<f64, String> {
+    let speed = speed.unwrap_or(1.0);
+    if (MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        Ok(speed)
+    } else {
+        Err(format!(
+            "Replay speed must be between {} and {}",
+            MIN_SPEED, MAX_SPEED
+        ))
+    }
+}
+
+/// Replays events in order, stopping at the first that can't be replayed.
+///
+/// # Arguments
+/// * `events` - Events to replay
+/// * `wait_for` - How long to replay a recorded wait
+/// * `before_click` - Called with the cursor on a click's position after
+///   `SETTLE_DELAY`; returns whether the event got new screenshots
+/// * `on_replayed` - Called after every event with the events replayed and
+///   the clicks captured so far
+///
+/// # Returns
+/// Why the replay stopped early (`None` if every event was replayed), and
+/// the ids of the events with new screenshots
+fn replay_events(
+    events: &mut [Event],
+    wait_for: impl Fn(Duration) -> Duration,
+    mut before_click: impl FnMut(&mut Event, f64, f64) -> bool,
+    mut on_replayed: impl FnMut(usize, usize),
+) -> (Option<String>, HashSet<String>) {
+    let mut captured: HashSet<String> = HashSet::new();
+
+    for index in 0..events.len() {
+        let actions = match actions_for(&events[index]) {
+            Ok(actions) => actions,
+            Err(reason) => return (Some(format!("Step {}: {}", index + 1, reason)), captured),
+        };
+
+        for action in actions {
+            if STOP_REQUESTED.load(Ordering::SeqCst) {
+                return (Some("Stopped by the user".to_string()), captured);
+            }
+            let action = match action {
+                ReplayAction::Wait(duration) => ReplayAction::Wait(wait_for(duration)),
+                action => action,
+            };
+            if let ReplayAction::Click { x, y, .. } = action {
+                if let Err(e) = send(&rdev::EventType::MouseMove { x, y }) {
+                    return (Some(format!("Step {}: {}", index + 1, e)), captured);
+                }
+                wait(SETTLE_DELAY);
+                if before_click(&mut events[index], x, y) {
+                    captured.insert(events[index].id.to_string());
+                }
+            }
+            if let Err(e) = perform(&action) {
+                return (Some(format!("Step {}: {}", index + 1, e)), captured);
+            }
+        }
+
+        on_replayed(index + 1, captured.len());
+    }
+    (None, captured)
+}
+
+/// Turns the partial captures drawn onto click frames into full frames (see
+/// `frame_diff::detach()`), so a replay that stops early leaves none of them
+/// drawn onto a recaptured frame.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of events that no longer depend on a click frame
+/// * `Err(String)` - Error if a partial capture couldn't be reconstructed
+fn detach_recaptured_bases(session: &mut RecordingSession) -> Result<usize, String> {
+    let bases: Vec<String> = session
+        .events
+        .iter()
+        .filter(|event| matches!(event.event_type, EventType::Click { .. }))
+        .filter(|event| event.screenshots.full_screen_patch.is_none())
+        .filter_map(|event| event.screenshots.full_screen.clone())
+        .collect();
+    let mut detached = 0;
+    for base in &bases {
+        detached += frame_diff::detach(session, base, &HashSet::new())?;
+    }
+    Ok(detached)
+}
+
+/// Captures new screenshots for a click and points the event at them.
+///
+/// # Returns
+/// `true` if the event has new screenshots (failures keep the old ones)
+fn recapture(session_id: &str, event: &mut Event, x: f64, y: f64) -> bool {
+    let capture = match screenshot::capture_all_for_event(
+        session_id,
+        &event.id.to_string(),
+        x as i32,
+        y as i32,
+    ) {
+        Ok(Some(capture)) => capture,
+        Ok(None) => return false,
+        Err(e) => {
+            eprintln!("⚠️  Keeping old screenshots of event {}: {}", event.id, e);
+            return false;
+        }
+    };
+
+    // A frame reconstructed from the old capture must not be reused by exports
+    if capture.full_screen_patch.is_some() {
+        let stale =
+            storage::session_asset_path(session_id, &format!("event_{}_full.png", event.id));
+        let _ = fs::remove_file(storage::resolve_asset_path(&stale));
+    }
+
+    event.screenshots.full_screen = Some(capture.full_screen);
+    event.screenshots.full_screen_patch = capture.full_screen_patch;
+    event.screenshots.window_crop = capture.window_crop;
+    event.screenshots.click_crop = capture.click_crop;
+    event.error_dialog_detected = capture.error_dialog_detected;
+    event.annotation_colors = capture.annotation_colors;
+    true
+}
+
+/// Inputs that reproduce an event (see module docs).
+///
+/// # Returns
+/// * `Ok(Vec<ReplayAction>)` - Inputs to send (empty for timing-only events)
+/// * `Err(String)` - Why the event can't be replayed
+fn actions_for(event: &Event) -> Result<Vec<ReplayAction>, String> {
+    let unknown_key = |key: &str| format!("Key {} can't be replayed", key);
+
+    let actions = match &event.event_type {
+        EventType::Click { button, .. } => {
+            let position = event
+                .position
+                .as_ref()
+                .ok_or_else(|| "Click without a position".to_string())?;
+            vec![ReplayAction::Click {
+                button: crate::rdev_button(button),
+                x: position.x,
+                y: position.y,
+            }]
+        }
+        EventType::KeyPress {
+            key,
+            shift,
+            repeat_count,
+            ..
+        } => vec![ReplayAction::Keys {
+            key: key_from_name(key).ok_or_else(|| unknown_key(key))?,
+            modifiers: if *shift {
+                vec![rdev::Key::ShiftLeft]
+            } else {
+                Vec::new()
+            },
+            times: 1 + repeat_count,
+        }],
+        EventType::Shortcut { modifiers, key } => vec![ReplayAction::Keys {
+            key: key_from_name(key).ok_or_else(|| unknown_key(key))?,
+            modifiers: modifiers
+                .iter()
+                .map(|modifier| modifier_key(modifier).ok_or_else(|| unknown_key(modifier)))
+                .collect::<Result<_, _>>()?,
+            times: 1,
+        }],
+        EventType::TextInput { text, .. } => text
+            .chars()
+            .map(|character| {
+                let (key, shift) = char_key(character)
+                    .ok_or_else(|| format!("Character '{}' can't be typed", character))?;
+                Ok(ReplayAction::Keys {
+                    key,
+                    modifiers: if shift {
+                        vec![rdev::Key::ShiftLeft]
+                    } else {
+                        Vec::new()
+                    },
+                    times: 1,
+                })
+            })
+            .collect::<Result<_, String>>()?,
+        EventType::Scroll { delta_x, delta_y } => {
+            let position = event
+                .position
+                .as_ref()
+                .ok_or_else(|| "Scroll without a position".to_string())?;
+            vec![ReplayAction::Scroll {
+                x: position.x,
+                y: position.y,
+                delta_x: *delta_x,
+                delta_y: *delta_y,
+            }]
+        }
+        EventType::Wait {
+            duration_seconds, ..
+        } => vec![ReplayAction::Wait(
+            Duration::try_from_secs_f64(duration_seconds.max(0.0))
+                .map_err(|_| format!("Wait of {}s can't be replayed", duration_seconds))?,
+        )],
+        EventType::CredentialsEntered { .. } => {
+            return Err("Credentials were not recorded and can't be replayed".to_string())
+        }
+        EventType::Idle { .. }
+        | EventType::Hover { .. }
+        | EventType::Paused { .. }
+        | EventType::MouseTrail { .. }
+        | EventType::Marker { .. }
+        | EventType::AppSwitch { .. }
+        | EventType::Capture { .. }
+        | EventType::ManualStep { .. }
+        | EventType::Lifecycle { .. }
+        | EventType::RecorderRestart { .. }
+        | EventType::CaptureError { .. } => Vec::new(),
+    };
+
+    if event.redacted && !actions.is_empty() {
+        return Err("Redacted events can't be replayed".to_string());
+    }
+    Ok(actions)
+}
+
+/// Sends the inputs of one action (the move and capture of clicks happen before).
+fn perform(action: &ReplayAction) -> Result<(), String> {
+    match action {
+        ReplayAction::Click { button, .. } => {
+            send(&rdev::EventType::ButtonPress(*button))?;
+            send(&rdev::EventType::ButtonRelease(*button))
+        }
+        ReplayAction::Keys {
+            key,
+            modifiers,
+            times,
+        } => {
+            for modifier in modifiers {
+                send(&rdev::EventType::KeyPress(*modifier))?;
+            }
+            for _ in 0..*times {
+                send(&rdev::EventType::KeyPress(*key))?;
+                send(&rdev::EventType::KeyRelease(*key))?;
+            }
+            for modifier in modifiers.iter().rev() {
+                send(&rdev::EventType::KeyRelease(*modifier))?;
+            }
+            Ok(())
+        }
+        ReplayAction::Scroll {
+            x,
+            y,
+            delta_x,
+            delta_y,
+        } => {
+            send(&rdev::EventType::MouseMove { x: *x, y: *y })?;
+            send(&rdev::EventType::Wheel {
+                delta_x: *delta_x,
+                delta_y: *delta_y,
+            })
+        }
+        ReplayAction::Wait(duration) => {
+            wait(*duration);
+            Ok(())
+        }
+    }
+}
+
+/// Sends one synthetic input.
+fn send(event_type: &rdev::EventType) -> Result<(), String> {
+    *LAST_SIMULATED.lock().unwrap() = Instant::now();
+    input_grab::expect(event_type);
+    rdev::simulate(event_type).map_err(|e| format!("Failed to replay input: {:?}", e))?;
+    std::thread::sleep(INPUT_DELAY);
+    Ok(())
+}
+
+/// Sleeps, waking early if a stop is requested.
+fn wait(duration: Duration) {
+    let until = Instant::now() + duration;
+    while Instant::now() < until && !STOP_REQUESTED.load(Ordering::SeqCst) {
+        std::thread::sleep(
+            Duration::from_millis(50).min(until.saturating_duration_since(Instant::now())),
+        );
+    }
+}
+
+fn key_from_name(name: &str) -> Option<rdev::Key> {
+    KEYS.iter()
+        .find(|(key_name, _)| *key_name == name)
+        .map(|(_, key)| *key)
+}
+
+/// Key for a recorded modifier name (`"Control"`, `"Alt"`, `"Shift"`, `"Meta"`).
+fn modifier_key(name: &str) -> Option<rdev::Key> {
+    match name {
+        "Control" => Some(rdev::Key::ControlLeft),
+        "Alt" => Some(rdev::Key::Alt),
+        "Shift" => Some(rdev::Key::ShiftLeft),
+        "Meta" => Some(rdev::Key::MetaLeft),
+        _ => None,
+    }
+}
+
+/// Key (and whether Shift is needed) that types a character on a US layout.
+fn char_key(character: char) -> Option<(rdev::Key, bool)> {
+    KEYS.iter().find_map(|(name, key)| {
+        [false, true]
+            .into_iter()
+            .find(|shift| postprocess::typed_char(name, *shift) == Some(character))
+            .map(|shift| (*key, shift))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actions_replay_typing_and_shortcuts() {
+        let typed = Event::new(
+            EventType::TextInput {
+                text: "Hi!".to_string(),
+                keystrokes: 4,
+            },
+            None,
+        );
+        let shift = vec![rdev::Key::ShiftLeft];
+        assert_eq!(
+            actions_for(&typed).unwrap(),
+            vec![
+                ReplayAction::Keys {
+                    key: rdev::Key::KeyH,
+                    modifiers: shift.clone(),
+                    times: 1
+                },
+                ReplayAction::Keys {
+                    key: rdev::Key::KeyI,
+                    modifiers: Vec::new(),
+                    times: 1
+                },
+                ReplayAction::Keys {
+                    key: rdev::Key::Num1,
+                    modifiers: shift,
+                    times: 1
+                },
+            ]
+        );
+
+        let save = Event::new(
+            EventType::Shortcut {
+                modifiers: vec!["Meta".to_string()],
+                key: "KeyS".to_string(),
+            },
+            None,
+        );
+        assert_eq!(
+            actions_for(&save).unwrap(),
+            vec![ReplayAction::Keys {
+                key: rdev::Key::KeyS,
+                modifiers: vec![rdev::Key::MetaLeft],
+                times: 1
+            }]
+        );
+
+        let credentials = Event::new(
+            EventType::CredentialsEntered {
+                duration_seconds: 4.0,
+            },
+            None,
+        );
+        assert!(actions_for(&credentials).is_err());
+        assert_eq!(char_key('é'), None);
+    }
+
+    #[test]
+    fn test_waits_are_recorded_in_full_and_speed_is_bounded() {
+        let wait = Event::new(
+            EventType::Wait {
+                duration_seconds: 30.0,
+                cause: None,
+            },
+            None,
+        );
+        // Capped by the refresh, scaled by the replay
+        assert_eq!(
+            actions_for(&wait).unwrap(),
+            vec![ReplayAction::Wait(Duration::from_secs(30))]
+        );
+        // An imported session may claim any duration
+        let endless = Event::new(
+            EventType::Wait {
+                duration_seconds: f64::INFINITY,
+                cause: None,
+            },
+            None,
+        );
+        assert!(actions_for(&endless).is_err());
+        assert_eq!(validate_speed(None), Ok(1.0));
+        assert_eq!(validate_speed(Some(2.0)), Ok(2.0));
+        assert!(validate_speed(Some(0.0)).is_err());
+        assert!(validate_speed(Some(f64::NAN)).is_err());
+    }
+}
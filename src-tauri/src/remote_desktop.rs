@@ -0,0 +1,112 @@
+//! # Remote Desktop Module - Recording Inside VMs and RDP Windows
+//!
+//! A virtual machine or remote desktop client shows a whole other screen in
+//! one window. Input and screenshots still work there, but what the host
+//! sees is only the client: the focused app is "VMware Fusion", the window
+//! title is the VM's name, and the app actually used is inside the picture.
+//!
+//! ## Detection
+//! Events whose focused application is a known client are tagged
+//! `remote_desktop: true` (see `screenshot::stamp_focused_window()`):
+//!
+//! | Client | App names |
+//! |--------|-----------|
+//! | VMware | `VMware Fusion`, `VMware Workstation`, `vmware`, `vmplayer` |
+//! | Parallels | `Parallels Desktop`, `prl_client_app` |
+//! | Microsoft Remote Desktop | `Microsoft Remote Desktop`, `Windows App`, `mstsc` |
+//!
+//! More can be added with `remote_desktop.extra_apps` in the recorder config.
+//! Detection uses the real application name, before any `anonymize` alias.
+//!
+//! ## Click Crops
+//! Coordinates are the host's, so a click crop is still centered on the
+//! click; but the remote screen is often scaled down in its window, so the
+//! default 300px crop shows less of its UI. `remote_desktop.click_crop_size`
+//! (logical px) enlarges the crop for tagged clicks only.
+//!
+//! ## Missing Accessibility Data
+//! The host's accessibility API sees the client's canvas, not the controls
+//! drawn in it. For tagged events:
+//! - No `element` is looked up: clicks keep the position-based description
+//!   instead of naming the canvas ("Clicked group 'VM'")
+//! - Secure text fields inside the remote screen can't be detected (see
+//!   `credentials` module), so typing isn't protected automatically; switch
+//!   keys off with `set_capture_flags` while entering passwords there
+//! - `window_title` is the client's (usually the VM or host name)
+
+use crate::config::{self, RemoteDesktopConfig};
+
+/// Click crop size in logical px outside remote windows.
+pub const DEFAULT_CLICK_CROP_SIZE: u32 = 300;
+
+/// App names of known VM and remote desktop clients (lowercase).
+const KNOWN_CLIENTS: &[&str] = &[
+    "vmware fusion",
+    "vmware workstation",
+    "vmware",
+    "vmplayer",
+    "parallels desktop",
+    "prl_client_app",
+    "microsoft remote desktop",
+    "windows app",
+    "mstsc",
+];
+
+/// Whether an application is a VM or remote desktop client (see module docs).
+pub fn is_remote_client(app_name: &str) -> bool {
+    is_remote_client_with(app_name, &config::remote_desktop())
+}
+
+/// Click crop size in logical px for a click in the given application.
+pub fn click_crop_size(app_name: Option<&str>) -> u32 {
+    click_crop_size_with(app_name, &config::remote_desktop())
+}
+
+fn is_remote_client_with(app_name: &str, config: &RemoteDesktopConfig) -> bool {
+    let name = app_name.trim().to_lowercase();
+    // Windows reports executables ("vmware.exe")
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    !name.is_empty()
+        && (KNOWN_CLIENTS.contains(&name)
+            || config
+                .extra_apps
+                .iter()
+                .any(|extra| extra.trim().to_lowercase() == name))
+}
+
+fn click_crop_size_with(app_name: Option<&str>, config: &RemoteDesktopConfig) -> u32 {
+    match (app_name, config.click_crop_size) {
+        (Some(app_name), Some(size)) if is_remote_client_with(app_name, config) => size,
+        _ => DEFAULT_CLICK_CROP_SIZE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clients_are_detected_and_get_larger_crops() {
+        let config = RemoteDesktopConfig {
+            extra_apps: vec!["Citrix Viewer".to_string()],
+            click_crop_size: Some(600),
+        };
+
+        assert!(is_remote_client_with("VMware Fusion", &config));
+        assert!(is_remote_client_with("mstsc.exe", &config));
+        assert!(is_remote_client_with("citrix viewer", &config));
+        assert!(!is_remote_client_with("Google Chrome", &config));
+        assert!(!is_remote_client_with("", &config));
+
+        assert_eq!(
+            click_crop_size_with(Some("Parallels Desktop"), &config),
+            600
+        );
+        assert_eq!(click_crop_size_with(Some("Finder"), &config), 300);
+        assert_eq!(click_crop_size_with(None, &config), 300);
+        assert_eq!(
+            click_crop_size_with(Some("Windows App"), &RemoteDesktopConfig::default()),
+            DEFAULT_CLICK_CROP_SIZE
+        );
+    }
+}
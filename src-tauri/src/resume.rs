@@ -0,0 +1,258 @@
+//! # Resume Module - Continuing a Recording After a Restart
+//!
+//! A recording only lived in memory until `stop_recording()`, so a crash or
+//! an update mid-recording lost it. While recording, the session is now
+//! checkpointed to its `session.json` and `recordings/active-recording.json`
+//! names it. If FlowTrace starts and finds that file, the user can resume
+//! the previous session (new events are appended, after a `RecorderRestart`
+//! event) or finish it as it was checkpointed.
+//!
+//! ## Persisted State
+//! ```json
+//! {
+//!   "session_id": "f2e904d2-286e-484c-83e8-5949bd8697f1",
+//!   "filter": {...},
+//!   "event_count": 42,
+//!   "checkpointed_at": "2026-02-01T15:43:18.855192Z"
+//! }
+//! ```
+//! - **filter**: The recording's filter (see `filter` module), reused on resume
+//! - **event_count**: Sequence counter: events in the last checkpoint;
+//!   checkpoints are skipped while it doesn't change
+//!
+//! ## Schedule
+//! The watcher in `lib.rs` checks every `CHECKPOINT_POLL` whether a
+//! checkpoint is `due()`: new events, and `checkpoints.interval_seconds`
+//! passed or `checkpoints.every_events` recorded since the last one (see
+//! `config::CheckpointConfig`). A burst of clicks is saved within a fraction
+//! of a second of its 25th event instead of waiting for the interval.
+//!
+//! Events recorded after the last checkpoint are read back from the
+//! session's event journal (see `journal` module); the `RecorderRestart`
+//! event's `gap_seconds` is the time since the last recorded event.
+//! Re-recordings (see `splice` module) aren't checkpointed.
+
+use crate::config::{CheckpointConfig, FilterConfig};
+use crate::journal;
+use crate::storage;
+use crate::types::{Event, EventType, RecordingSession};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How often the watcher in `lib.rs` checks whether a checkpoint is due.
+pub const CHECKPOINT_POLL: Duration = Duration::from_millis(250);
+
+/// Contents of `active-recording.json`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ActiveRecording {
+    pub session_id: String,
+    #[serde(default)]
+    pub filter: FilterConfig,
+    #[serde(default)]
+    pub event_count: usize,
+    pub checkpointed_at: DateTime<Utc>,
+}
+
+/// Location of the active recording state.
+pub fn state_path() -> PathBuf {
+    storage::recordings_root().join("active-recording.json")
+}
+
+/// Records that `session` is being recorded with `filter`.
+///
+/// A different interrupted recording that wasn't resumed is finished first
+/// (see `finish_interrupted()`).
+///
+/// # Returns
+/// * `Ok(())` - State and first checkpoint written
+/// * `Err(String)` - Error if either couldn't be written
+pub fn begin(session: &RecordingSession, filter: &FilterConfig) -> Result<(), String> {
+    if let Some(previous) = load().filter(|state| state.session_id != session.session_id) {
+        if let Err(e) = finish(&previous) {
+            eprintln!("⚠️  Failed to finish interrupted recording: {}", e);
+        }
+    }
+    storage::save_session(session)?;
+    write_state(&ActiveRecording {
+        session_id: session.session_id.clone(),
+        filter: filter.clone(),
+        event_count: session.events.len(),
+        checkpointed_at: Utc::now(),
+    })
+}
+
+/// Saves the active recording if it has new events.
+///
+/// Does nothing for sessions that weren't started with `begin()`.
+///
+/// # Returns
+/// * `Ok(bool)` - Whether a checkpoint was written
+/// * `Err(String)` - Error if the session or state couldn't be written
+pub fn checkpoint(session: &RecordingSession) -> Result<bool, String> {
+    let Some(mut state) = load() else {
+        return Ok(false);
+    };
+    if state.session_id != session.session_id || state.event_count == session.events.len() {
+        return Ok(false);
+    }
+
+    storage::save_session(session)?;
+    state.event_count = session.events.len();
+    state.checkpointed_at = Utc::now();
+    write_state(&state)?;
+    Ok(true)
+}
+
+/// Whether the active recording should be checkpointed (see "Schedule").
+///
+/// # Arguments
+/// * `state` - State of the last checkpoint
+/// * `event_count` - Events the recording holds now
+/// * `now` - Current time
+/// * `config` - Checkpoint schedule
+pub fn due(
+    state: &ActiveRecording,
+    event_count: usize,
+    now: DateTime<Utc>,
+    config: &CheckpointConfig,
+) -> bool {
+    let new_events = event_count.abs_diff(state.event_count);
+    if new_events == 0 {
+        return false;
+    }
+    let elapsed = (now - state.checkpointed_at).num_milliseconds() as f64 / 1000.0;
+    elapsed >= config.interval_seconds
+        || config.every_events.is_some_and(|every| new_events >= every)
+}
+
+/// Forgets the active recording once `session_id` was stopped and saved.
+pub fn clear(session_id: &str) {
+    if load().is_some_and(|state| state.session_id == session_id) {
+        let _ = fs::remove_file(state_path());
+    }
+}
+
+/// Returns the recording interrupted by a restart, if any.
+pub fn load() -> Option<ActiveRecording> {
+    let json_data = fs::read_to_string(state_path()).ok()?;
+    serde_json::from_str(&json_data)
+        .map_err(|e| eprintln!("⚠️  Invalid active recording file, ignoring it: {:?}", e))
+        .ok()
+}
+
+/// Loads the interrupted session (journal applied) with a `RecorderRestart` event appended.
+///
+/// # Returns
+/// * `Ok((RecordingSession, FilterConfig))` - Session to record into, and its filter
+/// * `Err(String)` - Error if there is nothing to resume or the checkpoint is unreadable
+pub fn take_resumable() -> Result<(RecordingSession, FilterConfig), String> {
+    let state = load().ok_or_else(|| "No interrupted recording to resume".to_string())?;
+    let mut session = recovered_session(&state)?;
+    let last_recorded = last_recorded_at(&state, &session);
+    session.stopped_at = None;
+    session.add_event(restart_event(last_recorded, Utc::now()));
+    Ok((session, state.filter))
+}
+
+/// Saves the interrupted session as it was checkpointed and forgets it.
+///
+/// # Returns
+/// * `Ok(String)` - Id of the finished session
+/// * `Err(String)` - Error if there is nothing to finish or it couldn't be saved
+pub fn finish_interrupted() -> Result<String, String> {
+    let state = load().ok_or_else(|| "No interrupted recording".to_string())?;
+    finish(&state)?;
+    clear(&state.session_id);
+    Ok(state.session_id)
+}
+
+fn finish(state: &ActiveRecording) -> Result<(), String> {
+    let mut session = recovered_session(state)?;
+    session.stopped_at = Some(last_recorded_at(state, &session));
+    storage::save_session(&session)?;
+    journal::remove(&state.session_id);
+    Ok(())
+}
+
+/// The last checkpoint with the events journaled since.
+fn recovered_session(state: &ActiveRecording) -> Result<RecordingSession, String> {
+    let mut session = storage::load_session(&state.session_id)?;
+    let merged = journal::merge_into(&mut session);
+    if merged > 0 {
+        #[cfg(debug_assertions)]
+        println!("📓 Recovered {} events from the event journal", merged);
+    }
+    Ok(session)
+}
+
+/// When the interrupted recording last recorded anything.
+fn last_recorded_at(state: &ActiveRecording, session: &RecordingSession) -> DateTime<Utc> {
+    session
+        .events
+        .iter()
+        .map(|event| event.timestamp)
+        .max()
+        .map_or(state.checkpointed_at, |last| {
+            last.max(state.checkpointed_at)
+        })
+}
+
+fn restart_event(since: DateTime<Utc>, now: DateTime<Utc>) -> Event {
+    Event::new(
+        EventType::RecorderRestart {
+            gap_seconds: (now - since).num_milliseconds().max(0) as f64 / 1000.0,
+        },
+        None,
+    )
+}
+
+/// Writes the state via a temporary file so a crash never truncates it.
+fn write_state(state: &ActiveRecording) -> Result<(), String> {
+    storage::write_json_atomic(&state_path(), state, "active recording file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_due_by_time_or_event_count() {
+        let at = |time: &str| time.parse::<DateTime<Utc>>().unwrap();
+        let state = ActiveRecording {
+            session_id: "resume-test".to_string(),
+            filter: FilterConfig::default(),
+            event_count: 40,
+            checkpointed_at: at("2026-02-01T15:43:18Z"),
+        };
+        let config = CheckpointConfig::default();
+
+        // Nothing new: never, however long ago
+        assert!(!due(&state, 40, at("2026-02-01T16:00:00Z"), &config));
+        // A few events: once the interval passed
+        assert!(!due(&state, 43, at("2026-02-01T15:43:20Z"), &config));
+        assert!(due(&state, 43, at("2026-02-01T15:43:23Z"), &config));
+        // A burst: right away
+        assert!(due(&state, 65, at("2026-02-01T15:43:19Z"), &config));
+    }
+
+    #[test]
+    fn test_restart_event_covers_time_since_checkpoint() {
+        let at = |time: &str| time.parse::<DateTime<Utc>>().unwrap();
+        let state = ActiveRecording {
+            session_id: "resume-test".to_string(),
+            filter: FilterConfig::default(),
+            event_count: 42,
+            checkpointed_at: at("2026-02-01T15:43:18Z"),
+        };
+
+        let event = restart_event(state.checkpointed_at, at("2026-02-01T15:45:00.500Z"));
+        assert!(matches!(
+            event.event_type,
+            EventType::RecorderRestart { gap_seconds } if gap_seconds == 101.5
+        ));
+        assert_eq!(event.action_category, "restart");
+    }
+}
@@ -3,22 +3,143 @@
 //! Handles all screenshot capture operations including:
 //! - Full screen capture
 //! - Active window detection and cropping
-//! - Click-region cropping (300x300px around click)
+//! - Click-region cropping (300x300 logical px around click, larger in
+//!   remote desktop windows if configured, see `remote_desktop` module)
 //!
-//! ## Known Limitation: Retina Display Coordinate Scaling
+//! ## HiDPI and Fractional Scaling
 //!
-//! On Retina/HiDPI displays, coordinate system mismatch causes offset crops:
-//! - **Problem**: Event coordinates are logical (e.g., 713, 395)
-//! - **Reality**: Screenshots are physical pixels (e.g., 2880x1800 on 2x display)
-//! - **Result**: Window and click crops appear ~2x offset from intended position
-//! - **Status**: Full screen works perfectly, crops documented as known limitation
-
-use crate::storage;
-use active_win_pos_rs::get_active_window;
-use image::DynamicImage;
-use screenshots::Screen;
+//! Event coordinates are logical (e.g., 713, 395) while screenshots are
+//! physical pixels (e.g., 2880x1800 on a 2x display). Crops translate through
+//! `display::DisplayGeometry`, which handles integer (2x, 3x) and fractional
+//! (125%, 150%, 175%) scale factors alike.
+//!
+//! ## Multiple Displays
+//! Event captures come from the display the click happened on (falling
+//! back to the primary display if it can't be determined), so a click on a
+//! secondary monitor shows that monitor's content.
+//!
+//! ## Keyword Scrubbing
+//! If scrub rules are configured, matching screen text is blurred in the
+//! full capture before anything is saved (see `scrub` module).
+//!
+//! ## Partial Captures
+//! With `partial_capture` enabled, rapid clicks in the same window save only
+//! the changed region of the full screen (see `frame_diff` module).
+//!
+//! ## Color Profiles
+//! Every PNG is tagged with the captured display's ICC profile (see
+//! `color_profile` module) so wide-gamut captures render correctly.
+//!
+//! ## Backends
+//! Frames come from a `capturer::ScreenCapturer` (the `screenshots` crate
+//! by default); `capture_all_for_event_with()` takes any backend.
+//!
+//! ## Dry Runs
+//! Screenshots of a dry-run session are encoded but not written (see
+//! `dry_run` module).
+
+use crate::capturer::{self, ScreenCapturer};
+use crate::display::DisplayGeometry;
+use crate::error_dialog::{self, DialogSignals};
+use crate::types::{AnnotationColors, Event, FramePatch};
+use crate::{
+    annotation, anonymize, buffer_pool, color_profile, config, dry_run, focus, frame_diff, latency,
+    remote_desktop, scrub, storage, window_capture,
+};
+use active_win_pos_rs::ActiveWindow;
+use image::{DynamicImage, ImageOutputFormat};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Screenshots and capture-time observations for one click event.
+#[derive(Debug, Clone)]
+pub struct EventCapture {
+    /// Full screen capture (always present; only the changed region if patched)
+    pub full_screen: String,
+    /// Set if `full_screen` is a patch of an earlier capture
+    pub full_screen_patch: Option<FramePatch>,
+    /// Active window crop (`None` if window detection failed)
+    pub window_crop: Option<String>,
+    /// Click region crop (`None` if the crop failed)
+    pub click_crop: Option<String>,
+    /// Focused window looks like an error dialog (see `error_dialog` module)
+    pub error_dialog_detected: bool,
+    /// Marker colors for the click region (`None` if the crop failed)
+    pub annotation_colors: Option<AnnotationColors>,
+    /// Unscrubbed frame to measure the click's response against
+    /// (`None` unless `latency.enabled`; see `latency` module)
+    pub latency_reference: Option<latency::ReferenceFrame>,
+}
+
+/// Where (and how) the screenshots of a single event are written.
+///
+/// Bundles the per-event output parameters shared by the full screen,
+/// window crop, and click crop so each capture step only needs the image.
+struct CaptureTarget<'a> {
+    session_id: &'a str,
+    event_id: &'a str,
+    session_dir: PathBuf,
+    /// Captured display (for dry-run reports)
+    display_id: Option<u32>,
+    /// ICC profile of the captured display, embedded into every saved PNG
+    icc_profile: Option<Vec<u8>>,
+    /// Per-display downscale limit from `config::DisplayOverride`
+    max_dimension: Option<u32>,
+}
+
+impl CaptureTarget<'_> {
+    /// Saves `image` as `event_[id]_[suffix].png` and returns its stored path.
+    fn save(&self, image: &DynamicImage, suffix: &str) -> Result<String, String> {
+        let filename = format!("event_{}_{}.png", self.event_id, suffix);
+
+        // Crops are computed on the full-resolution capture; only the saved file shrinks
+        let downscaled = match self.max_dimension {
+            Some(max) if image.width() > max || image.height() > max => {
+                Some(image.resize(max, max, image::imageops::FilterType::Triangle))
+            }
+            _ => None,
+        };
+
+        let image = downscaled.as_ref().unwrap_or(image);
+        if dry_run::covers(self.session_id) {
+            let png = encode_png(image, self.icc_profile.as_deref())
+                .map_err(|e| format!("Failed to encode {} screenshot: {}", suffix, e))?;
+            dry_run::record_screenshot(self.session_id, self.display_id, png.len());
+        } else {
+            save_png(
+                image,
+                &self.session_dir.join(&filename),
+                self.icc_profile.as_deref(),
+            )
+            .map_err(|e| format!("Failed to save {} screenshot: {}", suffix, e))?;
+        }
+
+        Ok(storage::session_asset_path(self.session_id, &filename))
+    }
+}
+
+/// Encodes an image as PNG and writes it, embedding the display's ICC profile.
+///
+/// Without a profile, viewers assume sRGB and wide-gamut captures look
+/// washed out (see `color_profile` module).
+fn save_png(image: &DynamicImage, path: &Path, icc_profile: Option<&[u8]>) -> Result<(), String> {
+    let png = encode_png(image, icc_profile)?;
+    fs::write(path, png).map_err(|e| format!("Failed to write PNG: {:?}", e))
+}
+
+/// Encodes an image as PNG in memory, embedding the display's ICC profile.
+fn encode_png(image: &DynamicImage, icc_profile: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {:?}", e))?;
+
+    match icc_profile {
+        Some(profile) => color_profile::embed_icc_profile(&png, profile),
+        None => Ok(png),
+    }
+}
 
 /// Captures a full-screen screenshot for spike testing.
 ///
@@ -33,7 +154,7 @@ use std::path::PathBuf;
 /// Saves to: `recordings/screenshot_YYYYMMDD_HHMMSS.png`
 ///
 /// # How It Works
-/// 1. Get primary screen from `screenshots` crate
+/// 1. Get primary display from the capture backend (see `capturer` module)
 /// 2. Capture entire display as PNG
 /// 3. Save with timestamp-based filename
 ///
@@ -49,26 +170,25 @@ pub fn capture_full_screen() -> Result<PathBuf, String> {
     #[cfg(debug_assertions)]
     println!("📸 Attempting to capture screenshot...");
 
-    // Get the primary screen (index 0)
-    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {:?}", e))?;
-
-    let primary_screen = screens
-        .first()
+    // Get the primary display (listed first)
+    let backend = capturer::default_backend();
+    let primary_display = backend
+        .displays()?
+        .into_iter()
+        .next()
         .ok_or_else(|| "No screens found".to_string())?;
 
     #[cfg(debug_assertions)]
     println!(
         "📸 Found screen: {}x{}",
-        primary_screen.display_info.width, primary_screen.display_info.height
+        primary_display.logical_width, primary_display.logical_height
     );
 
-    // Capture the screen as raw image data
-    let image = primary_screen
-        .capture()
-        .map_err(|e| format!("Failed to capture screen: {:?}", e))?;
+    // Capture the screen as an RGBA image
+    let image = backend.capture(&primary_display)?;
 
     // Ensure recordings directory exists
-    let recordings_dir = PathBuf::from("recordings");
+    let recordings_dir = storage::recordings_root();
 
     fs::create_dir_all(&recordings_dir)
         .map_err(|e| format!("Failed to create recordings directory: {:?}", e))?;
@@ -78,10 +198,14 @@ pub fn capture_full_screen() -> Result<PathBuf, String> {
     let filename = format!("screenshot_{}.png", timestamp);
     let filepath = recordings_dir.join(&filename);
 
-    // Save to disk as PNG
-    image
-        .save(&filepath)
-        .map_err(|e| format!("Failed to save screenshot: {:?}", e))?;
+    // Save to disk as PNG, tagged with the display's color profile
+    let icc_profile = color_profile::display_icc_profile(primary_display.id);
+    save_png(
+        &DynamicImage::ImageRgba8(image),
+        &filepath,
+        icc_profile.as_deref(),
+    )
+    .map_err(|e| format!("Failed to save screenshot: {}", e))?;
 
     #[cfg(debug_assertions)]
     println!("✅ Screenshot saved to: {:?}", filepath);
@@ -101,52 +225,59 @@ pub fn capture_full_screen() -> Result<PathBuf, String> {
 /// * `click_y` - Y coordinate of click in logical pixels
 ///
 /// # Returns
-/// * `Ok((full_path, window_path, click_path))` - Relative paths to saved screenshots
-///   - `full_path` - Always `Some(String)` (full screen capture)
-///   - `window_path` - `Option<String>` (may fail if window detection fails)
-///   - `click_path` - `Option<String>` (may fail if crop out of bounds)
+/// * `Ok(Some(EventCapture))` - Relative paths to saved screenshots
+///   - `full_screen` - Always present (full screen capture or patch)
+///   - `full_screen_patch` - Base frame and offset if only the changed region was saved
+///   - `window_crop` - `Option<String>` (may fail if window detection fails)
+///   - `click_crop` - `Option<String>` (may fail if crop out of bounds)
+///   - `error_dialog_detected` - Error dialog heuristics on the focused window
+/// * `Ok(None)` - Capture skipped for this display by configuration
 /// * `Err(String)` - Error message if full screen capture fails
 ///
+/// # Per-Display Settings
+/// `config::DisplayOverride` entries for the captured display are applied here:
+/// - `skip_capture` → nothing is captured or written (`Ok(None)`)
+/// - `max_dimension` → saved images are downscaled (crops still use full resolution)
+///
 /// # Screenshot Types
 ///
 /// 1. **Full Screen** (~2.2MB each)
-///    - Captures entire primary display
+///    - Captures the entire display containing the click
 ///    - Always succeeds (unless screen capture permission missing)
-///    - File: `event_[id]_full.png`
+///    - File: `event_[id]_full.png`, or `event_[id]_patch.png` holding only
+///      the region that changed since the base frame (`partial_capture`)
 ///
 /// 2. **Window Crop** (variable size)
-///    - Detects active window via `active-win-pos-rs`
+///    - Detects active window via `focus::focused_window()`
 ///    - Crops full screen to window bounds
 ///    - May fail if window detection fails
 ///    - File: `event_[id]_window.png`
-///    - **Known Issue**: Offset on Retina displays
 ///
-/// 3. **Click Crop** (300x300px)
-///    - Crops 300x300px region centered on click position
-///    - Bounded to screen edges (won't crop beyond display)
-///    - May fail if position calculation errors
+/// 3. **Click Crop** (300x300 logical px)
+///    - Crops a 300x300 logical-pixel region centered on click position
+///    - Shifted to stay inside screen edges (won't crop beyond display)
 ///    - File: `event_[id]_click.png`
-///    - **Known Issue**: Offset on Retina displays
 ///
-/// # Known Limitation: Retina Display Coordinate Scaling
+/// # Coordinate Translation
 ///
-/// On 2x Retina displays:
-/// - Input coordinates are logical (713, 395)
-/// - Screenshot is physical pixels (2880x1800)
-/// - Crops apply logical coords to physical image → 2x offset
-/// - **Fix**: Detect scale factor and multiply coordinates (not implemented)
+/// Event and window coordinates are logical, the screenshot is physical pixels.
+/// Both crops convert through `display::DisplayGeometry`, which derives the
+/// scale from the captured image size, so 1x, 1.25x, 1.5x, 2x, and 3x
+/// displays all crop the intended region.
 ///
 /// # File Locations
 /// All saved to: `recordings/[session_id]/event_[event_id]_[type].png`
 ///
 /// # Error Handling
 /// - Full screen failure → returns `Err` (critical)
+/// - Scrub failure (OCR unavailable while rules are configured) → returns
+///   `Err`, nothing is saved
 /// - Window crop failure → logs warning, returns `None` for window_path
 /// - Click crop failure → logs warning, returns `None` for click_path
 ///
 /// # Example
 /// ```rust
-/// let (full, window, click) = capture_all_for_event(
+/// let capture = capture_all_for_event(
 ///     "f2e904d2-286e-484c-83e8-5949bd8697f1",
 ///     "cece1f95-8a90-4fa5-8fcc-2995113918ab",
 ///     709,
@@ -158,259 +289,372 @@ pub fn capture_all_for_event(
     event_id: &str,
     click_x: i32,
     click_y: i32,
-) -> Result<(String, Option<String>, Option<String>), String> {
-    // STEP 1: Get primary screen and capture full screenshot
-    let screens = Screen::all().map_err(|e| format!("Failed to get screens: {:?}", e))?;
-    let primary_screen = screens
-        .first()
-        .ok_or_else(|| "No screens found".to_string())?;
+) -> Result<Option<EventCapture>, String> {
+    // Injected test input (see `testing` module)
+    #[cfg(any(test, feature = "testing"))]
+    if let Some(capture) = crate::testing::mock_capture(session_id, event_id) {
+        return Ok(Some(capture));
+    }
+    #[cfg(any(test, feature = "testing"))]
+    if let Some(backend) = crate::testing::capturer() {
+        return capture_all_for_event_with(
+            backend.as_ref(),
+            session_id,
+            event_id,
+            click_x,
+            click_y,
+        );
+    }
+
+    capture_all_for_event_with(
+        capturer::default_backend(),
+        session_id,
+        event_id,
+        click_x,
+        click_y,
+    )
+}
 
-    // Capture full screen as raw image data (this is the expensive operation)
-    let full_image_raw = primary_screen
-        .capture()
-        .map_err(|e| format!("Failed to capture screen: {:?}", e))?;
+/// `capture_all_for_event()` with frames from the given backend.
+///
+/// # Arguments
+/// * `backend` - Where frames come from (see `capturer` module)
+/// * Others as for `capture_all_for_event()`
+pub fn capture_all_for_event_with(
+    backend: &dyn ScreenCapturer,
+    session_id: &str,
+    event_id: &str,
+    click_x: i32,
+    click_y: i32,
+) -> Result<Option<EventCapture>, String> {
+    // STEP 1: Get the display the click happened on
+    let display = capturer::display_or_primary(backend, click_x, click_y)?;
+
+    // Respect per-display overrides before doing any expensive work
+    let display_override = config::display_override(display.id);
+    if display_override.as_ref().is_some_and(|o| o.skip_capture) {
+        return Ok(None);
+    }
 
-    // STEP 2: Convert screenshots::Image to image::DynamicImage for manipulation
+    // STEP 2: Capture full screen (this is the expensive operation) as a DynamicImage
     // Why: DynamicImage provides crop_imm() and other image processing methods
-    let width = full_image_raw.width();
-    let height = full_image_raw.height();
-    let dynamic_image = image::RgbaImage::from_raw(
-        width,
-        height,
-        full_image_raw.as_raw().to_vec(), // Copy raw pixel data
-    )
-    .ok_or_else(|| "Failed to convert screenshot to image format".to_string())?;
-    let dynamic_image = DynamicImage::ImageRgba8(dynamic_image);
+    let mut dynamic_image = DynamicImage::ImageRgba8(backend.capture(&display)?);
+    let (width, height) = (dynamic_image.width(), dynamic_image.height());
+
+    // Response sampling compares raw frames, so keep one from before scrubbing
+    let latency_reference = dynamic_image
+        .as_rgba8()
+        .filter(|_| config::current().latency.enabled)
+        .and_then(|frame| latency::ReferenceFrame::new(display.id, frame));
+
+    // Blur configured keywords before any file is written (fails closed)
+    if let Some(rules) = scrub::ScrubRules::compile(&config::current().scrub)? {
+        scrub::scrub(&mut dynamic_image, &rules)?;
+    }
+    let geometry = DisplayGeometry {
+        physical_width: width,
+        physical_height: height,
+        ..display
+    };
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📐 Display {}: scale {:.2}x{:.2}",
+        geometry.id,
+        geometry.scale_x(),
+        geometry.scale_y()
+    );
 
     // STEP 3: Ensure session directory exists
     let session_dir = storage::get_session_dir(session_id);
-    fs::create_dir_all(&session_dir)
-        .map_err(|e| format!("Failed to create session directory: {:?}", e))?;
+    if !dry_run::covers(session_id) {
+        fs::create_dir_all(&session_dir)
+            .map_err(|e| format!("Failed to create session directory: {:?}", e))?;
+    }
 
-    // STEP 4: Save full screen screenshot (always succeeds at this point)
-    let full_filename = format!("event_{}_full.png", event_id);
-    let full_filepath = session_dir.join(&full_filename);
-    dynamic_image
-        .save(&full_filepath)
-        .map_err(|e| format!("Failed to save full screenshot: {:?}", e))?;
-    let full_relative = format!("recordings/{}/{}", session_id, full_filename);
+    let target = CaptureTarget {
+        session_id,
+        event_id,
+        session_dir,
+        display_id: Some(display.id),
+        icc_profile: color_profile::display_icc_profile(display.id),
+        max_dimension: display_override.and_then(|o| o.max_dimension),
+    };
+
+    // STEP 4: Save full screen screenshot, or only its changed region (see
+    // `frame_diff` module)
+    let active_window = focus::focused_window();
+    let window_key = active_window
+        .as_ref()
+        .ok()
+        // Per display too: a window moved to another monitor needs a new base
+        .map(|window| format!("{}:{}:{}", geometry.id, window.process_id, window.window_id));
+    let partial_config = config::current().partial_capture;
+    let rgba = dynamic_image
+        .as_rgba8()
+        .filter(|_| partial_config.enabled && target.max_dimension.is_none());
+    let patch = rgba.and_then(|frame| {
+        frame_diff::plan_patch(session_id, window_key.as_deref(), frame, &partial_config)
+    });
+    let (full_relative, full_screen_patch) = match patch {
+        Some((patch, (x, y, width, height))) => (
+            target.save(&dynamic_image.crop_imm(x, y, width, height), "patch")?,
+            Some(patch),
+        ),
+        None => {
+            let path = target.save(&dynamic_image, "full")?;
+            if let Some(frame) = rgba {
+                frame_diff::set_base(session_id, window_key.as_deref(), &path, frame);
+            }
+            (path, None)
+        }
+    };
+
+    // Larger click crops inside VM / remote desktop windows (see `remote_desktop` module)
+    let click_crop_size = remote_desktop::click_crop_size(
+        active_window
+            .as_ref()
+            .ok()
+            .map(|window| window.app_name.as_str()),
+    );
 
     // STEP 5: Try to capture window crop (graceful failure)
     // Non-fatal: If window detection fails, continue without window crop
-    let window_relative =
-        match capture_window_crop(&dynamic_image, session_id, event_id, &session_dir) {
-            Ok(path) => {
-                #[cfg(debug_assertions)]
-                println!("✅ Window crop saved");
-                Some(path)
-            }
-            Err(e) => {
-                #[cfg(debug_assertions)]
-                println!("⚠️  Window crop failed: {}", e);
-                None // Continue recording without window crop
-            }
-        };
+    let mut error_dialog_detected = false;
+    let window_crop = active_window
+        .map_err(|e| format!("Failed to get active window: {:?}", e))
+        .and_then(|window| {
+            let cropped = capture_window_crop(&dynamic_image, &geometry, &window)?;
+            error_dialog_detected =
+                error_dialog::is_error_dialog(&dialog_signals(&window, &cropped, &geometry));
+            target.save(&cropped, "window")
+        });
+    let window_relative = match window_crop {
+        Ok(path) => {
+            #[cfg(debug_assertions)]
+            println!("✅ Window crop saved");
+            Some(path)
+        }
+        Err(e) => {
+            #[cfg(debug_assertions)]
+            println!("⚠️  Window crop failed: {}", e);
+            None // Continue recording without window crop
+        }
+    };
 
     // STEP 6: Try to capture click crop (graceful failure)
     // Non-fatal: If crop calculation fails, continue without click crop
-    let click_relative = match capture_click_crop(
+    let (click_relative, annotation_colors) = match capture_click_crop(
         &dynamic_image,
-        session_id,
-        event_id,
-        &session_dir,
+        &target,
+        &geometry,
         click_x,
         click_y,
-        width as i32,
-        height as i32,
+        click_crop_size,
     ) {
-        Ok(path) => {
+        Ok((path, colors)) => {
             #[cfg(debug_assertions)]
             println!("✅ Click crop saved");
-            Some(path)
+            (Some(path), Some(colors))
         }
         Err(e) => {
             #[cfg(debug_assertions)]
             println!("⚠️  Click crop failed: {}", e);
-            None // Continue recording without click crop
+            (None, None) // Continue recording without click crop
         }
     };
 
-    Ok((full_relative, window_relative, click_relative))
+    // Reuse the converted frame's memory for the next capture
+    buffer_pool::give(dynamic_image.into_rgba8().into_raw());
+
+    Ok(Some(EventCapture {
+        full_screen: full_relative,
+        full_screen_patch,
+        window_crop: window_relative,
+        click_crop: click_relative,
+        error_dialog_detected,
+        annotation_colors,
+        latency_reference,
+    }))
+}
+
+/// Captures one window (see `window_capture` module) as an event's window crop.
+///
+/// Scrub rules and per-display overrides apply as for click captures.
+///
+/// # Returns
+/// * `Ok(String)` - Stored path of `event_[id]_window.png`
+/// * `Err(String)` - Error if the window couldn't be captured or saved
+pub fn capture_window_for_event(
+    session_id: &str,
+    event_id: &str,
+    window: &window_capture::WindowInfo,
+) -> Result<String, String> {
+    // Display overrides apply to the display showing the window's center
+    let display = display_at(
+        window.x + window.width / 2.0,
+        window.y + window.height / 2.0,
+    );
+    let display_override = display.and_then(|display| config::display_override(display.id));
+    if display_override.as_ref().is_some_and(|o| o.skip_capture) {
+        return Err("Capture is disabled for the display showing this window".to_string());
+    }
+
+    let mut image = DynamicImage::ImageRgba8(window_capture::capture(window)?);
+    if let Some(rules) = scrub::ScrubRules::compile(&config::current().scrub)? {
+        scrub::scrub(&mut image, &rules)?;
+    }
+
+    let session_dir = storage::get_session_dir(session_id);
+    if !dry_run::covers(session_id) {
+        fs::create_dir_all(&session_dir)
+            .map_err(|e| format!("Failed to create session directory: {:?}", e))?;
+    }
+
+    let target = CaptureTarget {
+        session_id,
+        event_id,
+        session_dir,
+        display_id: display.map(|display| display.id),
+        icc_profile: display.and_then(|display| color_profile::display_icc_profile(display.id)),
+        max_dimension: display_override.and_then(|o| o.max_dimension),
+    };
+    target.save(&image, "window")
+}
+
+/// Collects the capture-time error dialog signals for the focused window.
+fn dialog_signals<'a>(
+    window: &'a ActiveWindow,
+    window_crop: &DynamicImage,
+    geometry: &DisplayGeometry,
+) -> DialogSignals<'a> {
+    let display_area = geometry.logical_width as f64 * geometry.logical_height as f64;
+    let window_area = window.position.width * window.position.height;
+
+    DialogSignals {
+        window_title: &window.title,
+        window_area_ratio: if display_area > 0.0 {
+            window_area / display_area
+        } else {
+            0.0
+        },
+        red_ratio: error_dialog::red_pixel_ratio(window_crop),
+    }
+}
+
+/// Attaches the focused window's application name and title to an event.
+///
+/// Both stay `None` if the focused window can't be determined (e.g.,
+/// missing Screen Recording permission on macOS, which also hides titles).
+/// Counts toward the session's `capture_stats` if the window came from the
+/// window list fallback (see `focus` module). VM and remote desktop clients
+/// set `remote_desktop` (see `remote_desktop` module). The configured
+/// rewrites are applied to both names, and to the app names of `AppSwitch`
+/// events (see `anonymize` module).
+pub fn stamp_focused_window(event: &mut Event) {
+    if let Ok(window) = focus::focused_window_for_event() {
+        event.remote_desktop = remote_desktop::is_remote_client(&window.app_name);
+        event.app_name = Some(window.app_name).filter(|name| !name.is_empty());
+        event.window_title = Some(window.title).filter(|title| !title.is_empty());
+    }
+    anonymize::apply(event);
+}
+
+/// Returns the geometry of the display containing a global logical point.
+///
+/// Doesn't capture anything, so the physical size is unknown (`0`); use it
+/// for logical math such as `DisplayGeometry::normalize()`.
+pub fn display_at(x: f64, y: f64) -> Option<DisplayGeometry> {
+    capturer::default_backend().display_at(x as i32, y as i32)
 }
 
-/// Captures a cropped screenshot of the active window.
+/// Crops the full screen capture to the active window.
 ///
-/// Uses `active-win-pos-rs` to detect the currently focused window and
-/// crops the full screen image to that window's boundaries.
+/// The focused window is detected by the caller via `focus::focused_window()`
+/// (also used for error dialog detection); the crop is saved by the caller.
 ///
 /// # Arguments
 /// * `dynamic_image` - Full screen capture to crop from
-/// * `session_id` - UUID for file path
-/// * `event_id` - UUID for filename
-/// * `session_dir` - Directory to save crop
+/// * `geometry` - Coordinate translation for the captured display
+/// * `window` - Focused window (logical bounds)
 ///
 /// # Returns
-/// * `Ok(String)` - Relative path to saved window crop
-/// * `Err(String)` - Error if window detection or crop fails
+/// * `Ok(DynamicImage)` - Window crop
+/// * `Err(String)` - Error if the window isn't on the captured display
 ///
 /// # How It Works
-/// 1. Get active window position/size via `active-win-pos-rs`
-/// 2. Extract bounds: `x`, `y`, `width`, `height`
-/// 3. Apply bounds checking (prevent crop beyond image edges)
+/// 1. Take active window position/size from `active-win-pos-rs` (logical)
+/// 2. Convert bounds to physical pixels via `DisplayGeometry::rect_to_physical()`
+/// 3. Clip to the captured image (windows may extend past the screen edge)
 /// 4. Crop full screen image to window rectangle
-/// 5. Save as separate PNG file
-///
-/// # Known Issue: Retina Display Coordinate Scaling
-///
-/// **Problem**: Window bounds are in logical coordinates but image is physical pixels.
 ///
 /// **Example on 2x Retina Display**:
 /// - Window reports: `x=200, y=100, width=800, height=600` (logical)
-/// - Screenshot is: 2880x1800 (physical pixels, 2x scale)
-/// - Crop applies: (200, 100, 800, 600) to physical image
-/// - **Expected crop**: (400, 200, 1600, 1200) in physical pixels
-/// - **Result**: Crops wrong area, offset by 2x
-///
-/// **Fix** (not implemented):
-/// ```rust
-/// let scale = detect_display_scale_factor(); // e.g., 2.0
-/// let physical_x = (logical_x * scale) as u32;
-/// let physical_y = (logical_y * scale) as u32;
-/// // ... use physical coordinates for crop
-/// ```
-///
-/// # Bounds Checking
-/// Prevents crop from extending beyond image edges:
-/// - `x.max(0.0)` - Clamp negative x to 0
-/// - `width.min(image_width - x)` - Clamp width to remaining space
+/// - Screenshot is: 2880x1800 (physical pixels)
+/// - Crop applied: `(400, 200, 1600, 1200)` (physical pixels)
 fn capture_window_crop(
     dynamic_image: &DynamicImage,
-    session_id: &str,
-    event_id: &str,
-    session_dir: &PathBuf,
-) -> Result<String, String> {
-    // Detect active window position and dimensions
-    let window =
-        get_active_window().map_err(|e| format!("Failed to get active window: {:?}", e))?;
-
-    // Extract window bounds (in logical coordinates)
-    // ISSUE: These are logical coords, but image is in physical pixels (Retina)
-    let x = window.position.x.max(0.0) as u32;
-    let y = window.position.y.max(0.0) as u32;
-    let width = (window.position.width as u32).min(dynamic_image.width() - x);
-    let height = (window.position.height as u32).min(dynamic_image.height() - y);
+    geometry: &DisplayGeometry,
+    window: &ActiveWindow,
+) -> Result<DynamicImage, String> {
+    // Convert logical window bounds to a clipped physical crop rectangle
+    let (x, y, width, height) = geometry
+        .rect_to_physical(
+            window.position.x,
+            window.position.y,
+            window.position.width,
+            window.position.height,
+        )
+        .ok_or_else(|| "Active window is not on the captured display".to_string())?;
 
     // Crop full screen image to window bounds
-    let cropped = dynamic_image.crop_imm(x, y, width, height);
-
-    // Save window crop to disk
-    let window_filename = format!("event_{}_window.png", event_id);
-    let window_filepath = session_dir.join(&window_filename);
-    cropped
-        .save(&window_filepath)
-        .map_err(|e| format!("Failed to save window crop: {:?}", e))?;
-
-    Ok(format!("recordings/{}/{}", session_id, window_filename))
+    Ok(dynamic_image.crop_imm(x, y, width, height))
 }
 
-/// Captures a 300x300px crop centered on the click position.
+/// Captures a square crop (300x300 logical px by default) centered on the click position.
 ///
 /// Provides visual context of the UI element that was clicked, useful for
 /// understanding what button/link/element the user interacted with.
 ///
 /// # Arguments
 /// * `dynamic_image` - Full screen capture to crop from
-/// * `session_id` - UUID for file path
-/// * `event_id` - UUID for filename
-/// * `session_dir` - Directory to save crop
+/// * `target` - Session/event the crop belongs to (file path + color profile)
+/// * `geometry` - Coordinate translation for the captured display
 /// * `click_x` - X coordinate of click in logical pixels
 /// * `click_y` - Y coordinate of click in logical pixels
-/// * `screen_width` - Full screen width (for bounds checking)
-/// * `screen_height` - Full screen height (for bounds checking)
+/// * `crop_size` - Side length in logical pixels (see `remote_desktop::click_crop_size()`)
 ///
 /// # Returns
-/// * `Ok(String)` - Relative path to saved click crop
+/// * `Ok((String, AnnotationColors))` - Relative path to saved click crop and
+///   the marker colors chosen for it (see `annotation` module)
 /// * `Err(String)` - Error if crop calculation or save fails
 ///
 /// # Crop Dimensions
-/// - Target size: 300x300px square
-/// - Centered on click position (±150px in each direction)
-/// - Bounded to screen edges (won't extend beyond display)
-///
-/// # How It Works
-/// 1. Calculate crop center: `(click_x, click_y)`
-/// 2. Calculate crop bounds: `(x - 150, y - 150)` to `(x + 150, y + 150)`
-/// 3. Apply bounds checking:
-///    - Clamp to screen edges
-///    - Ensure crop doesn't extend beyond 0 or max dimensions
-/// 4. Crop full screen image to calculated rectangle
-/// 5. Save as separate PNG file
-///
-/// # Known Issue: Retina Display Coordinate Scaling
-///
-/// **Problem**: Click coordinates are logical but image is physical pixels.
-///
-/// **Example on 2x Retina Display**:
-/// - Click at: `(713, 395)` logical coordinates
-/// - Screenshot: 2880x1800 physical pixels
-/// - Expected crop center: `(1426, 790)` physical pixels (713×2, 395×2)
-/// - Actual crop center: `(713, 395)` physical pixels
-/// - **Result**: Crop centered on wrong location, offset by 2x
-///
-/// **Visual Impact**:
-/// - If user clicks a button at logical (713, 395)
-/// - Crop captures area around physical (713, 395) instead of (1426, 790)
-/// - Button appears offset from center of crop image
-///
-/// **Fix** (not implemented):
-/// ```rust
-/// let scale = detect_display_scale_factor(); // e.g., 2.0
-/// let physical_x = (click_x as f64 * scale) as i32;
-/// let physical_y = (click_y as f64 * scale) as i32;
-/// // Calculate crop bounds using physical coordinates
-/// ```
-///
-/// # Bounds Checking Logic
-/// - `(click_x - 150).max(0)` - Prevent negative x
-/// - `.min(screen_width - 300)` - Prevent extending beyond right edge
-/// - Similar logic for y-axis
-/// - Final width/height clamped to remaining space if near edge
+/// - Target size: 300x300 logical pixels (600x600 physical on a 2x display,
+///   450x450 at 150%) so every display shows the same amount of UI context;
+///   `remote_desktop.click_crop_size` inside VM / remote desktop windows
+/// - Centered on click position (±150 logical px in each direction)
+/// - Shifted to stay inside the image near screen edges
 fn capture_click_crop(
     dynamic_image: &DynamicImage,
-    session_id: &str,
-    event_id: &str,
-    session_dir: &PathBuf,
+    target: &CaptureTarget,
+    geometry: &DisplayGeometry,
     click_x: i32,
     click_y: i32,
-    screen_width: i32,
-    screen_height: i32,
-) -> Result<String, String> {
-    /// Crop size: 300x300px provides good UI element context without being too large
-    const CROP_SIZE: i32 = 300;
-    /// Half of crop size used for centering calculation (±150px from click point)
-    const HALF_SIZE: i32 = CROP_SIZE / 2;
-
-    // Calculate crop bounds centered on click position
-    // ISSUE: click_x and click_y are logical coords, but image is physical pixels (Retina)
-    // Apply bounds checking to prevent cropping beyond screen edges
-    let x = (click_x - HALF_SIZE).max(0).min(screen_width - CROP_SIZE) as u32;
-    let y = (click_y - HALF_SIZE).max(0).min(screen_height - CROP_SIZE) as u32;
-
-    // Final dimensions may be less than CROP_SIZE if near screen edge
-    let width = CROP_SIZE.min(screen_width - x as i32) as u32;
-    let height = CROP_SIZE.min(screen_height - y as i32) as u32;
+    crop_size: u32,
+) -> Result<(String, AnnotationColors), String> {
+    // Calculate physical crop bounds centered on click position
+    let (x, y, width, height) = geometry.centered_crop(click_x as f64, click_y as f64, crop_size);
+    if width == 0 || height == 0 {
+        return Err("Captured image is empty".to_string());
+    }
 
     // Perform crop operation (non-mutating, returns new image)
     let cropped = dynamic_image.crop_imm(x, y, width, height);
 
     // Save click crop to disk
-    let click_filename = format!("event_{}_click.png", event_id);
-    let click_filepath = session_dir.join(&click_filename);
-    cropped
-        .save(&click_filepath)
-        .map_err(|e| format!("Failed to save click crop: {:?}", e))?;
-
-    Ok(format!("recordings/{}/{}", session_id, click_filename))
+    let path = target.save(&cropped, "click")?;
+    Ok((path, annotation::colors_for(&cropped)))
 }
 
 #[cfg(test)]
@@ -0,0 +1,206 @@
+//! # Scrub Module - Keyword Redaction of Screenshot Text
+//!
+//! Blurs configured keywords (customer names, project codenames) wherever
+//! they appear in a capture, *before* any screenshot file is written. This
+//! redacts by content, independent of where on screen the text shows up.
+//!
+//! ## Pipeline
+//! ```text
+//! Full capture → ocr::recognize_words() → match keywords/patterns per line
+//!              → blur matched word boxes → save full / window / click crops
+//! ```
+//! Crops are cut from the scrubbed full capture, so one OCR run covers all
+//! three screenshots.
+//!
+//! ## Rules (`scrub` in the recorder config)
+//! - **keywords**: Literal words or phrases, case-insensitive; phrases may
+//!   span several words of a line (e.g., `"Acme Corp"`)
+//! - **patterns**: Regular expressions matched against each text line
+//!   (case-sensitive unless the pattern starts with `(?i)`)
+//!
+//! ## Failure Behavior
+//! Scrubbing fails closed: if OCR can't run while rules are configured, the
+//! capture is discarded (the event is still recorded, without screenshots)
+//! rather than saved unredacted.
+//!
+//! ## Performance
+//! OCR of a full capture takes 0.5-2s and runs on the event thread for every
+//! click while rules are configured. Without rules nothing extra happens.
+
+use crate::config::ScrubConfig;
+use crate::ocr::{self, OcrWord};
+use image::{DynamicImage, GenericImageView};
+use regex::Regex;
+
+/// Blur strength; high enough that text is unreadable at any capture scale.
+const BLUR_SIGMA: f32 = 12.0;
+
+/// Extra pixels blurred around each word box (ascenders, anti-aliasing).
+const BOX_PADDING: u32 = 4;
+
+/// Compiled scrub rules.
+pub struct ScrubRules {
+    rules: Vec<Regex>,
+}
+
+impl ScrubRules {
+    /// Compiles the configured keywords and patterns.
+    ///
+    /// # Returns
+    /// * `Ok(Some(ScrubRules))` - Rules to apply
+    /// * `Ok(None)` - Nothing configured (scrubbing disabled)
+    /// * `Err(String)` - A pattern is not a valid regular expression
+    pub fn compile(config: &ScrubConfig) -> Result<Option<Self>, String> {
+        let keywords = config
+            .keywords
+            .iter()
+            .map(|keyword| keyword.trim())
+            .filter(|keyword| !keyword.is_empty())
+            .map(|keyword| format!("(?i){}", regex::escape(keyword)));
+        let patterns = config
+            .patterns
+            .iter()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .cloned();
+
+        let rules = keywords
+            .chain(patterns)
+            .map(|rule| {
+                Regex::new(&rule).map_err(|e| format!("Invalid scrub pattern '{}': {}", rule, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(if rules.is_empty() {
+            None
+        } else {
+            Some(Self { rules })
+        })
+    }
+
+    /// Whether any rule matches somewhere in `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.rules.iter().any(|rule| rule.is_match(text))
+    }
+}
+
+/// Blurs every configured keyword visible in a capture.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of word boxes blurred
+/// * `Err(String)` - OCR failed; the capture must not be saved
+pub fn scrub(image: &mut DynamicImage, rules: &ScrubRules) -> Result<usize, String> {
+    let words =
+        ocr::recognize_words(image).map_err(|e| format!("Failed to scrub capture: {}", e))?;
+    let boxes = matching_boxes(&words, rules);
+
+    for &(x, y, width, height) in &boxes {
+        blur_region(image, x, y, width, height);
+    }
+
+    #[cfg(debug_assertions)]
+    if !boxes.is_empty() {
+        println!("🫥 Scrubbed {} word(s) from capture", boxes.len());
+    }
+
+    Ok(boxes.len())
+}
+
+/// Returns the boxes of all words that are part of a rule match.
+///
+/// Words are joined into lines with single spaces, so multi-word keywords
+/// match across word boundaries; a word is blurred if any match overlaps it.
+fn matching_boxes(words: &[OcrWord], rules: &ScrubRules) -> Vec<(u32, u32, u32, u32)> {
+    let mut boxes = Vec::new();
+
+    for line in words.chunk_by(|a, b| a.line == b.line) {
+        let mut text = String::new();
+        let mut spans = Vec::with_capacity(line.len());
+        for word in line {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            let start = text.len();
+            text.push_str(&word.text);
+            spans.push(start..text.len());
+        }
+
+        for (word, span) in line.iter().zip(&spans) {
+            let matched = rules.rules.iter().any(|rule| {
+                rule.find_iter(&text)
+                    .any(|found| found.start() < span.end && span.start < found.end())
+            });
+            if matched {
+                boxes.push((word.left, word.top, word.width, word.height));
+            }
+        }
+    }
+
+    boxes
+}
+
+/// Blurs a rectangle in place (padded, clipped to the image).
+fn blur_region(image: &mut DynamicImage, x: u32, y: u32, width: u32, height: u32) {
+    let (image_width, image_height) = image.dimensions();
+    let left = x.saturating_sub(BOX_PADDING).min(image_width);
+    let top = y.saturating_sub(BOX_PADDING).min(image_height);
+    let right = (x + width + BOX_PADDING).min(image_width);
+    let bottom = (y + height + BOX_PADDING).min(image_height);
+    if right <= left || bottom <= top {
+        return;
+    }
+
+    let blurred = image
+        .crop_imm(left, top, right - left, bottom - top)
+        .blur(BLUR_SIGMA);
+    image::imageops::replace(image, &blurred, left as i64, top as i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, line: u32, left: u32) -> OcrWord {
+        OcrWord {
+            text: text.to_string(),
+            line: (1, 1, line),
+            left,
+            top: line * 20,
+            width: 50,
+            height: 12,
+        }
+    }
+
+    #[test]
+    fn test_keywords_and_patterns_select_word_boxes() {
+        let rules = ScrubRules::compile(&ScrubConfig {
+            keywords: vec!["acme corp".to_string()],
+            patterns: vec![r"PRJ-\d{4}".to_string()],
+        })
+        .unwrap()
+        .unwrap();
+        let words = vec![
+            word("Invoice", 1, 0),
+            word("for", 1, 60),
+            word("ACME", 1, 120),
+            word("Corp", 1, 180),
+            word("Acme", 2, 0),
+            word("PRJ-1234", 2, 60),
+        ];
+
+        let boxes = matching_boxes(&words, &rules);
+
+        // Both words of the phrase, not the lone "Acme" on the next line
+        assert_eq!(
+            boxes,
+            vec![(120, 20, 50, 12), (180, 20, 50, 12), (60, 40, 50, 12)]
+        );
+        assert!(ScrubRules::compile(&ScrubConfig::default())
+            .unwrap()
+            .is_none());
+        assert!(ScrubRules::compile(&ScrubConfig {
+            patterns: vec!["(".to_string()],
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
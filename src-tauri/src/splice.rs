@@ -0,0 +1,167 @@
+//! # Splice Module - Re-Recorded Step Replacement
+//!
+//! Replaces one botched step of a saved session with a short, freshly
+//! recorded snippet, so a 20-minute recording doesn't have to be redone.
+//!
+//! ## Timestamps
+//! Event timestamps always stay what was actually recorded (the snippet's
+//! events are from "now"). The *edited* order in time is stored separately
+//! in `RecordingSession::edited_timeline`:
+//!
+//! ```text
+//! original:  A ──3s── B ──5s── C          (B is re-recorded)
+//! snippet:            B1 ─2s─ B2
+//! edited:    A ──3s── B1 ─2s─ B2 ──5s── C
+//! ```
+//!
+//! - The first snippet event inherits the gap before the replaced step
+//! - Gaps inside the snippet are kept as recorded
+//! - The step after the replaced one keeps its original gap
+//!
+//! Splicing an already edited session builds on its `edited_timeline`.
+
+use crate::types::{Event, RecordingSession, TimelineEntry};
+use chrono::{DateTime, Duration, Utc};
+
+/// Replaces the event `event_id` with `replacement` and rebuilds the edited timeline.
+///
+/// # Arguments
+/// * `session` - Saved session to edit
+/// * `event_id` - Step to replace
+/// * `replacement` - Events of the re-recorded snippet (in recorded order);
+///   empty removes the step
+///
+/// # Returns
+/// * `Ok(())` - Session edited in place
+/// * `Err(String)` - Error if `event_id` isn't part of the session
+pub fn splice_step(
+    session: &mut RecordingSession,
+    event_id: &str,
+    replacement: Vec<Event>,
+) -> Result<(), String> {
+    let index = session
+        .events
+        .iter()
+        .position(|event| event.has_id(event_id))
+        .ok_or_else(|| format!("Event {} not found in session", event_id))?;
+
+    let recorded_gaps = gaps(session);
+    let gap = |i: usize| recorded_gaps[i];
+
+    // Gap before each event of the spliced sequence
+    let mut gaps: Vec<Duration> = (0..index).map(gap).collect();
+    let replaced_gap = gap(index);
+    let mut carried_gap = replaced_gap;
+    let mut previous_snippet_time: Option<DateTime<Utc>> = None;
+    for event in &replacement {
+        gaps.push(match previous_snippet_time {
+            None => replaced_gap,
+            Some(previous) => event.timestamp - previous,
+        });
+        previous_snippet_time = Some(event.timestamp);
+        carried_gap = Duration::zero();
+    }
+    for i in index + 1..session.events.len() {
+        // Removing a step without replacement folds its gap into the next one
+        gaps.push(gap(i) + carried_gap);
+        carried_gap = Duration::zero();
+    }
+
+    session.events.remove(index);
+    let tail = session.events.split_off(index);
+    session.events.extend(replacement);
+    session.events.extend(tail);
+    set_timeline(session, gaps);
+
+    #[cfg(debug_assertions)]
+    println!(
+        "✂️  Spliced step {} ({} events now)",
+        event_id,
+        session.events.len()
+    );
+
+    Ok(())
+}
+
+/// Time before each event in playback order (the first from `started_at`).
+pub fn gaps(session: &RecordingSession) -> Vec<Duration> {
+    let timeline = session.timeline();
+    let mut previous = session.started_at;
+    timeline
+        .into_iter()
+        .map(|time| {
+            let gap = time - previous;
+            previous = time;
+            gap
+        })
+        .collect()
+}
+
+/// Rebuilds the edited timeline from the time before each event.
+pub fn set_timeline(session: &mut RecordingSession, gaps: Vec<Duration>) {
+    let mut time = session.started_at;
+    session.edited_timeline = Some(
+        session
+            .events
+            .iter()
+            .zip(gaps)
+            .map(|(event, gap)| {
+                time += gap;
+                TimelineEntry {
+                    event_id: event.id,
+                    timestamp: time,
+                }
+            })
+            .collect(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EventType;
+
+    fn key_event(key: &str, timestamp: DateTime<Utc>) -> Event {
+        let mut event = Event::new(
+            EventType::KeyPress {
+                key: key.to_string(),
+                shift: false,
+                text: None,
+                duration_ms: None,
+                repeat_count: 0,
+            },
+            None,
+        );
+        event.timestamp = timestamp;
+        event
+    }
+
+    #[test]
+    fn test_splice_keeps_surrounding_gaps() {
+        let start = Utc::now() - Duration::hours(1);
+        let mut session = RecordingSession::new("splice-test".to_string());
+        session.started_at = start;
+        for (key, offset) in [("KeyA", 1), ("KeyB", 4), ("KeyC", 9)] {
+            session.add_event(key_event(key, start + Duration::seconds(offset)));
+        }
+        let replaced_id = session.events[1].id.to_string();
+
+        let now = Utc::now();
+        let snippet = vec![
+            key_event("KeyX", now),
+            key_event("KeyY", now + Duration::seconds(2)),
+        ];
+        splice_step(&mut session, &replaced_id, snippet).unwrap();
+
+        let offsets: Vec<i64> = session
+            .edited_timeline
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|entry| (entry.timestamp - start).num_seconds())
+            .collect();
+        assert_eq!(offsets, vec![1, 4, 6, 11]);
+        assert_eq!(session.events.len(), 4);
+        assert!(splice_step(&mut session, "missing", Vec::new()).is_err());
+    }
+}
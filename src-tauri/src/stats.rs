@@ -0,0 +1,258 @@
+//! # Stats Module - Anonymized Usage Aggregation (Opt-In)
+//!
+//! Rolls saved sessions up into a single statistics file so users can keep
+//! long-term usage trends after deleting the raw recordings.
+//!
+//! ## What Is Kept
+//! Only counts: events per action category, per event type, per foreground
+//! application, and per hour of day (UTC). No screenshots, descriptions, key
+//! names, positions, or window content ever enter the stats file.
+//!
+//! Applications are counted under a hash of their name (`app-1a2b3c4d`),
+//! after the `anonymize` rules, so the file never names the tools or
+//! customer systems someone works with. Aliased apps share one key.
+//!
+//! ## Per-Session App Usage
+//! `app_usage()` summarizes a single session by foreground application
+//! (events and time spent). It reads the session directly and doesn't
+//! need telemetry to be enabled.
+//!
+//! ## Storage
+//! ```text
+//! recordings/stats.json
+//! ```
+//!
+//! ## Rolling Aggregation
+//! Each run only adds sessions that haven't been aggregated yet (tracked by
+//! session id), so counts survive deletion of the sessions they came from
+//! and running the job twice never double-counts.
+//!
+//! ## Opt-In
+//! Disabled by default. Enable via `telemetry.enabled` in the recorder
+//! config; when enabled, the job runs after every stopped recording.
+
+use crate::anonymize;
+use crate::config;
+use crate::storage;
+use crate::types::RecordingSession;
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// Aggregated, screenshot-free usage statistics across sessions.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct UsageStats {
+    /// When the stats file was last updated
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Sessions already included in the counts (random UUIDs, no user data)
+    pub aggregated_sessions: BTreeSet<String>,
+    pub event_count: u64,
+    /// Total recorded time in seconds (sessions with a stop time only)
+    pub recorded_seconds: f64,
+    /// Events per action category (`interaction`, `text_input`, ...)
+    pub by_category: BTreeMap<String, u64>,
+    /// Events per event type (`Click`, `KeyPress`, `Wait`, ...)
+    pub by_event_type: BTreeMap<String, u64>,
+    /// Events per foreground application, keyed by name hash (`Unknown` if
+    /// not recorded)
+    pub by_app: BTreeMap<String, u64>,
+    /// Events per hour of day (UTC, `0`-`23`)
+    pub by_hour: BTreeMap<u32, u64>,
+}
+
+/// Adds any saved sessions not yet included to the stats file.
+///
+/// # Arguments
+/// * `active_session_id` - Session currently being recorded (never aggregated)
+///
+/// # Returns
+/// * `Ok(UsageStats)` - Updated statistics
+/// * `Err(String)` - Error if telemetry is disabled, or the file couldn't be
+///   parsed (it is left as is) or written
+pub fn aggregate_usage_stats(active_session_id: Option<&str>) -> Result<UsageStats, String> {
+    if !config::current().telemetry.enabled {
+        return Err("Usage statistics are disabled (enable telemetry in settings)".to_string());
+    }
+
+    let stats =
+        storage::update_json_index(&stats_path(), "usage stats", |stats: &mut UsageStats| {
+            for session_id in storage::list_session_ids() {
+                if stats.aggregated_sessions.contains(&session_id)
+                    || active_session_id == Some(session_id.as_str())
+                {
+                    continue;
+                }
+
+                match storage::load_session(&session_id) {
+                    Ok(session) => add_session(stats, &session),
+                    Err(e) => eprintln!("⚠️  Skipping session {} in stats: {}", session_id, e),
+                }
+            }
+
+            stats.updated_at = Some(Utc::now());
+            Ok(stats.clone())
+        })?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "📈 Usage stats updated: {} sessions, {} events",
+        stats.aggregated_sessions.len(),
+        stats.event_count
+    );
+
+    Ok(stats)
+}
+
+/// Time and events per foreground application within one session.
+#[derive(Serialize, Debug, Clone)]
+pub struct AppUsage {
+    /// Application name (`Unknown` for events recorded without one)
+    pub app_name: String,
+    pub event_count: u64,
+    /// Time from each of the app's events to the next event (or the stop time)
+    pub active_seconds: f64,
+}
+
+/// Summarizes a session by foreground application, most used first.
+pub fn app_usage(session: &RecordingSession) -> Vec<AppUsage> {
+    let timeline = session.timeline();
+    let mut usage: BTreeMap<String, (u64, f64)> = BTreeMap::new();
+
+    for (index, event) in session.events.iter().enumerate() {
+        let until = timeline
+            .get(index + 1)
+            .copied()
+            .or(session.stopped_at)
+            .unwrap_or(timeline[index]);
+        let seconds = (until - timeline[index]).num_milliseconds().max(0) as f64 / 1000.0;
+
+        let entry = usage.entry(app_label(&event.app_name)).or_default();
+        entry.0 += 1;
+        entry.1 += seconds;
+    }
+
+    let mut usage: Vec<AppUsage> = usage
+        .into_iter()
+        .map(|(app_name, (event_count, active_seconds))| AppUsage {
+            app_name,
+            event_count,
+            active_seconds,
+        })
+        .collect();
+    usage.sort_by(|a, b| b.active_seconds.total_cmp(&a.active_seconds));
+    usage
+}
+
+/// Location of the stats file.
+pub fn stats_path() -> PathBuf {
+    storage::recordings_root().join("stats.json")
+}
+
+/// Adds the counts of one session to `stats`.
+fn add_session(stats: &mut UsageStats, session: &RecordingSession) {
+    for event in &session.events {
+        stats.event_count += 1;
+        *stats
+            .by_category
+            .entry(event.action_category.clone())
+            .or_default() += 1;
+        *stats.by_hour.entry(event.timestamp.hour()).or_default() += 1;
+
+        let event_type = serde_json::to_value(&event.event_type)
+            .ok()
+            .and_then(|value| value.get("type")?.as_str().map(|t| t.to_string()))
+            .unwrap_or_else(|| "Unknown".to_string());
+        *stats.by_event_type.entry(event_type).or_default() += 1;
+        *stats.by_app.entry(app_key(&event.app_name)).or_default() += 1;
+    }
+
+    if let Some(stopped_at) = session.stopped_at {
+        stats.recorded_seconds +=
+            (stopped_at - session.started_at).num_milliseconds().max(0) as f64 / 1000.0;
+    }
+
+    stats.aggregated_sessions.insert(session.session_id.clone());
+}
+
+fn app_label(app_name: &Option<String>) -> String {
+    app_name.clone().unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// Stats key for an application: hash of its anonymized name (see "What Is Kept").
+fn app_key(app_name: &Option<String>) -> String {
+    match app_name {
+        Some(name) => {
+            let name = anonymize::app_name(name);
+            format!(
+                "app-{:08x}",
+                crc32fast::hash(name.trim().to_lowercase().as_bytes())
+            )
+        }
+        None => "Unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position};
+
+    #[test]
+    fn test_add_session_counts_once() {
+        let mut session = RecordingSession::new("stats-test".to_string());
+        session.add_event(Event::new(
+            EventType::Click {
+                button: MouseButton::Left,
+                duration_ms: None,
+            },
+            Some(Position::new(10.0, 20.0)),
+        ));
+        session.add_event(Event::new(
+            EventType::KeyPress {
+                key: "KeyA".to_string(),
+                shift: false,
+                text: None,
+                duration_ms: None,
+                repeat_count: 0,
+            },
+            None,
+        ));
+
+        let mut stats = UsageStats::default();
+        add_session(&mut stats, &session);
+
+        assert_eq!(stats.event_count, 2);
+        assert_eq!(stats.by_category.get("interaction"), Some(&1));
+        assert_eq!(stats.by_event_type.get("KeyPress"), Some(&1));
+        assert_eq!(stats.by_app.get("Unknown"), Some(&2));
+        assert_eq!(stats.by_hour.values().sum::<u64>(), 2);
+        assert!(stats.aggregated_sessions.contains("stats-test"));
+    }
+
+    #[test]
+    fn test_app_names_are_not_stored() {
+        let mut session = RecordingSession::new("stats-test-apps".to_string());
+        for app in ["Falcon CRM", "falcon crm "] {
+            let mut event = Event::new(
+                EventType::Scroll {
+                    delta_x: 0,
+                    delta_y: 1,
+                },
+                None,
+            );
+            event.app_name = Some(app.to_string());
+            session.add_event(event);
+        }
+
+        let mut stats = UsageStats::default();
+        add_session(&mut stats, &session);
+
+        assert_eq!(stats.by_app.len(), 1);
+        let (key, count) = stats.by_app.iter().next().unwrap();
+        assert!(key.starts_with("app-"));
+        assert!(!key.to_lowercase().contains("falcon"));
+        assert_eq!(*count, 2);
+    }
+}
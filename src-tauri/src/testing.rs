@@ -0,0 +1,440 @@
+//! # Testing Module - Synthetic Event Injection
+//!
+//! Feeds `rdev` events straight into `handle_event()`, the callback of the
+//! live listener, so the whole capture pipeline (wait detection,
+//! classification, screenshots, session append) runs in CI without real
+//! hardware input.
+//!
+//! ```text
+//! inject_event() / play() → handle_event() → Wait/Hover detection → Event::new() → filter → mock capture → session
+//! ```
+//!
+//! `play()` runs an `EventSource` (e.g., a `ScriptedSource`, see
+//! `event_source` module) on the calling thread, the way the listener runs
+//! the OS source.
+//!
+//! ## What Is Not Real
+//! - **Recording**: `begin()` installs a session like `start_recording()`
+//!   (default filter), but doesn't spawn the listener or the watcher threads.
+//!   `finish()` returns the session's JSON instead of saving it; `stop()`
+//!   runs the real `stop_recording()` (post-processing and save)
+//! - **Screenshots**: with mock capture on, `screenshot::capture_all_for_event()`
+//!   returns the paths it would write without touching the screen or disk;
+//!   `begin_with_capturer()` instead runs the real capture (encoding, file
+//!   writes) on frames from a `capturer::ScreenCapturer` such as `MockCapturer`
+//! - **Time**: wait detection measures real time, so `elapse()` moves the
+//!   last input back instead of sleeping
+//!
+//! The pipeline state is global (one recording per process): a `Recording`
+//! holds a lock for its lifetime, so tests injecting events in parallel run
+//! one after another.
+//!
+//! Compiled for unit tests and with the `testing` feature (for integration
+//! tests of downstream crates).
+
+use crate::capturer::ScreenCapturer;
+use crate::config::FilterConfig;
+use crate::event_source::{synthetic_event, EventSource};
+use crate::screenshot::EventCapture;
+use crate::storage;
+use crate::types::RecordingSession;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Held by the `Recording` injecting events.
+static RECORDING: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Whether screenshots are mocked.
+static MOCK_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Backend the real capture reads frames from (see `begin_with_capturer()`).
+static CAPTURER: Lazy<Mutex<Option<Arc<dyn ScreenCapturer>>>> = Lazy::new(|| Mutex::new(None));
+
+/// An active recording fed by injected events (see module docs).
+pub struct Recording {
+    _exclusive: MutexGuard<'static, ()>,
+}
+
+/// Starts recording a new session without listening to real input.
+///
+/// # Arguments
+/// * `session_id` - ID of the new session
+/// * `mock_capture` - `true` mocks screenshots; `false` leaves them to the OS
+///
+/// # Returns
+/// * `Ok(Recording)` - Inject events with `inject_event()`, end with `finish()`
+/// * `Err(String)` - Error if another recording is already in progress
+pub fn begin(session_id: &str, mock_capture: bool) -> Result<Recording, String> {
+    // A test that panicked mid-recording leaves the lock poisoned, not the next one failing
+    let exclusive = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+    crate::install_session(
+        RecordingSession::new(session_id.to_string()),
+        FilterConfig::default(),
+    )?;
+    MOCK_CAPTURE.store(mock_capture, Ordering::SeqCst);
+    Ok(Recording {
+        _exclusive: exclusive,
+    })
+}
+
+/// Starts recording like `begin()`, with screenshots captured from `capturer`.
+///
+/// Clicks go through the whole of `screenshot::capture_all_for_event()`
+/// (crops, encoding, files under `storage::recordings_root()`), only the
+/// frames are canned.
+#[cfg(any(test, feature = "bench"))]
+pub(crate) fn begin_with_capturer(
+    session_id: &str,
+    capturer: Arc<dyn ScreenCapturer>,
+) -> Result<Recording, String> {
+    let recording = begin(session_id, false)?;
+    *CAPTURER.lock().unwrap() = Some(capturer);
+    Ok(recording)
+}
+
+impl Recording {
+    /// Injects an event as if the listener had captured it.
+    pub fn inject_event(&self, event: rdev::Event) {
+        crate::handle_event(event);
+    }
+
+    /// Injects an event of the given type, timestamped now.
+    pub fn inject(&self, event_type: rdev::EventType) {
+        self.inject_event(synthetic_event(event_type, None));
+    }
+
+    /// Delivers every event of a source, returning once it is exhausted.
+    pub fn play(&self, source: impl EventSource + 'static) -> Result<(), String> {
+        Box::new(source).run(Box::new(crate::handle_event))
+    }
+
+    /// Injects a key press producing `text`, and its release.
+    pub fn type_key(&self, key: rdev::Key, text: &str) {
+        self.inject_event(synthetic_event(rdev::EventType::KeyPress(key), Some(text)));
+        self.inject(rdev::EventType::KeyRelease(key));
+    }
+
+    /// Moves the mouse to a point and clicks the left button there.
+    pub fn click_at(&self, x: f64, y: f64) {
+        self.inject(rdev::EventType::MouseMove { x, y });
+        self.inject(rdev::EventType::ButtonPress(rdev::Button::Left));
+        self.inject(rdev::EventType::ButtonRelease(rdev::Button::Left));
+    }
+
+    /// Number of events recorded so far.
+    pub fn event_count(&self) -> usize {
+        crate::CURRENT_SESSION
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, |session| session.events.len())
+    }
+
+    /// Pretends `seconds` passed without input since the last event.
+    pub fn elapse(&self, seconds: f64) {
+        let elapsed = chrono::Duration::milliseconds((seconds * 1000.0) as i64);
+        if let Some(last_time) = crate::LAST_EVENT_TIME.lock().unwrap().as_mut() {
+            *last_time -= elapsed;
+        }
+    }
+
+    /// Ends the recording.
+    ///
+    /// # Returns
+    /// The session as recorded (`session.json` format), not stopped,
+    /// post-processed, or saved
+    pub fn finish(self) -> Option<serde_json::Value> {
+        crate::RECORDING_ACTIVE.store(false, Ordering::SeqCst);
+        let session = crate::CURRENT_SESSION.lock().unwrap().take()?;
+        serde_json::to_value(session).ok()
+    }
+
+    /// Ends the recording with `stop_recording()`, as the Stop button does.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The command's message (the session is saved)
+    /// * `Err(String)` - Error from `stop_recording()`
+    pub fn stop(self) -> Result<String, String> {
+        crate::stop_recording()
+    }
+}
+
+impl Drop for Recording {
+    /// Leaves no session or mock behind for the next recording.
+    fn drop(&mut self) {
+        crate::RECORDING_ACTIVE.store(false, Ordering::SeqCst);
+        crate::CURRENT_SESSION.lock().unwrap().take();
+        MOCK_CAPTURE.store(false, Ordering::SeqCst);
+        *CAPTURER.lock().unwrap() = None;
+    }
+}
+
+/// Backend installed by `begin_with_capturer()`, if any.
+pub(crate) fn capturer() -> Option<Arc<dyn ScreenCapturer>> {
+    CAPTURER.lock().unwrap().clone()
+}
+
+/// Mock result of `screenshot::capture_all_for_event()`, if screenshots are mocked.
+///
+/// Paths follow the real naming; nothing is written.
+pub(crate) fn mock_capture(session_id: &str, event_id: &str) -> Option<EventCapture> {
+    if !MOCK_CAPTURE.load(Ordering::SeqCst) {
+        return None;
+    }
+    let path = |suffix: &str| {
+        storage::session_asset_path(session_id, &format!("event_{}_{}.png", event_id, suffix))
+    };
+    Some(EventCapture {
+        full_screen: path("full"),
+        full_screen_patch: None,
+        window_crop: Some(path("window")),
+        click_crop: Some(path("click")),
+        error_dialog_detected: false,
+        annotation_colors: None,
+        latency_reference: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capturer::MockCapturer;
+    use crate::display::DisplayGeometry;
+    use crate::event_source::ScriptedSource;
+    use std::time::{Duration, SystemTime};
+
+    /// An event as the listener would report it `ms` milliseconds after `start`.
+    fn event_at(
+        start: SystemTime,
+        ms: u64,
+        event_type: rdev::EventType,
+        text: Option<&str>,
+    ) -> rdev::Event {
+        let mut event = synthetic_event(event_type, text);
+        event.time = start + Duration::from_millis(ms);
+        event
+    }
+
+    fn event_types(events: &[serde_json::Value]) -> Vec<&str> {
+        events
+            .iter()
+            .map(|event| event["event_type"]["type"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_injected_input_runs_the_pipeline() {
+        let recording = begin("injected", true).unwrap();
+        recording.click_at(709.0, 328.0);
+        recording.elapse(4.0);
+        recording.type_key(rdev::Key::KeyA, "a");
+        recording
+            .play(
+                ScriptedSource::default()
+                    .then(Duration::ZERO, rdev::EventType::KeyPress(rdev::Key::Return))
+                    .then(
+                        Duration::ZERO,
+                        rdev::EventType::KeyRelease(rdev::Key::Return),
+                    ),
+            )
+            .unwrap();
+        let session = recording.finish().unwrap();
+
+        let events = session["events"].as_array().unwrap();
+        let types: Vec<_> = events
+            .iter()
+            .map(|event| event["event_type"]["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(types, ["Click", "Wait", "KeyPress", "KeyPress"]);
+
+        let click = &events[0];
+        assert_eq!(click["action_category"], "interaction");
+        let full_screen = format!("event_{}_full.png", click["id"].as_str().unwrap());
+        assert_eq!(
+            click["screenshots"]["full_screen"],
+            storage::session_asset_path("injected", &full_screen)
+        );
+        assert!(
+            events[1]["event_type"]["duration_seconds"]
+                .as_f64()
+                .unwrap()
+                >= 4.0
+        );
+        assert_eq!(events[2]["event_type"]["text"], "a");
+        assert_eq!(events[3]["event_type"]["key"], "Return");
+    }
+
+    #[test]
+    fn test_clicks_are_captured_from_the_backend() {
+        let display = DisplayGeometry {
+            id: 1,
+            origin_x: 0,
+            origin_y: 0,
+            logical_width: 1440,
+            logical_height: 900,
+            physical_width: 0,
+            physical_height: 0,
+            reported_scale: 2.0,
+        };
+        let capturer = MockCapturer::solid(&[display], image::Rgba([255, 255, 255, 255]));
+        let recording = begin_with_capturer("injected-capture", Arc::new(capturer)).unwrap();
+        recording.click_at(709.0, 328.0);
+        let session = recording.finish().unwrap();
+
+        // Written by the real capture, from the canned frame
+        let full_screen = session["events"][0]["screenshots"]["full_screen"]
+            .as_str()
+            .unwrap();
+        let frame = image::open(storage::resolve_asset_path(full_screen)).unwrap();
+        assert_eq!((frame.width(), frame.height()), (2880, 1800));
+
+        std::fs::remove_dir_all(storage::get_session_dir("injected-capture")).unwrap();
+    }
+
+    #[test]
+    fn test_handle_event_classifies_input() {
+        let recording = begin("handled", true).unwrap();
+        recording.inject(rdev::EventType::MouseMove { x: 120.0, y: 80.0 });
+        recording.inject(rdev::EventType::KeyPress(rdev::Key::ControlLeft));
+        recording.type_key(rdev::Key::KeyC, "c");
+        recording.inject(rdev::EventType::KeyRelease(rdev::Key::ControlLeft));
+        recording.inject(rdev::EventType::KeyPress(rdev::Key::ShiftLeft));
+        recording.type_key(rdev::Key::KeyA, "A");
+        recording.inject(rdev::EventType::KeyRelease(rdev::Key::ShiftLeft));
+        recording.inject(rdev::EventType::ButtonPress(rdev::Button::Right));
+        recording.inject(rdev::EventType::ButtonRelease(rdev::Button::Right));
+        let session = recording.finish().unwrap();
+
+        // Moves and modifiers aren't events of their own
+        let events = session["events"].as_array().unwrap();
+        assert_eq!(event_types(events), ["Shortcut", "KeyPress", "Click"]);
+        assert_eq!(
+            events[0]["event_type"]["modifiers"],
+            serde_json::json!(["Control"])
+        );
+        assert_eq!(events[0]["event_type"]["key"], "KeyC");
+        assert_eq!(events[1]["event_type"]["shift"], true);
+        assert_eq!(events[1]["event_type"]["text"], "A");
+        assert_eq!(events[2]["event_type"]["button"], "Right");
+        assert_eq!(events[2]["position"]["x"], 120.0);
+        assert_eq!(events[2]["position"]["y"], 80.0);
+    }
+
+    #[test]
+    fn test_click_duration_pairs_press_with_release() {
+        let recording = begin("click-pairing", true).unwrap();
+        let start = SystemTime::now();
+        let at = |ms, event_type| recording.inject_event(event_at(start, ms, event_type, None));
+        at(0, rdev::EventType::MouseMove { x: 300.0, y: 200.0 });
+        at(0, rdev::EventType::ButtonPress(rdev::Button::Left));
+        // Pressed and released while the left button is held
+        at(40, rdev::EventType::ButtonPress(rdev::Button::Right));
+        at(120, rdev::EventType::ButtonRelease(rdev::Button::Right));
+        at(650, rdev::EventType::ButtonRelease(rdev::Button::Left));
+        // No recorded press to pair with
+        at(700, rdev::EventType::ButtonRelease(rdev::Button::Middle));
+        at(800, rdev::EventType::ButtonPress(rdev::Button::Left));
+        at(880, rdev::EventType::ButtonRelease(rdev::Button::Left));
+        let session = recording.finish().unwrap();
+
+        let clicks: Vec<_> = session["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| {
+                let click = &event["event_type"];
+                (
+                    click["button"].as_str().unwrap(),
+                    click["duration_ms"].as_u64(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            clicks,
+            [("Left", Some(650)), ("Right", Some(80)), ("Left", Some(80))]
+        );
+    }
+
+    #[test]
+    fn test_key_release_pairs_with_press_and_repeats() {
+        let recording = begin("key-pairing", true).unwrap();
+        let start = SystemTime::now();
+        let at =
+            |ms, event_type, text| recording.inject_event(event_at(start, ms, event_type, text));
+        at(0, rdev::EventType::KeyPress(rdev::Key::KeyA), Some("a"));
+        // Auto-repeats while held
+        at(500, rdev::EventType::KeyPress(rdev::Key::KeyA), Some("a"));
+        at(533, rdev::EventType::KeyPress(rdev::Key::KeyA), Some("a"));
+        at(560, rdev::EventType::KeyRelease(rdev::Key::KeyA), None);
+        at(600, rdev::EventType::KeyPress(rdev::Key::KeyB), Some("b"));
+        at(690, rdev::EventType::KeyRelease(rdev::Key::KeyB), None);
+        // Released without a recorded press
+        at(700, rdev::EventType::KeyRelease(rdev::Key::KeyC), None);
+        // The release was missed (longer than any repeat delay): a new press
+        at(800, rdev::EventType::KeyPress(rdev::Key::KeyC), Some("c"));
+        at(3800, rdev::EventType::KeyPress(rdev::Key::KeyC), Some("c"));
+        at(3900, rdev::EventType::KeyRelease(rdev::Key::KeyC), None);
+        let session = recording.finish().unwrap();
+
+        let presses: Vec<_> = session["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| {
+                let press = &event["event_type"];
+                (
+                    press["key"].as_str().unwrap(),
+                    press["duration_ms"].as_u64(),
+                    press["repeat_count"].as_u64().unwrap_or(0),
+                )
+            })
+            .collect();
+        assert_eq!(
+            presses,
+            [
+                ("KeyA", Some(560), 2),
+                ("KeyB", Some(90), 0),
+                ("KeyC", None, 0),
+                ("KeyC", Some(100), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stop_recording_post_processes_and_saves() {
+        let recording = begin("stopped", true).unwrap();
+        recording.click_at(400.0, 300.0);
+        recording.type_key(rdev::Key::KeyH, "h");
+        recording.type_key(rdev::Key::KeyI, "i");
+        let message = recording.stop().unwrap();
+        assert!(
+            message.starts_with("Recording stopped. 2 events captured"),
+            "{}",
+            message
+        );
+
+        // Keystrokes are merged into text before the save
+        let saved = storage::load_session("stopped").unwrap();
+        assert!(saved.stopped_at.is_some());
+        let events = serde_json::to_value(&saved.events).unwrap();
+        let events = events.as_array().unwrap();
+        assert_eq!(event_types(events), ["Click", "TextInput"]);
+        assert_eq!(events[1]["event_type"]["text"], "hi");
+
+        // Nothing left to stop
+        let exclusive = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+        assert!(crate::stop_recording().is_err());
+        drop(exclusive);
+
+        // The preview track is written in the background
+        let preview = crate::preview::preview_path("stopped");
+        for _ in 0..50 {
+            if preview.exists() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        std::fs::remove_dir_all(storage::get_session_dir("stopped")).unwrap();
+    }
+}
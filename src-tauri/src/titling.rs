@@ -0,0 +1,195 @@
+//! # Titling Module - Automatic Session Titles
+//!
+//! Sessions recorded without a title get one at `stop_recording()`, so the
+//! session list reads "Google Chrome — Create Purchase Order" instead of a
+//! wall of UUIDs.
+//!
+//! ## Sources
+//! 1. **Dominant app**: The application most events were recorded in
+//! 2. **Heading**: The first prominent line of text in the first captured
+//!    window (OCR of `event_[id]_window.png`, else the full screen)
+//! 3. **Window title**: Fallback for the heading if OCR finds none (e.g.,
+//!    tesseract isn't installed)
+//!
+//! A line is prominent if its words are at least `HEADING_HEIGHT_RATIO`
+//! times as tall as the median line. Headings and window titles containing
+//! personal data (see `privacy` module) are skipped.
+//!
+//! ## Cost
+//! One OCR run (0.5-2s) on the stop path, for untitled sessions only.
+
+use crate::ocr::{self, OcrWord};
+use crate::privacy;
+use crate::storage;
+use crate::types::RecordingSession;
+use std::collections::HashMap;
+
+/// How much taller than the median line a heading must be.
+const HEADING_HEIGHT_RATIO: f64 = 1.25;
+
+/// Longest heading (in characters) that still reads as a title.
+const MAX_HEADING_CHARS: usize = 80;
+
+/// Builds a title for a finished session.
+///
+/// # Returns
+/// * `Some(String)` - Title such as `"Google Chrome — Create Purchase Order"`
+/// * `None` - Neither an app nor a heading could be determined
+pub fn generate(session: &RecordingSession) -> Option<String> {
+    let app = dominant_app(session);
+    let heading = first_capture_heading(session).or_else(|| first_window_title(session, &app));
+
+    match (app, heading) {
+        (Some(app), Some(heading)) if !heading.eq_ignore_ascii_case(&app) => {
+            Some(format!("{} — {}", app, heading))
+        }
+        (Some(app), _) => Some(app),
+        (None, heading) => heading,
+    }
+}
+
+/// Most frequent application among the events (earliest wins ties).
+fn dominant_app(session: &RecordingSession) -> Option<String> {
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new(); // count, first index
+    for (index, app) in session
+        .events
+        .iter()
+        .filter_map(|event| event.app_name.as_deref())
+        .enumerate()
+    {
+        counts.entry(app).or_insert((0, index)).0 += 1;
+    }
+
+    counts
+        .into_iter()
+        .max_by(|(_, (a_count, a_first)), (_, (b_count, b_first))| {
+            a_count.cmp(b_count).then(b_first.cmp(a_first))
+        })
+        .map(|(app, _)| app.to_string())
+}
+
+/// OCRs the first complete capture and returns its heading.
+fn first_capture_heading(session: &RecordingSession) -> Option<String> {
+    let path = session.events.iter().find_map(|event| {
+        let screenshots = &event.screenshots;
+        let full_screen = screenshots
+            .full_screen
+            .as_ref()
+            .filter(|_| screenshots.full_screen_patch.is_none());
+        screenshots.window_crop.as_ref().or(full_screen)
+    })?;
+
+    let image = image::open(storage::resolve_asset_path(path)).ok()?;
+    // Without tesseract the window title stands in
+    let words = ocr::recognize_words(&image).ok()?;
+    heading_from_words(&words)
+}
+
+/// Picks the first line set noticeably larger than the rest.
+fn heading_from_words(words: &[OcrWord]) -> Option<String> {
+    let lines: Vec<(u32, String)> = words
+        .chunk_by(|a, b| a.line == b.line)
+        .map(|line| {
+            let mut heights: Vec<u32> = line.iter().map(|word| word.height).collect();
+            heights.sort_unstable();
+            let text = line
+                .iter()
+                .map(|word| word.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (heights[heights.len() / 2], text)
+        })
+        .collect();
+
+    let mut heights: Vec<u32> = lines.iter().map(|(height, _)| *height).collect();
+    heights.sort_unstable();
+    let median = *heights.get(heights.len() / 2)? as f64;
+
+    lines
+        .into_iter()
+        .filter(|(height, _)| *height as f64 >= median * HEADING_HEIGHT_RATIO)
+        .map(|(_, text)| text)
+        .find(|text| is_title_like(text))
+}
+
+/// First window title in the dominant app, without a trailing `" — App"` suffix.
+fn first_window_title(session: &RecordingSession, app: &Option<String>) -> Option<String> {
+    session
+        .events
+        .iter()
+        .filter(|event| app.is_none() || event.app_name == *app)
+        .filter_map(|event| event.window_title.as_deref())
+        .map(|title| match app {
+            Some(app) => [" — ", " - ", " – "]
+                .iter()
+                .find_map(|separator| title.strip_suffix(&format!("{}{}", separator, app)))
+                .unwrap_or(title),
+            None => title,
+        })
+        .map(str::trim)
+        .find(|title| is_title_like(title))
+        .map(str::to_string)
+}
+
+/// Short, mostly textual, and free of personal data.
+fn is_title_like(text: &str) -> bool {
+    let letters = text.chars().filter(|c| c.is_alphabetic()).count();
+    letters >= 3
+        && text.chars().count() <= MAX_HEADING_CHARS
+        && privacy::find_personal_data(text).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Event, EventType, MouseButton, Position};
+
+    fn word(text: &str, line: u32, height: u32) -> OcrWord {
+        OcrWord {
+            text: text.to_string(),
+            line: (1, 1, line),
+            left: 0,
+            top: line * 40,
+            width: 60,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_title_from_app_and_heading() {
+        let words = vec![
+            word("File", 1, 12),
+            word("Edit", 1, 12),
+            word("Create", 2, 24),
+            word("Purchase", 2, 24),
+            word("Order", 2, 24),
+            word("Supplier", 3, 12),
+            word("Quantity", 4, 12),
+        ];
+        assert_eq!(
+            heading_from_words(&words).as_deref(),
+            Some("Create Purchase Order")
+        );
+        assert_eq!(heading_from_words(&[]), None);
+
+        let mut session = RecordingSession::new("titling-test".to_string());
+        for app in ["Finder", "Google Chrome", "Google Chrome"] {
+            let mut event = Event::new(
+                EventType::Click {
+                    button: MouseButton::Left,
+                    duration_ms: None,
+                },
+                Some(Position::new(10.0, 10.0)),
+            );
+            event.app_name = Some(app.to_string());
+            event.window_title = Some(format!("Invoices - {}", app));
+            session.add_event(event);
+        }
+
+        // No screenshots: the window title stands in for the heading
+        assert_eq!(
+            generate(&session).as_deref(),
+            Some("Google Chrome — Invoices")
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! # Wait Cause Module - Slowness vs. Think-Time
+//!
+//! A 12-second Wait reads the same whether a report was rendering or the user
+//! was reading it. With `wait_causes.enabled`, a sampler thread in `lib.rs`
+//! captures the display under the cursor every `sample_interval_ms` while
+//! recording and notes when it last changed (same tile comparison as the
+//! `latency` module). When the next input turns the gap into a Wait, the
+//! Wait gets a `cause`:
+//!
+//! | Cause | Gap |
+//! |-------|-----|
+//! | `loading` | The screen was changing for at least half of it, or the focused window changed without input |
+//! | `away` | The screen was static for `away_seconds` (60 by default) or longer |
+//! | `reading` | Otherwise: static screen, same window |
+//!
+//! ```text
+//! click ── changing ──┬─ static ── key     changing ≥ static → loading
+//!                 last change              static ≥ 60s      → away
+//!                                          else              → reading
+//! ```
+//!
+//! The cause is left unset when the samples don't cover the whole gap
+//! (sampling off, paused, or the capture failed), unless the window changed.
+//! Gaps long enough to be `Idle` aren't Waits and get no cause.
+
+use crate::buffer_pool;
+use crate::capturer::{self, ScreenCapturer};
+use crate::config::WaitCauseConfig;
+use crate::display::DisplayGeometry;
+use crate::latency;
+use crate::types::WaitCause;
+use chrono::{DateTime, Utc};
+use image::RgbaImage;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Shortest accepted sampling interval (lower values would capture back to back).
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What the sampler has seen since it last started.
+#[derive(Default)]
+struct Activity {
+    /// First sample of the current uninterrupted run
+    since: Option<DateTime<Utc>>,
+    /// Latest sample that differed from the one before
+    last_change: Option<DateTime<Utc>>,
+    display_id: u32,
+    frame: Option<RgbaImage>,
+}
+
+static ACTIVITY: Lazy<Mutex<Activity>> = Lazy::new(|| Mutex::new(Activity::default()));
+
+/// Time between two samples.
+pub fn sample_interval(config: &WaitCauseConfig) -> Duration {
+    Duration::from_millis(config.sample_interval_ms).max(MIN_SAMPLE_INTERVAL)
+}
+
+/// Captures the display containing a point and notes whether it changed.
+///
+/// A failed capture or a different display starts a new run.
+///
+/// # Arguments
+/// * `x`, `y` - Cursor position in global logical pixels
+/// * `config` - Sampling settings
+pub fn sample(x: f64, y: f64, config: &WaitCauseConfig) {
+    let backend = capturer::default_backend();
+    let Some(display) = backend.display_at(x as i32, y as i32) else {
+        reset();
+        return;
+    };
+    // The screen is read at the start of the capture
+    let sampled_at = Utc::now();
+    let Some(frame) = capture(backend, &display) else {
+        reset();
+        return;
+    };
+
+    let mut activity = ACTIVITY.lock().unwrap();
+    let display_id = display.id;
+    match &activity.frame {
+        Some(previous) if activity.display_id == display_id => {
+            if latency::changed_ratio(previous, &frame) >= config.min_changed_ratio {
+                activity.last_change = Some(sampled_at);
+            }
+        }
+        _ => {
+            activity.since = Some(sampled_at);
+            activity.last_change = None;
+            activity.display_id = display_id;
+        }
+    }
+    if let Some(previous) = activity.frame.replace(frame) {
+        buffer_pool::give(previous.into_raw());
+    }
+}
+
+/// Forgets all samples (the next gap is unknown until sampling covers it).
+pub fn reset() {
+    let previous = std::mem::take(&mut *ACTIVITY.lock().unwrap());
+    if let Some(frame) = previous.frame {
+        buffer_pool::give(frame.into_raw());
+    }
+}
+
+/// Likely cause of a gap without input (see module docs).
+///
+/// # Arguments
+/// * `config` - Attribution settings
+/// * `gap_start` - Last input before the gap
+/// * `gap_end` - Input that ended the gap
+/// * `window_changed` - The focused window differs from the one at `gap_start`
+///
+/// # Returns
+/// `None` if attribution is off or the samples don't cover the gap
+pub fn cause(
+    config: &WaitCauseConfig,
+    gap_start: DateTime<Utc>,
+    gap_end: DateTime<Utc>,
+    window_changed: bool,
+) -> Option<WaitCause> {
+    if !config.enabled {
+        return None;
+    }
+    if window_changed {
+        return Some(WaitCause::Loading);
+    }
+
+    let activity = ACTIVITY.lock().unwrap();
+    let grace = chrono::Duration::from_std(sample_interval(config)).unwrap_or_default();
+    if !activity
+        .since
+        .is_some_and(|since| since <= gap_start + grace)
+    {
+        return None;
+    }
+    Some(attribute(
+        gap_start,
+        gap_end,
+        activity.last_change,
+        config.away_seconds,
+    ))
+}
+
+/// Splits a gap at the last screen change and picks the cause.
+fn attribute(
+    gap_start: DateTime<Utc>,
+    gap_end: DateTime<Utc>,
+    last_change: Option<DateTime<Utc>>,
+    away_seconds: f64,
+) -> WaitCause {
+    let settled_at = last_change
+        .filter(|at| *at > gap_start)
+        .unwrap_or(gap_start)
+        .min(gap_end);
+    let changing_seconds = (settled_at - gap_start).num_milliseconds() as f64 / 1000.0;
+    let static_seconds = (gap_end - settled_at).num_milliseconds() as f64 / 1000.0;
+
+    if changing_seconds >= static_seconds {
+        WaitCause::Loading
+    } else if static_seconds >= away_seconds {
+        WaitCause::Away
+    } else {
+        WaitCause::Reading
+    }
+}
+
+fn capture(backend: &dyn ScreenCapturer, display: &DisplayGeometry) -> Option<RgbaImage> {
+    backend
+        .capture(display)
+        .map_err(|e| {
+            eprintln!(
+                "⚠️  Failed to sample screen for wait causes ({}): {}",
+                backend.name(),
+                e
+            )
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_is_split_at_last_change() {
+        let at = |seconds: i64| {
+            "2026-02-01T15:43:00Z".parse::<DateTime<Utc>>().unwrap()
+                + chrono::Duration::seconds(seconds)
+        };
+
+        // Rendering for 8 of 10 seconds
+        assert_eq!(
+            attribute(at(0), at(10), Some(at(8)), 60.0),
+            WaitCause::Loading
+        );
+        // Responded at once, then read for 9 seconds
+        assert_eq!(
+            attribute(at(0), at(10), Some(at(1)), 60.0),
+            WaitCause::Reading
+        );
+        // A change before the gap doesn't count
+        assert_eq!(
+            attribute(at(5), at(10), Some(at(4)), 60.0),
+            WaitCause::Reading
+        );
+        assert_eq!(attribute(at(0), at(90), None, 60.0), WaitCause::Away);
+    }
+}
@@ -0,0 +1,187 @@
+//! # Watchdog Module - Dead Listener Detection
+//!
+//! If `rdev::listen()` errors out or its thread panics, the recording
+//! silently stops accumulating events while the UI still says "recording".
+//! The listener thread leaves a heartbeat for every input event it receives
+//! and reports its own exit (`ListenerGuard`); a watcher thread in `lib.rs`
+//! turns an exit during a recording into a `recording-listener-died` Tauri
+//! event and marks the session as truncated.
+//!
+//! ## Panics
+//! A panic in `handle_event()` (e.g., a poisoned mutex) is caught per event
+//! in `lib.rs`: it's recorded as a `CaptureError` event and capture goes on.
+//! A panic inside `rdev` itself ends the listener; it is respawned up to
+//! `MAX_RESPAWNS` times per recording before the session is truncated.
+//!
+//! ## Why Not a Timeout
+//! `rdev::listen()` only calls back on input, so a quiet heartbeat can't tell
+//! an idle user from a dead listener. The heartbeat instead dates the
+//! truncation: nothing after the last input received was recorded.
+
+use crate::types::Truncation;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::any::Any;
+use std::sync::atomic::{AtomicI64, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Listener respawns after `rdev` panics, per recording.
+const MAX_RESPAWNS: u32 = 3;
+
+/// A repeated panic message is recorded at most once per this interval
+/// (a bug hit by every mouse move would otherwise flood the session).
+const REPEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Time of the last input received, in Unix milliseconds (`0` if none yet).
+static HEARTBEAT_MS: AtomicI64 = AtomicI64::new(0);
+
+/// Set when the listener thread exits, until the watcher takes it.
+static FAILURE: Lazy<Arc<Mutex<Option<ListenerFailure>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Listener respawns in the active recording.
+static RESPAWNS: AtomicU32 = AtomicU32::new(0);
+
+/// Last panic recorded as a `CaptureError`, and when.
+static LAST_REPORTED: Lazy<Mutex<Option<(String, Instant)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Payload of the `recording-listener-died` Tauri event.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ListenerFailure {
+    /// Error reported by `rdev::listen()` (or why the thread ended)
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+    /// Last input the listener received (`None` if it never received any)
+    pub last_heartbeat: Option<DateTime<Utc>>,
+    /// Ended by a panic (respawned if `respawn_allowed()`) rather than an error
+    pub panicked: bool,
+}
+
+impl ListenerFailure {
+    /// Truncation of a session that started at `started_at`.
+    pub fn truncation(&self, started_at: DateTime<Utc>) -> Truncation {
+        let at = self
+            .last_heartbeat
+            .filter(|heartbeat| *heartbeat >= started_at)
+            .unwrap_or(self.failed_at);
+        Truncation {
+            at,
+            reason: format!("Event listener stopped: {}", self.error),
+        }
+    }
+}
+
+/// Reports the listener thread's exit when dropped (on errors and panics).
+#[derive(Default)]
+pub struct ListenerGuard {
+    error: Option<String>,
+    panicked: bool,
+}
+
+impl ListenerGuard {
+    /// Sets the error reported when the thread exits.
+    pub fn fail(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Sets the panic reported when the thread exits.
+    pub fn panicked(&mut self, message: String) {
+        self.error = Some(format!("listener panicked: {}", message));
+        self.panicked = true;
+    }
+}
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        let panicked = self.panicked || std::thread::panicking();
+        let error = match self.error.take() {
+            Some(error) => error,
+            None if panicked => "listener thread panicked".to_string(),
+            None => "listener exited".to_string(),
+        };
+        let last_heartbeat = match HEARTBEAT_MS.load(Ordering::SeqCst) {
+            0 => None,
+            millis => DateTime::from_timestamp_millis(millis),
+        };
+
+        *FAILURE.lock().unwrap_or_else(|e| e.into_inner()) = Some(ListenerFailure {
+            error,
+            failed_at: Utc::now(),
+            last_heartbeat,
+            panicked,
+        });
+    }
+}
+
+/// Records that the listener received an input event.
+pub fn beat() {
+    HEARTBEAT_MS.store(Utc::now().timestamp_millis(), Ordering::SeqCst);
+}
+
+/// Forgets earlier failures and respawns for the recording that is starting.
+pub fn reset() {
+    *FAILURE.lock().unwrap() = None;
+    RESPAWNS.store(0, Ordering::SeqCst);
+}
+
+/// Takes the listener failure, if one happened since the last call.
+pub fn take_failure() -> Option<ListenerFailure> {
+    FAILURE.lock().unwrap().take()
+}
+
+/// Whether a panicked listener may be respawned (counts the respawn).
+pub fn respawn_allowed() -> bool {
+    RESPAWNS.fetch_add(1, Ordering::SeqCst) < MAX_RESPAWNS
+}
+
+/// Whether a caught panic should be recorded (not a repeat of the last one).
+pub fn should_report(message: &str) -> bool {
+    let mut last = LAST_REPORTED.lock().unwrap_or_else(|e| e.into_inner());
+    let now = Instant::now();
+    let repeat = last.as_ref().is_some_and(|(last_message, at)| {
+        last_message == message && now.duration_since(*at) < REPEAT_INTERVAL
+    });
+    if !repeat {
+        *last = Some((message.to_string(), now));
+    }
+    !repeat
+}
+
+/// Text of a caught panic payload (`panic!` with a literal or formatted message).
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncation_starts_at_last_heartbeat() {
+        let at = |time: &str| time.parse::<DateTime<Utc>>().unwrap();
+        let mut failure = ListenerFailure {
+            error: "EventTapError".to_string(),
+            failed_at: at("2026-02-01T15:50:00Z"),
+            last_heartbeat: Some(at("2026-02-01T15:45:00Z")),
+            panicked: false,
+        };
+
+        let truncation = failure.truncation(at("2026-02-01T15:40:00Z"));
+        assert_eq!(truncation.at, at("2026-02-01T15:45:00Z"));
+        assert_eq!(truncation.reason, "Event listener stopped: EventTapError");
+
+        // Input from before the session says nothing about it
+        let truncation = failure.truncation(at("2026-02-01T15:46:00Z"));
+        assert_eq!(truncation.at, at("2026-02-01T15:50:00Z"));
+
+        failure.last_heartbeat = None;
+        let truncation = failure.truncation(at("2026-02-01T15:40:00Z"));
+        assert_eq!(truncation.at, at("2026-02-01T15:50:00Z"));
+    }
+}
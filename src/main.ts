@@ -1,4 +1,6 @@
 import { createApp } from "vue";
 import App from "./App.vue";
+import FollowOverlay from "./FollowOverlay.vue";
 
-createApp(App).mount("#app");
+// The follow-mode overlay window loads the same bundle with `#follow`
+createApp(location.hash === "#follow" ? FollowOverlay : App).mount("#app");